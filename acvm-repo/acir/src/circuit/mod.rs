@@ -2,9 +2,11 @@ pub mod black_box_functions;
 pub mod brillig;
 pub mod directives;
 pub mod opcodes;
+mod r1cs;
 
 use crate::native_types::Witness;
 pub use opcodes::Opcode;
+pub use r1cs::R1CSSerializationError;
 use thiserror::Error;
 
 use std::{io::prelude::*, num::ParseIntError, str::FromStr};
@@ -0,0 +1,432 @@
+//! Serialization of ACIR circuits and witnesses into the binary formats used by iden3/circom tooling.
+//!
+//! - `.r1cs`: <https://github.com/iden3/r1csfile/blob/master/doc/r1cs_bin_format.md>
+//! - `.wtns`: the witness format produced by circom's witness generators and consumed by snarkjs.
+//!
+//! Both formats identify variables by "wires" rather than by witness index. Wire 0 always holds the constant `1`,
+//! followed by the public outputs, the public inputs, the private inputs and finally all remaining witnesses.
+//! Each wire is labelled with the index of the ACIR [`Witness`] it represents, so ACIR's unused witness 0
+//! coincides with the label of the constant wire.
+
+use std::collections::BTreeMap;
+
+use acir_field::FieldElement;
+use thiserror::Error;
+
+use super::{Circuit, Opcode, OpcodeLocation};
+use crate::native_types::{Expression, Witness, WitnessMap};
+
+const R1CS_MAGIC: &[u8; 4] = b"r1cs";
+const R1CS_VERSION: u32 = 1;
+const R1CS_HEADER_SECTION: u32 = 1;
+const R1CS_CONSTRAINTS_SECTION: u32 = 2;
+const R1CS_WIRE_TO_LABEL_SECTION: u32 = 3;
+
+const WTNS_MAGIC: &[u8; 4] = b"wtns";
+const WTNS_VERSION: u32 = 2;
+const WTNS_HEADER_SECTION: u32 = 1;
+const WTNS_DATA_SECTION: u32 = 2;
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum R1CSSerializationError {
+    #[error("Opcode {0} cannot be represented as a rank-1 constraint")]
+    UnsupportedOpcode(OpcodeLocation),
+    #[error("Witness 0 is reserved for the constant wire of the R1CS")]
+    ReservedWitness,
+    #[error("Witness {} exceeds the circuit's current witness index", .0.witness_index())]
+    UnknownWitness(Witness),
+}
+
+/// A linear combination of wires.
+type LinearCombination = BTreeMap<u32, FieldElement>;
+
+/// A rank-1 constraint `A * B = C`.
+type Constraint = (LinearCombination, LinearCombination, LinearCombination);
+
+/// The assignment of ACIR witnesses to R1CS wires.
+struct WireLayout {
+    /// The ACIR witness held by each wire, starting from wire 1.
+    wires: Vec<Witness>,
+    /// Maps each witness onto the wire used to reference it in constraints.
+    witness_to_wire: BTreeMap<Witness, u32>,
+    num_public_outputs: u32,
+    num_public_inputs: u32,
+    num_private_inputs: u32,
+    /// Public inputs which are also returned by the circuit. These are assigned a second wire
+    /// and constrained to be equal to their public output wire.
+    aliased_wires: Vec<(u32, u32)>,
+}
+
+impl WireLayout {
+    fn new(circuit: &Circuit) -> Result<Self, R1CSSerializationError> {
+        if circuit.circuit_arguments().contains(&Witness(0))
+            || circuit.return_values.0.contains(&Witness(0))
+        {
+            return Err(R1CSSerializationError::ReservedWitness);
+        }
+
+        let mut layout = WireLayout {
+            wires: Vec::new(),
+            witness_to_wire: BTreeMap::new(),
+            num_public_outputs: 0,
+            num_public_inputs: 0,
+            num_private_inputs: 0,
+            aliased_wires: Vec::new(),
+        };
+
+        for witness in &circuit.return_values.0 {
+            layout.push_wire(*witness);
+            layout.num_public_outputs += 1;
+        }
+        for witness in &circuit.public_parameters.0 {
+            let output_wire = layout.witness_to_wire.get(witness).copied();
+            let wire = layout.push_wire(*witness);
+            if let Some(output_wire) = output_wire {
+                layout.aliased_wires.push((output_wire, wire));
+            }
+            layout.num_public_inputs += 1;
+        }
+        for witness in &circuit.private_parameters {
+            if !layout.witness_to_wire.contains_key(witness) {
+                layout.push_wire(*witness);
+                layout.num_private_inputs += 1;
+            }
+        }
+        for index in 1..=circuit.current_witness_index {
+            let witness = Witness(index);
+            if !layout.witness_to_wire.contains_key(&witness) {
+                layout.push_wire(witness);
+            }
+        }
+
+        Ok(layout)
+    }
+
+    /// Allocates a new wire for `witness`, returning its index.
+    fn push_wire(&mut self, witness: Witness) -> u32 {
+        self.wires.push(witness);
+        let wire = self.wires.len() as u32;
+        self.witness_to_wire.entry(witness).or_insert(wire);
+        wire
+    }
+
+    /// Returns the wire used to reference `witness` in constraints.
+    fn wire(&self, witness: &Witness) -> Result<u32, R1CSSerializationError> {
+        match self.witness_to_wire.get(witness) {
+            Some(wire) => Ok(*wire),
+            None if witness.0 == 0 => Err(R1CSSerializationError::ReservedWitness),
+            None => Err(R1CSSerializationError::UnknownWitness(*witness)),
+        }
+    }
+
+    /// The total number of wires, including the constant wire.
+    fn num_wires(&self) -> u32 {
+        self.wires.len() as u32 + 1
+    }
+
+    /// Splits the arithmetic opcode at `index` into the linear combinations `(A, B, C)`
+    /// such that `expr = 0` is equivalent to `A * B = C`.
+    fn constraint(
+        &self,
+        index: usize,
+        expr: &Expression,
+    ) -> Result<Constraint, R1CSSerializationError> {
+        let mut a = LinearCombination::new();
+        let mut b = LinearCombination::new();
+        match expr.mul_terms.as_slice() {
+            [] => (),
+            [(q_m, w_l, w_r)] => {
+                a.insert(self.wire(w_l)?, *q_m);
+                b.insert(self.wire(w_r)?, FieldElement::one());
+            }
+            _ => {
+                return Err(R1CSSerializationError::UnsupportedOpcode(OpcodeLocation::Acir(index)))
+            }
+        }
+
+        let mut c = LinearCombination::new();
+        for (q_l, w_l) in &expr.linear_combinations {
+            let coefficient = c.entry(self.wire(w_l)?).or_insert(FieldElement::zero());
+            *coefficient -= *q_l;
+        }
+        if !expr.q_c.is_zero() {
+            c.insert(0, -expr.q_c);
+        }
+        c.retain(|_, coefficient| !coefficient.is_zero());
+
+        Ok((a, b, c))
+    }
+}
+
+impl Circuit {
+    /// Serializes the circuit into the iden3 `.r1cs` binary format.
+    ///
+    /// The circuit is expected to have been compiled for an R1CS backend such that every
+    /// [`Opcode::Arithmetic`] contains at most one multiplication term. Opcodes which are only used to compute
+    /// the witness (Brillig and directives) do not add any constraints and are skipped.
+    pub fn serialize_r1cs(&self) -> Result<Vec<u8>, R1CSSerializationError> {
+        let layout = WireLayout::new(self)?;
+
+        let mut constraints = Vec::new();
+        for (index, opcode) in self.opcodes.iter().enumerate() {
+            match opcode {
+                Opcode::Arithmetic(expr) => constraints.push(layout.constraint(index, expr)?),
+                Opcode::Brillig(_) | Opcode::Directive(_) => (),
+                Opcode::BlackBoxFuncCall(_)
+                | Opcode::MemoryInit { .. }
+                | Opcode::MemoryOp { .. } => {
+                    return Err(R1CSSerializationError::UnsupportedOpcode(OpcodeLocation::Acir(
+                        index,
+                    )))
+                }
+            }
+        }
+        for (output_wire, input_wire) in &layout.aliased_wires {
+            // 1 * (output - input) = 0
+            let a = LinearCombination::from([(0, FieldElement::one())]);
+            let b = LinearCombination::from([
+                (*output_wire, FieldElement::one()),
+                (*input_wire, -FieldElement::one()),
+            ]);
+            constraints.push((a, b, LinearCombination::new()));
+        }
+
+        let field_size = field_size_in_bytes();
+
+        let mut header = Vec::new();
+        write_u32(&mut header, field_size);
+        write_field_modulus(&mut header, field_size);
+        write_u32(&mut header, layout.num_wires());
+        write_u32(&mut header, layout.num_public_outputs);
+        write_u32(&mut header, layout.num_public_inputs);
+        write_u32(&mut header, layout.num_private_inputs);
+        write_u64(&mut header, self.num_vars() as u64);
+        write_u32(&mut header, constraints.len() as u32);
+
+        let mut constraints_section = Vec::new();
+        for (a, b, c) in &constraints {
+            for linear_combination in [a, b, c] {
+                write_u32(&mut constraints_section, linear_combination.len() as u32);
+                for (wire, coefficient) in linear_combination {
+                    write_u32(&mut constraints_section, *wire);
+                    write_field(&mut constraints_section, *coefficient, field_size);
+                }
+            }
+        }
+
+        let mut wire_to_label = Vec::new();
+        write_u64(&mut wire_to_label, 0);
+        for witness in &layout.wires {
+            write_u64(&mut wire_to_label, witness.0 as u64);
+        }
+
+        let mut bytes = Vec::new();
+        bytes.extend_from_slice(R1CS_MAGIC);
+        write_u32(&mut bytes, R1CS_VERSION);
+        write_u32(&mut bytes, 3);
+        write_section(&mut bytes, R1CS_HEADER_SECTION, &header);
+        write_section(&mut bytes, R1CS_CONSTRAINTS_SECTION, &constraints_section);
+        write_section(&mut bytes, R1CS_WIRE_TO_LABEL_SECTION, &wire_to_label);
+        Ok(bytes)
+    }
+}
+
+impl WitnessMap {
+    /// Serializes the witness into the `.wtns` binary format, ordered by the wires of `circuit`'s `.r1cs` representation.
+    ///
+    /// Witnesses which have not been assigned a value are written as zero.
+    pub fn serialize_wtns(&self, circuit: &Circuit) -> Result<Vec<u8>, R1CSSerializationError> {
+        let layout = WireLayout::new(circuit)?;
+        let field_size = field_size_in_bytes();
+
+        let mut header = Vec::new();
+        write_u32(&mut header, field_size);
+        write_field_modulus(&mut header, field_size);
+        write_u32(&mut header, layout.num_wires());
+
+        let mut values = Vec::new();
+        write_field(&mut values, FieldElement::one(), field_size);
+        for witness in &layout.wires {
+            let value = self.get(witness).copied().unwrap_or(FieldElement::zero());
+            write_field(&mut values, value, field_size);
+        }
+
+        let mut bytes = Vec::new();
+        bytes.extend_from_slice(WTNS_MAGIC);
+        write_u32(&mut bytes, WTNS_VERSION);
+        write_u32(&mut bytes, 2);
+        write_section(&mut bytes, WTNS_HEADER_SECTION, &header);
+        write_section(&mut bytes, WTNS_DATA_SECTION, &values);
+        Ok(bytes)
+    }
+}
+
+/// The number of bytes used to encode a field element, rounded up to a multiple of 8 bytes as done by circom.
+fn field_size_in_bytes() -> u32 {
+    ((FieldElement::max_num_bits() - 1) / 64 + 1) * 8
+}
+
+fn write_u32(bytes: &mut Vec<u8>, value: u32) {
+    bytes.extend_from_slice(&value.to_le_bytes());
+}
+
+fn write_u64(bytes: &mut Vec<u8>, value: u64) {
+    bytes.extend_from_slice(&value.to_le_bytes());
+}
+
+fn write_le_bytes(bytes: &mut Vec<u8>, mut le_bytes: Vec<u8>, field_size: u32) {
+    le_bytes.resize(field_size as usize, 0);
+    bytes.extend(le_bytes);
+}
+
+fn write_field(bytes: &mut Vec<u8>, value: FieldElement, field_size: u32) {
+    let mut le_bytes = value.to_be_bytes();
+    le_bytes.reverse();
+    write_le_bytes(bytes, le_bytes, field_size);
+}
+
+fn write_field_modulus(bytes: &mut Vec<u8>, field_size: u32) {
+    write_le_bytes(bytes, FieldElement::modulus().to_bytes_le(), field_size);
+}
+
+fn write_section(bytes: &mut Vec<u8>, section_type: u32, content: &[u8]) {
+    write_u32(bytes, section_type);
+    write_u64(bytes, content.len() as u64);
+    bytes.extend_from_slice(content);
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeSet;
+
+    use acir_field::FieldElement;
+
+    use super::{R1CSSerializationError, WireLayout};
+    use crate::{
+        circuit::{Circuit, Opcode, OpcodeLocation, PublicInputs},
+        native_types::{Expression, Witness, WitnessMap},
+    };
+
+    fn read_u32(bytes: &[u8], offset: usize) -> u32 {
+        u32::from_le_bytes(bytes[offset..offset + 4].try_into().unwrap())
+    }
+
+    fn read_u64(bytes: &[u8], offset: usize) -> u64 {
+        u64::from_le_bytes(bytes[offset..offset + 8].try_into().unwrap())
+    }
+
+    /// x * y - z = 0 where `z` is returned and `y` is public.
+    fn multiplication_circuit() -> Circuit {
+        Circuit {
+            current_witness_index: 3,
+            opcodes: vec![Opcode::Arithmetic(Expression {
+                mul_terms: vec![(FieldElement::one(), Witness(1), Witness(2))],
+                linear_combinations: vec![(-FieldElement::one(), Witness(3))],
+                q_c: FieldElement::zero(),
+            })],
+            private_parameters: BTreeSet::from([Witness(1)]),
+            public_parameters: PublicInputs(BTreeSet::from([Witness(2)])),
+            return_values: PublicInputs(BTreeSet::from([Witness(3)])),
+            ..Circuit::default()
+        }
+    }
+
+    #[test]
+    fn orders_wires_by_visibility() {
+        let layout = WireLayout::new(&multiplication_circuit()).unwrap();
+
+        assert_eq!(layout.wires, vec![Witness(3), Witness(2), Witness(1)]);
+        assert_eq!(layout.num_wires(), 4);
+        assert_eq!(
+            (layout.num_public_outputs, layout.num_public_inputs, layout.num_private_inputs),
+            (1, 1, 1)
+        );
+    }
+
+    #[test]
+    fn serializes_r1cs_header_and_constraints() {
+        let bytes = multiplication_circuit().serialize_r1cs().unwrap();
+
+        assert_eq!(&bytes[0..4], b"r1cs");
+        assert_eq!(read_u32(&bytes, 4), 1);
+        assert_eq!(read_u32(&bytes, 8), 3);
+
+        // Header section
+        assert_eq!(read_u32(&bytes, 12), 1);
+        let header_size = read_u64(&bytes, 16) as usize;
+        let header = &bytes[24..24 + header_size];
+        let field_size = read_u32(header, 0) as usize;
+        assert_eq!(field_size, 32);
+        let header = &header[4 + field_size..];
+        // nWires, nPubOut, nPubIn, nPrvIn
+        assert_eq!(
+            (read_u32(header, 0), read_u32(header, 4), read_u32(header, 8), read_u32(header, 12)),
+            (4, 1, 1, 1)
+        );
+        assert_eq!(read_u64(header, 16), 4);
+        assert_eq!(read_u32(header, 24), 1);
+
+        // Constraints section: A = [x], B = [y], C = [z]
+        let constraints = &bytes[24 + header_size..];
+        assert_eq!(read_u32(constraints, 0), 2);
+        let constraints = &constraints[12..];
+        let term_size = 4 + field_size;
+        for (index, wire) in [3, 2, 1].into_iter().enumerate() {
+            let term = &constraints[index * (4 + term_size)..];
+            assert_eq!(read_u32(term, 0), 1);
+            assert_eq!(read_u32(term, 4), wire);
+            assert_eq!(term[8], 1);
+        }
+    }
+
+    #[test]
+    fn rejects_non_rank_one_constraints() {
+        let mut circuit = multiplication_circuit();
+        circuit.opcodes.push(Opcode::Arithmetic(Expression {
+            mul_terms: vec![
+                (FieldElement::one(), Witness(1), Witness(1)),
+                (FieldElement::one(), Witness(2), Witness(2)),
+            ],
+            linear_combinations: vec![],
+            q_c: FieldElement::zero(),
+        }));
+
+        assert_eq!(
+            circuit.serialize_r1cs(),
+            Err(R1CSSerializationError::UnsupportedOpcode(OpcodeLocation::Acir(1)))
+        );
+    }
+
+    #[test]
+    fn aliases_returned_public_inputs() {
+        let mut circuit = multiplication_circuit();
+        circuit.return_values.0.insert(Witness(2));
+
+        let layout = WireLayout::new(&circuit).unwrap();
+        assert_eq!(layout.wires, vec![Witness(2), Witness(3), Witness(2), Witness(1)]);
+        assert_eq!(layout.aliased_wires, vec![(1, 3)]);
+        assert_eq!(layout.witness_to_wire[&Witness(2)], 1);
+    }
+
+    #[test]
+    fn serializes_witness_in_wire_order() {
+        let circuit = multiplication_circuit();
+        let witness = WitnessMap::from(std::collections::BTreeMap::from([
+            (Witness(1), FieldElement::from(2_u128)),
+            (Witness(2), FieldElement::from(3_u128)),
+            (Witness(3), FieldElement::from(6_u128)),
+        ]));
+
+        let bytes = witness.serialize_wtns(&circuit).unwrap();
+
+        assert_eq!(&bytes[0..4], b"wtns");
+        assert_eq!(read_u32(&bytes, 4), 2);
+        assert_eq!(read_u32(&bytes, 8), 2);
+        let header_size = read_u64(&bytes, 16) as usize;
+        assert_eq!(read_u32(&bytes, 24 + header_size - 4), 4);
+
+        let values = &bytes[24 + header_size + 12..];
+        let values: Vec<u8> = values.chunks(32).map(|value| value[0]).collect();
+        assert_eq!(values, vec![1, 6, 3, 2]);
+    }
+}
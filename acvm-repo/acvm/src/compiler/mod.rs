@@ -53,6 +53,15 @@ impl AcirTransformationMap {
                 }
             })
     }
+
+    /// Returns the new index of the first opcode at or after `old_acir_index` which remains after
+    /// the transformation, or the number of opcodes in the new circuit if there is no such opcode.
+    pub fn new_acir_index_at_or_after(&self, old_acir_index: usize) -> usize {
+        self.acir_opcode_positions
+            .iter()
+            .position(|&old_index| old_index >= old_acir_index)
+            .unwrap_or(self.acir_opcode_positions.len())
+    }
 }
 
 fn transform_assert_messages(
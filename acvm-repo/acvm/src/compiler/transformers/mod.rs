@@ -17,6 +17,37 @@ pub(crate) use r1cs::R1CSTransformer;
 
 use super::{transform_assert_messages, AcirTransformationMap, CompileError};
 
+/// The transformer used to break up [`Opcode::Arithmetic`] expressions for the target [`Language`].
+enum Transformer {
+    R1CS(R1CSTransformer),
+    CSat(CSatTransformer),
+}
+
+impl Transformer {
+    fn mark_solvable(&mut self, witness: Witness) {
+        match self {
+            Transformer::R1CS(transformer) => transformer.mark_solvable(witness),
+            Transformer::CSat(transformer) => transformer.mark_solvable(witness),
+        }
+    }
+
+    fn transform(
+        &mut self,
+        opcode: Expression,
+        intermediate_variables: &mut IndexMap<Expression, (FieldElement, Witness)>,
+        num_witness: &mut u32,
+    ) -> Expression {
+        match self {
+            Transformer::R1CS(transformer) => {
+                transformer.transform(opcode, intermediate_variables, num_witness)
+            }
+            Transformer::CSat(transformer) => {
+                transformer.transform(opcode, intermediate_variables, num_witness)
+            }
+        }
+    }
+}
+
 /// Applies [`ProofSystemCompiler`][crate::ProofSystemCompiler] specific optimizations to a [`Circuit`].
 pub fn transform(
     acir: Circuit,
@@ -49,23 +80,12 @@ pub(super) fn transform_internal(
         FallbackTransformer::transform(acir, is_opcode_supported, acir_opcode_positions)?;
 
     let mut transformer = match &np_language {
-        crate::Language::R1CS => {
-            let transformation_map = AcirTransformationMap { acir_opcode_positions };
-            let transformer = R1CSTransformer::new(acir);
-            return Ok((transformer.transform(), transformation_map));
-        }
-        crate::Language::PLONKCSat { width } => {
-            let mut csat = CSatTransformer::new(*width);
-            for value in acir.circuit_arguments() {
-                csat.mark_solvable(value);
-            }
-            csat
-        }
+        crate::Language::R1CS => Transformer::R1CS(R1CSTransformer::new()),
+        crate::Language::PLONKCSat { width } => Transformer::CSat(CSatTransformer::new(*width)),
     };
-
-    // TODO: the code below is only for CSAT transformer
-    // TODO it may be possible to refactor it in a way that we do not need to return early from the r1cs
-    // TODO or at the very least, we could put all of it inside of CSatOptimizer pass
+    for value in acir.circuit_arguments() {
+        transformer.mark_solvable(value);
+    }
 
     let mut new_acir_opcode_positions: Vec<usize> = Vec::with_capacity(acir_opcode_positions.len());
     // Optimize the arithmetic gates by reducing them into the correct width and
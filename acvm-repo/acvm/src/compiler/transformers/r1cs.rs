@@ -1,16 +1,361 @@
-use acir::circuit::Circuit;
+use std::collections::HashSet;
 
-/// Currently a "noop" transformer.
+use acir::{
+    native_types::{Expression, Witness},
+    FieldElement,
+};
+use indexmap::IndexMap;
+
+use super::CSatTransformer;
+
+/// A transformer which processes any [`Expression`]s to break them up such that each of them
+/// forms a single rank-1 constraint, i.e. it contains at most one multiplication term.
+///
+/// This transformer is only used when targetting the [`R1CS`][crate::Language::R1CS] language.
+///
+/// An expression `q_M1 * wL1 * wR1 + q_M2 * wL2 * wR2 + ... + linear + q_C = 0` is rewritten by moving
+/// multiplication terms into intermediate variables which are then used as linear terms, e.g.
+/// `t = wL2 * wR2` results in `q_M1 * wL1 * wR1 + q_M2 * t + ... + linear + q_C = 0`.
+/// The remaining multiplication term together with the linear terms can then be read as `A * B = C`.
 pub(crate) struct R1CSTransformer {
-    acir: Circuit,
+    /// Track the witness that can be solved
+    solvable_witness: HashSet<Witness>,
 }
 
 impl R1CSTransformer {
-    pub(crate) fn new(acir: Circuit) -> Self {
-        Self { acir }
+    pub(crate) fn new() -> Self {
+        Self { solvable_witness: HashSet::new() }
+    }
+
+    /// Adds the witness to set of solvable witness
+    pub(crate) fn mark_solvable(&mut self, witness: Witness) {
+        self.solvable_witness.insert(witness);
+    }
+
+    fn is_solvable(&self, witness: &Witness) -> bool {
+        self.solvable_witness.contains(witness)
+    }
+
+    /// Check if the equation 'expression=0' can be solved, and if yes, add the solved witness to set of solvable witness
+    fn try_solve(&mut self, opcode: &Expression) {
+        let mut unresolved = HashSet::new();
+        for (_, w1, w2) in &opcode.mul_terms {
+            match (self.is_solvable(w1), self.is_solvable(w2)) {
+                (true, true) => (),
+                (false, true) => {
+                    unresolved.insert(*w1);
+                }
+                (true, false) => {
+                    unresolved.insert(*w2);
+                }
+                // A product of two unknown witnesses cannot be solved for.
+                (false, false) => return,
+            }
+        }
+        for (_, w) in &opcode.linear_combinations {
+            if !self.is_solvable(w) {
+                unresolved.insert(*w);
+            }
+        }
+        if unresolved.len() == 1 {
+            let witness = unresolved.into_iter().next().expect("checked length above");
+            self.mark_solvable(witness);
+        }
+    }
+
+    /// Lowers `opcode` into an expression containing at most one multiplication term.
+    ///
+    /// Any intermediate variables which are required to do so are added to `intermediate_variables`.
+    pub(crate) fn transform(
+        &mut self,
+        opcode: Expression,
+        intermediate_variables: &mut IndexMap<Expression, (FieldElement, Witness)>,
+        num_witness: &mut u32,
+    ) -> Expression {
+        let mut opcode = if opcode.mul_terms.len() <= 1 {
+            opcode
+        } else {
+            self.extract_mul_terms(opcode, intermediate_variables, num_witness)
+        };
+        opcode.sort();
+        self.try_solve(&opcode);
+        opcode
+    }
+
+    // In order for the ACVM to be able to solve the circuit, the intermediate variables must be computable
+    // from witnesses which are known at the point the opcode is reached.
+    //
+    // All multiplication terms for which both witnesses are already solvable are replaced by an intermediate variable
+    // `t = wL * wR`. The multiplication terms which remain involve the witness which the opcode solves for, if any.
+    // If there are several of them and they share a common witness `w`, we factor it out, i.e.
+    // `q_1 * x_1 * w + q_2 * x_2 * w` becomes `t * w` with the intermediate variable `t = q_1 * x_1 + q_2 * x_2`.
+    //
+    // Otherwise the expression could not have been solved in the first place and we simply extract all but the first
+    // of the remaining multiplication terms so that the constraint still has the correct shape.
+    fn extract_mul_terms(
+        &mut self,
+        opcode: Expression,
+        intermediate_variables: &mut IndexMap<Expression, (FieldElement, Witness)>,
+        num_witness: &mut u32,
+    ) -> Expression {
+        let (solvable_terms, mut unsolvable_terms): (Vec<_>, Vec<_>) = opcode
+            .mul_terms
+            .into_iter()
+            .partition(|(_, w_l, w_r)| self.is_solvable(w_l) && self.is_solvable(w_r));
+
+        let mut new_opcode = Expression {
+            mul_terms: Vec::new(),
+            linear_combinations: opcode.linear_combinations,
+            q_c: opcode.q_c,
+        };
+        new_opcode.sort();
+
+        let mut solvable_terms = solvable_terms.into_iter();
+        if unsolvable_terms.is_empty() {
+            // We can keep one multiplication term in the expression.
+            new_opcode.mul_terms.extend(solvable_terms.next());
+        }
+        for (q_m, w_l, w_r) in solvable_terms {
+            let intermediate =
+                self.intermediate_product(w_l, w_r, intermediate_variables, num_witness, true);
+            new_opcode = new_opcode.add_mul(q_m, &intermediate);
+        }
+
+        if unsolvable_terms.len() > 1 {
+            if let Some(factored_term) =
+                self.factor_common_witness(&unsolvable_terms, intermediate_variables, num_witness)
+            {
+                unsolvable_terms = factored_term;
+            }
+        }
+
+        let mut unsolvable_terms = unsolvable_terms.into_iter();
+        if let Some((q_m, w_l, w_r)) = unsolvable_terms.next() {
+            let mut product = Expression::default();
+            product.push_multiplication_term(q_m, w_l, w_r);
+            new_opcode = new_opcode.add_mul(FieldElement::one(), &product);
+        }
+        for (q_m, w_l, w_r) in unsolvable_terms {
+            let intermediate =
+                self.intermediate_product(w_l, w_r, intermediate_variables, num_witness, false);
+            new_opcode = new_opcode.add_mul(q_m, &intermediate);
+        }
+
+        new_opcode
+    }
+
+    /// Returns an expression for an intermediate variable equal to `w_l * w_r`.
+    fn intermediate_product(
+        &mut self,
+        w_l: Witness,
+        w_r: Witness,
+        intermediate_variables: &mut IndexMap<Expression, (FieldElement, Witness)>,
+        num_witness: &mut u32,
+        solvable: bool,
+    ) -> Expression {
+        let mut product = Expression::default();
+        product.push_multiplication_term(FieldElement::one(), w_l, w_r);
+
+        let (scale, witness) = CSatTransformer::get_or_create_intermediate_vars(
+            intermediate_variables,
+            product,
+            num_witness,
+        );
+        if solvable {
+            self.mark_solvable(witness);
+        }
+
+        let mut expr = Expression::default();
+        expr.push_addition_term(scale, witness);
+        expr
+    }
+
+    /// Attempts to rewrite `q_1 * x_1 * w + q_2 * x_2 * w + ...` as a single multiplication term `t * w`,
+    /// where `t = q_1 * x_1 + q_2 * x_2 + ...` is a solvable intermediate variable.
+    fn factor_common_witness(
+        &mut self,
+        mul_terms: &[(FieldElement, Witness, Witness)],
+        intermediate_variables: &mut IndexMap<Expression, (FieldElement, Witness)>,
+        num_witness: &mut u32,
+    ) -> Option<Vec<(FieldElement, Witness, Witness)>> {
+        let (_, first_l, first_r) = mul_terms[0];
+        let common_witness = [first_l, first_r].into_iter().find(|witness| {
+            !self.is_solvable(witness)
+                && mul_terms.iter().all(|(_, w_l, w_r)| w_l == witness || w_r == witness)
+        })?;
+
+        let mut factor = Expression::default();
+        for (q_m, w_l, w_r) in mul_terms {
+            let other_witness = if *w_l == common_witness { *w_r } else { *w_l };
+            if !self.is_solvable(&other_witness) {
+                return None;
+            }
+            let mut term = Expression::default();
+            term.push_addition_term(*q_m, other_witness);
+            factor = factor.add_mul(FieldElement::one(), &term);
+        }
+
+        let factored_term = match factor.linear_combinations.as_slice() {
+            // The multiplication terms cancel each other out.
+            [] => Vec::new(),
+            [(q_m, other_witness)] => vec![(*q_m, *other_witness, common_witness)],
+            _ => {
+                let (scale, witness) = CSatTransformer::get_or_create_intermediate_vars(
+                    intermediate_variables,
+                    factor,
+                    num_witness,
+                );
+                self.mark_solvable(witness);
+                vec![(scale, witness, common_witness)]
+            }
+        };
+        Some(factored_term)
     }
-    // TODO: We could possibly make sure that all polynomials are at most degree-2
-    pub(crate) fn transform(self) -> Circuit {
-        self.acir
+}
+
+#[cfg(test)]
+mod tests {
+    use acir::{
+        circuit::{Circuit, Opcode, OpcodeLocation, PublicInputs},
+        native_types::{Expression, Witness},
+        FieldElement,
+    };
+    use indexmap::IndexMap;
+
+    use super::R1CSTransformer;
+    use crate::{compiler::transform, Language};
+
+    fn mul_term(w_l: u32, w_r: u32) -> (FieldElement, Witness, Witness) {
+        (FieldElement::one(), Witness(w_l), Witness(w_r))
+    }
+
+    #[test]
+    fn leaves_rank_one_constraints_untouched() {
+        // a * b - c = 0
+        let opcode = Expression {
+            mul_terms: vec![mul_term(1, 2)],
+            linear_combinations: vec![(-FieldElement::one(), Witness(3))],
+            q_c: FieldElement::zero(),
+        };
+
+        let mut intermediate_variables = IndexMap::new();
+        let mut num_witness = 4;
+        let mut transformer = R1CSTransformer::new();
+        transformer.mark_solvable(Witness(1));
+        transformer.mark_solvable(Witness(2));
+
+        let transformed =
+            transformer.transform(opcode.clone(), &mut intermediate_variables, &mut num_witness);
+
+        assert_eq!(transformed, opcode);
+        assert!(intermediate_variables.is_empty());
+        assert!(transformer.is_solvable(&Witness(3)));
+    }
+
+    #[test]
+    fn extracts_known_products() {
+        // a * b + c * d - e = 0
+        let opcode = Expression {
+            mul_terms: vec![mul_term(1, 2), mul_term(3, 4)],
+            linear_combinations: vec![(-FieldElement::one(), Witness(5))],
+            q_c: FieldElement::zero(),
+        };
+
+        let mut intermediate_variables = IndexMap::new();
+        let mut num_witness = 6;
+        let mut transformer = R1CSTransformer::new();
+        for witness in 1..=4 {
+            transformer.mark_solvable(Witness(witness));
+        }
+
+        let transformed =
+            transformer.transform(opcode, &mut intermediate_variables, &mut num_witness);
+
+        // a * b + t - e = 0 where t = c * d
+        let t = Witness(6);
+        assert_eq!(transformed.mul_terms, vec![mul_term(1, 2)]);
+        assert_eq!(
+            transformed.linear_combinations,
+            vec![(-FieldElement::one(), Witness(5)), (FieldElement::one(), t)]
+        );
+        assert_eq!(intermediate_variables.len(), 1);
+        let (expr, (_, witness)) = intermediate_variables.first().unwrap();
+        assert_eq!(expr.mul_terms, vec![mul_term(3, 4)]);
+        assert_eq!(*witness, t);
+        assert!(transformer.is_solvable(&Witness(5)));
+    }
+
+    #[test]
+    fn factors_out_unknown_witness() {
+        // a * x + b * x - 1 = 0, where x is unknown
+        let x = Witness(3);
+        let opcode = Expression {
+            mul_terms: vec![mul_term(1, 3), mul_term(2, 3)],
+            linear_combinations: vec![],
+            q_c: -FieldElement::one(),
+        };
+
+        let mut intermediate_variables = IndexMap::new();
+        let mut num_witness = 4;
+        let mut transformer = R1CSTransformer::new();
+        transformer.mark_solvable(Witness(1));
+        transformer.mark_solvable(Witness(2));
+
+        let transformed =
+            transformer.transform(opcode, &mut intermediate_variables, &mut num_witness);
+
+        // t * x - 1 = 0 where t = a + b
+        let t = Witness(4);
+        assert_eq!(transformed.mul_terms, vec![(FieldElement::one(), t, x)]);
+        assert!(transformed.linear_combinations.is_empty());
+        let (expr, (_, witness)) = intermediate_variables.first().unwrap();
+        assert!(expr.is_linear());
+        assert_eq!(*witness, t);
+        assert!(transformer.is_solvable(&x));
+    }
+
+    #[test]
+    fn transformation_map_tracks_intermediate_opcodes() {
+        // a * b + c * d + e * f - g = 0
+        let opcodes = vec![
+            Opcode::Arithmetic(Expression {
+                mul_terms: vec![mul_term(1, 2), mul_term(3, 4), mul_term(5, 6)],
+                linear_combinations: vec![(-FieldElement::one(), Witness(7))],
+                q_c: FieldElement::zero(),
+            }),
+            Opcode::Arithmetic(Expression {
+                mul_terms: vec![mul_term(7, 7)],
+                linear_combinations: vec![(-FieldElement::one(), Witness(8))],
+                q_c: FieldElement::zero(),
+            }),
+        ];
+        let circuit = Circuit {
+            current_witness_index: 8,
+            opcodes,
+            private_parameters: (1..=6).map(Witness).collect(),
+            return_values: PublicInputs([Witness(8)].into()),
+            assert_messages: vec![(OpcodeLocation::Acir(1), "square".to_string())],
+            ..Circuit::default()
+        };
+
+        let (transformed, transformation_map) =
+            transform(circuit, Language::R1CS, |_| true).unwrap();
+
+        for opcode in &transformed.opcodes {
+            let Opcode::Arithmetic(expr) = opcode else { panic!("unexpected opcode") };
+            assert!(expr.mul_terms.len() <= 1);
+        }
+
+        // Two intermediate products were created for the first opcode.
+        assert_eq!(transformed.opcodes.len(), 4);
+        assert_eq!(
+            transformation_map.new_locations(OpcodeLocation::Acir(0)).collect::<Vec<_>>(),
+            vec![OpcodeLocation::Acir(0), OpcodeLocation::Acir(1), OpcodeLocation::Acir(2)]
+        );
+        assert_eq!(
+            transformed.assert_messages,
+            vec![(OpcodeLocation::Acir(3), "square".to_string())]
+        );
+        assert!(transformed.current_witness_index > 8);
     }
 }
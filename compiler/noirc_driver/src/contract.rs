@@ -1,5 +1,5 @@
 use serde::{Deserialize, Serialize};
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet};
 
 use acvm::acir::circuit::Circuit;
 use fm::FileId;
@@ -8,6 +8,7 @@ use noirc_errors::debug_info::DebugInfo;
 use noirc_evaluator::errors::SsaReport;
 
 use super::debug::DebugFile;
+use super::{CompiledProgram, NOIR_ARTIFACT_VERSION_STRING};
 
 /// Describes the types of smart contract functions that are allowed.
 /// Unlike the similar enum in noirc_frontend, 'open' and 'unconstrained'
@@ -26,7 +27,7 @@ pub enum ContractFunctionType {
     Unconstrained,
 }
 
-#[derive(Serialize, Deserialize)]
+#[derive(Clone, Serialize, Deserialize)]
 pub struct CompiledContract {
     pub noir_version: String,
 
@@ -51,10 +52,14 @@ pub struct CompiledContract {
 /// A contract function unlike a regular Noir program
 /// however can have additional properties.
 /// One of these being a function type.
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ContractFunction {
     pub name: String,
 
+    /// Hash of the [`Program`][noirc_frontend::monomorphization::ast::Program] from which this
+    /// function was compiled, see [`CompiledProgram::hash`].
+    pub hash: u64,
+
     pub function_type: ContractFunctionType,
 
     pub is_internal: bool,
@@ -68,6 +73,43 @@ pub struct ContractFunction {
     pub bytecode: Circuit,
 
     pub debug: DebugInfo,
+
+    /// The warnings raised while compiling this function, which are also included in the
+    /// warnings of its contract. They aren't stored separately in the contract's artifacts.
+    #[serde(skip)]
+    pub warnings: Vec<SsaReport>,
+}
+
+impl ContractFunction {
+    /// Converts a function of a previously compiled contract into a program, so that it can be
+    /// reused in place of compiling the same function again. `file_map` holds the files of the
+    /// contract, of which the program keeps those its debug info refers to.
+    pub(super) fn into_compiled_program(
+        self,
+        file_map: &BTreeMap<FileId, DebugFile>,
+    ) -> CompiledProgram {
+        let files: BTreeSet<FileId> = self
+            .debug
+            .locations
+            .values()
+            .flat_map(|call_stack| call_stack.iter().map(|location| location.file))
+            .collect();
+        let file_map = file_map
+            .iter()
+            .filter(|(file_id, _)| files.contains(file_id))
+            .map(|(file_id, file)| (*file_id, file.clone()))
+            .collect();
+
+        CompiledProgram {
+            noir_version: NOIR_ARTIFACT_VERSION_STRING.to_string(),
+            hash: self.hash,
+            circuit: self.bytecode,
+            abi: self.abi,
+            debug: self.debug,
+            file_map,
+            warnings: self.warnings,
+        }
+    }
 }
 
 impl ContractFunctionType {
@@ -9,14 +9,18 @@ use iter_extended::vecmap;
 use noirc_abi::{AbiParameter, AbiType, ContractEvent};
 use noirc_errors::{CustomDiagnostic, FileDiagnostic};
 use noirc_evaluator::errors::RuntimeError;
+use noirc_evaluator::ssa::pipeline::{SsaOptions, SsaPass, SsaPipeline};
 use noirc_evaluator::{create_circuit, into_abi_params};
 use noirc_frontend::graph::{CrateId, CrateName};
 use noirc_frontend::hir::def_map::{Contract, CrateDefMap};
 use noirc_frontend::hir::Context;
-use noirc_frontend::monomorphization::monomorphize;
+use noirc_frontend::macros_api::MacroProcessor;
+use noirc_frontend::monomorphization::{monomorphize, monomorphize_debug};
 use noirc_frontend::node_interner::FuncId;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::path::Path;
+use std::rc::Rc;
 
 mod contract;
 mod debug;
@@ -45,9 +49,27 @@ pub struct CompileOptions {
     #[arg(long, hide = true)]
     pub show_ssa: bool,
 
+    /// Only emit the intermediate SSA IR after each run of the given SSA pass
+    #[arg(long, hide = true)]
+    pub show_ssa_pass: Option<SsaPass>,
+
+    /// Comma separated list of the SSA passes to run, in order, instead of the default pipeline.
+    /// Passes may be skipped, repeated or reordered, e.g. `inline,mem2reg,unroll,flatten_cfg`
+    #[arg(long, hide = true)]
+    pub ssa_passes: Option<SsaPipeline>,
+
+    /// Report the time taken by each SSA pass and the instruction counts before and after it
+    #[arg(long, hide = true)]
+    pub time_ssa_passes: bool,
+
     #[arg(long, hide = true)]
     pub show_brillig: bool,
 
+    /// Record where the values of source-level variables can be found during execution,
+    /// which allows the debugger to display them
+    #[arg(long, hide = true)]
+    pub instrument_debug: bool,
+
     /// Display the ACIR for compiled circuit
     #[arg(long)]
     pub print_acir: bool,
@@ -61,6 +83,20 @@ pub struct CompileOptions {
     pub silence_warnings: bool,
 }
 
+impl CompileOptions {
+    /// True if these options require compiling programs even if they haven't changed since they
+    /// were last compiled, e.g. to print their intermediate representations.
+    pub fn force_compile(&self) -> bool {
+        // A non-default SSA pipeline may also produce a different circuit.
+        self.print_acir
+            || self.show_brillig
+            || self.show_ssa
+            || self.show_ssa_pass.is_some()
+            || self.ssa_passes.is_some()
+            || self.time_ssa_passes
+    }
+}
+
 /// Helper type used to signify where only warnings are expected in file diagnostics
 pub type Warnings = Vec<FileDiagnostic>;
 
@@ -82,6 +118,9 @@ pub fn prepare_crate(context: &mut Context, file_name: &Path) -> CrateId {
 
     add_dep(context, root_crate_id, std_crate_id, STD_CRATE_NAME.parse().unwrap());
 
+    #[cfg(feature = "aztec")]
+    add_macro_processor(context, aztec_macros::AztecMacro);
+
     root_crate_id
 }
 
@@ -111,6 +150,12 @@ pub fn add_dep(
         .expect("cyclic dependency triggered");
 }
 
+/// Registers a processor to transform every crate checked with this context,
+/// e.g. to handle custom attributes
+pub fn add_macro_processor(context: &mut Context, processor: impl MacroProcessor + 'static) {
+    context.add_macro_processor(Rc::new(processor));
+}
+
 /// Run the lexing, parsing, name resolution, and type checking passes.
 ///
 /// This returns a (possibly empty) vector of any warnings found on success.
@@ -187,10 +232,15 @@ pub fn compile_main(
 }
 
 /// Run the frontend to check the crate for errors then compile all contracts if there were none
+///
+/// Functions of `cached_contract` whose programs are unchanged are reused rather than compiled
+/// again, unless `force_compile` is set.
 pub fn compile_contract(
     context: &mut Context,
     crate_id: CrateId,
     options: &CompileOptions,
+    cached_contract: Option<CompiledContract>,
+    force_compile: bool,
 ) -> CompilationResult<CompiledContract> {
     let (_, warnings) = check_crate(context, crate_id, options.deny_warnings)?;
 
@@ -212,8 +262,20 @@ pub fn compile_contract(
         return Err(vec![err]);
     };
 
+    // Only a single contract is compiled, so all cached functions belong to it.
+    let (mut cached_functions, cached_file_map) = cached_contract
+        .map(|contract| (contract.functions, contract.file_map))
+        .unwrap_or_default();
+
     for contract in contracts {
-        match compile_contract_inner(context, contract, options) {
+        match compile_contract_inner(
+            context,
+            contract,
+            options,
+            &mut cached_functions,
+            &cached_file_map,
+            force_compile,
+        ) {
             Ok(contract) => compiled_contracts.push(contract),
             Err(mut more_errors) => errors.append(&mut more_errors),
         }
@@ -253,6 +315,9 @@ fn compile_contract_inner(
     context: &Context,
     contract: Contract,
     options: &CompileOptions,
+    cached_functions: &mut Vec<ContractFunction>,
+    cached_file_map: &BTreeMap<FileId, DebugFile>,
+    force_compile: bool,
 ) -> Result<CompiledContract, ErrorsAndWarnings> {
     let mut functions = Vec::new();
     let mut errors = Vec::new();
@@ -271,14 +336,22 @@ fn compile_contract_inner(
             continue;
         }
 
-        let function = match compile_no_check(context, options, function_id, None, true) {
-            Ok(function) => function,
-            Err(new_error) => {
-                errors.push(FileDiagnostic::from(new_error));
-                continue;
-            }
-        };
-        warnings.extend(function.warnings);
+        let cached_program = cached_functions
+            .iter()
+            .position(|function| function.name == name)
+            .map(|index| {
+                cached_functions.swap_remove(index).into_compiled_program(cached_file_map)
+            });
+
+        let function =
+            match compile_no_check(context, options, function_id, cached_program, force_compile) {
+                Ok(function) => function,
+                Err(new_error) => {
+                    errors.push(FileDiagnostic::from(new_error));
+                    continue;
+                }
+            };
+        warnings.extend(function.warnings.iter().cloned());
         let modifiers = context.def_interner.function_modifiers(&function_id);
         let func_type = modifiers
             .contract_function_type
@@ -288,11 +361,13 @@ fn compile_contract_inner(
 
         functions.push(ContractFunction {
             name,
+            hash: function.hash,
             function_type,
             is_internal: modifiers.is_internal.unwrap_or(false),
             abi: function.abi,
             bytecode: function.circuit,
             debug: function.debug,
+            warnings: function.warnings,
         });
     }
 
@@ -331,22 +406,31 @@ pub fn compile_no_check(
     cached_program: Option<CompiledProgram>,
     force_compile: bool,
 ) -> Result<CompiledProgram, RuntimeError> {
-    let program = monomorphize(main_function, &context.def_interner);
+    let program = if options.instrument_debug {
+        monomorphize_debug(main_function, &context.def_interner)
+    } else {
+        monomorphize(main_function, &context.def_interner)
+    };
 
     let hash = fxhash::hash64(&program);
     let hashes_match = cached_program.as_ref().map_or(false, |program| program.hash == hash);
 
     // If user has specified that they want to see intermediate steps printed then we should
     // force compilation even if the program hasn't changed.
-    let force_compile =
-        force_compile || options.print_acir || options.show_brillig || options.show_ssa;
+    let force_compile = force_compile || options.force_compile();
 
     if !force_compile && hashes_match {
         return Ok(cached_program.expect("cache must exist for hashes to match"));
     }
 
+    let ssa_options = SsaOptions {
+        pipeline: options.ssa_passes.clone().unwrap_or_default(),
+        print_ssa: options.show_ssa,
+        print_after: options.show_ssa_pass,
+        time_passes: options.time_ssa_passes,
+    };
     let (circuit, debug, abi, warnings) =
-        create_circuit(context, program, options.show_ssa, options.show_brillig)?;
+        create_circuit(context, program, &ssa_options, options.show_brillig)?;
 
     let file_map = filter_relevant_files(&[debug.clone()], &context.file_manager);
 
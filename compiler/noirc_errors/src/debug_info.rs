@@ -1,4 +1,6 @@
 use acvm::acir::circuit::OpcodeLocation;
+use acvm::acir::native_types::Expression;
+use acvm::brillig_vm::brillig::RegisterOrMemory;
 use acvm::compiler::AcirTransformationMap;
 
 use serde_with::serde_as;
@@ -8,6 +10,7 @@ use std::collections::HashMap;
 use std::mem;
 
 use crate::Location;
+use noirc_printable_type::PrintableType;
 use serde::{Deserialize, Serialize};
 
 #[serde_as]
@@ -18,6 +21,47 @@ pub struct DebugInfo {
     /// that they should be serialized to/from strings.
     #[serde_as(as = "BTreeMap<DisplayFromStr, _>")]
     pub locations: BTreeMap<OpcodeLocation, Vec<Location>>,
+    /// The source-level variables which can be inspected by the debugger.
+    /// This is only filled in when compiling with debug instrumentation.
+    #[serde(default)]
+    pub variables: BTreeMap<DebugVarId, DebugVariable>,
+    /// The variables which are assigned a new value once execution reaches an opcode,
+    /// in the order in which they are assigned.
+    #[serde_as(as = "BTreeMap<DisplayFromStr, _>")]
+    #[serde(default)]
+    pub variable_assignments: BTreeMap<OpcodeLocation, Vec<DebugVarAssignment>>,
+}
+
+/// Identifies a source-level variable within a program
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Deserialize, Serialize)]
+pub struct DebugVarId(pub u32);
+
+/// A source-level variable along with the type needed to decode its value
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct DebugVariable {
+    pub name: String,
+    pub typ: PrintableType,
+}
+
+/// A new value being assigned to a variable
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct DebugVarAssignment {
+    pub var_id: DebugVarId,
+    /// The call stack of the function call the variable belongs to.
+    /// This is empty for the variables of the entry point function.
+    pub frame: Vec<Location>,
+    pub value: DebugVarValue,
+}
+
+/// Where the flattened fields of a variable's value can be found during execution
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub enum DebugVarValue {
+    /// The value is computed by the ACIR opcodes. The assignment only takes place if
+    /// `predicate` evaluates to a non-zero value, as the variable may be assigned within
+    /// a branch which is not taken.
+    Acir { predicate: Expression, values: Vec<Expression> },
+    /// The value is held in the registers of the Brillig VM, with any arrays stored in its memory.
+    Brillig(Vec<RegisterOrMemory>),
 }
 
 /// Holds OpCodes Counts for Acir and Brillig Opcodes
@@ -29,7 +73,7 @@ pub struct OpCodesCount {
 
 impl DebugInfo {
     pub fn new(locations: BTreeMap<OpcodeLocation, Vec<Location>>) -> Self {
-        DebugInfo { locations }
+        DebugInfo { locations, ..DebugInfo::default() }
     }
 
     /// Updates the locations map when the [`Circuit`][acvm::acir::circuit::Circuit] is modified.
@@ -45,6 +89,24 @@ impl DebugInfo {
                 self.locations.insert(new_opcode_location, source_locations.clone());
             });
         }
+
+        // An assignment happens once execution reaches its opcode, so if that opcode is removed
+        // the assignment is moved to the next opcode which remains.
+        let old_assignments = mem::take(&mut self.variable_assignments);
+        for (old_opcode_location, assignments) in old_assignments {
+            let new_opcode_location = match old_opcode_location {
+                OpcodeLocation::Acir(old_acir_index) => {
+                    OpcodeLocation::Acir(update_map.new_acir_index_at_or_after(old_acir_index))
+                }
+                OpcodeLocation::Brillig { .. } => {
+                    match update_map.new_locations(old_opcode_location).next() {
+                        Some(new_opcode_location) => new_opcode_location,
+                        None => continue,
+                    }
+                }
+            };
+            self.variable_assignments.entry(new_opcode_location).or_default().extend(assignments);
+        }
     }
 
     pub fn opcode_location(&self, loc: &OpcodeLocation) -> Option<Vec<Location>> {
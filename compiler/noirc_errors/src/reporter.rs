@@ -2,7 +2,7 @@ use crate::{FileDiagnostic, Location, Span};
 use codespan_reporting::diagnostic::{Diagnostic, Label};
 use codespan_reporting::files::Files;
 use codespan_reporting::term;
-use codespan_reporting::term::termcolor::{ColorChoice, StandardStream};
+use codespan_reporting::term::termcolor::{ColorChoice, StandardStream, WriteColor};
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct CustomDiagnostic {
@@ -138,6 +138,21 @@ impl FileDiagnostic {
     ) -> bool {
         report(files, &self.diagnostic, Some(self.file_id), &self.call_stack, deny_warnings)
     }
+
+    /// Writes the diagnostic to `writer` in the same way as it would be reported to stderr.
+    pub fn emit<'files>(
+        &self,
+        files: &'files impl Files<'files, FileId = fm::FileId>,
+        deny_warnings: bool,
+        writer: &mut impl WriteColor,
+    ) {
+        let config = codespan_reporting::term::Config::default();
+
+        let stack_trace = stack_trace(files, &self.call_stack);
+        let diagnostic =
+            convert_diagnostic(&self.diagnostic, Some(self.file_id), stack_trace, deny_warnings);
+        term::emit(writer, &config, files, &diagnostic).unwrap();
+    }
 }
 
 /// Report the given diagnostic, and return true if it was an error
@@ -18,6 +18,7 @@ use acvm::brillig_vm::brillig::HeapVector;
 use acvm::FieldElement;
 use fxhash::{FxHashMap as HashMap, FxHashSet as HashSet};
 use iter_extended::vecmap;
+use noirc_errors::debug_info::DebugVarId;
 use num_bigint::BigUint;
 
 use super::brillig_black_box::convert_black_box_call;
@@ -375,6 +376,15 @@ impl<'block> BrilligBlock<'block> {
                         &function_results,
                     );
                 }
+                Value::Intrinsic(Intrinsic::DebugVarAssign) => {
+                    let var_id = dfg
+                        .get_numeric_constant(arguments[0])
+                        .expect("ICE: debug variable ids are constants");
+                    let var_id = DebugVarId(var_id.to_u128() as u32);
+                    let values =
+                        vecmap(&arguments[1..], |value_id| self.convert_ssa_value(*value_id, dfg));
+                    self.brillig_context.debug_var_assign(var_id, values);
+                }
                 Value::Intrinsic(Intrinsic::ArrayLen) => {
                     let result_register = self.variables.define_register_variable(
                         self.function_context,
@@ -35,6 +35,7 @@ pub(crate) fn directive_invert() -> GeneratedBrillig {
         ],
         assert_messages: Default::default(),
         locations: Default::default(),
+        debug_var_assignments: Default::default(),
     }
 }
 
@@ -85,5 +86,6 @@ pub(crate) fn directive_quotient(bit_size: u32) -> GeneratedBrillig {
         ],
         assert_messages: Default::default(),
         locations: Default::default(),
+        debug_var_assignments: Default::default(),
     }
 }
@@ -24,6 +24,7 @@ use acvm::{
     FieldElement,
 };
 use debug_show::DebugShow;
+use noirc_errors::debug_info::DebugVarId;
 
 /// Integer arithmetic in Brillig is limited to 127 bit
 /// integers.
@@ -965,6 +966,11 @@ impl BrilligContext {
     pub(crate) fn set_call_stack(&mut self, call_stack: CallStack) {
         self.obj.set_call_stack(call_stack);
     }
+
+    /// Records where the value of a source-level variable is held for the debugger
+    pub(crate) fn debug_var_assign(&mut self, var_id: DebugVarId, values: Vec<RegisterOrMemory>) {
+        self.obj.add_debug_var_assignment(var_id, values);
+    }
 }
 
 pub(crate) fn extract_register(variable: RegisterOrMemory) -> RegisterIndex {
@@ -1,4 +1,5 @@
-use acvm::acir::brillig::Opcode as BrilligOpcode;
+use acvm::acir::brillig::{Opcode as BrilligOpcode, RegisterOrMemory};
+use noirc_errors::debug_info::{DebugVarAssignment, DebugVarId, DebugVarValue};
 use std::collections::{BTreeMap, HashMap};
 
 use crate::ssa::ir::dfg::CallStack;
@@ -18,6 +19,7 @@ pub(crate) struct GeneratedBrillig {
     pub(crate) byte_code: Vec<BrilligOpcode>,
     pub(crate) locations: BTreeMap<OpcodeLocation, CallStack>,
     pub(crate) assert_messages: BTreeMap<OpcodeLocation, String>,
+    pub(crate) debug_var_assignments: BTreeMap<OpcodeLocation, Vec<DebugVarAssignment>>,
 }
 
 #[derive(Default, Debug, Clone)]
@@ -41,6 +43,8 @@ pub(crate) struct BrilligArtifact {
     unresolved_external_call_labels: Vec<(JumpInstructionPosition, UnresolvedJumpLocation)>,
     /// Maps the opcodes that are associated with a callstack to it.
     locations: BTreeMap<OpcodeLocation, CallStack>,
+    /// The source-level variables which are assigned a new value once execution reaches an opcode
+    debug_var_assignments: BTreeMap<OpcodeLocation, Vec<DebugVarAssignment>>,
     /// The current call stack. All opcodes that are pushed will be associated with this call stack.
     call_stack: CallStack,
 }
@@ -75,6 +79,7 @@ impl BrilligArtifact {
             byte_code: self.byte_code,
             locations: self.locations,
             assert_messages: self.assert_messages,
+            debug_var_assignments: self.debug_var_assignments,
         }
     }
 
@@ -145,6 +150,10 @@ impl BrilligArtifact {
         for (position_in_bytecode, call_stack) in obj.locations.iter() {
             self.locations.insert(position_in_bytecode + offset, call_stack.clone());
         }
+
+        for (position_in_bytecode, assignments) in &obj.debug_var_assignments {
+            self.debug_var_assignments.insert(position_in_bytecode + offset, assignments.clone());
+        }
     }
 
     /// Adds a brillig instruction to the brillig byte code
@@ -258,4 +267,23 @@ impl BrilligArtifact {
         let position = self.index_of_next_opcode() - 1;
         self.assert_messages.insert(position, message);
     }
+
+    /// Records that the variable `var_id` is held in `values` once the next opcode is reached.
+    pub(crate) fn add_debug_var_assignment(
+        &mut self,
+        var_id: DebugVarId,
+        values: Vec<RegisterOrMemory>,
+    ) {
+        // The last location of the call stack is the variable's definition,
+        // the rest of it identifies the function call the variable belongs to.
+        let mut frame = self.call_stack.clone();
+        frame.pop_back();
+        let assignment = DebugVarAssignment {
+            var_id,
+            frame: frame.into_iter().collect(),
+            value: DebugVarValue::Brillig(values),
+        };
+        let position = self.index_of_next_opcode();
+        self.debug_var_assignments.entry(position).or_default().push(assignment);
+    }
 }
@@ -10,6 +10,7 @@
 use std::{
     collections::{BTreeMap, BTreeSet},
     ops::Range,
+    time::Instant,
 };
 
 use crate::{
@@ -21,19 +22,26 @@ use acvm::acir::{
     native_types::Witness,
 };
 
-use noirc_errors::debug_info::DebugInfo;
+use noirc_errors::debug_info::{DebugInfo, DebugVarId, DebugVariable};
 
 use noirc_abi::Abi;
 
 use noirc_frontend::{hir::Context, monomorphization::ast::Program};
 
-use self::{abi_gen::gen_abi, acir_gen::GeneratedAcir, ssa_gen::Ssa};
+use self::{
+    abi_gen::gen_abi,
+    acir_gen::GeneratedAcir,
+    pipeline::{SsaOptions, SsaPass},
+    ssa_gen::Ssa,
+};
 
 pub mod abi_gen;
 mod acir_gen;
 pub(super) mod function_builder;
 pub mod ir;
 mod opt;
+mod parser;
+pub mod pipeline;
 pub mod ssa_gen;
 
 /// Optimize the given program by converting it into SSA
@@ -41,29 +49,15 @@ pub mod ssa_gen;
 /// convert the final SSA into ACIR and return it.
 pub(crate) fn optimize_into_acir(
     program: Program,
-    print_ssa_passes: bool,
+    ssa_options: &SsaOptions,
     print_brillig_trace: bool,
 ) -> Result<GeneratedAcir, RuntimeError> {
     let abi_distinctness = program.return_distinctness;
 
-    let ssa_builder = SsaBuilder::new(program, print_ssa_passes)?
-        .run_pass(Ssa::defunctionalize, "After Defunctionalization:")
-        .run_pass(Ssa::inline_functions, "After Inlining:")
-        // Run mem2reg with the CFG separated into blocks
-        .run_pass(Ssa::mem2reg, "After Mem2Reg:")
-        .try_run_pass(Ssa::evaluate_assert_constant, "After Assert Constant:")?
-        .try_run_pass(Ssa::unroll_loops, "After Unrolling:")?
-        .run_pass(Ssa::simplify_cfg, "After Simplifying:")
-        // Run mem2reg before flattening to handle any promotion
-        // of values that can be accessed after loop unrolling.
-        // If there are slice mergers uncovered by loop unrolling
-        // and this pass is missed, slice merging will fail inside of flattening.
-        .run_pass(Ssa::mem2reg, "After Mem2Reg:")
-        .run_pass(Ssa::flatten_cfg, "After Flattening:")
-        // Run mem2reg once more with the flattened CFG to catch any remaining loads/stores
-        .run_pass(Ssa::mem2reg, "After Mem2Reg:")
-        .run_pass(Ssa::fold_constants, "After Constant Folding:")
-        .run_pass(Ssa::dead_instruction_elimination, "After Dead Instruction Elimination:");
+    let mut ssa_builder = SsaBuilder::new(program, ssa_options)?;
+    for pass in ssa_options.pipeline.passes() {
+        ssa_builder = ssa_builder.run_pass(*pass)?;
+    }
 
     let brillig = ssa_builder.to_brillig(print_brillig_trace);
 
@@ -71,7 +65,7 @@ pub(crate) fn optimize_into_acir(
     // We only need to fill out nested slices as we need to have a known length when dealing with memory operations
     // in ACIR gen while this is not necessary in the Brillig IR.
     let ssa = ssa_builder
-        .run_pass(Ssa::fill_internal_slices, "After Fill Internal Slice Dummy Data:")
+        .run_function(Ssa::fill_internal_slices, "Fill Internal Slice Dummy Data")
         .finish();
 
     let last_array_uses = ssa.find_last_array_uses();
@@ -84,12 +78,16 @@ pub(crate) fn optimize_into_acir(
 pub fn create_circuit(
     context: &Context,
     program: Program,
-    enable_ssa_logging: bool,
+    ssa_options: &SsaOptions,
     enable_brillig_logging: bool,
 ) -> Result<(Circuit, DebugInfo, Abi, Vec<SsaReport>), RuntimeError> {
     let func_sig = program.main_function_signature.clone();
-    let mut generated_acir =
-        optimize_into_acir(program, enable_ssa_logging, enable_brillig_logging)?;
+    let debug_variables = program.debug_variables.iter().map(|(id, variable)| {
+        let variable = DebugVariable { name: variable.name.clone(), typ: variable.typ.clone() };
+        (DebugVarId(id.0), variable)
+    });
+    let debug_variables = debug_variables.collect();
+    let mut generated_acir = optimize_into_acir(program, ssa_options, enable_brillig_logging)?;
     let opcodes = generated_acir.take_opcodes();
     let GeneratedAcir {
         current_witness_index,
@@ -97,6 +95,7 @@ pub fn create_circuit(
         locations,
         input_witnesses,
         assert_messages,
+        debug_var_assignments,
         warnings,
         ..
     } = generated_acir;
@@ -127,6 +126,8 @@ pub fn create_circuit(
         .collect();
 
     let mut debug_info = DebugInfo::new(locations);
+    debug_info.variables = debug_variables;
+    debug_info.variable_assignments = debug_var_assignments;
 
     // Perform any ACIR-level optimizations
     let (optimized_circuit, transformation_map) = acvm::compiler::optimize(circuit);
@@ -135,47 +136,67 @@ pub fn create_circuit(
     Ok((optimized_circuit, debug_info, abi, warnings))
 }
 
-// This is just a convenience object to bundle the ssa with `print_ssa_passes` for debug printing.
-struct SsaBuilder {
+// This is just a convenience object to bundle the ssa with the options
+// controlling how passes are printed and timed.
+struct SsaBuilder<'options> {
     ssa: Ssa,
-    print_ssa_passes: bool,
+    options: &'options SsaOptions,
 }
 
-impl SsaBuilder {
-    fn new(program: Program, print_ssa_passes: bool) -> Result<SsaBuilder, RuntimeError> {
+impl<'options> SsaBuilder<'options> {
+    fn new(program: Program, options: &'options SsaOptions) -> Result<Self, RuntimeError> {
         let ssa = ssa_gen::generate_ssa(program)?;
-        Ok(SsaBuilder { print_ssa_passes, ssa }.print("Initial SSA:"))
+        let builder = SsaBuilder { ssa, options };
+        if options.print_ssa {
+            builder.print("Initial SSA:");
+        }
+        Ok(builder)
     }
 
     fn finish(self) -> Ssa {
         self.ssa
     }
 
-    /// Runs the given SSA pass and prints the SSA afterward if `print_ssa_passes` is true.
-    fn run_pass(mut self, pass: fn(Ssa) -> Ssa, msg: &str) -> Self {
+    /// Runs the given SSA pass, printing or timing it if requested by the options.
+    fn run_pass(mut self, pass: SsaPass) -> Result<Self, RuntimeError> {
+        let timer = self.start_timer();
+        self.ssa = pass.run(self.ssa)?;
+        self.finish_pass(timer, pass.description(), self.options.print_after == Some(pass));
+        Ok(self)
+    }
+
+    /// Runs an SSA pass which is not part of the configurable pipeline.
+    fn run_function(mut self, pass: fn(Ssa) -> Ssa, description: &str) -> Self {
+        let timer = self.start_timer();
         self.ssa = pass(self.ssa);
-        self.print(msg)
+        self.finish_pass(timer, description, false);
+        self
     }
 
-    /// The same as `run_pass` but for passes that may fail
-    fn try_run_pass(
-        mut self,
-        pass: fn(Ssa) -> Result<Ssa, RuntimeError>,
-        msg: &str,
-    ) -> Result<Self, RuntimeError> {
-        self.ssa = pass(self.ssa)?;
-        Ok(self.print(msg))
+    /// Returns the instruction count and start time of a pass if passes are being timed.
+    fn start_timer(&self) -> Option<(usize, Instant)> {
+        self.options.time_passes.then(|| (self.ssa.instruction_count(), Instant::now()))
+    }
+
+    fn finish_pass(&self, timer: Option<(usize, Instant)>, description: &str, print_after: bool) {
+        if let Some((instructions_before, start)) = timer {
+            let elapsed = start.elapsed();
+            let instructions_after = self.ssa.instruction_count();
+            println!(
+                "{description:<32} {elapsed:>12.3?}   instructions: {instructions_before} -> {instructions_after}"
+            );
+        }
+        if self.options.print_ssa || print_after {
+            self.print(&format!("After {description}:"));
+        }
     }
 
     fn to_brillig(&self, print_brillig_trace: bool) -> Brillig {
         self.ssa.to_brillig(print_brillig_trace)
     }
 
-    fn print(self, msg: &str) -> Self {
-        if self.print_ssa_passes {
-            println!("{msg}\n{}", self.ssa);
-        }
-        self
+    fn print(&self, msg: &str) {
+        println!("{msg}\n{}", self.ssa);
     }
 }
 
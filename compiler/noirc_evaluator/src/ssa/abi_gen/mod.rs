@@ -21,6 +21,7 @@ fn get_param_name<'a>(pattern: &HirPattern, interner: &'a NodeInterner) -> Optio
         HirPattern::Mutable(pattern, _) => get_param_name(pattern, interner),
         HirPattern::Tuple(_, _) => None,
         HirPattern::Struct(_, _, _) => None,
+        HirPattern::Literal(_, _) | HirPattern::Variant(..) => None,
     }
 }
 
@@ -22,6 +22,7 @@ use acvm::{
 use acvm::{BlackBoxFunctionSolver, BlackBoxResolutionError};
 use fxhash::FxHashMap as HashMap;
 use iter_extended::{try_vecmap, vecmap};
+use noirc_errors::debug_info::DebugVarId;
 use num_bigint::BigUint;
 use std::ops::RangeInclusive;
 use std::{borrow::Cow, hash::Hash};
@@ -1270,6 +1271,23 @@ impl AcirContext {
         Ok(outputs_var)
     }
 
+    /// Records the value of a source-level variable for the debugger. Arrays are flattened
+    /// into their elements, reading from memory any array which may be dynamically indexed.
+    pub(crate) fn debug_var_assign(
+        &mut self,
+        var_id: DebugVarId,
+        predicate: AcirVar,
+        values: Vec<AcirValue>,
+    ) -> Result<(), InternalError> {
+        let mut expressions = Vec::new();
+        for value in values {
+            self.brillig_array_input(&mut expressions, value)?;
+        }
+        let predicate = self.var_to_expression(predicate)?;
+        self.acir_ir.debug_var_assign(var_id, predicate, expressions);
+        Ok(())
+    }
+
     fn brillig_array_input(
         &mut self,
         var_expressions: &mut Vec<Expression>,
@@ -22,6 +22,7 @@ use acvm::{
     FieldElement,
 };
 use iter_extended::vecmap;
+use noirc_errors::debug_info::{DebugVarAssignment, DebugVarId, DebugVarValue};
 use num_bigint::BigUint;
 use std::ops::Range;
 
@@ -55,6 +56,9 @@ pub(crate) struct GeneratedAcir {
     /// Correspondence between an opcode index and the error message associated with it.
     pub(crate) assert_messages: BTreeMap<OpcodeLocation, String>,
 
+    /// The source-level variables which are assigned a new value once execution reaches an opcode
+    pub(crate) debug_var_assignments: BTreeMap<OpcodeLocation, Vec<DebugVarAssignment>>,
+
     pub(crate) warnings: Vec<SsaReport>,
 }
 
@@ -522,6 +526,33 @@ impl GeneratedAcir {
                 message,
             );
         }
+        for (brillig_index, assignments) in generated_brillig.debug_var_assignments {
+            self.debug_var_assignments.insert(
+                OpcodeLocation::Brillig { acir_index: self.opcodes.len() - 1, brillig_index },
+                assignments,
+            );
+        }
+    }
+
+    /// Records that the variable `var_id` holds the given flattened values once the next opcode
+    /// is reached, provided that `predicate` is non-zero.
+    pub(crate) fn debug_var_assign(
+        &mut self,
+        var_id: DebugVarId,
+        predicate: Expression,
+        values: Vec<Expression>,
+    ) {
+        // The last location of the call stack is the variable's definition,
+        // the rest of it identifies the function call the variable belongs to.
+        let mut frame = self.call_stack.clone();
+        frame.pop_back();
+        let assignment = DebugVarAssignment {
+            var_id,
+            frame: frame.into_iter().collect(),
+            value: DebugVarValue::Acir { predicate, values },
+        };
+        let location = OpcodeLocation::Acir(self.opcodes.len());
+        self.debug_var_assignments.entry(location).or_default().push(assignment);
     }
 
     /// Generate gates and control bits witnesses which ensure that out_expr is a permutation of in_expr
@@ -34,6 +34,7 @@ use acvm::{
 use fxhash::FxHashMap as HashMap;
 use im::Vector;
 use iter_extended::{try_vecmap, vecmap};
+use noirc_errors::debug_info::DebugVarId;
 use noirc_frontend::Distinctness;
 
 /// Context struct for the acir generation pass.
@@ -1937,6 +1938,19 @@ impl Context {
                     removed_elem,
                 ])
             }
+            Intrinsic::DebugVarAssign => {
+                let var_id = dfg
+                    .get_numeric_constant(arguments[0])
+                    .expect("ICE: debug variable ids are constants");
+                let var_id = DebugVarId(var_id.to_u128() as u32);
+                let values = vecmap(&arguments[1..], |arg| self.convert_value(*arg, dfg));
+                self.acir_context.debug_var_assign(
+                    var_id,
+                    self.current_side_effects_enabled_var,
+                    values,
+                )?;
+                Ok(Vec::new())
+            }
             _ => todo!("expected a black box function"),
         }
     }
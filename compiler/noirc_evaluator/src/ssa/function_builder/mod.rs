@@ -34,6 +34,10 @@ pub(crate) struct FunctionBuilder {
     current_block: BasicBlockId,
     finished_functions: Vec<Function>,
     call_stack: CallStack,
+
+    /// Whether instructions are simplified when they are inserted. This is only disabled
+    /// when the exact instructions given must be kept, e.g. when parsing textual SSA.
+    pub(super) simplify: bool,
 }
 
 impl FunctionBuilder {
@@ -55,6 +59,7 @@ impl FunctionBuilder {
             current_block,
             finished_functions: Vec::new(),
             call_stack: CallStack::new(),
+            simplify: true,
         }
     }
 
@@ -147,12 +152,19 @@ impl FunctionBuilder {
         instruction: Instruction,
         ctrl_typevars: Option<Vec<Type>>,
     ) -> InsertInstructionResult {
-        self.current_function.dfg.insert_instruction_and_results(
-            instruction,
-            self.current_block,
-            ctrl_typevars,
-            self.call_stack.clone(),
-        )
+        let block = self.current_block;
+        let call_stack = self.call_stack.clone();
+        let dfg = &mut self.current_function.dfg;
+        if self.simplify {
+            dfg.insert_instruction_and_results(instruction, block, ctrl_typevars, call_stack)
+        } else {
+            dfg.insert_instruction_and_results_without_simplification(
+                instruction,
+                block,
+                ctrl_typevars,
+                call_stack,
+            )
+        }
     }
 
     /// Switch to inserting instructions in the given block.
@@ -261,7 +273,7 @@ impl FunctionBuilder {
         result_types: Vec<Type>,
     ) -> Cow<[ValueId]> {
         if let Value::Intrinsic(intrinsic) = &self.current_function.dfg[func] {
-            if intrinsic == &Intrinsic::WrappingShiftLeft {
+            if self.simplify && intrinsic == &Intrinsic::WrappingShiftLeft {
                 let result_type = self.current_function.dfg.type_of_value(arguments[0]);
                 let bit_size = match result_type {
                     Type::Numeric(NumericType::Signed { bit_size })
@@ -2,7 +2,7 @@ use std::collections::BTreeSet;
 
 use super::{
     basic_block::{BasicBlock, BasicBlockId},
-    function::Function,
+    function::{Function, RuntimeType},
 };
 use fxhash::FxHashMap as HashMap;
 
@@ -24,6 +24,10 @@ struct CfgNode {
 /// basic blocks.
 pub(crate) struct ControlFlowGraph {
     data: HashMap<BasicBlockId, CfgNode>,
+    /// Whether blocks may have more than two predecessors. This is only the case in Brillig
+    /// functions, where `break` and `continue` can each jump to the end or start of their loop.
+    /// The flattening of ACIR functions relies on every block having at most two predecessors.
+    allow_many_predecessors: bool,
 }
 
 impl ControlFlowGraph {
@@ -37,7 +41,8 @@ impl ControlFlowGraph {
         let mut data = HashMap::default();
         data.insert(entry_block, empty_node);
 
-        let mut cfg = ControlFlowGraph { data };
+        let allow_many_predecessors = func.runtime() == RuntimeType::Brillig;
+        let mut cfg = ControlFlowGraph { data, allow_many_predecessors };
         cfg.compute(func);
         cfg
     }
@@ -96,7 +101,7 @@ impl ControlFlowGraph {
         predecessor_node.successors.insert(to);
         let successor_node = self.data.entry(to).or_default();
         assert!(
-            successor_node.predecessors.len() < 2,
+            self.allow_many_predecessors || successor_node.predecessors.len() < 2,
             "ICE: A cfg node cannot have more than two predecessors"
         );
         successor_node.predecessors.insert(from);
@@ -132,7 +137,8 @@ impl ControlFlowGraph {
 #[cfg(test)]
 mod tests {
     use crate::ssa::ir::{
-        dfg::CallStack, instruction::TerminatorInstruction, map::Id, types::Type,
+        basic_block::BasicBlockId, dfg::CallStack, function::RuntimeType,
+        instruction::TerminatorInstruction, map::Id, types::Type,
     };
 
     use super::{super::function::Function, ControlFlowGraph};
@@ -276,4 +282,52 @@ mod tests {
             assert!(block2_successors.contains(&ret_block_id));
         }
     }
+
+    /// Builds a function whose last block is jumped to by three other blocks, as happens for the
+    /// end of a loop containing a `break`.
+    fn function_with_three_predecessors(runtime: RuntimeType) -> (Function, BasicBlockId) {
+        let func_id = Id::test_new(0);
+        let mut func = Function::new("func".into(), func_id);
+        func.set_runtime(runtime);
+        let block0_id = func.entry_block();
+        let cond = func.dfg.add_block_parameter(block0_id, Type::unsigned(1));
+        let block1_id = func.dfg.make_block();
+        let block2_id = func.dfg.make_block();
+        let exit_id = func.dfg.make_block();
+
+        func.dfg[block0_id].set_terminator(TerminatorInstruction::JmpIf {
+            condition: cond,
+            then_destination: block1_id,
+            else_destination: exit_id,
+        });
+        func.dfg[block1_id].set_terminator(TerminatorInstruction::JmpIf {
+            condition: cond,
+            then_destination: block2_id,
+            else_destination: exit_id,
+        });
+        func.dfg[block2_id].set_terminator(TerminatorInstruction::Jmp {
+            destination: exit_id,
+            arguments: vec![],
+            call_stack: im::Vector::new(),
+        });
+        func.dfg[exit_id].set_terminator(TerminatorInstruction::Return {
+            return_values: vec![],
+            call_stack: CallStack::new(),
+        });
+        (func, exit_id)
+    }
+
+    #[test]
+    fn brillig_blocks_may_have_many_predecessors() {
+        let (func, exit_id) = function_with_three_predecessors(RuntimeType::Brillig);
+        let cfg = ControlFlowGraph::with_function(&func);
+        assert_eq!(cfg.predecessors(exit_id).len(), 3);
+    }
+
+    #[test]
+    #[should_panic(expected = "cannot have more than two predecessors")]
+    fn acir_blocks_have_at_most_two_predecessors() {
+        let (func, _) = function_with_three_predecessors(RuntimeType::Acir);
+        ControlFlowGraph::with_function(&func);
+    }
 }
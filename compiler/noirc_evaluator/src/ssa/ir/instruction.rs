@@ -47,6 +47,7 @@ pub(crate) enum Intrinsic {
     FromField,
     AsField,
     WrappingShiftLeft,
+    DebugVarAssign,
 }
 
 impl std::fmt::Display for Intrinsic {
@@ -70,6 +71,7 @@ impl std::fmt::Display for Intrinsic {
             Intrinsic::FromField => write!(f, "from_field"),
             Intrinsic::AsField => write!(f, "as_field"),
             Intrinsic::WrappingShiftLeft => write!(f, "wrapping_shift_left"),
+            Intrinsic::DebugVarAssign => write!(f, "debug_var_assign"),
         }
     }
 }
@@ -81,6 +83,9 @@ impl Intrinsic {
     pub(crate) fn has_side_effects(&self) -> bool {
         match self {
             Intrinsic::AssertConstant => true,
+            // Records the value of a variable for the debugger, so it must be kept even though
+            // it has no results.
+            Intrinsic::DebugVarAssign => true,
 
             Intrinsic::Sort
             | Intrinsic::ArrayLen
@@ -123,6 +128,7 @@ impl Intrinsic {
             "from_field" => Some(Intrinsic::FromField),
             "as_field" => Some(Intrinsic::AsField),
             "wrapping_shift_left" => Some(Intrinsic::WrappingShiftLeft),
+            "debug_var_assign" => Some(Intrinsic::DebugVarAssign),
             other => BlackBoxFunc::lookup(other).map(Intrinsic::BlackBox),
         }
     }
@@ -248,6 +254,19 @@ impl Instruction {
         }
     }
 
+    /// True if the ACIR generated for this instruction depends on the predicate set by the
+    /// last [`Instruction::EnableSideEffects`], in which case it cannot be replaced by the same
+    /// instruction executed under another predicate.
+    pub(crate) fn requires_acir_gen_predicate(&self) -> bool {
+        match self {
+            Instruction::Binary(binary) => {
+                matches!(binary.operator, BinaryOp::Div | BinaryOp::Mod | BinaryOp::Lt)
+            }
+            Instruction::ArrayGet { .. } | Instruction::ArraySet { .. } => true,
+            _ => false,
+        }
+    }
+
     pub(crate) fn has_side_effects(&self, dfg: &DataFlowGraph) -> bool {
         use Instruction::*;
         match self {
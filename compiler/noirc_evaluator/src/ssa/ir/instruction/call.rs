@@ -248,6 +248,7 @@ pub(super) fn simplify_call(
         Intrinsic::WrappingShiftLeft => {
             unreachable!("ICE - wrapping shift left should have been proccessed before")
         }
+        Intrinsic::DebugVarAssign => SimplifyResult::None,
     }
 }
 
@@ -68,13 +68,12 @@ fn value(function: &Function, id: ValueId) -> String {
         }
         Value::Function(id) => id.to_string(),
         Value::Intrinsic(intrinsic) => intrinsic.to_string(),
-        Value::Array { array, .. } => {
+        Value::ForeignFunction(name) => name.clone(),
+        Value::Array { array, typ } => {
             let elements = vecmap(array, |element| value(function, *element));
-            format!("[{}]", elements.join(", "))
-        }
-        Value::Param { .. } | Value::Instruction { .. } | Value::ForeignFunction(_) => {
-            id.to_string()
+            format!("{typ} [{}]", elements.join(", "))
         }
+        Value::Param { .. } | Value::Instruction { .. } => id.to_string(),
     }
 }
 
@@ -93,6 +92,26 @@ fn value_list(function: &Function, values: &[ValueId]) -> String {
     vecmap(values, |id| value(function, *id)).join(", ")
 }
 
+/// Display the types of each result of an instruction, e.g. ` -> Field, u32`.
+/// This is needed for instructions whose result types cannot be inferred from their
+/// arguments so that the printed SSA can be parsed back in.
+fn result_types(function: &Function, results: &[ValueId]) -> String {
+    if results.is_empty() {
+        String::new()
+    } else {
+        let types = vecmap(results, |id| function.dfg.type_of_value(*id).to_string());
+        format!(" -> {}", types.join(", "))
+    }
+}
+
+/// Display an optional assert message, e.g. ` 'attempt to add with overflow'`
+fn assert_message(message: &Option<String>) -> String {
+    match message {
+        Some(message) => format!(" '{message}'"),
+        None => String::new(),
+    }
+}
+
 /// Display a terminator instruction
 pub(crate) fn display_terminator(
     function: &Function,
@@ -145,15 +164,18 @@ pub(crate) fn display_instruction(
             let value = show(*value);
             writeln!(f, "truncate {value} to {bit_size} bits, max_bit_size: {max_bit_size}",)
         }
-        Instruction::Constrain(lhs, rhs, message) => match message {
-            Some(message) => writeln!(f, "constrain {} == {} '{message}'", show(*lhs), show(*rhs)),
-            None => writeln!(f, "constrain {} == {}", show(*lhs), show(*rhs)),
-        },
+        Instruction::Constrain(lhs, rhs, message) => {
+            let message = assert_message(message);
+            writeln!(f, "constrain {} == {}{message}", show(*lhs), show(*rhs))
+        }
         Instruction::Call { func, arguments } => {
-            writeln!(f, "call {}({})", show(*func), value_list(function, arguments))
+            let arguments = value_list(function, arguments);
+            writeln!(f, "call {}({arguments}){}", show(*func), result_types(function, results))
         }
         Instruction::Allocate => writeln!(f, "allocate"),
-        Instruction::Load { address } => writeln!(f, "load {}", show(*address)),
+        Instruction::Load { address } => {
+            writeln!(f, "load {}{}", show(*address), result_types(function, results))
+        }
         Instruction::Store { address, value } => {
             writeln!(f, "store {} at {}", show(*value), show(*address))
         }
@@ -161,7 +183,8 @@ pub(crate) fn display_instruction(
             writeln!(f, "enable_side_effects {}", show(*condition))
         }
         Instruction::ArrayGet { array, index } => {
-            writeln!(f, "array_get {}, index {}", show(*array), show(*index))
+            let result_types = result_types(function, results);
+            writeln!(f, "array_get {}, index {}{result_types}", show(*array), show(*index))
         }
         Instruction::ArraySet { array, index, value } => {
             writeln!(
@@ -172,8 +195,9 @@ pub(crate) fn display_instruction(
                 show(*value)
             )
         }
-        Instruction::RangeCheck { value, max_bit_size, .. } => {
-            write!(f, "range_check {} to {} bits", show(*value), *max_bit_size,)
+        Instruction::RangeCheck { value, max_bit_size, assert_message: message } => {
+            let message = assert_message(message);
+            writeln!(f, "range_check {} to {} bits{message}", show(*value), *max_bit_size)
         }
     }
 }
@@ -64,6 +64,10 @@ fn constant_fold(function: &mut Function) {
     }
 }
 
+/// Maps pure instructions to their results, along with the predicate they were executed under
+/// for those whose ACIR depends on it.
+type InstructionResultCache = HashMap<(Instruction, Option<ValueId>), Vec<ValueId>>;
+
 #[derive(Default)]
 struct Context {
     /// Maps pre-folded ValueIds to the new ValueIds obtained by re-inserting the instruction.
@@ -76,7 +80,10 @@ impl Context {
         let instructions = function.dfg[block].take_instructions();
 
         // Cache of instructions without any side-effects along with their outputs.
-        let mut cached_instruction_results: HashMap<Instruction, Vec<ValueId>> = HashMap::default();
+        let mut cached_instruction_results: InstructionResultCache = HashMap::default();
+
+        // The condition set by the last `EnableSideEffects` instruction, if any.
+        let mut side_effects_enabled_var = None;
 
         for instruction_id in instructions {
             Self::fold_constants_into_instruction(
@@ -84,6 +91,7 @@ impl Context {
                 block,
                 instruction_id,
                 &mut cached_instruction_results,
+                &mut side_effects_enabled_var,
             );
         }
         self.block_queue.extend(function.dfg[block].successors());
@@ -93,13 +101,26 @@ impl Context {
         dfg: &mut DataFlowGraph,
         block: BasicBlockId,
         id: InstructionId,
-        instruction_result_cache: &mut HashMap<Instruction, Vec<ValueId>>,
+        instruction_result_cache: &mut InstructionResultCache,
+        side_effects_enabled_var: &mut Option<ValueId>,
     ) {
         let instruction = Self::resolve_instruction(id, dfg);
         let old_results = dfg.instruction_results(id).to_vec();
 
+        if let Instruction::EnableSideEffects { condition } = instruction {
+            *side_effects_enabled_var = Some(condition);
+        }
+        // Instructions depending on the predicate may only reuse results computed under the same one.
+        let predicate = if instruction.requires_acir_gen_predicate() {
+            *side_effects_enabled_var
+        } else {
+            None
+        };
+
         // If a copy of this instruction exists earlier in the block, then reuse the previous results.
-        if let Some(cached_results) = instruction_result_cache.get(&instruction) {
+        if let Some(cached_results) =
+            instruction_result_cache.get(&(instruction.clone(), predicate))
+        {
             Self::replace_result_ids(dfg, &old_results, cached_results);
             return;
         }
@@ -109,7 +130,7 @@ impl Context {
 
         Self::replace_result_ids(dfg, &old_results, &new_results);
 
-        Self::cache_instruction(instruction, new_results, dfg, instruction_result_cache);
+        Self::cache_instruction(instruction, predicate, new_results, dfg, instruction_result_cache);
     }
 
     /// Fetches an [`Instruction`] by its [`InstructionId`] and fully resolves its inputs.
@@ -152,14 +173,15 @@ impl Context {
 
     fn cache_instruction(
         instruction: Instruction,
+        predicate: Option<ValueId>,
         instruction_results: Vec<ValueId>,
         dfg: &DataFlowGraph,
-        instruction_result_cache: &mut HashMap<Instruction, Vec<ValueId>>,
+        instruction_result_cache: &mut InstructionResultCache,
     ) {
         // If the instruction doesn't have side-effects, cache the results so we can reuse them if
         // the same instruction appears again later in the block.
         if instruction.is_pure(dfg) {
-            instruction_result_cache.insert(instruction, instruction_results);
+            instruction_result_cache.insert((instruction, predicate), instruction_results);
         }
     }
 
@@ -333,4 +355,43 @@ mod test {
 
         assert_eq!(instruction, &Instruction::Cast(ValueId::test_new(0), Type::unsigned(32)));
     }
+
+    #[test]
+    fn predicated_instructions_are_not_deduplicated_across_predicates() {
+        // fn main f0 {
+        //   b0(v0: u32, v1: u32, v2: u1):
+        //     enable_side_effects v2
+        //     v3 = lt v0, v1
+        //     enable_side_effects u1 1
+        //     v4 = lt v0, v1
+        //     v5 = lt v0, v1
+        //     return v3, v4, v5
+        // }
+        //
+        // The ACIR generated for `lt` depends on the predicate, so the second comparison must be
+        // kept while the third can be replaced by the results of the second.
+        let main_id = Id::test_new(0);
+
+        let mut builder = FunctionBuilder::new("main".into(), main_id, RuntimeType::Acir);
+        let v0 = builder.add_parameter(Type::unsigned(32));
+        let v1 = builder.add_parameter(Type::unsigned(32));
+        let v2 = builder.add_parameter(Type::bool());
+
+        builder.insert_instruction(Instruction::EnableSideEffects { condition: v2 }, None);
+        let v3 = builder.insert_binary(v0, BinaryOp::Lt, v1);
+        let one = builder.numeric_constant(1u128, Type::bool());
+        builder.insert_instruction(Instruction::EnableSideEffects { condition: one }, None);
+        let v4 = builder.insert_binary(v0, BinaryOp::Lt, v1);
+        let v5 = builder.insert_binary(v0, BinaryOp::Lt, v1);
+        builder.terminate_with_return(vec![v3, v4, v5]);
+
+        let ssa = builder.finish().fold_constants();
+        let main = ssa.main();
+        let instructions = main.dfg[main.entry_block()].instructions();
+        assert_eq!(instructions.len(), 4);
+
+        let [v3, v4, v5] = [v3, v4, v5].map(|value| main.dfg.resolve(value));
+        assert_ne!(v3, v4);
+        assert_eq!(v4, v5);
+    }
 }
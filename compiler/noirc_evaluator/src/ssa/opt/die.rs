@@ -136,6 +136,7 @@ mod test {
             map::Id,
             types::Type,
         },
+        ssa_gen::Ssa,
     };
 
     #[test]
@@ -219,4 +220,23 @@ mod test {
         assert_eq!(main.dfg[main.entry_block()].instructions().len(), 1);
         assert_eq!(main.dfg[b1].instructions().len(), 6);
     }
+
+    #[test]
+    fn unused_instructions_of_parsed_ssa_are_removed() {
+        let src = "
+            acir fn main f0 {
+              b0(v0: Field):
+                v2 = add v0, Field 1
+                v4 = mul v0, Field 2
+                v5 = allocate
+                return v4
+            }
+        ";
+        let ssa: Ssa = src.parse().unwrap();
+        let ssa = ssa.dead_instruction_elimination();
+
+        let expected =
+            "acir fn main f0 {\n  b0(v0: Field):\n    v4 = mul v0, Field 2\n    return v4\n}\n";
+        assert_eq!(ssa.to_string(), expected);
+    }
 }
@@ -0,0 +1,185 @@
+//! Global Value Numbering (GVN) pass: Removes any pure instruction for which an identical
+//! instruction exists in a dominating block, replacing its results with the results of the
+//! dominating instruction.
+//!
+//! Constant folding only deduplicates instructions within the same block. This is sufficient for
+//! ACIR functions once they have been flattened into a single block but misses any redundant
+//! computations across the blocks of Brillig functions, which are never flattened.
+//!
+//! Blocks are visited in reverse post-order, so any block is visited after all of the blocks which
+//! dominate it. An instruction may then be replaced by any previously visited identical instruction
+//! whose block dominates the block the instruction is in.
+use crate::ssa::{
+    ir::{
+        basic_block::BasicBlockId,
+        dfg::DataFlowGraph,
+        dom::DominatorTree,
+        function::Function,
+        instruction::{Instruction, InstructionId},
+        post_order::PostOrder,
+        value::ValueId,
+    },
+    ssa_gen::Ssa,
+};
+use fxhash::FxHashMap as HashMap;
+
+impl Ssa {
+    /// Removes any pure instruction which is already computed by an identical instruction
+    /// in a dominating block.
+    ///
+    /// See [`gvn`][self] module for more information.
+    pub(crate) fn global_value_numbering(mut self) -> Ssa {
+        for function in self.functions.values_mut() {
+            global_value_numbering(function);
+        }
+        self
+    }
+}
+
+/// The results of an instruction, along with the block it is in.
+type AvailableResults = (BasicBlockId, Vec<ValueId>);
+
+fn global_value_numbering(function: &mut Function) {
+    let post_order = PostOrder::with_function(function);
+    let mut dom_tree = DominatorTree::with_function(function);
+
+    // Each pure instruction seen so far, along with the block it is in and its results.
+    // The same instruction may appear in several blocks which do not dominate one another.
+    // Instructions whose ACIR depends on the predicate are also keyed by the predicate they
+    // were executed under.
+    let mut available: HashMap<(Instruction, Option<ValueId>), Vec<AvailableResults>> =
+        HashMap::default();
+
+    for block in post_order.as_slice().iter().rev() {
+        let instructions = function.dfg[*block].take_instructions();
+        let mut kept_instructions = Vec::with_capacity(instructions.len());
+        let mut side_effects_enabled_var = None;
+
+        for instruction_id in instructions {
+            let instruction = resolve_instruction(instruction_id, &function.dfg);
+            if let Instruction::EnableSideEffects { condition } = instruction {
+                side_effects_enabled_var = Some(condition);
+            }
+            if !instruction.is_pure(&function.dfg) {
+                kept_instructions.push(instruction_id);
+                continue;
+            }
+
+            let predicate = if instruction.requires_acir_gen_predicate() {
+                side_effects_enabled_var
+            } else {
+                None
+            };
+            let candidates = available.entry((instruction, predicate)).or_default();
+            let existing = candidates
+                .iter()
+                .find(|(candidate_block, _)| dom_tree.dominates(*candidate_block, *block));
+
+            let results = function.dfg.instruction_results(instruction_id).to_vec();
+            if let Some((_, existing_results)) = existing {
+                for (result, existing_result) in results.into_iter().zip(existing_results) {
+                    function.dfg.set_value_from_id(result, *existing_result);
+                }
+            } else {
+                candidates.push((*block, results));
+                kept_instructions.push(instruction_id);
+            }
+        }
+
+        *function.dfg[*block].instructions_mut() = kept_instructions;
+    }
+}
+
+/// Fetches an [`Instruction`] by its [`InstructionId`] and fully resolves its inputs
+/// so that instructions whose inputs have been replaced compare equal.
+fn resolve_instruction(instruction_id: InstructionId, dfg: &DataFlowGraph) -> Instruction {
+    dfg[instruction_id].clone().map_values(|value_id| dfg.resolve(value_id))
+}
+
+#[cfg(test)]
+mod test {
+    use crate::ssa::opt::assert_printed_ssa;
+    use crate::ssa::ssa_gen::Ssa;
+
+    #[test]
+    fn removes_instructions_computed_in_dominating_blocks() {
+        let src = "
+            brillig fn main f0 {
+              b0(v0: Field, v1: Field, v2: u1):
+                v3 = mul v0, v1
+                jmpif v2 then: b1, else: b2
+              b1():
+                v4 = mul v0, v1
+                v5 = add v4, v0
+                jmp b3(v5)
+              b3(v6: Field):
+                v7 = mul v0, v1
+                v8 = add v6, v7
+                return v8
+              b2():
+                v9 = add v3, v0
+                jmp b3(v9)
+            }
+        ";
+        let ssa: Ssa = src.parse().unwrap();
+        let ssa = ssa.global_value_numbering();
+
+        let expected = "
+            brillig fn main f0 {
+              b0(v0: Field, v1: Field, v2: u1):
+                v3 = mul v0, v1
+                jmpif v2 then: b1, else: b2
+              b1():
+                v5 = add v3, v0
+                jmp b3(v5)
+              b3(v6: Field):
+                v8 = add v6, v3
+                return v8
+              b2():
+                v9 = add v3, v0
+                jmp b3(v9)
+            }
+        ";
+        assert_printed_ssa(&ssa, expected);
+    }
+
+    #[test]
+    fn keeps_instructions_in_sibling_blocks() {
+        // Neither branch dominates the other so both multiplications must remain
+        let src = "
+            brillig fn main f0 {
+              b0(v0: Field, v1: Field, v2: u1):
+                jmpif v2 then: b1, else: b2
+              b1():
+                v3 = mul v0, v1
+                jmp b3(v3)
+              b3(v4: Field):
+                return v4
+              b2():
+                v5 = mul v0, v1
+                jmp b3(v5)
+            }
+        ";
+        let ssa: Ssa = src.parse().unwrap();
+        let ssa = ssa.global_value_numbering();
+        assert_printed_ssa(&ssa, src);
+    }
+
+    #[test]
+    fn keeps_predicated_instructions_under_different_predicates() {
+        // The ACIR generated for `lt` depends on the predicate it is executed under
+        let src = "
+            acir fn main f0 {
+              b0(v0: u32, v1: u32, v2: u1):
+                enable_side_effects v2
+                v3 = lt v0, v1
+                enable_side_effects u1 1
+                v5 = lt v0, v1
+                return v3, v5
+            }
+        ";
+        let ssa: Ssa = src.parse().unwrap();
+        let ssa = ssa.global_value_numbering();
+        assert_printed_ssa(&ssa, src);
+    }
+}
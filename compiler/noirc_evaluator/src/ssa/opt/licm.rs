@@ -0,0 +1,297 @@
+//! Loop Invariant Code Motion (LICM) pass: Moves instructions whose operands do not change between
+//! iterations of a loop out of the loop and into the block preceding it.
+//!
+//! ACIR functions have all of their loops unrolled so this pass only applies to Brillig functions.
+//!
+//! A loop is identified by a back edge from a block in the loop to the loop's header, where the
+//! header dominates every block in the loop. Instructions are only hoisted into the loop's
+//! pre-header, the single block outside of the loop which jumps to its header. Loops without such
+//! a block, which are not produced by SSA-gen, are left as they are.
+//!
+//! Hoisted instructions are executed even when the loop body never runs or when the branch of the
+//! loop body they were in is not taken, so only instructions which can never fail are hoisted.
+//! Instructions are visited in reverse post-order so that an instruction depending only on
+//! previously hoisted instructions can be hoisted as well.
+use std::collections::{BTreeSet, HashSet};
+
+use crate::ssa::{
+    ir::{
+        basic_block::BasicBlockId,
+        cfg::ControlFlowGraph,
+        dfg::DataFlowGraph,
+        dom::DominatorTree,
+        function::{Function, RuntimeType},
+        instruction::{Instruction, InstructionId, TerminatorInstruction},
+        post_order::PostOrder,
+        value::ValueId,
+    },
+    ssa_gen::Ssa,
+};
+
+impl Ssa {
+    /// Hoists loop invariant instructions out of the loops of Brillig functions.
+    ///
+    /// See [`licm`][self] module for more information.
+    pub(crate) fn loop_invariant_code_motion(mut self) -> Ssa {
+        for function in self.functions.values_mut() {
+            if function.runtime() == RuntimeType::Brillig {
+                loop_invariant_code_motion(function);
+            }
+        }
+        self
+    }
+}
+
+struct Loop {
+    header: BasicBlockId,
+    /// Every block in the loop, including its header
+    blocks: BTreeSet<BasicBlockId>,
+}
+
+fn loop_invariant_code_motion(function: &mut Function) {
+    let cfg = ControlFlowGraph::with_function(function);
+    let post_order = PostOrder::with_function(function);
+    let mut dom_tree = DominatorTree::with_cfg_and_post_order(&cfg, &post_order);
+    let reverse_post_order: Vec<_> = post_order.as_slice().iter().rev().copied().collect();
+
+    let mut loops = find_loops(&cfg, &mut dom_tree, &reverse_post_order);
+
+    // Hoist out of inner loops first. Their pre-headers are part of the enclosing loop,
+    // so invariant instructions may then be hoisted further out of the enclosing loop.
+    loops.sort_by_key(|loop_| loop_.blocks.len());
+
+    for loop_ in loops {
+        if let Some(pre_header) = pre_header(function, &cfg, &loop_) {
+            let blocks = reverse_post_order.iter().filter(|block| loop_.blocks.contains(block));
+            hoist_loop_invariants(function, blocks.copied().collect(), pre_header);
+        }
+    }
+}
+
+/// Finds every natural loop in the function. Loops sharing a header, such as the loop
+/// body and a `continue` both jumping back to the loop condition, are merged together.
+fn find_loops(
+    cfg: &ControlFlowGraph,
+    dom_tree: &mut DominatorTree,
+    reverse_post_order: &[BasicBlockId],
+) -> Vec<Loop> {
+    let mut loops: Vec<Loop> = Vec::new();
+
+    for block in reverse_post_order {
+        for successor in cfg.successors(*block) {
+            // An edge to a block dominating this one is a back edge to a loop header
+            if !dom_tree.dominates(successor, *block) {
+                continue;
+            }
+
+            let blocks = loop_blocks(cfg, successor, *block);
+            match loops.iter_mut().find(|loop_| loop_.header == successor) {
+                Some(loop_) => loop_.blocks.extend(blocks),
+                None => loops.push(Loop { header: successor, blocks }),
+            }
+        }
+    }
+    loops
+}
+
+/// Returns the blocks of the loop formed by the back edge from `back_edge_start` to `header`:
+/// the header along with every block which can reach the back edge without going through the header.
+fn loop_blocks(
+    cfg: &ControlFlowGraph,
+    header: BasicBlockId,
+    back_edge_start: BasicBlockId,
+) -> BTreeSet<BasicBlockId> {
+    let mut blocks = BTreeSet::from([header]);
+    let mut stack = vec![back_edge_start];
+
+    while let Some(block) = stack.pop() {
+        if blocks.insert(block) {
+            stack.extend(cfg.predecessors(block));
+        }
+    }
+    blocks
+}
+
+/// Returns the loop's pre-header if it has one: the only block outside of the loop
+/// jumping to the loop header, which must do so unconditionally.
+fn pre_header(function: &Function, cfg: &ControlFlowGraph, loop_: &Loop) -> Option<BasicBlockId> {
+    let mut entries = cfg.predecessors(loop_.header).filter(|block| !loop_.blocks.contains(block));
+    let pre_header = entries.next()?;
+    if entries.next().is_some() {
+        return None;
+    }
+
+    match function.dfg[pre_header].terminator() {
+        Some(TerminatorInstruction::Jmp { .. }) => Some(pre_header),
+        _ => None,
+    }
+}
+
+fn hoist_loop_invariants(
+    function: &mut Function,
+    blocks: Vec<BasicBlockId>,
+    pre_header: BasicBlockId,
+) {
+    // Every value defined within the loop. Values which are not in this set are the same
+    // on every iteration of the loop.
+    let mut defined_in_loop: HashSet<ValueId> = HashSet::new();
+    for block in &blocks {
+        defined_in_loop.extend(function.dfg.block_parameters(*block));
+        for instruction in function.dfg[*block].instructions() {
+            defined_in_loop.extend(function.dfg.instruction_results(*instruction));
+        }
+    }
+
+    for block in blocks {
+        let instructions = function.dfg[block].take_instructions();
+        let mut kept_instructions = Vec::with_capacity(instructions.len());
+
+        for instruction_id in instructions {
+            if can_be_hoisted(&function.dfg, instruction_id, &defined_in_loop) {
+                for result in function.dfg.instruction_results(instruction_id) {
+                    defined_in_loop.remove(result);
+                }
+                function.dfg[pre_header].insert_instruction(instruction_id);
+            } else {
+                kept_instructions.push(instruction_id);
+            }
+        }
+
+        *function.dfg[block].instructions_mut() = kept_instructions;
+    }
+}
+
+/// An instruction can be hoisted if none of its operands are defined within the loop and
+/// executing it can never fail. Instructions which may fail, such as division by a value which
+/// may be zero or an out of bounds array access, could be guarded by a condition within the loop.
+fn can_be_hoisted(
+    dfg: &DataFlowGraph,
+    instruction_id: InstructionId,
+    defined_in_loop: &HashSet<ValueId>,
+) -> bool {
+    let instruction = &dfg[instruction_id];
+    let cannot_fail = match instruction {
+        Instruction::Binary(_) => !instruction.has_side_effects(dfg),
+        Instruction::Cast(..) | Instruction::Not(_) => true,
+        _ => false,
+    };
+
+    let mut is_invariant = true;
+    instruction.for_each_value(|value| {
+        is_invariant &= !defined_in_loop.contains(&dfg.resolve(value));
+    });
+
+    cannot_fail && is_invariant
+}
+
+#[cfg(test)]
+mod test {
+    use crate::ssa::opt::assert_printed_ssa;
+    use crate::ssa::ssa_gen::Ssa;
+
+    #[test]
+    fn hoists_invariant_instructions_out_of_loops() {
+        // unconstrained fn main(x: Field, y: Field) -> Field {
+        //     let mut sum = 0;
+        //     for i in 0..4 {
+        //         sum += i * (x * y + 1);
+        //     }
+        //     sum
+        // }
+        let src = "
+            brillig fn main f0 {
+              b0(v0: Field, v1: Field):
+                v2 = allocate
+                store Field 0 at v2
+                jmp b1(Field 0)
+              b1(v4: Field):
+                v6 = lt v4, Field 4
+                jmpif v6 then: b2, else: b3
+              b2():
+                v7 = mul v0, v1
+                v9 = add v7, Field 1
+                v10 = mul v4, v9
+                v11 = load v2 -> Field
+                v12 = add v11, v10
+                store v12 at v2
+                v13 = add v4, Field 1
+                jmp b1(v13)
+              b3():
+                v14 = load v2 -> Field
+                return v14
+            }
+        ";
+        let ssa: Ssa = src.parse().unwrap();
+        let ssa = ssa.loop_invariant_code_motion();
+
+        let expected = "
+            brillig fn main f0 {
+              b0(v0: Field, v1: Field):
+                v2 = allocate
+                store Field 0 at v2
+                v7 = mul v0, v1
+                v9 = add v7, Field 1
+                jmp b1(Field 0)
+              b1(v4: Field):
+                v6 = lt v4, Field 4
+                jmpif v6 then: b2, else: b3
+              b2():
+                v10 = mul v4, v9
+                v11 = load v2 -> Field
+                v12 = add v11, v10
+                store v12 at v2
+                v13 = add v4, Field 1
+                jmp b1(v13)
+              b3():
+                v14 = load v2 -> Field
+                return v14
+            }
+        ";
+        assert_printed_ssa(&ssa, expected);
+    }
+
+    #[test]
+    fn does_not_hoist_instructions_which_may_fail() {
+        let src = "
+            brillig fn main f0 {
+              b0(v0: u32, v1: u32, v2: [Field; 2]):
+                jmp b1(u32 0)
+              b1(v4: u32):
+                v6 = lt v4, u32 4
+                jmpif v6 then: b2, else: b3
+              b2():
+                v7 = div v0, v1
+                v8 = array_get v2, index v0 -> Field
+                v10 = add v4, u32 1
+                jmp b1(v10)
+              b3():
+                return
+            }
+        ";
+        let ssa: Ssa = src.parse().unwrap();
+        let ssa = ssa.loop_invariant_code_motion();
+        assert_printed_ssa(&ssa, src);
+    }
+
+    #[test]
+    fn does_not_hoist_out_of_acir_functions() {
+        let src = "
+            acir fn main f0 {
+              b0(v0: Field, v1: Field):
+                jmp b1(Field 0)
+              b1(v3: Field):
+                v5 = lt v3, Field 4
+                jmpif v5 then: b2, else: b3
+              b2():
+                v6 = mul v0, v1
+                v8 = add v3, Field 1
+                jmp b1(v8)
+              b3():
+                return
+            }
+        ";
+        let ssa: Ssa = src.parse().unwrap();
+        let ssa = ssa.loop_invariant_code_motion();
+        assert_printed_ssa(&ssa, src);
+    }
+}
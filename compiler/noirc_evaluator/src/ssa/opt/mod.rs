@@ -10,7 +10,19 @@ mod defunctionalize;
 mod die;
 mod fill_internal_slices;
 pub(crate) mod flatten_cfg;
+mod gvn;
 mod inlining;
+mod licm;
 mod mem2reg;
 mod simplify_cfg;
 mod unrolling;
+
+/// Compares the printed SSA to the expected source, ignoring indentation and blank lines
+#[cfg(test)]
+pub(crate) fn assert_printed_ssa(ssa: &crate::ssa::ssa_gen::Ssa, expected: &str) {
+    let normalize = |ssa: &str| {
+        let lines = ssa.lines().map(str::trim).filter(|line| !line.is_empty());
+        lines.collect::<Vec<_>>().join("\n")
+    };
+    assert_eq!(normalize(&ssa.to_string()), normalize(expected));
+}
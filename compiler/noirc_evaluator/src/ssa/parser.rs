@@ -0,0 +1,809 @@
+//! A parser for the textual form of the SSA IR produced by [`super::ir::printer`].
+//!
+//! This allows optimization passes to be tested against small hand-written SSA snippets
+//! and makes it possible to turn the SSA printed by `--show-ssa` back into an [`Ssa`],
+//! e.g. to reduce a bug report down to a minimal reproduction.
+//!
+//! The parser builds the SSA directly through a [`FunctionBuilder`] with simplification
+//! disabled so that instructions are kept exactly as written. Value ids are assigned in the
+//! order values are first created however, so hand-written SSA may be renumbered when it is
+//! printed again. Printing the parsed SSA and parsing it again produces identical output.
+use std::{collections::HashSet, rc::Rc, str::FromStr};
+
+use acvm::FieldElement;
+use fxhash::FxHashMap as HashMap;
+use thiserror::Error;
+
+use self::lexer::{Lexer, SpannedToken, Token};
+use super::{
+    function_builder::FunctionBuilder,
+    ir::{
+        basic_block::BasicBlockId,
+        function::{Function, FunctionId, RuntimeType},
+        instruction::{BinaryOp, Instruction},
+        map::AtomicCounter,
+        types::Type,
+        value::ValueId,
+    },
+    ssa_gen::Ssa,
+};
+
+mod lexer;
+
+#[derive(Debug, Error)]
+pub(crate) enum SsaParseError {
+    #[error("line {line}: unexpected character '{char}'")]
+    UnexpectedChar { char: char, line: usize },
+    #[error("line {line}: invalid number `{number}`")]
+    InvalidNumber { number: String, line: usize },
+    #[error("line {line}: unterminated string")]
+    UnterminatedString { line: usize },
+    #[error("line {line}: expected {expected}, found `{found}`")]
+    Expected { expected: String, found: String, line: usize },
+    #[error("expected {expected}, found end of input")]
+    UnexpectedEof { expected: String },
+    #[error("line {line}: `{name}` is not a known value, function, intrinsic or type")]
+    UnknownValue { name: String, line: usize },
+    #[error("line {line}: expected {expected} result(s) but the instruction produced {found}")]
+    ResultCountMismatch { expected: usize, found: usize, line: usize },
+    #[error("block {block} of function {function} is jumped to but never defined")]
+    UndefinedBlock { block: String, function: String },
+    #[error("function {function} is referenced but never defined")]
+    UndefinedFunction { function: String },
+    #[error("expected at least one function")]
+    NoFunctions,
+}
+
+impl FromStr for Ssa {
+    type Err = SsaParseError;
+
+    /// Parses the textual form of the SSA. The first function is taken to be `main`.
+    fn from_str(source: &str) -> Result<Ssa, SsaParseError> {
+        let tokens = Lexer::new(source).lex()?;
+        Parser::new(tokens).parse_ssa()
+    }
+}
+
+struct Parser {
+    tokens: Vec<SpannedToken>,
+    position: usize,
+
+    /// Only created once the first function header has been parsed since
+    /// a FunctionBuilder always starts out building a function.
+    builder: Option<FunctionBuilder>,
+
+    /// Maps each function id in the source, e.g. `f3`, to the id of the parsed function.
+    function_ids: HashMap<String, FunctionId>,
+    next_function_id: AtomicCounter<Function>,
+    defined_functions: HashSet<String>,
+
+    /// Blocks and values are local to the function currently being parsed.
+    blocks: HashMap<String, BasicBlockId>,
+    defined_blocks: HashSet<String>,
+    values: HashMap<String, ValueId>,
+}
+
+impl Parser {
+    fn new(tokens: Vec<SpannedToken>) -> Self {
+        Self {
+            tokens,
+            position: 0,
+            builder: None,
+            function_ids: HashMap::default(),
+            next_function_id: AtomicCounter::default(),
+            defined_functions: HashSet::new(),
+            blocks: HashMap::default(),
+            defined_blocks: HashSet::new(),
+            values: HashMap::default(),
+        }
+    }
+
+    fn parse_ssa(mut self) -> Result<Ssa, SsaParseError> {
+        self.number_functions();
+        while self.peek().is_some() {
+            self.parse_function()?;
+        }
+
+        if let Some(function) =
+            self.function_ids.keys().find(|name| !self.defined_functions.contains(*name))
+        {
+            return Err(SsaParseError::UndefinedFunction { function: function.clone() });
+        }
+
+        self.builder.map(FunctionBuilder::finish).ok_or(SsaParseError::NoFunctions)
+    }
+
+    /// Assigns ids to functions in the order they are defined, rather than the order they are
+    /// first mentioned in. Since functions are printed in order of their ids this keeps
+    /// printing and then re-parsing the parsed SSA from reordering its functions.
+    fn number_functions(&mut self) {
+        let fn_keyword = Token::Ident("fn".to_owned());
+        let headers = self.tokens.windows(3).filter_map(|window| match &window[2].token {
+            Token::Ident(id) if window[0].token == fn_keyword && is_id(id, 'f') => Some(id.clone()),
+            _ => None,
+        });
+        for id in headers.collect::<Vec<_>>() {
+            self.function_id(&id);
+        }
+    }
+
+    /// Parses a function of the form `acir fn main f0 { <blocks> }`
+    fn parse_function(&mut self) -> Result<(), SsaParseError> {
+        let runtime = match self.eat_ident("a runtime")?.as_str() {
+            "acir" => RuntimeType::Acir,
+            "brillig" => RuntimeType::Brillig,
+            other => return self.expected_previous("`acir` or `brillig`", other),
+        };
+        self.eat_keyword("fn")?;
+        let name = self.eat_ident("a function name")?;
+        let id_name = self.eat_ident("a function id")?;
+        let id = self.function_id(&id_name);
+        self.defined_functions.insert(id_name);
+        self.eat(Token::LeftBrace)?;
+
+        match &mut self.builder {
+            None => {
+                let mut builder = FunctionBuilder::new(name.clone(), id, runtime);
+                builder.simplify = false;
+                self.builder = Some(builder);
+            }
+            Some(builder) => match runtime {
+                RuntimeType::Acir => builder.new_function(name.clone(), id),
+                RuntimeType::Brillig => builder.new_brillig_function(name.clone(), id),
+            },
+        }
+        self.blocks.clear();
+        self.defined_blocks.clear();
+        self.values.clear();
+
+        // The first block of each function is its entry block
+        let entry_block = self.builder().current_block();
+        let entry_name = self.peek_ident().unwrap_or_default();
+        self.blocks.insert(entry_name, entry_block);
+
+        while !self.eat_if(Token::RightBrace) {
+            self.parse_block()?;
+        }
+
+        if let Some(block) = self.blocks.keys().find(|name| !self.defined_blocks.contains(*name)) {
+            return Err(SsaParseError::UndefinedBlock { block: block.clone(), function: name });
+        }
+        Ok(())
+    }
+
+    /// Parses a block of the form `b0(v0: Field, v1: u32): <instructions> <terminator>`
+    fn parse_block(&mut self) -> Result<(), SsaParseError> {
+        let block_name = self.eat_ident("a block")?;
+        let block = self.block_id(&block_name);
+        self.defined_blocks.insert(block_name);
+        self.builder().switch_to_block(block);
+
+        self.eat(Token::LeftParen)?;
+        let parameters = self.parse_separated(Token::RightParen, |this| {
+            let name = this.eat_ident("a block parameter")?;
+            this.eat(Token::Colon)?;
+            Ok((name, this.parse_type()?))
+        })?;
+        for (name, typ) in parameters {
+            let parameter = self.builder().add_block_parameter(block, typ);
+            self.values.insert(name, parameter);
+        }
+        self.eat(Token::Colon)?;
+
+        loop {
+            if self.at_block_end() {
+                return Ok(());
+            }
+            if self.parse_terminator()? {
+                return Ok(());
+            }
+            self.parse_instruction()?;
+        }
+    }
+
+    /// A block ends at the closing brace of its function or at the start of the next block.
+    /// Blocks normally end with a terminator instead; this only happens when printing a
+    /// function that is still under construction.
+    fn at_block_end(&self) -> bool {
+        match self.peek() {
+            None | Some(Token::RightBrace) => true,
+            Some(Token::Ident(name)) => {
+                is_id(name, 'b') && self.peek_nth(1) == Some(&Token::LeftParen)
+            }
+            _ => false,
+        }
+    }
+
+    /// Parses a terminator if there is one, returning whether one was found.
+    fn parse_terminator(&mut self) -> Result<bool, SsaParseError> {
+        let Some(Token::Ident(name)) = self.peek() else {
+            return Ok(false);
+        };
+
+        match name.as_str() {
+            "jmp" => {
+                self.advance();
+                let destination = self.eat_ident("a block")?;
+                let destination = self.block_id(&destination);
+                self.eat(Token::LeftParen)?;
+                let arguments = self.parse_separated(Token::RightParen, Self::parse_value)?;
+                self.builder().terminate_with_jmp(destination, arguments);
+            }
+            "jmpif" => {
+                self.advance();
+                let condition = self.parse_value()?;
+                self.eat_keyword("then")?;
+                self.eat(Token::Colon)?;
+                let then_destination = self.eat_ident("a block")?;
+                let then_destination = self.block_id(&then_destination);
+                self.eat(Token::Comma)?;
+                self.eat_keyword("else")?;
+                self.eat(Token::Colon)?;
+                let else_destination = self.eat_ident("a block")?;
+                let else_destination = self.block_id(&else_destination);
+                self.builder().terminate_with_jmpif(condition, then_destination, else_destination);
+            }
+            "return" => {
+                // There is no closing delimiter after the returned values so they
+                // must all be on the same line as the `return` itself.
+                let line = self.tokens[self.position].line;
+                self.advance();
+                let mut return_values = Vec::new();
+                while self.tokens.get(self.position).map_or(false, |token| token.line == line) {
+                    return_values.push(self.parse_value()?);
+                    if !self.eat_if(Token::Comma) {
+                        break;
+                    }
+                }
+                self.builder().terminate_with_return(return_values);
+            }
+            _ => return Ok(false),
+        }
+        Ok(true)
+    }
+
+    /// Parses a single instruction, optionally preceded by the values it defines: `v2, v3 = ...`
+    fn parse_instruction(&mut self) -> Result<(), SsaParseError> {
+        let line = self.current_line();
+        let mut result_names = Vec::new();
+        if matches!(self.peek_nth(1), Some(Token::Comma | Token::Assign)) {
+            result_names = self.parse_separated(Token::Assign, |this| this.eat_ident("a value"))?;
+        }
+
+        let instruction = self.eat_ident("an instruction")?;
+        let results = match instruction.as_str() {
+            "allocate" => vec![self.builder().insert_allocate()],
+            "array_get" => {
+                let array = self.parse_value()?;
+                self.eat(Token::Comma)?;
+                self.eat_keyword("index")?;
+                let index = self.parse_value()?;
+                let typ = self.parse_single_result_type()?;
+                vec![self.builder().insert_array_get(array, index, typ)]
+            }
+            "array_set" => {
+                let array = self.parse_value()?;
+                self.eat(Token::Comma)?;
+                self.eat_keyword("index")?;
+                let index = self.parse_value()?;
+                self.eat(Token::Comma)?;
+                self.eat_keyword("value")?;
+                let value = self.parse_value()?;
+                vec![self.builder().insert_array_set(array, index, value)]
+            }
+            "call" => {
+                let function = self.parse_value()?;
+                self.eat(Token::LeftParen)?;
+                let arguments = self.parse_separated(Token::RightParen, Self::parse_value)?;
+                let result_types = self.parse_result_types()?;
+                self.builder().insert_call(function, arguments, result_types).to_vec()
+            }
+            "cast" => {
+                let value = self.parse_value()?;
+                self.eat_keyword("as")?;
+                let typ = self.parse_type()?;
+                vec![self.builder().insert_cast(value, typ)]
+            }
+            "constrain" => {
+                let lhs = self.parse_value()?;
+                self.eat(Token::Equal)?;
+                let rhs = self.parse_value()?;
+                let message = self.parse_assert_message();
+                self.builder().insert_constrain(lhs, rhs, message);
+                Vec::new()
+            }
+            "enable_side_effects" => {
+                let condition = self.parse_value()?;
+                let instruction = Instruction::EnableSideEffects { condition };
+                self.builder().insert_instruction(instruction, None);
+                Vec::new()
+            }
+            "load" => {
+                let address = self.parse_value()?;
+                let typ = self.parse_single_result_type()?;
+                vec![self.builder().insert_load(address, typ)]
+            }
+            "not" => {
+                let value = self.parse_value()?;
+                vec![self.builder().insert_not(value)]
+            }
+            "range_check" => {
+                let value = self.parse_value()?;
+                self.eat_keyword("to")?;
+                let max_bit_size = self.eat_u32()?;
+                self.eat_keyword("bits")?;
+                let assert_message = self.parse_assert_message();
+                let instruction = Instruction::RangeCheck { value, max_bit_size, assert_message };
+                self.builder().insert_instruction(instruction, None);
+                Vec::new()
+            }
+            "store" => {
+                let value = self.parse_value()?;
+                self.eat_keyword("at")?;
+                let address = self.parse_value()?;
+                self.builder().insert_store(address, value);
+                Vec::new()
+            }
+            "truncate" => {
+                let value = self.parse_value()?;
+                self.eat_keyword("to")?;
+                let bit_size = self.eat_u32()?;
+                self.eat_keyword("bits")?;
+                self.eat(Token::Comma)?;
+                self.eat_keyword("max_bit_size")?;
+                self.eat(Token::Colon)?;
+                let max_bit_size = self.eat_u32()?;
+                vec![self.builder().insert_truncate(value, bit_size, max_bit_size)]
+            }
+            other => {
+                let Some(operator) = binary_operator(other) else {
+                    return self.expected_previous("an instruction", other);
+                };
+                let lhs = self.parse_value()?;
+                self.eat(Token::Comma)?;
+                let rhs = self.parse_value()?;
+                vec![self.builder().insert_binary(lhs, operator, rhs)]
+            }
+        };
+
+        if results.len() != result_names.len() {
+            let (expected, found) = (result_names.len(), results.len());
+            return Err(SsaParseError::ResultCountMismatch { expected, found, line });
+        }
+        self.values.extend(result_names.into_iter().zip(results));
+        Ok(())
+    }
+
+    /// Parses the optional ` -> T1, T2` suffix of instructions whose result types are not
+    /// determined by their arguments.
+    fn parse_result_types(&mut self) -> Result<Vec<Type>, SsaParseError> {
+        let mut types = Vec::new();
+        if self.eat_if(Token::Arrow) {
+            types.push(self.parse_type()?);
+            while self.eat_if(Token::Comma) {
+                types.push(self.parse_type()?);
+            }
+        }
+        Ok(types)
+    }
+
+    fn parse_single_result_type(&mut self) -> Result<Type, SsaParseError> {
+        self.eat(Token::Arrow)?;
+        self.parse_type()
+    }
+
+    fn parse_assert_message(&mut self) -> Option<String> {
+        match self.peek() {
+            Some(Token::Str(message)) => {
+                let message = message.clone();
+                self.advance();
+                Some(message)
+            }
+            _ => None,
+        }
+    }
+
+    /// Parses a value in any of the forms the printer may display it in:
+    /// - `v3` for parameters and instruction results
+    /// - `Field 2`, `u32 7` for numeric constants
+    /// - `[Field; 2] [Field 1, v0]` for array constants
+    /// - `f1` for functions
+    /// - `to_le_bits`, `print` for intrinsics and foreign functions
+    fn parse_value(&mut self) -> Result<ValueId, SsaParseError> {
+        if self.peek() == Some(&Token::LeftBracket) {
+            let typ = self.parse_type()?;
+            self.eat(Token::LeftBracket)?;
+            let elements = self.parse_separated(Token::RightBracket, Self::parse_value)?;
+            return Ok(self.builder().array_constant(elements.into(), typ));
+        }
+
+        let line = self.current_line();
+        let name = self.eat_ident("a value")?;
+        if let Some(typ) = numeric_type(&name) {
+            let constant = self.eat_int()?;
+            Ok(self.builder().numeric_constant(constant, typ))
+        } else if is_id(&name, 'v') {
+            self.values.get(&name).copied().ok_or(SsaParseError::UnknownValue { name, line })
+        } else if is_id(&name, 'f') {
+            let function = self.function_id(&name);
+            Ok(self.builder().import_function(function))
+        } else if let Some(intrinsic) = self.builder().import_intrinsic(&name) {
+            Ok(intrinsic)
+        } else {
+            Ok(self.builder().import_foreign_function(&name))
+        }
+    }
+
+    /// Parses a type: `Field`, `u32`, `i8`, `reference`, `function`, `[Field, u8; 3]` or `[u8]`
+    fn parse_type(&mut self) -> Result<Type, SsaParseError> {
+        if self.eat_if(Token::LeftBracket) {
+            let mut element_types = vec![self.parse_type()?];
+            while self.eat_if(Token::Comma) {
+                element_types.push(self.parse_type()?);
+            }
+
+            return if self.eat_if(Token::Semicolon) {
+                let length = self.eat_u32()? as usize;
+                self.eat(Token::RightBracket)?;
+                Ok(Type::Array(Rc::new(element_types), length))
+            } else {
+                self.eat(Token::RightBracket)?;
+                Ok(Type::Slice(Rc::new(element_types)))
+            };
+        }
+
+        let name = self.eat_ident("a type")?;
+        match name.as_str() {
+            "reference" => Ok(Type::Reference),
+            "function" => Ok(Type::Function),
+            other => match numeric_type(other) {
+                Some(typ) => Ok(typ),
+                None => self.expected_previous("a type", other),
+            },
+        }
+    }
+
+    /// Parses a list of items separated by commas and ended by the given token,
+    /// consuming the terminating token as well.
+    fn parse_separated<T>(
+        &mut self,
+        end: Token,
+        mut parse_item: impl FnMut(&mut Self) -> Result<T, SsaParseError>,
+    ) -> Result<Vec<T>, SsaParseError> {
+        let mut items = Vec::new();
+        while !self.eat_if(end.clone()) {
+            items.push(parse_item(self)?);
+            if !self.eat_if(Token::Comma) {
+                self.eat(end)?;
+                break;
+            }
+        }
+        Ok(items)
+    }
+
+    fn builder(&mut self) -> &mut FunctionBuilder {
+        self.builder.as_mut().expect("ICE: the builder is created by the first function header")
+    }
+
+    fn function_id(&mut self, name: &str) -> FunctionId {
+        let next_id = &self.next_function_id;
+        *self.function_ids.entry(name.to_owned()).or_insert_with(|| next_id.next())
+    }
+
+    fn block_id(&mut self, name: &str) -> BasicBlockId {
+        if let Some(block) = self.blocks.get(name) {
+            return *block;
+        }
+        let block = self.builder().insert_block();
+        self.blocks.insert(name.to_owned(), block);
+        block
+    }
+
+    fn peek(&self) -> Option<&Token> {
+        self.peek_nth(0)
+    }
+
+    fn peek_nth(&self, n: usize) -> Option<&Token> {
+        self.tokens.get(self.position + n).map(|token| &token.token)
+    }
+
+    fn peek_ident(&self) -> Option<String> {
+        match self.peek() {
+            Some(Token::Ident(name)) => Some(name.clone()),
+            _ => None,
+        }
+    }
+
+    fn advance(&mut self) {
+        self.position += 1;
+    }
+
+    fn current_line(&self) -> usize {
+        let last_token = self.tokens.len().saturating_sub(1);
+        self.tokens.get(self.position.min(last_token)).map_or(1, |token| token.line)
+    }
+
+    /// Consumes the next token if it is the given token, returning whether it did so.
+    fn eat_if(&mut self, token: Token) -> bool {
+        if self.peek() == Some(&token) {
+            self.advance();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn eat(&mut self, token: Token) -> Result<(), SsaParseError> {
+        if self.eat_if(token.clone()) {
+            Ok(())
+        } else {
+            self.expected(format!("`{token}`"))
+        }
+    }
+
+    fn eat_ident(&mut self, expected: &str) -> Result<String, SsaParseError> {
+        match self.peek_ident() {
+            Some(name) => {
+                self.advance();
+                Ok(name)
+            }
+            None => self.expected(expected.to_owned()),
+        }
+    }
+
+    fn eat_keyword(&mut self, keyword: &str) -> Result<(), SsaParseError> {
+        if self.peek_ident().as_deref() == Some(keyword) {
+            self.advance();
+            Ok(())
+        } else {
+            self.expected(format!("`{keyword}`"))
+        }
+    }
+
+    fn eat_int(&mut self) -> Result<FieldElement, SsaParseError> {
+        match self.peek() {
+            Some(Token::Int(value)) => {
+                let value = *value;
+                self.advance();
+                Ok(value)
+            }
+            _ => self.expected("an integer".to_owned()),
+        }
+    }
+
+    fn eat_u32(&mut self) -> Result<u32, SsaParseError> {
+        let value = self.eat_int()?;
+        match value.try_to_u64().and_then(|value| u32::try_from(value).ok()) {
+            Some(value) => Ok(value),
+            None => self.expected_previous("a u32", &value.to_string()),
+        }
+    }
+
+    /// Returns an error that the current token was found where `expected` should have been.
+    fn expected<T>(&self, expected: String) -> Result<T, SsaParseError> {
+        match self.tokens.get(self.position) {
+            Some(token) => {
+                let found = token.token.to_string();
+                Err(SsaParseError::Expected { expected, found, line: token.line })
+            }
+            None => Err(SsaParseError::UnexpectedEof { expected }),
+        }
+    }
+
+    /// Returns an error that the already consumed identifier `found` is not what was expected.
+    fn expected_previous<T>(&self, expected: &str, found: &str) -> Result<T, SsaParseError> {
+        let line = self.tokens[self.position - 1].line;
+        let found = found.to_owned();
+        Err(SsaParseError::Expected { expected: expected.to_owned(), found, line })
+    }
+}
+
+/// Returns true if `name` is an id with the given prefix, such as `v12` or `b3`
+fn is_id(name: &str, prefix: char) -> bool {
+    name.strip_prefix(prefix)
+        .map_or(false, |index| !index.is_empty() && index.chars().all(|c| c.is_ascii_digit()))
+}
+
+fn numeric_type(name: &str) -> Option<Type> {
+    if name == "Field" {
+        return Some(Type::field());
+    }
+    let bit_size = |prefix| name.strip_prefix(prefix).and_then(|bits: &str| bits.parse().ok());
+    if let Some(bit_size) = bit_size('u') {
+        Some(Type::unsigned(bit_size))
+    } else {
+        bit_size('i').map(Type::signed)
+    }
+}
+
+fn binary_operator(name: &str) -> Option<BinaryOp> {
+    let operator = match name {
+        "add" => BinaryOp::Add,
+        "sub" => BinaryOp::Sub,
+        "mul" => BinaryOp::Mul,
+        "div" => BinaryOp::Div,
+        "eq" => BinaryOp::Eq,
+        "mod" => BinaryOp::Mod,
+        "lt" => BinaryOp::Lt,
+        "and" => BinaryOp::And,
+        "or" => BinaryOp::Or,
+        "xor" => BinaryOp::Xor,
+        _ => return None,
+    };
+    Some(operator)
+}
+
+#[cfg(test)]
+mod tests {
+    use acvm::FieldElement;
+
+    use super::SsaParseError;
+    use crate::ssa::{ir::instruction::TerminatorInstruction, opt::assert_printed_ssa, ssa_gen::Ssa};
+
+    fn assert_round_trip(source: &str) {
+        let ssa: Ssa = source.parse().unwrap();
+        assert_printed_ssa(&ssa, source);
+    }
+
+    #[test]
+    fn round_trips_single_block() {
+        assert_round_trip(
+            "
+            acir fn main f0 {
+              b0(v0: Field, v1: Field):
+                v2 = add v0, v1
+                v4 = mul v2, Field 3
+                v5 = not v0
+                v6 = cast v4 as u32
+                constrain v4 == Field 9 'not nine'
+                return v4, v6
+            }
+            ",
+        );
+    }
+
+    #[test]
+    fn round_trips_control_flow_and_calls() {
+        assert_round_trip(
+            "
+            acir fn main f0 {
+              b0(v0: u32, v1: [Field; 2]):
+                v4 = call f1(v0, Field 5) -> u1
+                jmpif v4 then: b1, else: b2
+              b1():
+                v6 = array_get v1, index u32 1 -> Field
+                jmp b3(v6)
+              b3(v7: Field):
+                v8 = allocate
+                store v7 at v8
+                v9 = load v8 -> Field
+                v11 = array_set v1, index u32 0, value v9
+                return v11
+              b2():
+                v12 = truncate v0 to 8 bits, max_bit_size: 32
+                range_check v12 to 8 bits 'too big'
+                enable_side_effects u1 1
+                jmp b3(Field -1)
+            }
+            brillig fn is_small f1 {
+              b0(v0: u32, v1: Field):
+                v3 = lt v0, u32 2⁸
+                call print(v1)
+                v7 = call to_le_bits(v1, u32 8) -> [u1; 8]
+                v11 = call f0(v0, [Field; 2] [v1, Field 2]) -> [Field; 2]
+                return v3
+            }
+            ",
+        );
+    }
+
+    #[test]
+    fn instructions_are_not_simplified() {
+        assert_round_trip(
+            "
+            acir fn main f0 {
+              b0():
+                v2 = add Field 1, Field 2
+                constrain v2 == v2
+                return v2
+            }
+            ",
+        );
+    }
+
+    #[test]
+    fn parses_every_constant_notation() {
+        let source = "
+            acir fn main f0 {
+              b0():
+                return Field 2⁶⁴, Field 2³²×3, Field -2⁶⁴, Field 0x10, Field -7
+            }
+        ";
+        let ssa: Ssa = source.parse().unwrap();
+        let main = ssa.main();
+        let Some(TerminatorInstruction::Return { return_values, .. }) =
+            main.dfg[main.entry_block()].terminator()
+        else {
+            panic!("Expected main to return");
+        };
+
+        let two_to_the_64 = FieldElement::from(1u128 << 64);
+        let expected = [
+            two_to_the_64,
+            FieldElement::from(3u128 << 32),
+            -two_to_the_64,
+            FieldElement::from(16u128),
+            -FieldElement::from(7u128),
+        ];
+        for (value, expected) in return_values.iter().zip(expected) {
+            assert_eq!(main.dfg.get_numeric_constant(*value), Some(expected));
+        }
+    }
+
+    #[test]
+    fn printing_parsed_ssa_is_stable() {
+        // Ids which don't match the order the builder creates values in are renumbered,
+        // after which printing and parsing the result again does not change it further.
+        let source = "
+            acir fn main f3 {
+              b7(v10: Field):
+                v20 = add v10, Field 1
+                jmp b4(v20)
+              b4(v30: Field):
+                return v30
+            }
+        ";
+        let ssa: Ssa = source.parse().unwrap();
+        let printed = ssa.to_string();
+        assert_printed_ssa(
+            &ssa,
+            "
+            acir fn main f0 {
+              b0(v0: Field):
+                v2 = add v0, Field 1
+                jmp b1(v2)
+              b1(v3: Field):
+                return v3
+            }
+            ",
+        );
+
+        let reparsed: Ssa = printed.parse().unwrap();
+        assert_eq!(reparsed.to_string(), printed);
+    }
+
+    #[test]
+    fn reports_unknown_values() {
+        let source = "
+            acir fn main f0 {
+              b0(v0: Field):
+                v1 = add v0, v7
+                return v1
+            }
+        ";
+        let error = source.parse::<Ssa>().err().unwrap();
+        assert!(matches!(error, SsaParseError::UnknownValue { line: 4, .. }), "{error}");
+    }
+
+    #[test]
+    fn reports_undefined_functions_and_blocks() {
+        let source = "
+            acir fn main f0 {
+              b0(v0: Field):
+                call f1(v0)
+                return
+            }
+        ";
+        let error = source.parse::<Ssa>().err().unwrap();
+        assert!(matches!(error, SsaParseError::UndefinedFunction { .. }), "{error}");
+
+        let source = "
+            acir fn main f0 {
+              b0():
+                jmp b1()
+            }
+        ";
+        let error = source.parse::<Ssa>().err().unwrap();
+        assert!(matches!(error, SsaParseError::UndefinedBlock { .. }), "{error}");
+    }
+}
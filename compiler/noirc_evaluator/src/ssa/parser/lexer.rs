@@ -0,0 +1,207 @@
+use std::{iter::Peekable, str::Chars};
+
+use acvm::FieldElement;
+
+use super::SsaParseError;
+
+/// A single token of the textual SSA format along with the line it was found on.
+#[derive(Debug, Clone, PartialEq)]
+pub(super) struct SpannedToken {
+    pub(super) token: Token,
+    pub(super) line: usize,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub(super) enum Token {
+    /// Keywords, instruction names, value ids, block ids, function ids and type names
+    /// are all lexed as identifiers and are told apart by the parser.
+    Ident(String),
+    Int(FieldElement),
+    /// A string literal written with single quotes, e.g. `'attempt to add with overflow'`
+    Str(String),
+    LeftParen,
+    RightParen,
+    LeftBrace,
+    RightBrace,
+    LeftBracket,
+    RightBracket,
+    Comma,
+    Colon,
+    Semicolon,
+    /// =
+    Assign,
+    /// ==
+    Equal,
+    /// ->
+    Arrow,
+}
+
+impl std::fmt::Display for Token {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Token::Ident(name) => write!(f, "{name}"),
+            Token::Int(value) => write!(f, "{value}"),
+            Token::Str(string) => write!(f, "'{string}'"),
+            Token::LeftParen => write!(f, "("),
+            Token::RightParen => write!(f, ")"),
+            Token::LeftBrace => write!(f, "{{"),
+            Token::RightBrace => write!(f, "}}"),
+            Token::LeftBracket => write!(f, "["),
+            Token::RightBracket => write!(f, "]"),
+            Token::Comma => write!(f, ","),
+            Token::Colon => write!(f, ":"),
+            Token::Semicolon => write!(f, ";"),
+            Token::Assign => write!(f, "="),
+            Token::Equal => write!(f, "=="),
+            Token::Arrow => write!(f, "->"),
+        }
+    }
+}
+
+pub(super) struct Lexer<'a> {
+    chars: Peekable<Chars<'a>>,
+    line: usize,
+}
+
+impl<'a> Lexer<'a> {
+    pub(super) fn new(source: &'a str) -> Self {
+        Self { chars: source.chars().peekable(), line: 1 }
+    }
+
+    /// Splits the entire source into tokens.
+    pub(super) fn lex(mut self) -> Result<Vec<SpannedToken>, SsaParseError> {
+        let mut tokens = Vec::new();
+        while let Some(token) = self.next_token()? {
+            tokens.push(SpannedToken { token, line: self.line });
+        }
+        Ok(tokens)
+    }
+
+    fn next_token(&mut self) -> Result<Option<Token>, SsaParseError> {
+        self.skip_whitespace_and_comments();
+
+        let Some(char) = self.chars.next() else {
+            return Ok(None);
+        };
+
+        let token = match char {
+            '(' => Token::LeftParen,
+            ')' => Token::RightParen,
+            '{' => Token::LeftBrace,
+            '}' => Token::RightBrace,
+            '[' => Token::LeftBracket,
+            ']' => Token::RightBracket,
+            ',' => Token::Comma,
+            ':' => Token::Colon,
+            ';' => Token::Semicolon,
+            '=' if self.chars.next_if_eq(&'=').is_some() => Token::Equal,
+            '=' => Token::Assign,
+            '-' if self.chars.next_if_eq(&'>').is_some() => Token::Arrow,
+            '-' if self.chars.peek().map_or(false, char::is_ascii_digit) => {
+                let first_digit = self.chars.next().unwrap();
+                Token::Int(-self.lex_number(first_digit)?)
+            }
+            '\'' => self.lex_string()?,
+            char if char.is_ascii_digit() => Token::Int(self.lex_number(char)?),
+            char if is_ident_char(char) => {
+                let mut ident = char.to_string();
+                while let Some(char) = self.chars.next_if(|char| is_ident_char(*char)) {
+                    ident.push(char);
+                }
+                Token::Ident(ident)
+            }
+            other => return Err(SsaParseError::UnexpectedChar { char: other, line: self.line }),
+        };
+        Ok(Some(token))
+    }
+
+    fn skip_whitespace_and_comments(&mut self) {
+        loop {
+            match self.chars.peek() {
+                Some('\n') => {
+                    self.line += 1;
+                    self.chars.next();
+                }
+                Some(char) if char.is_whitespace() => {
+                    self.chars.next();
+                }
+                // Line comments so that hand-written snippets can be annotated
+                Some('/') => while self.chars.next_if(|char| *char != '\n').is_some() {},
+                _ => return,
+            }
+        }
+    }
+
+    /// Lexes an integer in any of the forms `FieldElement`'s Display impl may print it in:
+    /// `123`, `2⁶⁴` or `2³²×5`. Hex literals such as `0xff` are accepted as well.
+    fn lex_number(&mut self, first_digit: char) -> Result<FieldElement, SsaParseError> {
+        let mut digits = first_digit.to_string();
+        while let Some(char) = self.chars.next_if(|char| char.is_ascii_alphanumeric()) {
+            digits.push(char);
+        }
+        let mut value = self.parse_field(&digits)?;
+
+        let mut exponent = String::new();
+        while let Some(digit) = self.chars.peek().and_then(|char| superscript_digit(*char)) {
+            exponent.push(digit);
+            self.chars.next();
+        }
+        if !exponent.is_empty() {
+            value = value.pow(&self.parse_field(&exponent)?);
+        }
+
+        if self.chars.next_if_eq(&'×').is_some() {
+            let mut digits = String::new();
+            while let Some(char) = self.chars.next_if(char::is_ascii_digit) {
+                digits.push(char);
+            }
+            value = value * self.parse_field(&digits)?;
+        }
+        Ok(value)
+    }
+
+    fn parse_field(&self, digits: &str) -> Result<FieldElement, SsaParseError> {
+        FieldElement::try_from_str(digits).ok_or_else(|| SsaParseError::InvalidNumber {
+            number: digits.to_owned(),
+            line: self.line,
+        })
+    }
+
+    fn lex_string(&mut self) -> Result<Token, SsaParseError> {
+        let mut string = String::new();
+        loop {
+            match self.chars.next() {
+                Some('\'') => return Ok(Token::Str(string)),
+                Some(char) => {
+                    if char == '\n' {
+                        self.line += 1;
+                    }
+                    string.push(char);
+                }
+                None => return Err(SsaParseError::UnterminatedString { line: self.line }),
+            }
+        }
+    }
+}
+
+fn is_ident_char(char: char) -> bool {
+    char.is_ascii_alphanumeric() || char == '_'
+}
+
+/// Maps a superscript digit such as `⁶` to its ascii counterpart
+fn superscript_digit(char: char) -> Option<char> {
+    let digit = match char {
+        '⁰' => '0',
+        '¹' => '1',
+        '²' => '2',
+        '³' => '3',
+        '⁴' => '4',
+        '⁵' => '5',
+        '⁶' => '6',
+        '⁷' => '7',
+        '⁸' => '8',
+        '⁹' => '9',
+        _ => return None,
+    };
+    Some(digit)
+}
@@ -0,0 +1,259 @@
+//! Describes which SSA optimization passes are run, and in what order, when compiling a program.
+//!
+//! The default pipeline is the one `optimize_into_acir` has always used. A different pipeline may
+//! be given as a comma separated list of pass names, e.g. `inline,mem2reg,unroll,flatten_cfg`,
+//! which allows passes to be skipped, repeated or reordered without modifying the compiler.
+//! Note that ACIR generation still expects the program to be fully inlined, unrolled and
+//! flattened, so pipelines which omit those passes will likely fail to compile.
+use std::{fmt::Display, str::FromStr};
+
+use iter_extended::try_vecmap;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::errors::RuntimeError;
+
+use super::ssa_gen::Ssa;
+
+#[derive(Debug, Error)]
+pub enum SsaPipelineError {
+    #[error("Unknown SSA pass `{0}`. The available passes are: {}", SsaPass::names())]
+    UnknownPass(String),
+    #[error("An SSA pipeline must contain at least one pass")]
+    EmptyPipeline,
+}
+
+/// A single SSA optimization pass which may be named in an [`SsaPipeline`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub enum SsaPass {
+    Defunctionalize,
+    Inlining,
+    Mem2Reg,
+    AssertConstant,
+    Unrolling,
+    SimplifyCfg,
+    Flattening,
+    ConstantFolding,
+    LoopInvariantCodeMotion,
+    GlobalValueNumbering,
+    DeadInstructionElimination,
+}
+
+impl SsaPass {
+    const ALL: [SsaPass; 11] = [
+        SsaPass::Defunctionalize,
+        SsaPass::Inlining,
+        SsaPass::Mem2Reg,
+        SsaPass::AssertConstant,
+        SsaPass::Unrolling,
+        SsaPass::SimplifyCfg,
+        SsaPass::Flattening,
+        SsaPass::ConstantFolding,
+        SsaPass::LoopInvariantCodeMotion,
+        SsaPass::GlobalValueNumbering,
+        SsaPass::DeadInstructionElimination,
+    ];
+
+    /// The name used to refer to this pass in a pipeline description
+    pub fn name(self) -> &'static str {
+        match self {
+            SsaPass::Defunctionalize => "defunctionalize",
+            SsaPass::Inlining => "inline",
+            SsaPass::Mem2Reg => "mem2reg",
+            SsaPass::AssertConstant => "assert_constant",
+            SsaPass::Unrolling => "unroll",
+            SsaPass::SimplifyCfg => "simplify_cfg",
+            SsaPass::Flattening => "flatten_cfg",
+            SsaPass::ConstantFolding => "fold_constants",
+            SsaPass::LoopInvariantCodeMotion => "licm",
+            SsaPass::GlobalValueNumbering => "gvn",
+            SsaPass::DeadInstructionElimination => "die",
+        }
+    }
+
+    /// The human-readable description printed along with the SSA after this pass runs
+    pub(crate) fn description(self) -> &'static str {
+        match self {
+            SsaPass::Defunctionalize => "Defunctionalization",
+            SsaPass::Inlining => "Inlining",
+            SsaPass::Mem2Reg => "Mem2Reg",
+            SsaPass::AssertConstant => "Assert Constant",
+            SsaPass::Unrolling => "Unrolling",
+            SsaPass::SimplifyCfg => "Simplifying",
+            SsaPass::Flattening => "Flattening",
+            SsaPass::ConstantFolding => "Constant Folding",
+            SsaPass::LoopInvariantCodeMotion => "Loop Invariant Code Motion",
+            SsaPass::GlobalValueNumbering => "Global Value Numbering",
+            SsaPass::DeadInstructionElimination => "Dead Instruction Elimination",
+        }
+    }
+
+    pub(crate) fn run(self, ssa: Ssa) -> Result<Ssa, RuntimeError> {
+        match self {
+            SsaPass::Defunctionalize => Ok(ssa.defunctionalize()),
+            SsaPass::Inlining => Ok(ssa.inline_functions()),
+            SsaPass::Mem2Reg => Ok(ssa.mem2reg()),
+            SsaPass::AssertConstant => ssa.evaluate_assert_constant(),
+            SsaPass::Unrolling => ssa.unroll_loops(),
+            SsaPass::SimplifyCfg => Ok(ssa.simplify_cfg()),
+            SsaPass::Flattening => Ok(ssa.flatten_cfg()),
+            SsaPass::ConstantFolding => Ok(ssa.fold_constants()),
+            SsaPass::LoopInvariantCodeMotion => Ok(ssa.loop_invariant_code_motion()),
+            SsaPass::GlobalValueNumbering => Ok(ssa.global_value_numbering()),
+            SsaPass::DeadInstructionElimination => Ok(ssa.dead_instruction_elimination()),
+        }
+    }
+
+    fn names() -> String {
+        SsaPass::ALL.map(SsaPass::name).join(", ")
+    }
+}
+
+impl Display for SsaPass {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+impl FromStr for SsaPass {
+    type Err = SsaPipelineError;
+
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        let name = name.trim();
+        SsaPass::ALL
+            .into_iter()
+            .find(|pass| pass.name() == name)
+            .ok_or_else(|| SsaPipelineError::UnknownPass(name.to_owned()))
+    }
+}
+
+impl TryFrom<String> for SsaPass {
+    type Error = SsaPipelineError;
+
+    fn try_from(name: String) -> Result<Self, Self::Error> {
+        name.parse()
+    }
+}
+
+impl From<SsaPass> for String {
+    fn from(pass: SsaPass) -> Self {
+        pass.name().to_owned()
+    }
+}
+
+/// The ordered list of SSA passes to run before generating Brillig and ACIR.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct SsaPipeline {
+    passes: Vec<SsaPass>,
+}
+
+impl SsaPipeline {
+    pub fn passes(&self) -> &[SsaPass] {
+        &self.passes
+    }
+}
+
+impl Default for SsaPipeline {
+    fn default() -> Self {
+        let passes = vec![
+            SsaPass::Defunctionalize,
+            SsaPass::Inlining,
+            // Run mem2reg with the CFG separated into blocks
+            SsaPass::Mem2Reg,
+            SsaPass::AssertConstant,
+            SsaPass::Unrolling,
+            SsaPass::SimplifyCfg,
+            // Run mem2reg before flattening to handle any promotion
+            // of values that can be accessed after loop unrolling.
+            // If there are slice mergers uncovered by loop unrolling
+            // and this pass is missed, slice merging will fail inside of flattening.
+            SsaPass::Mem2Reg,
+            SsaPass::Flattening,
+            // Run mem2reg once more with the flattened CFG to catch any remaining loads/stores
+            SsaPass::Mem2Reg,
+            SsaPass::ConstantFolding,
+            // Brillig functions are neither unrolled nor flattened, so they still contain loops
+            // and redundant computations across blocks. Hoisting loop invariants first lets
+            // value numbering then deduplicate them against computations before the loop.
+            SsaPass::LoopInvariantCodeMotion,
+            SsaPass::GlobalValueNumbering,
+            SsaPass::DeadInstructionElimination,
+        ];
+        SsaPipeline { passes }
+    }
+}
+
+impl Display for SsaPipeline {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let names: Vec<_> = self.passes.iter().map(|pass| pass.name()).collect();
+        write!(f, "{}", names.join(","))
+    }
+}
+
+impl FromStr for SsaPipeline {
+    type Err = SsaPipelineError;
+
+    /// Parses a comma separated list of pass names such as `inline,mem2reg,unroll`
+    fn from_str(pipeline: &str) -> Result<Self, Self::Err> {
+        if pipeline.trim().is_empty() {
+            return Err(SsaPipelineError::EmptyPipeline);
+        }
+        let passes = try_vecmap(pipeline.split(','), SsaPass::from_str)?;
+        Ok(SsaPipeline { passes })
+    }
+}
+
+impl TryFrom<String> for SsaPipeline {
+    type Error = SsaPipelineError;
+
+    fn try_from(pipeline: String) -> Result<Self, Self::Error> {
+        pipeline.parse()
+    }
+}
+
+impl From<SsaPipeline> for String {
+    fn from(pipeline: SsaPipeline) -> Self {
+        pipeline.to_string()
+    }
+}
+
+/// Options controlling which SSA passes are run and what is reported about them.
+#[derive(Debug, Clone, Default)]
+pub struct SsaOptions {
+    /// The passes to run, in order.
+    pub pipeline: SsaPipeline,
+    /// Print the SSA after every pass.
+    pub print_ssa: bool,
+    /// Print the SSA only after each run of the given pass.
+    pub print_after: Option<SsaPass>,
+    /// Print the wall-clock time each pass took along with
+    /// the number of SSA instructions before and after it.
+    pub time_passes: bool,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{SsaPass, SsaPipeline, SsaPipelineError};
+
+    #[test]
+    fn default_pipeline_round_trips() {
+        let pipeline = SsaPipeline::default();
+        assert_eq!(pipeline.to_string().parse::<SsaPipeline>().unwrap(), pipeline);
+    }
+
+    #[test]
+    fn parses_repeated_and_reordered_passes() {
+        let pipeline: SsaPipeline = "inline, mem2reg,unroll,mem2reg".parse().unwrap();
+        let expected = [SsaPass::Inlining, SsaPass::Mem2Reg, SsaPass::Unrolling, SsaPass::Mem2Reg];
+        assert_eq!(pipeline.passes(), expected);
+    }
+
+    #[test]
+    fn rejects_unknown_passes() {
+        let error = "inline,cse".parse::<SsaPipeline>().unwrap_err();
+        assert!(matches!(error, SsaPipelineError::UnknownPass(name) if name == "cse"));
+        assert!(matches!("".parse::<SsaPipeline>(), Err(SsaPipelineError::EmptyPipeline)));
+    }
+}
@@ -10,11 +10,12 @@ use noirc_frontend::{BinaryOpKind, Signedness};
 
 use crate::errors::RuntimeError;
 use crate::ssa::function_builder::FunctionBuilder;
+use crate::ssa::ir::basic_block::BasicBlockId;
 use crate::ssa::ir::dfg::DataFlowGraph;
 use crate::ssa::ir::function::FunctionId as IrFunctionId;
 use crate::ssa::ir::function::{Function, RuntimeType};
 use crate::ssa::ir::instruction::BinaryOp;
-use crate::ssa::ir::instruction::Instruction;
+use crate::ssa::ir::instruction::{Instruction, Intrinsic};
 use crate::ssa::ir::map::AtomicCounter;
 use crate::ssa::ir::types::{NumericType, Type};
 use crate::ssa::ir::value::ValueId;
@@ -38,6 +39,23 @@ pub(super) struct FunctionContext<'a> {
 
     pub(super) builder: FunctionBuilder,
     shared_context: &'a SharedContext,
+
+    /// The loops enclosing the expression currently being compiled, innermost last.
+    loops: Vec<Loop>,
+}
+
+/// The blocks of a loop which `break` and `continue` jump to.
+#[derive(Debug, Copy, Clone)]
+pub(super) struct Loop {
+    /// The block which checks the loop condition, which `continue` jumps back to.
+    pub(super) loop_entry: BasicBlockId,
+
+    /// The index variable of a `for` loop. This is incremented before jumping back to
+    /// the loop entry. Loops without an index variable have no block parameters.
+    pub(super) loop_index: Option<ValueId>,
+
+    /// The block following the loop, which `break` jumps to.
+    pub(super) loop_end: BasicBlockId,
 }
 
 /// Shared context for all functions during ssa codegen. This is the only
@@ -96,7 +114,8 @@ impl<'a> FunctionContext<'a> {
             .1;
 
         let builder = FunctionBuilder::new(function_name, function_id, runtime);
-        let mut this = Self { definitions: HashMap::default(), builder, shared_context };
+        let mut this =
+            Self { definitions: HashMap::default(), builder, shared_context, loops: Vec::new() };
         this.add_parameters_to_scope(parameters);
         this
     }
@@ -108,6 +127,7 @@ impl<'a> FunctionContext<'a> {
     /// avoid calling new_function until the previous function is completely finished with ssa-gen.
     pub(super) fn new_function(&mut self, id: IrFunctionId, func: &ast::Function) {
         self.definitions.clear();
+        self.loops.clear();
         if func.unconstrained {
             self.builder.new_brillig_function(func.name.clone(), id);
         } else {
@@ -124,6 +144,9 @@ impl<'a> FunctionContext<'a> {
         for (id, mutable, _, typ) in parameters {
             self.add_parameter_to_scope(*id, typ, *mutable);
         }
+        for (id, ..) in parameters {
+            self.debug_var_assign(*id);
+        }
     }
 
     /// Adds a "single" parameter to scope.
@@ -643,6 +666,20 @@ impl<'a> FunctionContext<'a> {
         address
     }
 
+    pub(super) fn enter_loop(&mut self, loop_: Loop) {
+        self.loops.push(loop_);
+    }
+
+    pub(super) fn exit_loop(&mut self) {
+        self.loops.pop();
+    }
+
+    /// Returns the innermost loop enclosing the current expression.
+    /// The frontend ensures `break` and `continue` are only used within loops.
+    pub(super) fn current_loop(&self) -> Loop {
+        *self.loops.last().expect("ICE: `break` or `continue` used outside of a loop")
+    }
+
     /// Define a local variable to be some Values that can later be retrieved
     /// by calling self.lookup(id)
     pub(super) fn define(&mut self, id: LocalId, value: Values) {
@@ -656,6 +693,43 @@ impl<'a> FunctionContext<'a> {
         self.definitions.get(&id).expect("lookup: variable not defined").clone()
     }
 
+    /// Records the current value of a local variable for the debugger if the variable is tracked,
+    /// which is only the case when the program was monomorphized for debugging.
+    ///
+    /// The call recording the value is located at the variable's definition. Once functions are
+    /// inlined, the rest of its call stack then identifies the function call the variable is in.
+    pub(super) fn debug_var_assign(&mut self, id: LocalId) {
+        let shared_context = self.shared_context;
+        let Some(variable) = shared_context.program.debug_variables.get(&id) else {
+            return;
+        };
+
+        let call_stack = self.builder.get_call_stack();
+        self.builder.set_location(variable.location);
+
+        let mut arguments = vec![self.builder.field_constant(id.0 as u128)];
+        for value in self.lookup(id).flatten() {
+            arguments.push(value.eval(self));
+        }
+        let debug_var_assign = self.builder.import_intrinsic_id(Intrinsic::DebugVarAssign);
+        self.builder.insert_call(debug_var_assign, arguments, Vec::new());
+
+        self.builder.set_call_stack(call_stack);
+    }
+
+    /// Returns the local variable which is ultimately assigned to by the given lvalue
+    pub(super) fn lvalue_local_id(lvalue: &ast::LValue) -> Option<LocalId> {
+        match lvalue {
+            ast::LValue::Ident(ident) => match ident.definition {
+                ast::Definition::Local(id) => Some(id),
+                _ => None,
+            },
+            ast::LValue::Index { array: lvalue, .. }
+            | ast::LValue::MemberAccess { object: lvalue, .. }
+            | ast::LValue::Dereference { reference: lvalue, .. } => Self::lvalue_local_id(lvalue),
+        }
+    }
+
     /// Extract the given field of the tuple. Panics if the given Values is not
     /// a Tree::Branch or does not have enough fields.
     pub(super) fn get_field(tuple: Values, field_index: usize) -> Values {
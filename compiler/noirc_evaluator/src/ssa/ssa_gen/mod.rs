@@ -18,7 +18,7 @@ use crate::{
 };
 
 use self::{
-    context::FunctionContext,
+    context::{FunctionContext, Loop},
     value::{Tree, Values},
 };
 
@@ -98,6 +98,9 @@ impl<'a> FunctionContext<'a> {
             Expression::Index(index) => self.codegen_index(index),
             Expression::Cast(cast) => self.codegen_cast(cast),
             Expression::For(for_expr) => self.codegen_for(for_expr),
+            Expression::While(while_expr) => self.codegen_while(while_expr),
+            Expression::Break => Ok(self.codegen_break()),
+            Expression::Continue => Ok(self.codegen_continue()),
             Expression::If(if_expr) => self.codegen_if(if_expr),
             Expression::Tuple(tuple) => self.codegen_tuple(tuple),
             Expression::ExtractTupleField(tuple, index) => {
@@ -437,7 +440,10 @@ impl<'a> FunctionContext<'a> {
         // Compile the loop body
         self.builder.switch_to_block(loop_body);
         self.define(for_expr.index_variable, loop_index.into());
+        self.debug_var_assign(for_expr.index_variable);
+        self.enter_loop(Loop { loop_entry, loop_index: Some(loop_index), loop_end });
         self.codegen_expression(&for_expr.block)?;
+        self.exit_loop();
         let new_loop_index = self.make_offset(loop_index, 1);
         self.builder.terminate_with_jmp(loop_entry, vec![new_loop_index]);
 
@@ -446,6 +452,69 @@ impl<'a> FunctionContext<'a> {
         Ok(Self::unit_value())
     }
 
+    /// Codegens a while loop, which has no block parameters unlike a for loop.
+    /// `loop { .. }` is also compiled this way with a `true` condition.
+    ///
+    /// For example, the loop `while cond { block }` is codegen'd as:
+    ///
+    ///   br loop_entry()
+    /// loop_entry():
+    ///   v0 = ... codegen cond ...
+    ///   brif v0, then: loop_body, else: loop_end
+    /// loop_body():
+    ///   ... codegen block ...
+    ///   br loop_entry()
+    /// loop_end():
+    ///   ... This is the current insert point after codegen_while finishes ...
+    ///
+    /// These loops are only allowed in unconstrained code, so they are never unrolled.
+    fn codegen_while(&mut self, while_expr: &ast::While) -> Result<Values, RuntimeError> {
+        let loop_entry = self.builder.insert_block();
+        let loop_body = self.builder.insert_block();
+        let loop_end = self.builder.insert_block();
+
+        self.builder.terminate_with_jmp(loop_entry, vec![]);
+
+        self.builder.switch_to_block(loop_entry);
+        let condition = self.codegen_non_tuple_expression(&while_expr.condition)?;
+        self.builder.terminate_with_jmpif(condition, loop_body, loop_end);
+
+        self.builder.switch_to_block(loop_body);
+        self.enter_loop(Loop { loop_entry, loop_index: None, loop_end });
+        self.codegen_expression(&while_expr.block)?;
+        self.exit_loop();
+        self.builder.terminate_with_jmp(loop_entry, vec![]);
+
+        self.builder.switch_to_block(loop_end);
+        Ok(Self::unit_value())
+    }
+
+    fn codegen_break(&mut self) -> Values {
+        let loop_end = self.current_loop().loop_end;
+        self.builder.terminate_with_jmp(loop_end, vec![]);
+        self.switch_to_unreachable_block();
+        Self::unit_value()
+    }
+
+    fn codegen_continue(&mut self) -> Values {
+        let loop_ = self.current_loop();
+
+        let arguments = match loop_.loop_index {
+            Some(loop_index) => vec![self.make_offset(loop_index, 1)],
+            None => vec![],
+        };
+        self.builder.terminate_with_jmp(loop_.loop_entry, arguments);
+        self.switch_to_unreachable_block();
+        Self::unit_value()
+    }
+
+    /// Any code following a `break` or `continue` can never be executed. It is still compiled
+    /// into a fresh block without predecessors so the current block keeps its terminator.
+    fn switch_to_unreachable_block(&mut self) {
+        let block = self.builder.insert_block();
+        self.builder.switch_to_block(block);
+    }
+
     /// Codegens an if expression, handling the case of what to do if there is no 'else'.
     ///
     /// For example, the expression `if cond { a } else { b }` is codegen'd as:
@@ -583,6 +652,7 @@ impl<'a> FunctionContext<'a> {
         }
 
         self.define(let_expr.id, values);
+        self.debug_var_assign(let_expr.id);
         Ok(Self::unit_value())
     }
 
@@ -618,6 +688,9 @@ impl<'a> FunctionContext<'a> {
         let rhs = self.codegen_expression(&assign.expression)?;
 
         self.assign_new_value(lhs, rhs);
+        if let Some(id) = Self::lvalue_local_id(&assign.lvalue) {
+            self.debug_var_assign(id);
+        }
         Ok(Self::unit_value())
     }
 
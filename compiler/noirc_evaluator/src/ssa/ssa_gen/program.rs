@@ -39,6 +39,15 @@ impl Ssa {
         self.functions.get_mut(&self.main_id).expect("ICE: Ssa should have a main function")
     }
 
+    /// Returns the total number of instructions in the reachable blocks of every function.
+    /// Terminators are not counted.
+    pub(crate) fn instruction_count(&self) -> usize {
+        let blocks = self.functions.values().flat_map(|function| {
+            function.reachable_blocks().into_iter().map(|block| &function.dfg[block])
+        });
+        blocks.map(|block| block.instructions().len()).sum()
+    }
+
     /// Adds a new function to the program
     pub(crate) fn add_fn(
         &mut self,
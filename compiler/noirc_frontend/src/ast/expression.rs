@@ -23,6 +23,7 @@ pub enum ExpressionKind {
     Cast(Box<CastExpression>),
     Infix(Box<InfixExpression>),
     If(Box<IfExpression>),
+    Match(Box<MatchExpression>),
     Variable(Path),
     Tuple(Vec<Expression>),
     Lambda(Box<Lambda>),
@@ -343,6 +344,12 @@ pub struct IfExpression {
     pub alternative: Option<Expression>,
 }
 
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct MatchExpression {
+    pub expression: Expression,
+    pub rules: Vec<(Pattern, Expression)>,
+}
+
 #[derive(Debug, PartialEq, Eq, Clone)]
 pub struct Lambda {
     pub parameters: Vec<(Pattern, UnresolvedType)>,
@@ -472,6 +479,7 @@ impl Display for ExpressionKind {
             Cast(cast) => cast.fmt(f),
             Infix(infix) => infix.fmt(f),
             If(if_expr) => if_expr.fmt(f),
+            Match(match_expr) => match_expr.fmt(f),
             Variable(path) => path.fmt(f),
             Constructor(constructor) => constructor.fmt(f),
             MemberAccess(access) => access.fmt(f),
@@ -615,6 +623,13 @@ impl Display for IfExpression {
     }
 }
 
+impl Display for MatchExpression {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let rules = vecmap(&self.rules, |(pattern, branch)| format!("{pattern} => {branch}"));
+        write!(f, "match {} {{ {} }}", self.expression, rules.join(", "))
+    }
+}
+
 impl Display for Lambda {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let parameters = vecmap(&self.parameters, |(name, r#type)| format!("{name}: {type}"));
@@ -655,6 +670,21 @@ impl FunctionDefinition {
             return_distinctness: Distinctness::DuplicationAllowed,
         }
     }
+
+    /// Associated constants of traits are lowered to functions without parameters which
+    /// return the value of the constant. This lets generic code refer to the constant of
+    /// the impl it is monomorphized with, in the same way it calls its methods.
+    pub fn associated_constant(
+        name: &Ident,
+        typ: &UnresolvedType,
+        value: &Expression,
+    ) -> FunctionDefinition {
+        let statement =
+            Statement { kind: StatementKind::Expression(value.clone()), span: value.span };
+        let body = BlockExpression(vec![statement]);
+        let return_type = FunctionReturnType::Ty(typ.clone());
+        FunctionDefinition::normal(name, &Vec::new(), &[], &body, &[], &return_type)
+    }
 }
 
 impl Display for FunctionDefinition {
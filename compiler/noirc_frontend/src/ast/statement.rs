@@ -35,6 +35,10 @@ pub enum StatementKind {
     Expression(Expression),
     Assign(AssignStatement),
     For(ForLoopStatement),
+    While(WhileStatement),
+    Loop(LoopStatement),
+    Break,
+    Continue,
     // This is an expression with a trailing semi-colon
     Semi(Expression),
     // This statement is the result of a recovered parse error.
@@ -66,13 +70,22 @@ impl Statement {
                 }
                 self.kind
             }
-            // A semicolon on a for loop is optional and does nothing
-            StatementKind::For(_) => self.kind,
+            // As in rust, `break` and `continue` may omit their semicolon at the end of a block
+            StatementKind::Break | StatementKind::Continue => {
+                if semi.is_none() && !last_statement_in_block {
+                    emit_error(missing_semicolon);
+                }
+                self.kind
+            }
+            // A semicolon on a loop is optional and does nothing
+            StatementKind::For(_) | StatementKind::While(_) | StatementKind::Loop(_) => self.kind,
 
             StatementKind::Expression(expr) => {
                 match (&expr.kind, semi, last_statement_in_block) {
                     // Semicolons are optional for these expressions
-                    (ExpressionKind::Block(_), semi, _) | (ExpressionKind::If(_), semi, _) => {
+                    (ExpressionKind::Block(_), semi, _)
+                    | (ExpressionKind::If(_), semi, _)
+                    | (ExpressionKind::Match(_), semi, _) => {
                         if semi.is_some() {
                             StatementKind::Semi(expr)
                         } else {
@@ -433,6 +446,11 @@ pub enum Pattern {
     Mutable(Box<Pattern>, Span),
     Tuple(Vec<Pattern>, Span),
     Struct(Path, Vec<(Ident, Pattern)>, Span),
+    /// A literal pattern, e.g. `1`, `-1` or `true`. These are only valid within `match` expressions.
+    Literal(Expression),
+    /// An enum variant pattern, e.g. `Shape::Circle(radius)` or `Shape::Empty`.
+    /// These are only valid within `match` expressions.
+    Variant(Path, Vec<Pattern>, Span),
 }
 
 impl Pattern {
@@ -602,6 +620,19 @@ pub struct ForLoopStatement {
     pub span: Span,
 }
 
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct WhileStatement {
+    pub condition: Expression,
+    pub block: Expression,
+    pub span: Span,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct LoopStatement {
+    pub block: Expression,
+    pub span: Span,
+}
+
 impl Display for StatementKind {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -610,6 +641,10 @@ impl Display for StatementKind {
             StatementKind::Expression(expression) => expression.fmt(f),
             StatementKind::Assign(assign) => assign.fmt(f),
             StatementKind::For(for_loop) => for_loop.fmt(f),
+            StatementKind::While(while_loop) => while_loop.fmt(f),
+            StatementKind::Loop(loop_statement) => loop_statement.fmt(f),
+            StatementKind::Break => write!(f, "break"),
+            StatementKind::Continue => write!(f, "continue"),
             StatementKind::Semi(semi) => write!(f, "{semi};"),
             StatementKind::Error => write!(f, "Error"),
         }
@@ -685,6 +720,12 @@ impl Display for Pattern {
                 let fields = vecmap(fields, |(name, pattern)| format!("{name}: {pattern}"));
                 write!(f, "{} {{ {} }}", typename, fields.join(", "))
             }
+            Pattern::Literal(literal) => literal.fmt(f),
+            Pattern::Variant(path, fields, _) if fields.is_empty() => path.fmt(f),
+            Pattern::Variant(path, fields, _) => {
+                let fields = vecmap(fields, ToString::to_string);
+                write!(f, "{}({})", path, fields.join(", "))
+            }
         }
     }
 }
@@ -699,3 +740,15 @@ impl Display for ForLoopStatement {
         write!(f, "for {} in {range} {}", self.identifier, self.block)
     }
 }
+
+impl Display for WhileStatement {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "while {} {}", self.condition, self.block)
+    }
+}
+
+impl Display for LoopStatement {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "loop {}", self.block)
+    }
+}
@@ -14,14 +14,15 @@ use crate::hir::type_check::{type_check_func, TypeCheckError, TypeChecker};
 use crate::hir::Context;
 use crate::hir_def::traits::{Trait, TraitConstant, TraitFunction, TraitImpl, TraitType};
 use crate::node_interner::{
-    FuncId, NodeInterner, StmtId, StructId, TraitId, TraitImplId, TypeAliasId,
+    EnumId, FuncId, NodeInterner, StmtId, StructId, TraitId, TraitImplId, TypeAliasId,
 };
 
 use crate::parser::{ParserError, SortedModule};
 use crate::{
-    ExpressionKind, Generics, Ident, LetStatement, Literal, NoirFunction, NoirStruct, NoirTrait,
-    NoirTypeAlias, Path, Shared, StructType, TraitItem, Type, TypeBinding, TypeVariableKind,
-    UnresolvedGenerics, UnresolvedTraitConstraint, UnresolvedType,
+    Expression, ExpressionKind, FunctionDefinition, Generics, Ident, LetStatement, Literal,
+    NoirEnum, NoirFunction, NoirStruct, NoirTrait, NoirTypeAlias, Path, Shared, StructType,
+    TraitItem, Type, TypeBinding, TypeVariableKind, UnresolvedGenerics, UnresolvedTraitConstraint,
+    UnresolvedType,
 };
 use fm::FileId;
 use iter_extended::vecmap;
@@ -76,6 +77,12 @@ pub struct UnresolvedStruct {
     pub struct_def: NoirStruct,
 }
 
+pub struct UnresolvedEnum {
+    pub file_id: FileId,
+    pub module_id: LocalModuleId,
+    pub enum_def: NoirEnum,
+}
+
 #[derive(Clone)]
 pub struct UnresolvedTrait {
     pub file_id: FileId,
@@ -92,6 +99,8 @@ pub struct UnresolvedTraitImpl {
     pub trait_path: Path,
     pub object_type: UnresolvedType,
     pub methods: UnresolvedFunctions,
+    pub constants: Vec<(Ident, UnresolvedType, Expression)>,
+    pub associated_types: Vec<(Ident, UnresolvedType)>,
     pub generics: UnresolvedGenerics,
     pub where_clause: Vec<UnresolvedTraitConstraint>,
 }
@@ -117,6 +126,7 @@ pub struct DefCollector {
     pub(crate) collected_imports: Vec<ImportDirective>,
     pub(crate) collected_functions: Vec<UnresolvedFunctions>,
     pub(crate) collected_types: BTreeMap<StructId, UnresolvedStruct>,
+    pub(crate) collected_enums: BTreeMap<EnumId, UnresolvedEnum>,
     pub(crate) collected_type_aliases: BTreeMap<TypeAliasId, UnresolvedTypeAlias>,
     pub(crate) collected_traits: BTreeMap<TraitId, UnresolvedTrait>,
     pub(crate) collected_globals: Vec<UnresolvedGlobal>,
@@ -183,6 +193,7 @@ impl DefCollector {
             collected_imports: vec![],
             collected_functions: vec![],
             collected_types: BTreeMap::new(),
+            collected_enums: BTreeMap::new(),
             collected_type_aliases: BTreeMap::new(),
             collected_traits: BTreeMap::new(),
             collected_impls: HashMap::new(),
@@ -293,6 +304,7 @@ impl DefCollector {
         errors.extend(resolve_traits(context, def_collector.collected_traits, crate_id));
         // Must resolve structs before we resolve globals.
         errors.extend(resolve_structs(context, def_collector.collected_types, crate_id));
+        errors.extend(resolve_enums(context, def_collector.collected_enums, crate_id));
 
         // We must wait to resolve non-integer globals until after we resolve structs since structs
         // globals will need to reference the struct type they're initialized to to ensure they are valid.
@@ -342,8 +354,9 @@ impl DefCollector {
         errors.extend(resolved_globals.errors);
 
         // We run hir transformations before type checks
-        #[cfg(feature = "aztec")]
-        crate::hir::aztec_library::transform_hir(&crate_id, context);
+        for processor in context.macro_processors.clone() {
+            processor.process_typed_ast(&crate_id, context);
+        }
 
         errors.extend(type_check_globals(&mut context.def_interner, resolved_globals.globals));
 
@@ -442,18 +455,25 @@ fn collect_trait_impl_methods(
     // set of function ids that have a corresponding method in the trait
     let mut func_ids_in_trait = HashSet::new();
 
+    // Associated constants are implemented by methods without parameters returning their value
+    let module = ModuleId { local_id: trait_impl.module_id, krate: crate_id };
+    let constants = vecmap(&trait_impl.constants, |(name, typ, value)| {
+        let mut definition = FunctionDefinition::associated_constant(name, typ, value);
+        definition.where_clause = trait_impl.where_clause.clone();
+        let func_id = interner.push_empty_fn();
+        interner.push_function(func_id, &definition, module);
+        (trait_impl.module_id, func_id, NoirFunction::normal(definition))
+    });
+
     for method in &the_trait.methods {
-        let overrides: Vec<_> = trait_impl
-            .methods
-            .functions
-            .iter()
-            .filter(|(_, _, f)| f.name() == method.name.0.contents)
-            .collect();
+        let is_constant = the_trait.is_constant(&method.name);
+        let candidates = if is_constant { &constants } else { &trait_impl.methods.functions };
+        let overrides: Vec<_> =
+            candidates.iter().filter(|(_, _, f)| f.name() == method.name.0.contents).collect();
 
         if overrides.is_empty() {
             if let Some(default_impl) = &method.default_impl {
                 let func_id = interner.push_empty_fn();
-                let module = ModuleId { local_id: trait_impl.module_id, krate: crate_id };
                 interner.push_function(func_id, &default_impl.def, module);
                 func_ids_in_trait.insert(func_id);
                 ordered_methods.push((
@@ -462,10 +482,22 @@ fn collect_trait_impl_methods(
                     *default_impl.clone(),
                 ));
             } else {
-                let error = DefCollectorErrorKind::TraitMissingMethod {
-                    trait_name: the_trait.name.clone(),
-                    method_name: method.name.clone(),
-                    trait_impl_span: trait_impl.object_type.span.expect("type must have a span"),
+                let trait_name = the_trait.name.clone();
+                let trait_impl_span = trait_impl.object_type.span.expect("type must have a span");
+                let error = if is_constant {
+                    let constant_name = method.name.clone();
+                    DefCollectorErrorKind::TraitMissingConstant {
+                        trait_name,
+                        constant_name,
+                        trait_impl_span,
+                    }
+                } else {
+                    let method_name = method.name.clone();
+                    DefCollectorErrorKind::TraitMissingMethod {
+                        trait_name,
+                        method_name,
+                        trait_impl_span,
+                    }
                 };
                 errors.push((error.into(), trait_impl.file_id));
             }
@@ -476,7 +508,11 @@ fn collect_trait_impl_methods(
 
             if overrides.len() > 1 {
                 let error = DefCollectorErrorKind::Duplicate {
-                    typ: DuplicateType::TraitAssociatedFunction,
+                    typ: if is_constant {
+                        DuplicateType::TraitAssociatedConst
+                    } else {
+                        DuplicateType::TraitAssociatedFunction
+                    },
                     first_def: overrides[0].2.name_ident().clone(),
                     second_def: overrides[1].2.name_ident().clone(),
                 };
@@ -499,11 +535,67 @@ fn collect_trait_impl_methods(
         }
     }
 
+    for (_, func_id, constant) in &constants {
+        if !func_ids_in_trait.contains(func_id) {
+            let error = DefCollectorErrorKind::ConstantNotInTrait {
+                trait_name: the_trait.name.clone(),
+                impl_constant: constant.name_ident().clone(),
+            };
+            errors.push((error.into(), trait_impl.file_id));
+        }
+    }
+
+    errors.extend(check_trait_impl_associated_types(&the_trait, trait_impl));
+
     trait_impl.methods.functions = ordered_methods;
     trait_impl.methods.trait_id = Some(trait_id);
     errors
 }
 
+/// Checks that the impl chooses a type for each associated type of the trait, and only for those.
+fn check_trait_impl_associated_types(
+    the_trait: &Trait,
+    trait_impl: &UnresolvedTraitImpl,
+) -> Vec<(CompilationError, FileId)> {
+    let mut errors = Vec::new();
+
+    for trait_type in &the_trait.types {
+        let aliases: Vec<_> = trait_impl
+            .associated_types
+            .iter()
+            .filter(|(name, _)| *name == trait_type.name)
+            .collect();
+
+        if aliases.is_empty() {
+            let error = DefCollectorErrorKind::TraitMissingType {
+                trait_name: the_trait.name.clone(),
+                type_name: trait_type.name.clone(),
+                trait_impl_span: trait_impl.object_type.span.expect("type must have a span"),
+            };
+            errors.push((error.into(), trait_impl.file_id));
+        } else if aliases.len() > 1 {
+            let error = DefCollectorErrorKind::Duplicate {
+                typ: DuplicateType::TraitAssociatedType,
+                first_def: aliases[0].0.clone(),
+                second_def: aliases[1].0.clone(),
+            };
+            errors.push((error.into(), trait_impl.file_id));
+        }
+    }
+
+    for (name, _) in &trait_impl.associated_types {
+        if !the_trait.types.iter().any(|trait_type| trait_type.name == *name) {
+            let error = DefCollectorErrorKind::TypeNotInTrait {
+                trait_name: the_trait.name.clone(),
+                impl_type: name.clone(),
+            };
+            errors.push((error.into(), trait_impl.file_id));
+        }
+    }
+
+    errors
+}
+
 fn collect_trait_impl(
     context: &mut Context,
     crate_id: CrateId,
@@ -534,6 +626,16 @@ fn collect_trait_impl(
         let typ = resolver.resolve_type(unresolved_type);
         errors.extend(take_errors(trait_impl.file_id, resolver));
 
+        let the_trait = interner.get_trait(trait_id);
+        for (_, func_id, function) in &trait_impl.methods.functions {
+            if the_trait.is_constant(function.name_ident()) {
+                let mut resolver = Resolver::new(interner, &path_resolver, def_maps, file);
+                resolver.add_generics(&trait_impl.generics);
+                let value = resolver.eval_associated_constant(&function.def);
+                interner.push_associated_constant(*func_id, value);
+            }
+        }
+
         if let Some(struct_type) = get_struct_type(&typ) {
             let struct_type = struct_type.borrow();
             let module = get_module_mut(def_maps, struct_type.id.module_id());
@@ -723,21 +825,83 @@ fn resolve_structs(
     errors
 }
 
+/// Create the mappings from EnumId -> EnumType
+/// so that expressions can construct and match on enum variants
+fn resolve_enums(
+    context: &mut Context,
+    enums: BTreeMap<EnumId, UnresolvedEnum>,
+    crate_id: CrateId,
+) -> Vec<(CompilationError, FileId)> {
+    let mut errors: Vec<(CompilationError, FileId)> = vec![];
+    for (type_id, typ) in enums {
+        let file_id = typ.file_id;
+        let path_resolver =
+            StandardPathResolver::new(ModuleId { local_id: typ.module_id, krate: crate_id });
+        let (generics, variants, resolver_errors) =
+            Resolver::new(&mut context.def_interner, &path_resolver, &context.def_maps, file_id)
+                .resolve_enum_variants(typ.enum_def);
+        errors.extend(vecmap(resolver_errors, |err| (err.into(), file_id)));
+        context.def_interner.update_enum(type_id, |enum_def| {
+            enum_def.set_variants(variants);
+            enum_def.generics = generics;
+        });
+    }
+    errors
+}
+
 fn resolve_trait_types(
-    _context: &mut Context,
+    context: &mut Context,
     _crate_id: CrateId,
-    _unresolved_trait: &UnresolvedTrait,
+    unresolved_trait: &UnresolvedTrait,
 ) -> (Vec<TraitType>, Vec<(CompilationError, FileId)>) {
-    // TODO
-    (vec![], vec![])
+    let types = unresolved_trait.trait_def.items.iter().filter_map(|item| match item {
+        TraitItem::Type { name } => {
+            let typevar_id = context.def_interner.next_type_variable_id();
+            let typevar = Shared::new(TypeBinding::Unbound(typevar_id));
+            Some(TraitType { name: name.clone(), typevar_id, typevar, span: name.span() })
+        }
+        _ => None,
+    });
+    (types.collect(), vec![])
 }
+
 fn resolve_trait_constants(
-    _context: &mut Context,
-    _crate_id: CrateId,
-    _unresolved_trait: &UnresolvedTrait,
+    context: &mut Context,
+    trait_id: TraitId,
+    crate_id: CrateId,
+    unresolved_trait: &UnresolvedTrait,
 ) -> (Vec<TraitConstant>, Vec<(CompilationError, FileId)>) {
-    // TODO
-    (vec![], vec![])
+    let interner = &mut context.def_interner;
+    let def_maps = &mut context.def_maps;
+
+    let path_resolver = StandardPathResolver::new(ModuleId {
+        local_id: unresolved_trait.module_id,
+        krate: crate_id,
+    });
+    let file = def_maps[&crate_id].file_id(unresolved_trait.module_id);
+
+    let mut constants = vec![];
+    for item in &unresolved_trait.trait_def.items {
+        if let TraitItem::Constant { name, typ, default_value: _ } = item {
+            let the_trait = interner.get_trait(trait_id);
+            let mut resolver = Resolver::new(interner, &path_resolver, def_maps, file);
+            resolver.set_associated_types(associated_type_variables(&the_trait));
+
+            let ty = resolver.resolve_type(typ.clone());
+            constants.push(TraitConstant { name: name.clone(), ty, span: name.span() });
+        }
+    }
+    // Any error in the type of a constant is reported when resolving the method it is lowered to
+    (constants, vec![])
+}
+
+/// The types which `Self::Name` resolves to in the trait definition: the type variables
+/// standing for the associated types of the trait.
+fn associated_type_variables(the_trait: &Trait) -> Vec<(Ident, Type)> {
+    vecmap(&the_trait.types, |trait_type| {
+        let typ = Type::TypeVariable(trait_type.typevar.clone(), TypeVariableKind::Normal);
+        (trait_type.name.clone(), typ)
+    })
 }
 
 fn resolve_trait_methods(
@@ -758,54 +922,54 @@ fn resolve_trait_methods(
     let mut res = vec![];
     let mut resolver_errors = vec![];
     for item in &unresolved_trait.trait_def.items {
-        if let TraitItem::Function {
-            name,
-            generics,
-            parameters,
-            return_type,
-            where_clause: _,
-            body: _,
-        } = item
-        {
-            let the_trait = interner.get_trait(trait_id);
-            let self_type =
-                Type::TypeVariable(the_trait.self_type_typevar.clone(), TypeVariableKind::Normal);
+        // Associated constants are methods without parameters returning their value
+        let (name, generics, parameters, return_type) = match item {
+            TraitItem::Function { name, generics, parameters, return_type, .. } => {
+                (name, generics.clone(), parameters.clone(), return_type.get_type().into_owned())
+            }
+            TraitItem::Constant { name, typ, .. } => (name, Vec::new(), Vec::new(), typ.clone()),
+            TraitItem::Type { .. } => continue,
+        };
 
-            let mut resolver = Resolver::new(interner, &path_resolver, def_maps, file);
-            resolver.add_generics(generics);
-            resolver.set_self_type(Some(self_type));
+        let the_trait = interner.get_trait(trait_id);
+        let self_type =
+            Type::TypeVariable(the_trait.self_type_typevar.clone(), TypeVariableKind::Normal);
 
-            let arguments = vecmap(parameters, |param| resolver.resolve_type(param.1.clone()));
-            let resolved_return_type = resolver.resolve_type(return_type.get_type().into_owned());
-            let generics = resolver.get_generics().to_vec();
+        let mut resolver = Resolver::new(interner, &path_resolver, def_maps, file);
+        resolver.add_generics(&generics);
+        resolver.set_self_type(Some(self_type));
+        resolver.set_associated_types(associated_type_variables(&the_trait));
 
-            let name = name.clone();
-            let span: Span = name.span();
-            let default_impl_list: Vec<_> = unresolved_trait
-                .fns_with_default_impl
-                .functions
-                .iter()
-                .filter(|(_, _, q)| q.name() == name.0.contents)
-                .collect();
-            let default_impl = if default_impl_list.len() == 1 {
-                Some(Box::new(default_impl_list[0].2.clone()))
-            } else {
-                None
-            };
+        let arguments = vecmap(parameters, |param| resolver.resolve_type(param.1));
+        let resolved_return_type = resolver.resolve_type(return_type);
+        let generics = resolver.get_generics().to_vec();
 
-            let f = TraitFunction {
-                name,
-                generics,
-                arguments,
-                return_type: resolved_return_type,
-                span,
-                default_impl,
-                default_impl_file_id: unresolved_trait.file_id,
-                default_impl_module_id: unresolved_trait.module_id,
-            };
-            res.push(f);
-            resolver_errors.extend(take_errors_filter_self_not_resolved(file, resolver));
-        }
+        let name = name.clone();
+        let span: Span = name.span();
+        let default_impl_list: Vec<_> = unresolved_trait
+            .fns_with_default_impl
+            .functions
+            .iter()
+            .filter(|(_, _, q)| q.name() == name.0.contents)
+            .collect();
+        let default_impl = if default_impl_list.len() == 1 {
+            Some(Box::new(default_impl_list[0].2.clone()))
+        } else {
+            None
+        };
+
+        let f = TraitFunction {
+            name,
+            generics,
+            arguments,
+            return_type: resolved_return_type,
+            span,
+            default_impl,
+            default_impl_file_id: unresolved_trait.file_id,
+            default_impl_module_id: unresolved_trait.module_id,
+        };
+        res.push(f);
+        resolver_errors.extend(take_errors_filter_self_not_resolved(file, resolver));
     }
     (res, resolver_errors)
 }
@@ -846,9 +1010,18 @@ fn resolve_traits(
     for (trait_id, unresolved_trait) in traits {
         // Resolve order
         // 1. Trait Types ( Trait constants can have a trait type, therefore types before constants)
-        let _ = resolve_trait_types(context, crate_id, &unresolved_trait);
+        let (types, errors) = resolve_trait_types(context, crate_id, &unresolved_trait);
+        res.extend(errors);
+        context.def_interner.update_trait(trait_id, |trait_def| {
+            trait_def.set_types(types);
+        });
         // 2. Trait Constants ( Trait's methods can use trait types & constants, therefore they should be after)
-        let _ = resolve_trait_constants(context, crate_id, &unresolved_trait);
+        let (constants, errors) =
+            resolve_trait_constants(context, trait_id, crate_id, &unresolved_trait);
+        res.extend(errors);
+        context.def_interner.update_trait(trait_id, |trait_def| {
+            trait_def.set_constants(constants);
+        });
         // 3. Trait Methods
         let (methods, errors) =
             resolve_trait_methods(context, trait_id, crate_id, &unresolved_trait);
@@ -856,6 +1029,10 @@ fn resolve_traits(
         context.def_interner.update_trait(trait_id, |trait_def| {
             trait_def.set_methods(methods);
         });
+
+        if crate_id.is_stdlib() {
+            context.def_interner.try_add_operator_trait(trait_id);
+        }
     }
     res
 }
@@ -924,6 +1101,7 @@ fn resolve_impls(
                 Some(self_type.clone()),
                 None,
                 generics,
+                vec![], // no associated types
                 errors,
             );
             if self_type != Type::Error {
@@ -974,6 +1152,30 @@ fn resolve_trait_impls(
 
         let impl_id = interner.next_trait_impl_id();
 
+        // The types chosen by the impl for the associated types of the trait, in the order
+        // the trait declares them
+        let associated_types = match trait_impl.trait_id {
+            Some(trait_id) => {
+                let the_trait = interner.get_trait(trait_id);
+                let mut resolver =
+                    Resolver::new(interner, &path_resolver, &context.def_maps, trait_impl.file_id);
+                resolver.set_generics(generics.clone());
+                resolver.set_self_type(Some(self_type.clone()));
+
+                let associated_types = vecmap(&the_trait.types, |trait_type| {
+                    let alias = trait_impl
+                        .associated_types
+                        .iter()
+                        .find(|(name, _)| *name == trait_type.name)
+                        .map_or(Type::Error, |(_, alias)| resolver.resolve_type(alias.clone()));
+                    (trait_type.name.clone(), alias)
+                });
+                errors.extend(take_errors(trait_impl.file_id, resolver));
+                associated_types
+            }
+            None => Vec::new(),
+        };
+
         let mut impl_methods = resolve_function_set(
             interner,
             crate_id,
@@ -982,6 +1184,7 @@ fn resolve_trait_impls(
             Some(self_type.clone()),
             Some(impl_id),
             generics.clone(),
+            associated_types.clone(),
             errors,
         );
 
@@ -1003,6 +1206,7 @@ fn resolve_trait_impls(
 
         new_resolver.set_generics(generics);
         new_resolver.set_self_type(Some(self_type.clone()));
+        new_resolver.set_associated_types(associated_types.clone());
 
         if let Some(trait_id) = maybe_trait_id {
             check_methods_signatures(
@@ -1026,6 +1230,7 @@ fn resolve_trait_impls(
                 file: trait_impl.file_id,
                 where_clause,
                 methods: vecmap(&impl_methods, |(_, func_id)| *func_id),
+                associated_types: vecmap(associated_types, |(_, typ)| typ),
             });
 
             if let Err((prev_span, prev_file)) = interner.add_trait_implementation(
@@ -1068,6 +1273,13 @@ fn check_methods_signatures(
     // Temporarily bind the trait's Self type to self_type so we can type check
     let _ = the_trait.self_type_typevar.borrow_mut().bind_to(self_type.clone(), the_trait.span);
 
+    // Likewise for the associated types of the trait, which are bound to the types of the impl
+    for trait_type in &the_trait.types {
+        if let Some(typ) = resolver.lookup_associated_type(&trait_type.name) {
+            let _ = trait_type.typevar.borrow_mut().bind_to(typ, trait_type.span);
+        }
+    }
+
     for (file_id, func_id) in impl_methods {
         let impl_method = resolver.interner.function_meta(func_id);
         let func_name = resolver.interner.function_name(func_id).to_owned();
@@ -1146,6 +1358,9 @@ fn check_methods_signatures(
     }
 
     the_trait.self_type_typevar.borrow_mut().unbind(the_trait.self_type_typevar_id);
+    for trait_type in &the_trait.types {
+        trait_type.typevar.borrow_mut().unbind(trait_type.typevar_id);
+    }
 }
 
 fn resolve_free_functions(
@@ -1168,6 +1383,7 @@ fn resolve_free_functions(
                 self_type.clone(),
                 None,
                 vec![], // no impl generics
+                vec![], // no associated types
                 errors,
             )
         })
@@ -1183,6 +1399,7 @@ fn resolve_function_set(
     self_type: Option<Type>,
     trait_impl_id: Option<TraitImplId>,
     impl_generics: Vec<(Rc<String>, Shared<TypeBinding>, Span)>,
+    associated_types: Vec<(Ident, Type)>,
     errors: &mut Vec<(CompilationError, FileId)>,
 ) -> Vec<(FileId, FuncId)> {
     let file_id = unresolved_functions.file_id;
@@ -1203,6 +1420,7 @@ fn resolve_function_set(
         resolver.set_self_type(self_type.clone());
         resolver.set_trait_id(unresolved_functions.trait_id);
         resolver.set_trait_impl_id(trait_impl_id);
+        resolver.set_associated_types(associated_types.clone());
 
         // Without this, impl methods can accidentally be placed in contracts. See #3254
         if self_type.is_some() {
@@ -6,11 +6,11 @@ use noirc_errors::Location;
 
 use crate::{
     graph::CrateId,
-    hir::def_collector::dc_crate::{UnresolvedStruct, UnresolvedTrait},
+    hir::def_collector::dc_crate::{UnresolvedEnum, UnresolvedStruct, UnresolvedTrait},
     node_interner::{FunctionModifiers, TraitId, TypeAliasId},
     parser::{SortedModule, SortedSubModule},
-    FunctionDefinition, Ident, LetStatement, NoirFunction, NoirStruct, NoirTrait, NoirTraitImpl,
-    NoirTypeAlias, TraitImplItem, TraitItem, TypeImpl,
+    FunctionDefinition, Ident, LetStatement, NoirEnum, NoirFunction, NoirStruct, NoirTrait,
+    NoirTraitImpl, NoirTypeAlias, TraitImplItem, TraitItem, TypeImpl,
 };
 
 use super::{
@@ -18,9 +18,10 @@ use super::{
         CompilationError, DefCollector, UnresolvedFunctions, UnresolvedGlobal, UnresolvedTraitImpl,
         UnresolvedTypeAlias,
     },
+    derive::derive_trait_impls,
     errors::{DefCollectorErrorKind, DuplicateType},
 };
-use crate::hir::def_map::{parse_file, LocalModuleId, ModuleData, ModuleId};
+use crate::hir::def_map::{parse_crate_file, LocalModuleId, ModuleData, ModuleId};
 use crate::hir::resolution::import::ImportDirective;
 use crate::hir::Context;
 
@@ -66,6 +67,8 @@ pub fn collect_defs(
 
     errors.extend(collector.collect_structs(context, ast.types, crate_id));
 
+    errors.extend(collector.collect_enums(context, ast.enums, crate_id));
+
     errors.extend(collector.collect_type_aliases(context, ast.type_aliases));
 
     errors.extend(collector.collect_functions(context, ast.functions, crate_id));
@@ -155,11 +158,23 @@ impl<'a> ModCollector<'a> {
                 context.def_interner.push_function(*func_id, &noir_function.def, module);
             }
 
+            let mut constants = Vec::new();
+            let mut associated_types = Vec::new();
+            for item in trait_impl.items {
+                match item {
+                    TraitImplItem::Constant(name, typ, value) => constants.push((name, typ, value)),
+                    TraitImplItem::Type { name, alias } => associated_types.push((name, alias)),
+                    TraitImplItem::Function(_) => (),
+                }
+            }
+
             let unresolved_trait_impl = UnresolvedTraitImpl {
                 file_id: self.file_id,
                 module_id: self.module_id,
                 trait_path: trait_name,
                 methods: unresolved_functions,
+                constants,
+                associated_types,
                 object_type: trait_impl.object_type,
                 generics: trait_impl.impl_generics,
                 where_clause: trait_impl.where_clause,
@@ -255,9 +270,15 @@ impl<'a> ModCollector<'a> {
         krate: CrateId,
     ) -> Vec<(CompilationError, FileId)> {
         let mut definition_errors = vec![];
+        let mut derived_impls = vec![];
         for struct_definition in types {
             let name = struct_definition.name.clone();
 
+            let (impls, derive_errors) = derive_trait_impls(&struct_definition, krate);
+            derived_impls.extend(impls);
+            definition_errors
+                .extend(derive_errors.into_iter().map(|error| (error.into(), self.file_id)));
+
             let unresolved = UnresolvedStruct {
                 file_id: self.file_id,
                 module_id: self.module_id,
@@ -289,6 +310,54 @@ impl<'a> ModCollector<'a> {
             // And store the TypeId -> StructType mapping somewhere it is reachable
             self.def_collector.collected_types.insert(id, unresolved);
         }
+
+        // Impls requested by `#[derive(...)]` are collected as if they were written by hand
+        definition_errors.extend(self.collect_trait_impls(context, derived_impls, krate));
+        definition_errors
+    }
+
+    /// Collect any enum definitions declared within the ast.
+    /// Returns a vector of errors if any enums were already defined.
+    fn collect_enums(
+        &mut self,
+        context: &mut Context,
+        enums: Vec<NoirEnum>,
+        krate: CrateId,
+    ) -> Vec<(CompilationError, FileId)> {
+        let mut definition_errors = vec![];
+        for enum_definition in enums {
+            let name = enum_definition.name.clone();
+
+            let unresolved = UnresolvedEnum {
+                file_id: self.file_id,
+                module_id: self.module_id,
+                enum_def: enum_definition,
+            };
+
+            // Create the corresponding module for the enum namespace
+            let id = match self.push_child_module(&name, self.file_id, false, false) {
+                Ok(local_id) => context.def_interner.new_enum(&unresolved, krate, local_id),
+                Err(error) => {
+                    definition_errors.push((error.into(), self.file_id));
+                    continue;
+                }
+            };
+
+            // Add the enum to scope so its path can be looked up later
+            let result =
+                self.def_collector.def_map.modules[self.module_id.0].declare_enum(name, id);
+
+            if let Err((first_def, second_def)) = result {
+                let error = DefCollectorErrorKind::Duplicate {
+                    typ: DuplicateType::TypeDefinition,
+                    first_def,
+                    second_def,
+                };
+                definition_errors.push((error.into(), self.file_id));
+            }
+
+            self.def_collector.collected_enums.insert(id, unresolved);
+        }
         definition_errors
     }
 
@@ -371,7 +440,8 @@ impl<'a> ModCollector<'a> {
                 trait_id: None,
             };
             for trait_item in &trait_definition.items {
-                match trait_item {
+                // Associated constants are declared as methods without parameters
+                let (name, default_impl, duplicate_type) = match trait_item {
                     TraitItem::Function {
                         name,
                         generics,
@@ -380,64 +450,25 @@ impl<'a> ModCollector<'a> {
                         where_clause,
                         body,
                     } => {
-                        let func_id = context.def_interner.push_empty_fn();
-                        let modifiers = FunctionModifiers {
-                            name: name.to_string(),
-                            visibility: crate::FunctionVisibility::Public,
-                            // TODO(Maddiaa): Investigate trait implementations with attributes see: https://github.com/noir-lang/noir/issues/2629
-                            attributes: crate::token::Attributes::empty(),
-                            is_unconstrained: false,
-                            contract_function_type: None,
-                            is_internal: None,
-                        };
-
-                        context.def_interner.push_function_definition(func_id, modifiers, id.0);
-
-                        match self.def_collector.def_map.modules[id.0.local_id.0]
-                            .declare_function(name.clone(), func_id)
-                        {
-                            Ok(()) => {
-                                if let Some(body) = body {
-                                    let impl_method =
-                                        NoirFunction::normal(FunctionDefinition::normal(
-                                            name,
-                                            generics,
-                                            parameters,
-                                            body,
-                                            where_clause,
-                                            return_type,
-                                        ));
-                                    unresolved_functions.push_fn(
-                                        self.module_id,
-                                        func_id,
-                                        impl_method,
-                                    );
-                                }
-                            }
-                            Err((first_def, second_def)) => {
-                                let error = DefCollectorErrorKind::Duplicate {
-                                    typ: DuplicateType::TraitAssociatedFunction,
-                                    first_def,
-                                    second_def,
-                                };
-                                errors.push((error.into(), self.file_id));
-                            }
-                        }
+                        let default_impl = body.as_ref().map(|body| {
+                            NoirFunction::normal(FunctionDefinition::normal(
+                                name,
+                                generics,
+                                parameters,
+                                body,
+                                where_clause,
+                                return_type,
+                            ))
+                        });
+                        (name, default_impl, DuplicateType::TraitAssociatedFunction)
                     }
-                    TraitItem::Constant { name, .. } => {
-                        let stmt_id = context.def_interner.push_empty_global();
-
-                        if let Err((first_def, second_def)) = self.def_collector.def_map.modules
-                            [id.0.local_id.0]
-                            .declare_global(name.clone(), stmt_id)
-                        {
-                            let error = DefCollectorErrorKind::Duplicate {
-                                typ: DuplicateType::TraitAssociatedConst,
-                                first_def,
-                                second_def,
-                            };
-                            errors.push((error.into(), self.file_id));
-                        }
+                    TraitItem::Constant { name, typ, default_value } => {
+                        let default_impl = default_value.as_ref().map(|value| {
+                            NoirFunction::normal(FunctionDefinition::associated_constant(
+                                name, typ, value,
+                            ))
+                        });
+                        (name, default_impl, DuplicateType::TraitAssociatedConst)
                     }
                     TraitItem::Type { name } => {
                         // TODO(nickysn or alexvitkov): implement context.def_interner.push_empty_type_alias and get an id, instead of using TypeAliasId::dummy_id()
@@ -452,6 +483,38 @@ impl<'a> ModCollector<'a> {
                             };
                             errors.push((error.into(), self.file_id));
                         }
+                        continue;
+                    }
+                };
+
+                let func_id = context.def_interner.push_empty_fn();
+                let modifiers = FunctionModifiers {
+                    name: name.to_string(),
+                    visibility: crate::FunctionVisibility::Public,
+                    // TODO(Maddiaa): Investigate trait implementations with attributes see: https://github.com/noir-lang/noir/issues/2629
+                    attributes: crate::token::Attributes::empty(),
+                    is_unconstrained: false,
+                    contract_function_type: None,
+                    is_internal: None,
+                };
+
+                context.def_interner.push_function_definition(func_id, modifiers, id.0);
+
+                match self.def_collector.def_map.modules[id.0.local_id.0]
+                    .declare_function(name.clone(), func_id)
+                {
+                    Ok(()) => {
+                        if let Some(default_impl) = default_impl {
+                            unresolved_functions.push_fn(self.module_id, func_id, default_impl);
+                        }
+                    }
+                    Err((first_def, second_def)) => {
+                        let error = DefCollectorErrorKind::Duplicate {
+                            typ: duplicate_type,
+                            first_def,
+                            second_def,
+                        };
+                        errors.push((error.into(), self.file_id));
                     }
                 }
             }
@@ -539,7 +602,8 @@ impl<'a> ModCollector<'a> {
         context.visited_files.insert(child_file_id, location);
 
         // Parse the AST for the module we just found and then recursively look for it's defs
-        let (ast, parsing_errors) = parse_file(&context.file_manager, child_file_id);
+        let (ast, parsing_errors) =
+            parse_crate_file(context, crate_id, child_file_id);
         let ast = ast.into_sorted();
 
         errors.extend(
@@ -0,0 +1,310 @@
+//! Generates the trait impls requested by `#[derive(...)]` attributes on structs.
+//!
+//! Derived impls are built as ordinary `NoirTraitImpl`s during definition collection so they go
+//! through the same resolution and type checking as handwritten impls. Each struct field is
+//! required to implement the derived trait itself, as are any generics the fields are typed with.
+use noirc_errors::{Span, Spanned};
+
+use crate::{
+    graph::CrateId, hir::def_collector::errors::DefCollectorErrorKind, token::SecondaryAttribute,
+    ArrayLiteral, BinaryOpKind, BlockExpression, CallExpression, ConstructorExpression, Expression,
+    ExpressionKind, FunctionDefinition, FunctionReturnType, Ident, InfixExpression, Literal,
+    MemberAccessExpression, MethodCallExpression, NoirFunction, NoirStruct, NoirTraitImpl, Path,
+    PathKind, Statement, StatementKind, TraitBound, TraitImplItem, UnresolvedTraitConstraint,
+    UnresolvedType, UnresolvedTypeData,
+};
+
+/// Returns an impl for each trait listed in the `#[derive(...)]` attributes of the given struct,
+/// along with an error for each trait which cannot be derived.
+pub(crate) fn derive_trait_impls(
+    struct_def: &NoirStruct,
+    krate: CrateId,
+) -> (Vec<NoirTraitImpl>, Vec<DefCollectorErrorKind>) {
+    let mut impls = Vec::new();
+    let mut errors = Vec::new();
+
+    let derived_traits = struct_def.attributes.iter().filter_map(|attribute| match attribute {
+        SecondaryAttribute::Derive(traits) => Some(traits),
+        _ => None,
+    });
+
+    let deriver = Deriver { struct_def, span: struct_def.name.span() };
+    for trait_name in derived_traits.flatten() {
+        // Paired with the stdlib module each trait is declared in
+        let (module, method) = match trait_name.as_str() {
+            "Eq" => ("cmp", deriver.eq_method()),
+            "Default" => ("default", deriver.default_method(krate)),
+            "Serialize" => ("serialize", deriver.serialize_method()),
+            "Hash" => ("hash", deriver.hash_method(krate)),
+            _ => {
+                errors.push(DefCollectorErrorKind::UnsupportedDerive {
+                    trait_name: trait_name.clone(),
+                    span: struct_def.name.span(),
+                });
+                continue;
+            }
+        };
+
+        let trait_path = deriver.stdlib_path(krate, &[module, trait_name.as_str()]);
+        impls.push(deriver.trait_impl(trait_path, method));
+    }
+
+    (impls, errors)
+}
+
+struct Deriver<'a> {
+    struct_def: &'a NoirStruct,
+    /// Every node of a derived impl points at the name of the struct it was derived for
+    span: Span,
+}
+
+impl<'a> Deriver<'a> {
+    /// `impl<T> Trait for Struct<T> where T: Trait { method }`
+    fn trait_impl(&self, trait_path: Path, method: FunctionDefinition) -> NoirTraitImpl {
+        let generic_args = self
+            .struct_def
+            .generics
+            .iter()
+            .map(|generic| self.named_type(Path::from_ident(generic.clone())))
+            .collect();
+        let object_type =
+            UnresolvedTypeData::Named(self.path(&[&self.struct_def.name.0.contents]), generic_args)
+                .with_span(self.span);
+
+        let where_clause = self
+            .type_generics()
+            .into_iter()
+            .map(|generic| UnresolvedTraitConstraint {
+                typ: self.named_type(Path::from_ident(generic.clone())),
+                trait_bound: TraitBound {
+                    trait_path: trait_path.clone(),
+                    trait_id: None,
+                    trait_generics: Vec::new(),
+                },
+            })
+            .collect();
+
+        NoirTraitImpl {
+            impl_generics: self.struct_def.generics.clone(),
+            trait_name: trait_path,
+            trait_generics: Vec::new(),
+            object_type,
+            where_clause,
+            items: vec![TraitImplItem::Function(NoirFunction::normal(method))],
+        }
+    }
+
+    /// `fn eq(self, other: Self) -> bool { (self.a == other.a) & (self.b == other.b) }`
+    fn eq_method(&self) -> FunctionDefinition {
+        let body = self
+            .field_names()
+            .map(|field| {
+                let lhs = self.member_access("self", field);
+                let rhs = self.member_access("other", field);
+                self.infix(lhs, BinaryOpKind::Equal, rhs)
+            })
+            .reduce(|conjunction, eq| self.infix(conjunction, BinaryOpKind::And, eq))
+            .unwrap_or_else(|| self.expression(ExpressionKind::Literal(Literal::Bool(true))));
+
+        self.method(
+            "eq",
+            &[("self", self.self_type()), ("other", self.self_type())],
+            body,
+            UnresolvedTypeData::Bool,
+        )
+    }
+
+    /// `fn default() -> Self { Struct { a: std::default::default(), .. } }`
+    fn default_method(&self, krate: CrateId) -> FunctionDefinition {
+        let default = self.stdlib_path(krate, &["default", "default"]);
+        let fields = self
+            .struct_def
+            .fields
+            .iter()
+            .map(|(field, _)| (field.clone(), self.call(default.clone(), Vec::new())))
+            .collect();
+
+        let constructor = ConstructorExpression {
+            type_name: self.path(&[&self.struct_def.name.0.contents]),
+            fields,
+        };
+        let body = self.expression(ExpressionKind::Constructor(Box::new(constructor)));
+
+        self.method("default", &[], body, self.self_type().typ)
+    }
+
+    /// `fn serialize(self) -> [Field] { [].append(self.a.serialize()).append(self.b.serialize()) }`
+    fn serialize_method(&self) -> FunctionDefinition {
+        let empty = self.array(Vec::new());
+        let body = self.field_names().fold(empty, |fields, field| {
+            let serialized =
+                self.method_call(self.member_access("self", field), "serialize", vec![]);
+            self.method_call(fields, "append", vec![serialized])
+        });
+
+        let slice = UnresolvedTypeData::Array(None, Box::new(self.field_type()));
+        self.method("serialize", &[("self", self.self_type())], body, slice)
+    }
+
+    /// `fn hash(self) -> Field { std::hash::pedersen_hash([self.a.hash(), self.b.hash()]) }`
+    fn hash_method(&self, krate: CrateId) -> FunctionDefinition {
+        let hashes = self
+            .field_names()
+            .map(|field| self.method_call(self.member_access("self", field), "hash", vec![]))
+            .collect();
+
+        let pedersen_hash = self.stdlib_path(krate, &["hash", "pedersen_hash"]);
+        let body = self.call(pedersen_hash, vec![self.array(hashes)]);
+
+        self.method("hash", &[("self", self.self_type())], body, UnresolvedTypeData::FieldElement)
+    }
+
+    fn method(
+        &self,
+        name: &str,
+        parameters: &[(&str, UnresolvedType)],
+        body: Expression,
+        return_type: UnresolvedTypeData,
+    ) -> FunctionDefinition {
+        let parameters: Vec<_> =
+            parameters.iter().map(|(name, typ)| (self.ident(name), typ.clone())).collect();
+        let body = BlockExpression(vec![Statement {
+            kind: StatementKind::Expression(body),
+            span: self.span,
+        }]);
+        let return_type = FunctionReturnType::Ty(return_type.with_span(self.span));
+
+        FunctionDefinition::normal(
+            &self.ident(name),
+            &Vec::new(),
+            &parameters,
+            &body,
+            &[],
+            &return_type,
+        )
+    }
+
+    /// The generics of the struct which are used as the types of its fields, as opposed to
+    /// generics which are only used as array lengths.
+    fn type_generics(&self) -> Vec<&'a Ident> {
+        let generics = &self.struct_def.generics;
+        let mut used = vec![false; generics.len()];
+        for (_, typ) in &self.struct_def.fields {
+            mark_type_generics(typ, generics, &mut used);
+        }
+        generics.iter().zip(used).filter_map(|(generic, used)| used.then_some(generic)).collect()
+    }
+
+    fn field_names(&self) -> impl Iterator<Item = &'a str> {
+        self.struct_def.fields.iter().map(|(field, _)| field.0.contents.as_str())
+    }
+
+    /// Returns a path to an item of the stdlib, which is a dependency of every crate but itself.
+    fn stdlib_path(&self, krate: CrateId, segments: &[&str]) -> Path {
+        if krate.is_stdlib() {
+            Path { kind: PathKind::Crate, ..self.path(segments) }
+        } else {
+            let mut path = self.path(&["std"]);
+            path.segments.extend(segments.iter().map(|segment| self.ident(segment)));
+            Path { kind: PathKind::Dep, ..path }
+        }
+    }
+
+    fn path(&self, segments: &[&str]) -> Path {
+        let segments = segments.iter().map(|segment| self.ident(segment)).collect();
+        Path { segments, kind: PathKind::Plain }
+    }
+
+    fn ident(&self, name: &str) -> Ident {
+        Ident::new(name.to_string(), self.span)
+    }
+
+    fn self_type(&self) -> UnresolvedType {
+        self.named_type(self.path(&["Self"]))
+    }
+
+    fn field_type(&self) -> UnresolvedType {
+        UnresolvedTypeData::FieldElement.with_span(self.span)
+    }
+
+    fn named_type(&self, path: Path) -> UnresolvedType {
+        UnresolvedTypeData::Named(path, Vec::new()).with_span(self.span)
+    }
+
+    fn expression(&self, kind: ExpressionKind) -> Expression {
+        Expression::new(kind, self.span)
+    }
+
+    fn member_access(&self, variable: &str, field: &str) -> Expression {
+        let lhs = self.expression(ExpressionKind::Variable(self.path(&[variable])));
+        let access = MemberAccessExpression { lhs, rhs: self.ident(field) };
+        self.expression(ExpressionKind::MemberAccess(Box::new(access)))
+    }
+
+    fn infix(&self, lhs: Expression, operator: BinaryOpKind, rhs: Expression) -> Expression {
+        let operator = Spanned::from(self.span, operator);
+        let infix = InfixExpression { lhs, operator, rhs };
+        self.expression(ExpressionKind::Infix(Box::new(infix)))
+    }
+
+    fn call(&self, function: Path, arguments: Vec<Expression>) -> Expression {
+        let func = Box::new(self.expression(ExpressionKind::Variable(function)));
+        self.expression(ExpressionKind::Call(Box::new(CallExpression { func, arguments })))
+    }
+
+    fn method_call(
+        &self,
+        object: Expression,
+        name: &str,
+        arguments: Vec<Expression>,
+    ) -> Expression {
+        let call = MethodCallExpression { object, method_name: self.ident(name), arguments };
+        self.expression(ExpressionKind::MethodCall(Box::new(call)))
+    }
+
+    fn array(&self, elements: Vec<Expression>) -> Expression {
+        let array = Literal::Array(ArrayLiteral::Standard(elements));
+        self.expression(ExpressionKind::Literal(array))
+    }
+}
+
+/// Marks each of `generics` which `typ` refers to as a type.
+fn mark_type_generics(typ: &UnresolvedType, generics: &[Ident], used: &mut [bool]) {
+    match &typ.typ {
+        UnresolvedTypeData::Named(path, args) => {
+            if path.segments.len() == 1 && args.is_empty() {
+                if let Some(index) =
+                    generics.iter().position(|generic| *generic == path.segments[0])
+                {
+                    used[index] = true;
+                }
+            }
+            for arg in args {
+                mark_type_generics(arg, generics, used);
+            }
+        }
+        UnresolvedTypeData::Array(_, element)
+        | UnresolvedTypeData::FormatString(_, element)
+        | UnresolvedTypeData::MutableReference(element) => {
+            mark_type_generics(element, generics, used);
+        }
+        UnresolvedTypeData::TraitAsType(_, args) | UnresolvedTypeData::Tuple(args) => {
+            for arg in args {
+                mark_type_generics(arg, generics, used);
+            }
+        }
+        UnresolvedTypeData::Function(args, ret, env) => {
+            for arg in args.iter().chain([ret.as_ref(), env.as_ref()]) {
+                mark_type_generics(arg, generics, used);
+            }
+        }
+        UnresolvedTypeData::FieldElement
+        | UnresolvedTypeData::Integer(..)
+        | UnresolvedTypeData::Bool
+        | UnresolvedTypeData::Expression(_)
+        | UnresolvedTypeData::String(_)
+        | UnresolvedTypeData::Unit
+        | UnresolvedTypeData::Unspecified
+        | UnresolvedTypeData::Error => (),
+    }
+}
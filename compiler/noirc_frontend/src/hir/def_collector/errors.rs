@@ -23,6 +23,15 @@ pub enum DuplicateType {
     TraitAssociatedFunction,
 }
 
+/// An error raised by a [`MacroProcessor`][crate::macros_api::MacroProcessor] while
+/// transforming a crate
+#[derive(Debug, Clone)]
+pub struct MacroError {
+    pub primary_message: String,
+    pub secondary_message: Option<String>,
+    pub span: Option<Span>,
+}
+
 #[derive(Error, Debug, Clone)]
 pub enum DefCollectorErrorKind {
     #[error("duplicate {typ} found in namespace")]
@@ -65,6 +74,14 @@ pub enum DefCollectorErrorKind {
     TraitNotFound { trait_path: Path },
     #[error("Missing Trait method implementation")]
     TraitMissingMethod { trait_name: Ident, method_name: Ident, trait_impl_span: Span },
+    #[error("Constant is not defined in trait")]
+    ConstantNotInTrait { trait_name: Ident, impl_constant: Ident },
+    #[error("Missing Trait constant implementation")]
+    TraitMissingConstant { trait_name: Ident, constant_name: Ident, trait_impl_span: Span },
+    #[error("Type is not defined in trait")]
+    TypeNotInTrait { trait_name: Ident, impl_type: Ident },
+    #[error("Missing Trait type implementation")]
+    TraitMissingType { trait_name: Ident, type_name: Ident, trait_impl_span: Span },
     #[error("Module is already part of the crate")]
     ModuleAlreadyPartOfCrate { mod_name: Ident, span: Span },
     #[error("Module was originally declared here")]
@@ -73,15 +90,11 @@ pub enum DefCollectorErrorKind {
         "Either the type or the trait must be from the same crate as the trait implementation"
     )]
     TraitImplOrphaned { span: Span },
+    #[error("Trait cannot be derived")]
+    UnsupportedDerive { trait_name: String, span: Span },
 
-    // Aztec feature flag errors
-    // TODO(benesjan): https://github.com/AztecProtocol/aztec-packages/issues/2905
-    #[cfg(feature = "aztec")]
-    #[error("Aztec dependency not found. Please add aztec as a dependency in your Cargo.toml")]
-    AztecNotFound {},
-    #[cfg(feature = "aztec")]
-    #[error("compute_note_hash_and_nullifier function not found. Define it in your contract.")]
-    AztecComputeNoteHashAndNullifierNotFound { span: Span },
+    #[error("{}", .0.primary_message)]
+    MacroError(MacroError),
 }
 
 impl DefCollectorErrorKind {
@@ -222,6 +235,47 @@ impl From<DefCollectorErrorKind> for Diagnostic {
                     trait_impl_span,
                 )
             }
+            DefCollectorErrorKind::ConstantNotInTrait { trait_name, impl_constant } => {
+                let trait_name = trait_name.0.contents;
+                let impl_constant_span = impl_constant.span();
+                let impl_constant_name = impl_constant.0.contents;
+                let primary_message = format!("Constant with name `{impl_constant_name}` is not part of trait `{trait_name}`, therefore it can't be implemented");
+                Diagnostic::simple_error(primary_message, "".to_owned(), impl_constant_span)
+            }
+            DefCollectorErrorKind::TraitMissingConstant {
+                trait_name,
+                constant_name,
+                trait_impl_span,
+            } => {
+                let trait_name = trait_name.0.contents;
+                let constant_name = constant_name.0.contents;
+                let primary_message = format!(
+                    "Constant `{constant_name}` from trait `{trait_name}` is not implemented"
+                );
+                Diagnostic::simple_error(
+                    primary_message,
+                    format!("Please implement {constant_name} here"),
+                    trait_impl_span,
+                )
+            }
+            DefCollectorErrorKind::TypeNotInTrait { trait_name, impl_type } => {
+                let trait_name = trait_name.0.contents;
+                let impl_type_span = impl_type.span();
+                let impl_type_name = impl_type.0.contents;
+                let primary_message = format!("Type with name `{impl_type_name}` is not part of trait `{trait_name}`, therefore it can't be implemented");
+                Diagnostic::simple_error(primary_message, "".to_owned(), impl_type_span)
+            }
+            DefCollectorErrorKind::TraitMissingType { trait_name, type_name, trait_impl_span } => {
+                let trait_name = trait_name.0.contents;
+                let type_name = type_name.0.contents;
+                let primary_message =
+                    format!("Type `{type_name}` from trait `{trait_name}` is not implemented");
+                Diagnostic::simple_error(
+                    primary_message,
+                    format!("Please implement {type_name} here"),
+                    trait_impl_span,
+                )
+            }
             DefCollectorErrorKind::NotATrait { not_a_trait_name } => {
                 let span = not_a_trait_name.span();
                 Diagnostic::simple_error(
@@ -245,16 +299,21 @@ impl From<DefCollectorErrorKind> for Diagnostic {
                 "Either the type or the trait must be from the same crate as the trait implementation".into(),
                 span,
             ),
-            #[cfg(feature = "aztec")]
-            DefCollectorErrorKind::AztecNotFound {} => Diagnostic::from_message(
-                "Aztec dependency not found. Please add aztec as a dependency in your Cargo.toml",
-            ),
-            #[cfg(feature = "aztec")]
-            DefCollectorErrorKind::AztecComputeNoteHashAndNullifierNotFound  {span} => Diagnostic::simple_error(
-                "compute_note_hash_and_nullifier function not found. Define it in your contract.".into(),
-                "".into(),
-                span
-            ),
+            DefCollectorErrorKind::UnsupportedDerive { trait_name, span } => {
+                Diagnostic::simple_error(
+                    format!("`{trait_name}` cannot be derived"),
+                    "Only Eq, Default, Serialize and Hash can be derived".into(),
+                    span,
+                )
+            }
+            DefCollectorErrorKind::MacroError(macro_error) => match macro_error.span {
+                Some(span) => Diagnostic::simple_error(
+                    macro_error.primary_message,
+                    macro_error.secondary_message.unwrap_or_default(),
+                    span,
+                ),
+                None => Diagnostic::from_message(&macro_error.primary_message),
+            },
         }
     }
 }
@@ -61,7 +61,7 @@ impl ItemScope {
         match mod_def {
             ModuleDefId::ModuleId(_) => add_item(&mut self.types),
             ModuleDefId::FunctionId(_) => add_item(&mut self.values),
-            ModuleDefId::TypeId(_) => add_item(&mut self.types),
+            ModuleDefId::TypeId(_) | ModuleDefId::EnumId(_) => add_item(&mut self.types),
             ModuleDefId::TypeAliasId(_) => add_item(&mut self.types),
             ModuleDefId::TraitId(_) => add_item(&mut self.types),
             ModuleDefId::GlobalId(_) => add_item(&mut self.values),
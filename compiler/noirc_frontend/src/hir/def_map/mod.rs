@@ -1,8 +1,9 @@
 use crate::graph::CrateId;
 use crate::hir::def_collector::dc_crate::{CompilationError, DefCollector};
+use crate::hir::def_collector::errors::DefCollectorErrorKind;
 use crate::hir::Context;
 use crate::node_interner::{FuncId, NodeInterner, StructId};
-use crate::parser::{parse_program, ParsedModule, ParserError};
+use crate::parser::{parse_program, ParsedModule, ParserError, ParserErrorReason};
 use crate::token::{FunctionAttribute, SecondaryAttribute, TestScope};
 use arena::{Arena, Index};
 use fm::{FileId, FileManager};
@@ -16,6 +17,8 @@ mod module_data;
 pub use module_data::*;
 mod namespace;
 pub use namespace::*;
+mod parse_cache;
+pub use parse_cache::*;
 
 /// The name that is used for a non-contract program's entry-point function.
 pub const MAIN_FUNCTION: &str = "main";
@@ -82,17 +85,18 @@ impl CrateDefMap {
 
         // First parse the root file.
         let root_file_id = context.crate_graph[crate_id].root_file_id;
-        let (ast, parsing_errors) = parse_file(&context.file_manager, root_file_id);
-        let ast = ast.into_sorted();
-
-        #[cfg(feature = "aztec")]
-        let ast = match super::aztec_library::transform(ast, &crate_id, context) {
-            Ok(ast) => ast,
-            Err((error, file_id)) => {
-                errors.push((error.into(), file_id));
-                return errors;
-            }
-        };
+        let (ast, parsing_errors) = parse_crate_file(context, crate_id, root_file_id);
+        let mut ast = ast.into_sorted();
+        for processor in context.macro_processors.clone() {
+            ast = match processor.process_untyped_ast(ast, &crate_id, context) {
+                Ok(ast) => ast,
+                Err((error, file_id)) => {
+                    let def_error = DefCollectorErrorKind::MacroError(error);
+                    errors.push((def_error.into(), file_id));
+                    return errors;
+                }
+            };
+        }
 
         // Allocate a default Module for the root, giving it a ModuleId
         let mut modules: Arena<ModuleData> = Arena::default();
@@ -263,6 +267,24 @@ pub fn parse_file(fm: &FileManager, file_id: FileId) -> (ParsedModule, Vec<Parse
     parse_program(file.source())
 }
 
+/// Parses a file belonging to the given crate, reusing the result of parsing the same source
+/// before if there is one. The standard library may use experimental features without warnings
+/// about them being reported to every program depending on it.
+pub(crate) fn parse_crate_file(
+    context: &Context,
+    crate_id: CrateId,
+    file_id: FileId,
+) -> (ParsedModule, Vec<ParserError>) {
+    let file = context.file_manager.fetch_file(file_id);
+    let (ast, mut errors) = context.parse_cache.parse(file.source());
+    if crate_id.is_stdlib() {
+        errors.retain(|error| {
+            !matches!(error.reason(), Some(ParserErrorReason::ExperimentalFeature(_)))
+        });
+    }
+    (ast, errors)
+}
+
 impl std::ops::Index<LocalModuleId> for CrateDefMap {
     type Output = ModuleData;
     fn index(&self, local_module_id: LocalModuleId) -> &ModuleData {
@@ -3,7 +3,7 @@ use std::collections::HashMap;
 use noirc_errors::Location;
 
 use crate::{
-    node_interner::{FuncId, StmtId, StructId, TraitId, TypeAliasId},
+    node_interner::{EnumId, FuncId, StmtId, StructId, TraitId, TypeAliasId},
     Ident,
 };
 
@@ -80,6 +80,10 @@ impl ModuleData {
         self.declare(name, ModuleDefId::TypeId(id), None)
     }
 
+    pub fn declare_enum(&mut self, name: Ident, id: EnumId) -> Result<(), (Ident, Ident)> {
+        self.declare(name, id.into(), None)
+    }
+
     pub fn declare_type_alias(
         &mut self,
         name: Ident,
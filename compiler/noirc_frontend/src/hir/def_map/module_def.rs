@@ -1,4 +1,4 @@
-use crate::node_interner::{FuncId, StmtId, StructId, TraitId, TypeAliasId};
+use crate::node_interner::{EnumId, FuncId, StmtId, StructId, TraitId, TypeAliasId};
 
 use super::ModuleId;
 
@@ -8,6 +8,7 @@ pub enum ModuleDefId {
     ModuleId(ModuleId),
     FunctionId(FuncId),
     TypeId(StructId),
+    EnumId(EnumId),
     TypeAliasId(TypeAliasId),
     TraitId(TraitId),
     GlobalId(StmtId),
@@ -28,6 +29,13 @@ impl ModuleDefId {
         }
     }
 
+    pub fn as_enum(&self) -> Option<EnumId> {
+        match self {
+            ModuleDefId::EnumId(enum_id) => Some(*enum_id),
+            _ => None,
+        }
+    }
+
     pub fn as_type_alias(&self) -> Option<TypeAliasId> {
         match self {
             ModuleDefId::TypeAliasId(type_alias_id) => Some(*type_alias_id),
@@ -55,6 +63,7 @@ impl ModuleDefId {
         match self {
             ModuleDefId::FunctionId(_) => "function",
             ModuleDefId::TypeId(_) => "type",
+            ModuleDefId::EnumId(_) => "enum",
             ModuleDefId::TypeAliasId(_) => "type alias",
             ModuleDefId::TraitId(_) => "trait",
             ModuleDefId::ModuleId(_) => "module",
@@ -75,6 +84,12 @@ impl From<FuncId> for ModuleDefId {
     }
 }
 
+impl From<EnumId> for ModuleDefId {
+    fn from(enum_id: EnumId) -> Self {
+        ModuleDefId::EnumId(enum_id)
+    }
+}
+
 impl From<TypeAliasId> for ModuleDefId {
     fn from(fid: TypeAliasId) -> Self {
         ModuleDefId::TypeAliasId(fid)
@@ -127,6 +142,20 @@ impl TryFromModuleDefId for StructId {
     }
 }
 
+impl TryFromModuleDefId for EnumId {
+    fn try_from(id: ModuleDefId) -> Option<Self> {
+        id.as_enum()
+    }
+
+    fn dummy_id() -> Self {
+        EnumId::dummy_id()
+    }
+
+    fn description() -> String {
+        "enum".to_string()
+    }
+}
+
 impl TryFromModuleDefId for TypeAliasId {
     fn try_from(id: ModuleDefId) -> Option<Self> {
         id.as_type_alias()
@@ -0,0 +1,34 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use crate::parser::{parse_program, ParsedModule, ParserError};
+
+/// The module parsed from a file, along with any errors encountered while parsing it.
+type ParsedFile = (ParsedModule, Vec<ParserError>);
+
+/// The parsed files of one or more [`Context`][crate::hir::Context]s, keyed by their source code.
+///
+/// Cloning a `ParseCache` shares it, so that the contexts of several packages which are compiled
+/// together only parse the files they have in common, such as the stdlib, once.
+#[derive(Clone, Default)]
+pub struct ParseCache {
+    parsed_files: Arc<Mutex<HashMap<Arc<str>, ParsedFile>>>,
+}
+
+impl ParseCache {
+    /// Parses `source`, or returns the result of parsing it before if it was.
+    pub(crate) fn parse(&self, source: &str) -> ParsedFile {
+        if let Some(parsed) = self.parsed_files.lock().expect("parse cache poisoned").get(source) {
+            return parsed.clone();
+        }
+
+        // The lock isn't held while parsing, so other threads may parse the same file
+        // concurrently. They will produce the same result.
+        let parsed = parse_program(source);
+        self.parsed_files
+            .lock()
+            .expect("parse cache poisoned")
+            .insert(Arc::from(source), parsed.clone());
+        parsed
+    }
+}
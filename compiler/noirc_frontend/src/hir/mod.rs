@@ -4,16 +4,15 @@ pub mod resolution;
 pub mod scope;
 pub mod type_check;
 
-#[cfg(feature = "aztec")]
-pub(crate) mod aztec_library;
-
 use crate::graph::{CrateGraph, CrateId};
 use crate::hir_def::function::FuncMeta;
-use crate::node_interner::{FuncId, NodeInterner, StructId};
-use def_map::{Contract, CrateDefMap};
+use crate::macros_api::MacroProcessor;
+use crate::node_interner::{EnumId, FuncId, NodeInterner, StructId};
+use def_map::{Contract, CrateDefMap, ModuleId, ParseCache};
 use fm::FileManager;
 use noirc_errors::Location;
 use std::collections::BTreeMap;
+use std::rc::Rc;
 
 use self::def_map::TestFunction;
 
@@ -33,6 +32,12 @@ pub struct Context {
     /// Maps a given (contract) module id to the next available storage slot
     /// for that contract.
     pub storage_slots: BTreeMap<def_map::ModuleId, StorageSlot>,
+
+    /// The processors run on each crate to handle attributes unknown to the compiler.
+    pub(crate) macro_processors: Vec<Rc<dyn MacroProcessor>>,
+
+    /// The files parsed while collecting crates, which may be shared with other contexts.
+    pub(crate) parse_cache: ParseCache,
 }
 
 #[derive(Debug, Copy, Clone)]
@@ -53,9 +58,23 @@ impl Context {
             crate_graph,
             file_manager,
             storage_slots: BTreeMap::new(),
+            macro_processors: Vec::new(),
+            parse_cache: ParseCache::default(),
         }
     }
 
+    /// Shares the given cache of parsed files with this context, so that any file which was
+    /// already parsed by another context using it isn't parsed again.
+    pub fn set_parse_cache(&mut self, parse_cache: ParseCache) {
+        self.parse_cache = parse_cache;
+    }
+
+    /// Registers a processor to be run on every crate compiled from now on,
+    /// after the processors which were registered before it.
+    pub fn add_macro_processor(&mut self, processor: Rc<dyn MacroProcessor>) {
+        self.macro_processors.push(processor);
+    }
+
     /// Returns the CrateDefMap for a given CrateId.
     /// It is perfectly valid for the compiler to look
     /// up a CrateDefMap and it is not available.
@@ -107,7 +126,17 @@ impl Context {
     /// For example, if you project contains a `main.nr` and `foo.nr` and you provide the `main_crate_id` and the
     /// `bar_struct_id` where the `Bar` struct is inside `foo.nr`, this function would return `foo::Bar` as a [String].
     pub fn fully_qualified_struct_path(&self, crate_id: &CrateId, id: StructId) -> String {
-        let module_id = id.module_id();
+        self.fully_qualified_type_path(crate_id, id.module_id())
+    }
+
+    /// Returns a fully-qualified path to the given [EnumId] from the given [CrateId],
+    /// see [Context::fully_qualified_struct_path].
+    pub fn fully_qualified_enum_path(&self, crate_id: &CrateId, id: EnumId) -> String {
+        self.fully_qualified_type_path(crate_id, id.module_id())
+    }
+
+    /// Returns the path to the type defined in the module `module_id`, as seen from `crate_id`.
+    fn fully_qualified_type_path(&self, crate_id: &CrateId, module_id: ModuleId) -> String {
         let child_id = module_id.local_id.0;
         let def_map =
             self.def_map(&module_id.krate).expect("The local crate should be analyzed already");
@@ -121,7 +150,7 @@ impl Context {
         } else {
             let crates = self
                 .find_dependencies(crate_id, &module_id.krate)
-                .expect("The Struct was supposed to be defined in a dependency");
+                .expect("The type was supposed to be defined in a dependency");
             crates.join("::") + "::" + &module_path
         }
     }
@@ -32,6 +32,8 @@ pub enum ResolverError {
     DuplicateField { field: Ident },
     #[error("No such field in struct")]
     NoSuchField { field: Ident, struct_definition: Ident },
+    #[error("No such variant in enum")]
+    NoSuchVariant { variant: Ident, enum_definition: Ident },
     #[error("Missing fields from struct")]
     MissingFields { span: Span, missing_fields: Vec<String>, struct_definition: Ident },
     #[error("Unneeded 'mut', pattern is already marked as mutable")]
@@ -52,8 +54,6 @@ pub enum ResolverError {
     NoSuchNumericTypeVariable { path: crate::Path },
     #[error("Closures cannot capture mutable variables")]
     CapturedMutableVariable { span: Span },
-    #[error("Test functions are not allowed to have any parameters")]
-    TestFunctionHasParameters { span: Span },
     #[error("Only struct types can be used in constructor expressions")]
     NonStructUsedInConstructor { typ: Type, span: Span },
     #[error("Only struct types can have generics")]
@@ -82,6 +82,12 @@ pub enum ResolverError {
     NonCrateFunctionCalled { name: String, span: Span },
     #[error("Only sized types may be used in the entry point to a program")]
     InvalidTypeForEntryPoint { span: Span },
+    #[error("`while` and `loop` are only allowed in unconstrained functions")]
+    LoopInConstrainedFn { span: Span },
+    #[error("`break` and `continue` are only allowed in unconstrained functions")]
+    JumpInConstrainedFn { span: Span },
+    #[error("`break` and `continue` are only allowed within loops")]
+    JumpOutsideLoop { span: Span },
 }
 
 impl ResolverError {
@@ -148,6 +154,19 @@ impl From<ResolverError> for Diagnostic {
                 );
                 error
             }
+            ResolverError::NoSuchVariant { variant, enum_definition } => {
+                let mut error = Diagnostic::simple_error(
+                    format!("no such variant {variant} defined in enum {enum_definition}"),
+                    String::new(),
+                    variant.span(),
+                );
+
+                error.add_secondary(
+                    format!("{enum_definition} defined here with no {variant} variant"),
+                    enum_definition.span(),
+                );
+                error
+            }
             ResolverError::MissingFields { span, missing_fields, struct_definition } => {
                 let plural = if missing_fields.len() != 1 { "s" } else { "" };
                 let missing_fields = missing_fields.join(", ");
@@ -239,11 +258,6 @@ impl From<ResolverError> for Diagnostic {
                 "Mutable variable".into(),
                 span,
             ),
-            ResolverError::TestFunctionHasParameters { span } => Diagnostic::simple_error(
-                "Test functions cannot have any parameters".into(),
-                "Try removing the parameters or moving the test into a wrapper function".into(),
-                span,
-            ),
             ResolverError::NonStructUsedInConstructor { typ, span } => Diagnostic::simple_error(
                 "Only struct types can be used in constructor expressions".into(),
                 format!("{typ} has no fields to construct it with"),
@@ -304,6 +318,15 @@ impl From<ResolverError> for Diagnostic {
             ResolverError::InvalidTypeForEntryPoint { span } => Diagnostic::simple_error(
                 "Only sized types may be used in the entry point to a program".to_string(),
                 "Slices, references, or any type containing them may not be used in main or a contract function".to_string(), span),
+            ResolverError::LoopInConstrainedFn { span } => Diagnostic::simple_error(
+                "`while` and `loop` are only allowed in unconstrained functions".into(),
+                "Constrained code must be able to unroll every loop, try a `for` loop instead".into(), span),
+            ResolverError::JumpInConstrainedFn { span } => Diagnostic::simple_error(
+                "`break` and `continue` are only allowed in unconstrained functions".into(),
+                "Constrained code must run every iteration of a loop in full".into(), span),
+            ResolverError::JumpOutsideLoop { span } => Diagnostic::simple_error(
+                "`break` and `continue` are only allowed within loops".into(),
+                String::new(), span),
         }
     }
 }
@@ -13,35 +13,36 @@
 // XXX: Resolver does not check for unused functions
 use crate::hir_def::expr::{
     HirArrayLiteral, HirBinaryOp, HirBlockExpression, HirCallExpression, HirCapturedVar,
-    HirCastExpression, HirConstructorExpression, HirExpression, HirIdent, HirIfExpression,
-    HirIndexExpression, HirInfixExpression, HirLambda, HirLiteral, HirMemberAccess,
-    HirMethodCallExpression, HirPrefixExpression,
+    HirCastExpression, HirConstructorExpression, HirEnumConstructorExpression, HirExpression,
+    HirIdent, HirIfExpression, HirIndexExpression, HirInfixExpression, HirLambda, HirLiteral,
+    HirMatchExpression, HirMemberAccess, HirMethodCallExpression, HirPrefixExpression,
 };
 
 use crate::hir_def::traits::{Trait, TraitConstraint};
-use crate::token::FunctionAttribute;
 use regex::Regex;
 use std::collections::{BTreeMap, HashSet};
 use std::rc::Rc;
 
 use crate::graph::CrateId;
 use crate::hir::def_map::{LocalModuleId, ModuleDefId, TryFromModuleDefId, MAIN_FUNCTION};
-use crate::hir_def::stmt::{HirAssignStatement, HirForStatement, HirLValue, HirPattern};
+use crate::hir_def::stmt::{
+    HirAssignStatement, HirForStatement, HirLValue, HirPattern, HirWhileStatement,
+};
 use crate::node_interner::{
-    DefinitionId, DefinitionKind, ExprId, FuncId, NodeInterner, StmtId, StructId, TraitId,
+    DefinitionId, DefinitionKind, EnumId, ExprId, FuncId, NodeInterner, StmtId, StructId, TraitId,
     TraitImplId,
 };
 use crate::{
     hir::{def_map::CrateDefMap, resolution::path_resolver::PathResolver},
-    BlockExpression, Expression, ExpressionKind, FunctionKind, Ident, Literal, NoirFunction,
-    StatementKind,
+    BlockExpression, Expression, ExpressionKind, FunctionDefinition, FunctionKind, Ident, Literal,
+    NoirFunction, Statement, StatementKind,
 };
 use crate::{
-    ArrayLiteral, ContractFunctionType, Distinctness, ForRange, FunctionVisibility, Generics,
-    LValue, NoirStruct, NoirTypeAlias, Path, PathKind, Pattern, Shared, StructType, Type,
-    TypeAliasType, TypeBinding, TypeVariable, UnaryOp, UnresolvedGenerics,
-    UnresolvedTraitConstraint, UnresolvedType, UnresolvedTypeData, UnresolvedTypeExpression,
-    Visibility, ERROR_IDENT,
+    ArrayLiteral, ContractFunctionType, Distinctness, EnumType, EnumVariants, ForRange,
+    FunctionVisibility, Generics, LValue, NoirEnum, NoirStruct, NoirTypeAlias, Path, PathKind,
+    Pattern, Shared, StructType, Type, TypeAliasType, TypeBinding, TypeVariable, UnaryOp,
+    UnresolvedGenerics, UnresolvedTraitConstraint, UnresolvedType, UnresolvedTypeData,
+    UnresolvedTypeExpression, Visibility, ERROR_IDENT,
 };
 use fm::FileId;
 use iter_extended::vecmap;
@@ -93,6 +94,9 @@ pub struct Resolver<'a> {
     /// to the corresponding trait impl ID.
     current_trait_impl: Option<TraitImplId>,
 
+    /// The associated types which `Self::Name` refers to when resolving a trait or a trait impl.
+    associated_types: Vec<(Ident, Type)>,
+
     /// True if the current module is a contract.
     /// This is usually determined by self.path_resolver.module_id(), but it can
     /// be overriden for impls. Impls are an odd case since the methods within resolve
@@ -111,6 +115,14 @@ pub struct Resolver<'a> {
     /// that are captured. We do this in order to create the hidden environment
     /// parameter for the lambda function.
     lambda_stack: Vec<LambdaContext>,
+
+    /// True while resolving the body of an unconstrained function. Only these may contain
+    /// `while` and `loop` statements along with `break` and `continue`.
+    in_unconstrained_fn: bool,
+
+    /// The number of loops enclosing the statement currently being resolved.
+    /// Used to check that `break` and `continue` only occur within a loop.
+    nested_loops: usize,
 }
 
 /// ResolverMetas are tagged onto each definition to track how many times they are used
@@ -147,7 +159,10 @@ impl<'a> Resolver<'a> {
             generics: Vec::new(),
             errors: Vec::new(),
             lambda_stack: Vec::new(),
+            in_unconstrained_fn: false,
+            nested_loops: 0,
             current_trait_impl: None,
+            associated_types: Vec::new(),
             file,
             in_contract,
         }
@@ -169,6 +184,17 @@ impl<'a> Resolver<'a> {
         self.self_type.as_ref()
     }
 
+    pub fn set_associated_types(&mut self, associated_types: Vec<(Ident, Type)>) {
+        self.associated_types = associated_types;
+    }
+
+    /// Returns the type which `Self::name` refers to, if `name` is an associated type
+    /// of the trait or trait impl being resolved.
+    pub fn lookup_associated_type(&self, name: &Ident) -> Option<Type> {
+        let (_, typ) = self.associated_types.iter().find(|(type_name, _)| type_name == name)?;
+        Some(typ.clone())
+    }
+
     fn push_err(&mut self, err: ResolverError) {
         self.errors.push(err);
     }
@@ -358,6 +384,7 @@ impl<'a> Resolver<'a> {
                 HirFunction::empty()
             }
             FunctionKind::Normal => {
+                self.in_unconstrained_fn = func.def.is_unconstrained;
                 let expr_id = self.intern_block(func.def.body);
                 self.interner.push_expr_location(expr_id, func.def.span, self.file);
                 HirFunction::unchecked_from_expr(expr_id)
@@ -373,7 +400,7 @@ impl<'a> Resolver<'a> {
     ) -> Option<TraitConstraint> {
         let typ = self.resolve_type(constraint.typ);
         let trait_id = self.lookup_trait_or_error(constraint.trait_bound.trait_path)?.id;
-        Some(TraitConstraint { typ, trait_id })
+        Some(TraitConstraint::new(typ, trait_id))
     }
 
     /// Translates an UnresolvedType into a Type and appends any
@@ -460,6 +487,12 @@ impl<'a> Resolver<'a> {
             }
         }
 
+        if args.is_empty() && path.segments.len() == 2 {
+            if let Some(typ) = self.lookup_associated_type_path(&path) {
+                return typ;
+            }
+        }
+
         // Check if the path is a type variable first. We currently disallow generics on type
         // variables since we do not support higher-kinded types.
         if path.segments.len() == 1 {
@@ -499,6 +532,16 @@ impl<'a> Resolver<'a> {
             }
         }
 
+        if let Some(enum_type) = self.lookup_enum(path.clone()) {
+            let expected_generic_count = enum_type.borrow().generics.len();
+
+            self.verify_generics_count(expected_generic_count, &mut args, span, || {
+                enum_type.borrow().to_string()
+            });
+
+            return Type::Enum(enum_type, args);
+        }
+
         match self.lookup_struct_or_error(path) {
             Some(struct_type) => {
                 let expected_generic_count = struct_type.borrow().generics.len();
@@ -546,6 +589,18 @@ impl<'a> Resolver<'a> {
         }
     }
 
+    /// Resolves `Self::Name` to an associated type of the trait or trait impl being resolved,
+    /// and `T::Name` to the associated type of a trait which the generic `T` is constrained by.
+    fn lookup_associated_type_path(&self, path: &Path) -> Option<Type> {
+        let (typ, name) = (&path.segments[0], &path.segments[1]);
+        if typ.0.contents == SELF_TYPE_NAME {
+            return self.lookup_associated_type(name);
+        }
+
+        let (name, var, _) = self.find_generic(&format!("{typ}::{name}"))?;
+        Some(Type::NamedGeneric(var.clone(), name.clone()))
+    }
+
     fn lookup_generic_or_global_type(&mut self, path: &Path) -> Option<Type> {
         if path.segments.len() == 1 {
             let name = &path.last_segment().0.contents;
@@ -554,11 +609,33 @@ impl<'a> Resolver<'a> {
             }
         }
 
+        if let Some(func_id) = self.lookup_self_method(path) {
+            return self.interner.associated_constant_value(func_id).map(Type::Constant);
+        }
+
         // If we cannot find a local generic of the same name, try to look up a global
         match self.path_resolver.resolve(self.def_maps, path.clone()) {
             Ok(ModuleDefId::GlobalId(id)) => {
                 Some(Type::Constant(self.eval_global_as_array_length(id)))
             }
+            // Associated constants known at compile time, e.g. `MyStruct::SIZE`
+            Ok(ModuleDefId::FunctionId(id)) => {
+                self.interner.associated_constant_value(id).map(Type::Constant)
+            }
+            _ => None,
+        }
+    }
+
+    /// Looks up `Self::name` among the methods of the struct being implemented.
+    fn lookup_self_method(&self, path: &Path) -> Option<FuncId> {
+        if path.segments.len() != 2 || path.segments[0].0.contents != SELF_TYPE_NAME {
+            return None;
+        }
+        match &self.self_type {
+            Some(Type::Struct(struct_type, _)) => {
+                let module = struct_type.borrow().id.module_id().module(self.def_maps);
+                module.find_func_with_name(&path.segments[1])
+            }
             _ => None,
         }
     }
@@ -716,6 +793,22 @@ impl<'a> Resolver<'a> {
         (generics, fields, self.errors)
     }
 
+    pub fn resolve_enum_variants(
+        mut self,
+        unresolved: NoirEnum,
+    ) -> (Generics, EnumVariants, Vec<ResolverError>) {
+        let generics = self.add_generics(&unresolved.generics);
+
+        // Check whether the enum definition has globals in the local module and add them to the scope
+        self.resolve_local_globals();
+
+        let variants = vecmap(unresolved.variants, |(ident, fields)| {
+            (ident, vecmap(fields, |typ| self.resolve_type(typ)))
+        });
+
+        (generics, variants, self.errors)
+    }
+
     fn resolve_local_globals(&mut self) {
         for (stmt_id, global_info) in self.interner.get_all_globals() {
             if global_info.local_id == self.path_resolver.local_module_id() {
@@ -732,9 +825,40 @@ impl<'a> Resolver<'a> {
         &mut self,
         where_clause: &Vec<UnresolvedTraitConstraint>,
     ) -> Vec<TraitConstraint> {
-        vecmap(where_clause, |constraint| TraitConstraint {
-            typ: self.resolve_type(constraint.typ.clone()),
-            trait_id: constraint.trait_bound.trait_id.unwrap_or_else(TraitId::dummy_id),
+        vecmap(where_clause, |constraint| {
+            let typ = self.resolve_type(constraint.typ.clone());
+            let trait_id = constraint.trait_bound.trait_id.unwrap_or_else(TraitId::dummy_id);
+            let associated_types = self.add_associated_type_generics(constraint, &typ, trait_id);
+            TraitConstraint { typ, trait_id, associated_types }
+        })
+    }
+
+    /// The associated types of a trait are not known in code constrained by the trait, so
+    /// `T::Name` is an implicit generic of the function which the type checker binds to
+    /// the associated type of the impl used at each call site.
+    fn add_associated_type_generics(
+        &mut self,
+        constraint: &UnresolvedTraitConstraint,
+        typ: &Type,
+        trait_id: TraitId,
+    ) -> Vec<Type> {
+        // The trait failed to resolve, which has already been reported
+        if trait_id == TraitId::dummy_id() {
+            return Vec::new();
+        }
+
+        let the_trait = self.interner.get_trait(trait_id);
+        vecmap(the_trait.types, |trait_type| {
+            let name = Rc::new(format!("{typ}::{}", trait_type.name));
+            if let Some((name, var, _)) = self.find_generic(&name) {
+                return Type::NamedGeneric(var.clone(), name.clone());
+            }
+
+            let id = self.interner.next_type_variable_id();
+            let typevar = Shared::new(TypeBinding::Unbound(id));
+            let span = constraint.typ.span.unwrap_or_else(|| trait_type.name.span());
+            self.generics.push((name.clone(), typevar.clone(), span));
+            Type::NamedGeneric(typevar, name)
         })
     }
 
@@ -749,6 +873,10 @@ impl<'a> Resolver<'a> {
 
         let attributes = func.attributes().clone();
 
+        // This must happen before collecting the generics since constraints on traits with
+        // associated types add generics to the function.
+        let trait_constraints = self.resolve_trait_constraints(&func.def.where_clause);
+
         let mut generics =
             vecmap(self.generics.clone(), |(name, typevar, _)| match &*typevar.borrow() {
                 TypeBinding::Unbound(id) => (*id, typevar.clone()),
@@ -768,7 +896,8 @@ impl<'a> Resolver<'a> {
                 });
             }
 
-            if self.is_entry_point_function(func) {
+            // The parameters of test functions are fuzzed as if they were the inputs of a program
+            if self.is_entry_point_function(func) || attributes.is_test_function() {
                 self.verify_type_valid_for_program_input(&typ);
             }
 
@@ -804,14 +933,6 @@ impl<'a> Resolver<'a> {
             self.push_err(ResolverError::DistinctNotAllowed { ident: func.name_ident().clone() });
         }
 
-        if matches!(attributes.function, Some(FunctionAttribute::Test { .. }))
-            && !parameters.is_empty()
-        {
-            self.push_err(ResolverError::TestFunctionHasParameters {
-                span: func.name_ident().span(),
-            });
-        }
-
         let mut typ = Type::Function(parameter_types, return_type, Box::new(Type::Unit));
 
         if !generics.is_empty() {
@@ -834,7 +955,7 @@ impl<'a> Resolver<'a> {
             return_visibility: func.def.return_visibility,
             return_distinctness: func.def.return_distinctness,
             has_body: !func.def.body.is_empty(),
-            trait_constraints: self.resolve_trait_constraints(&func.def.where_clause),
+            trait_constraints,
         }
     }
 
@@ -975,6 +1096,17 @@ impl<'a> Resolver<'a> {
                     }
                 }
             }
+            Type::Enum(enum_type, generics) => {
+                for (i, generic) in generics.iter().enumerate() {
+                    if let Type::NamedGeneric(type_variable, name) = generic {
+                        if enum_type.borrow().generic_is_numeric(i) {
+                            found.insert(name.to_string(), type_variable.clone());
+                        }
+                    } else {
+                        Self::find_numeric_generics_in_type(generic, found);
+                    }
+                }
+            }
             Type::MutableReference(element) => Self::find_numeric_generics_in_type(element, found),
             Type::String(length) => {
                 if let Type::NamedGeneric(type_variable, name) = length.as_ref() {
@@ -1001,7 +1133,7 @@ impl<'a> Resolver<'a> {
         })
     }
 
-    pub fn resolve_stmt(&mut self, stmt: StatementKind) -> HirStatement {
+    pub fn resolve_stmt(&mut self, stmt: StatementKind, span: Span) -> HirStatement {
         match stmt {
             StatementKind::Let(let_stmt) => {
                 let expression = self.resolve_expression(let_stmt.expression);
@@ -1043,7 +1175,7 @@ impl<'a> Resolver<'a> {
                                 true,
                                 DefinitionKind::Local(None),
                             );
-                            (decl, this.resolve_expression(block))
+                            (decl, this.resolve_loop_body(block))
                         });
 
                         HirStatement::For(HirForStatement {
@@ -1056,19 +1188,62 @@ impl<'a> Resolver<'a> {
                     range @ ForRange::Array(_) => {
                         let for_stmt =
                             range.into_for(for_loop.identifier, for_loop.block, for_loop.span);
-                        self.resolve_stmt(for_stmt)
+                        self.resolve_stmt(for_stmt, span)
                     }
                 }
             }
+            StatementKind::While(while_loop) => {
+                if !self.in_unconstrained_fn {
+                    self.push_err(ResolverError::LoopInConstrainedFn { span: while_loop.span });
+                }
+                let condition = self.resolve_expression(while_loop.condition);
+                let block = self.resolve_loop_body(while_loop.block);
+                HirStatement::While(HirWhileStatement { condition, block })
+            }
+            StatementKind::Loop(loop_statement) => {
+                if !self.in_unconstrained_fn {
+                    self.push_err(ResolverError::LoopInConstrainedFn { span: loop_statement.span });
+                }
+                // `loop { .. }` is resolved as `while true { .. }`
+                let condition =
+                    self.interner.push_expr(HirExpression::Literal(HirLiteral::Bool(true)));
+                self.interner.push_expr_location(condition, loop_statement.span, self.file);
+                let block = self.resolve_loop_body(loop_statement.block);
+                HirStatement::While(HirWhileStatement { condition, block })
+            }
+            StatementKind::Break => {
+                self.check_loop_jump(span);
+                HirStatement::Break
+            }
+            StatementKind::Continue => {
+                self.check_loop_jump(span);
+                HirStatement::Continue
+            }
             StatementKind::Error => HirStatement::Error,
         }
     }
 
-    pub fn intern_stmt(&mut self, stmt: StatementKind) -> StmtId {
-        let hir_stmt = self.resolve_stmt(stmt);
+    pub fn intern_stmt(&mut self, stmt: Statement) -> StmtId {
+        let hir_stmt = self.resolve_stmt(stmt.kind, stmt.span);
         self.interner.push_stmt(hir_stmt)
     }
 
+    /// `break` and `continue` may only be used within loops of unconstrained functions.
+    fn check_loop_jump(&mut self, span: Span) {
+        if self.nested_loops == 0 {
+            self.push_err(ResolverError::JumpOutsideLoop { span });
+        } else if !self.in_unconstrained_fn {
+            self.push_err(ResolverError::JumpInConstrainedFn { span });
+        }
+    }
+
+    fn resolve_loop_body(&mut self, block: Expression) -> ExprId {
+        self.nested_loops += 1;
+        let block = self.resolve_expression(block);
+        self.nested_loops -= 1;
+        block
+    }
+
     fn resolve_lvalue(&mut self, lvalue: LValue) -> HirLValue {
         match lvalue {
             LValue::Ident(ident) => {
@@ -1207,8 +1382,10 @@ impl<'a> Resolver<'a> {
                 Literal::Unit => HirLiteral::Unit,
             }),
             ExpressionKind::Variable(path) => {
-                if let Some(expr) = self.resolve_trait_generic_path(&path) {
-                    expr
+                if let Some(enum_type) = self.lookup_variant_enum(&path) {
+                    self.resolve_enum_constructor(enum_type, &path, Vec::new())
+                } else if let Some(hir_expr) = self.resolve_trait_generic_path(&path) {
+                    self.call_if_associated_constant(hir_expr, expr.span)
                 } else {
                     // If the Path is being used as an Expression, then it is referring to a global from a separate module
                     // Otherwise, then it is referring to an Identifier
@@ -1248,7 +1425,7 @@ impl<'a> Resolver<'a> {
                         }
                     }
 
-                    HirExpression::Ident(hir_ident)
+                    self.call_if_associated_constant(HirExpression::Ident(hir_ident), expr.span)
                 }
             }
             ExpressionKind::Prefix(prefix) => {
@@ -1274,12 +1451,24 @@ impl<'a> Resolver<'a> {
                 })
             }
             ExpressionKind::Call(call_expr) => {
-                // Get the span and name of path for error reporting
-                let func = self.resolve_expression(*call_expr.func);
+                // Calls to enum variants such as `Shape::Circle(radius)` construct the variant
+                let variant = match &call_expr.func.kind {
+                    ExpressionKind::Variable(path) => {
+                        self.lookup_variant_enum(path).map(|enum_type| (enum_type, path.clone()))
+                    }
+                    _ => None,
+                };
 
-                let arguments = vecmap(call_expr.arguments, |arg| self.resolve_expression(arg));
-                let location = Location::new(expr.span, self.file);
-                HirExpression::Call(HirCallExpression { func, arguments, location })
+                if let Some((enum_type, path)) = variant {
+                    self.resolve_enum_constructor(enum_type, &path, call_expr.arguments)
+                } else {
+                    // Get the span and name of path for error reporting
+                    let func = self.resolve_expression(*call_expr.func);
+
+                    let arguments = vecmap(call_expr.arguments, |arg| self.resolve_expression(arg));
+                    let location = Location::new(expr.span, self.file);
+                    HirExpression::Call(HirCallExpression { func, arguments, location })
+                }
             }
             ExpressionKind::MethodCall(call_expr) => {
                 let method = call_expr.method_name;
@@ -1302,6 +1491,20 @@ impl<'a> Resolver<'a> {
                 consequence: self.resolve_expression(if_expr.consequence),
                 alternative: if_expr.alternative.map(|e| self.resolve_expression(e)),
             }),
+            ExpressionKind::Match(match_expr) => {
+                let expression = self.resolve_expression(match_expr.expression);
+                let rules = vecmap(match_expr.rules, |(pattern, branch)| {
+                    self.in_new_scope(|this| {
+                        let pattern = this.resolve_pattern_mutable(
+                            pattern,
+                            None,
+                            DefinitionKind::Local(None),
+                        );
+                        (pattern, this.resolve_expression(branch))
+                    })
+                });
+                HirExpression::Match(HirMatchExpression { expression, rules })
+            }
             ExpressionKind::Index(indexed_expr) => HirExpression::Index(HirIndexExpression {
                 collection: self.resolve_expression(indexed_expr.collection),
                 index: self.resolve_expression(indexed_expr.index),
@@ -1350,6 +1553,11 @@ impl<'a> Resolver<'a> {
 
                 this.lambda_stack.push(LambdaContext { captures: Vec::new(), scope_index });
 
+                // Lambdas are always compiled as constrained functions and cannot jump out
+                // of any loops they are defined in.
+                let in_unconstrained_fn = std::mem::take(&mut this.in_unconstrained_fn);
+                let nested_loops = std::mem::take(&mut this.nested_loops);
+
                 let parameters = vecmap(lambda.parameters, |(pattern, typ)| {
                     let parameter = DefinitionKind::Local(None);
                     (this.resolve_pattern(pattern, parameter), this.resolve_inferred_type(typ))
@@ -1359,6 +1567,8 @@ impl<'a> Resolver<'a> {
                 let body = this.resolve_expression(lambda.body);
 
                 let lambda_context = this.lambda_stack.pop().unwrap();
+                this.in_unconstrained_fn = in_unconstrained_fn;
+                this.nested_loops = nested_loops;
 
                 HirExpression::Lambda(HirLambda {
                     parameters,
@@ -1411,21 +1621,12 @@ impl<'a> Resolver<'a> {
                 HirPattern::Tuple(fields, span)
             }
             Pattern::Struct(name, fields, span) => {
-                let error_identifier = |this: &mut Self| {
-                    // Must create a name here to return a HirPattern::Identifier. Allowing
-                    // shadowing here lets us avoid further errors if we define ERROR_IDENT
-                    // multiple times.
-                    let name = ERROR_IDENT.into();
-                    let identifier = this.add_variable_decl(name, false, true, definition.clone());
-                    HirPattern::Identifier(identifier)
-                };
-
                 let (struct_type, generics) = match self.lookup_type_or_error(name) {
                     Some(Type::Struct(struct_type, generics)) => (struct_type, generics),
-                    None => return error_identifier(self),
+                    None => return self.error_pattern(definition),
                     Some(typ) => {
                         self.push_err(ResolverError::NonStructUsedInConstructor { typ, span });
-                        return error_identifier(self);
+                        return self.error_pattern(definition);
                     }
                 };
 
@@ -1439,9 +1640,79 @@ impl<'a> Resolver<'a> {
                 let typ = Type::Struct(struct_type, generics);
                 HirPattern::Struct(typ, fields, span)
             }
+            Pattern::Literal(literal) => {
+                let span = literal.span;
+                HirPattern::Literal(self.resolve_expression(literal), span)
+            }
+            Pattern::Variant(path, fields, span) => {
+                let enum_path = variant_enum_path(&path);
+                let enum_type = match enum_path.map(|path| self.lookup::<EnumId>(path)) {
+                    Some(Ok(enum_id)) => self.interner.get_enum(enum_id),
+                    Some(Err(error)) => {
+                        self.push_err(error);
+                        return self.error_pattern(definition);
+                    }
+                    None => unreachable!("Variant patterns always have at least two segments"),
+                };
+
+                let Some(variant) = self.lookup_variant_index(&enum_type, &path) else {
+                    return self.error_pattern(definition);
+                };
+
+                let fields = vecmap(fields, |field| {
+                    self.resolve_pattern_mutable(field, mutable, definition.clone())
+                });
+
+                let generics = enum_type.borrow().instantiate(self.interner);
+                HirPattern::Variant(Type::Enum(enum_type, generics), variant, fields, span)
+            }
         }
     }
 
+    /// Creates a placeholder pattern for a pattern which failed to resolve.
+    fn error_pattern(&mut self, definition: DefinitionKind) -> HirPattern {
+        // Must create a name here to return a HirPattern::Identifier. Allowing
+        // shadowing here lets us avoid further errors if we define ERROR_IDENT
+        // multiple times.
+        let name = ERROR_IDENT.into();
+        let identifier = self.add_variable_decl(name, false, true, definition);
+        HirPattern::Identifier(identifier)
+    }
+
+    /// Resolves the construction of an enum variant, e.g. `Shape::Circle(radius)` or `Shape::Empty`.
+    fn resolve_enum_constructor(
+        &mut self,
+        enum_type: Shared<EnumType>,
+        path: &Path,
+        arguments: Vec<Expression>,
+    ) -> HirExpression {
+        let arguments = vecmap(arguments, |arg| self.resolve_expression(arg));
+
+        let Some(variant) = self.lookup_variant_index(&enum_type, path) else {
+            return HirExpression::Error;
+        };
+
+        let enum_generics = enum_type.borrow().instantiate(self.interner);
+        HirExpression::EnumConstructor(HirEnumConstructorExpression {
+            r#type: enum_type,
+            enum_generics,
+            variant,
+            arguments,
+        })
+    }
+
+    /// Returns the index of the variant named by the last segment of `path`,
+    /// issuing an error if `enum_type` has no such variant.
+    fn lookup_variant_index(&mut self, enum_type: &Shared<EnumType>, path: &Path) -> Option<usize> {
+        let variant = path.last_segment();
+        let index = enum_type.borrow().variant_index(&variant.0.contents);
+        if index.is_none() {
+            let enum_definition = enum_type.borrow().name.clone();
+            self.push_err(ResolverError::NoSuchVariant { variant, enum_definition });
+        }
+        index
+    }
+
     /// Resolve all the fields of a struct constructor expression.
     /// Ensures all fields are present, none are repeated, and all
     /// are part of the struct.
@@ -1575,6 +1846,15 @@ impl<'a> Resolver<'a> {
         self.lookup(path).ok().map(|id| self.interner.get_type_alias(id))
     }
 
+    fn lookup_enum(&mut self, path: Path) -> Option<Shared<EnumType>> {
+        self.lookup(path).ok().map(|id| self.interner.get_enum(id))
+    }
+
+    /// If `path` has the form `Enum::Variant`, returns the enum it refers to.
+    fn lookup_variant_enum(&mut self, path: &Path) -> Option<Shared<EnumType>> {
+        variant_enum_path(path).and_then(|enum_path| self.lookup_enum(enum_path))
+    }
+
     // this resolves Self::some_static_method, inside an impl block (where we don't have a concrete self_type)
     fn resolve_trait_static_method_by_self(&mut self, path: &Path) -> Option<HirExpression> {
         if let Some(trait_id) = self.trait_id {
@@ -1634,13 +1914,43 @@ impl<'a> Resolver<'a> {
             .or_else(|| self.resolve_trait_method_by_named_generic(path))
     }
 
+    /// Associated constants are lowered to methods without parameters, so referring to one
+    /// calls the method implementing it.
+    fn call_if_associated_constant(
+        &mut self,
+        hir_expr: HirExpression,
+        span: Span,
+    ) -> HirExpression {
+        let is_constant = match &hir_expr {
+            HirExpression::Ident(ident) if ident.id != DefinitionId::dummy_id() => {
+                match self.interner.definition(ident.id).kind {
+                    DefinitionKind::Function(id) => self.interner.is_associated_constant(id),
+                    _ => false,
+                }
+            }
+            HirExpression::TraitMethodReference(_, method) => {
+                let the_trait = self.interner.get_trait(method.trait_id);
+                the_trait.is_constant(&the_trait.methods[method.method_index].name)
+            }
+            _ => false,
+        };
+        if !is_constant {
+            return hir_expr;
+        }
+
+        let func = self.interner.push_expr(hir_expr);
+        self.interner.push_expr_location(func, span, self.file);
+        let location = Location::new(span, self.file);
+        HirExpression::Call(HirCallExpression { func, arguments: Vec::new(), location })
+    }
+
     fn resolve_path(&mut self, path: Path) -> Result<ModuleDefId, ResolverError> {
         self.path_resolver.resolve(self.def_maps, path).map_err(ResolverError::PathResolutionError)
     }
 
     fn resolve_block(&mut self, block_expr: BlockExpression) -> HirExpression {
         let statements =
-            self.in_new_scope(|this| vecmap(block_expr.0, |stmt| this.intern_stmt(stmt.kind)));
+            self.in_new_scope(|this| vecmap(block_expr.0, |stmt| this.intern_stmt(stmt)));
         HirExpression::Block(HirBlockExpression(statements))
     }
 
@@ -1671,6 +1981,20 @@ impl<'a> Resolver<'a> {
         0
     }
 
+    /// Evaluates the value of an associated constant lowered to `function`, if it is an integer
+    /// known at compile time, so that the constant can be used as an array length.
+    pub fn eval_associated_constant(mut self, function: &FunctionDefinition) -> Option<u64> {
+        let [Statement { kind: StatementKind::Expression(value), span }] = function.body.0.as_slice()
+        else {
+            return None;
+        };
+        let length = UnresolvedTypeExpression::from_expr(value.clone(), *span).ok()?;
+        match self.convert_expression_type(length) {
+            Type::Constant(length) if self.errors.is_empty() => Some(length),
+            _ => None,
+        }
+    }
+
     fn try_eval_array_length_id(
         &self,
         rhs: ExprId,
@@ -1822,3 +2146,13 @@ pub fn verify_mutable_reference(interner: &NodeInterner, rhs: ExprId) -> Result<
         _ => Ok(()),
     }
 }
+
+/// Returns the path of the enum in a variant path such as `Shape::Circle`,
+/// or None if the path has only a single segment.
+fn variant_enum_path(path: &Path) -> Option<Path> {
+    let (_, enum_segments) = path.segments.split_last()?;
+    if enum_segments.is_empty() {
+        return None;
+    }
+    Some(Path { segments: enum_segments.to_vec(), kind: path.kind })
+}
@@ -113,6 +113,10 @@ pub enum TypeCheckError {
     },
     #[error("No matching impl found")]
     NoMatchingImplFound { constraints: Vec<(Type, String)>, span: Span },
+    #[error("Match is not exhaustive, pattern `{missing}` is not covered")]
+    NonExhaustiveMatch { missing: String, span: Span },
+    #[error("Variant {variant} holds {expected} values but {found} were given")]
+    VariantFieldCountMismatch { variant: String, expected: usize, found: usize, span: Span },
 }
 
 impl TypeCheckError {
@@ -269,6 +273,17 @@ impl From<TypeCheckError> for Diagnostic {
 
                 diagnostic
             }
+            TypeCheckError::NonExhaustiveMatch { missing, span } => Diagnostic::simple_error(
+                "Match is not exhaustive".to_string(),
+                format!("Pattern `{missing}` is not covered"),
+                span,
+            ),
+            TypeCheckError::VariantFieldCountMismatch { variant, expected, found, span } => {
+                let empty_or_s = if expected == 1 { "" } else { "s" };
+                let was_or_were = if found == 1 { "was" } else { "were" };
+                let msg = format!("Variant {variant} holds {expected} value{empty_or_s} but {found} {was_or_were} given");
+                Diagnostic::simple_error(msg, String::new(), span)
+            }
         }
     }
 }
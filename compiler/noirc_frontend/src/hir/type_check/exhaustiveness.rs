@@ -0,0 +1,367 @@
+//! Exhaustiveness checking for `match` expressions.
+//!
+//! This follows the "usefulness" algorithm described in Luc Maranget's
+//! [Warnings for pattern matching](http://moscova.inria.fr/~maranget/papers/warn/index.html).
+//! Each `HirPattern` is simplified into a tree of constructors and wildcards and we then search for
+//! a value which isn't matched by any rule. If one is found it is reported back to the user as an example.
+use acvm::FieldElement;
+use iter_extended::vecmap;
+use noirc_errors::Span;
+
+use crate::{
+    hir_def::{
+        expr::{HirExpression, HirLiteral, HirPrefixExpression},
+        stmt::HirPattern,
+    },
+    node_interner::ExprId,
+    Signedness, Type, UnaryOp,
+};
+
+use super::{errors::TypeCheckError, TypeChecker};
+
+/// Integer types with more bits than this are assumed to never be covered by literal patterns alone.
+const MAX_ENUMERABLE_INTEGER_BITS: u32 = 16;
+
+#[derive(Debug, Clone)]
+enum Pattern {
+    Wildcard,
+    Constructor(Constructor, Vec<Pattern>),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Constructor {
+    Bool(bool),
+    Integer(FieldElement),
+    Str(String),
+    /// The single constructor of a tuple, struct or unit type.
+    Product,
+    /// The variant of an enum with the given index.
+    Variant(usize),
+}
+
+impl<'interner> TypeChecker<'interner> {
+    /// Checks that the `rules` of a match expression over a value of type `typ` cover every possible value.
+    pub(super) fn check_match_exhaustiveness(
+        &mut self,
+        rules: &[(HirPattern, ExprId)],
+        typ: &Type,
+        span: Span,
+    ) {
+        let typ = typ.follow_bindings();
+        if typ == Type::Error {
+            return;
+        }
+
+        let rows = vecmap(rules, |(pattern, _)| vec![self.simplify_pattern(pattern)]);
+        if let Some(mut missing) = find_missing_value(&rows, &[typ]) {
+            self.errors
+                .push(TypeCheckError::NonExhaustiveMatch { missing: missing.remove(0), span });
+        }
+    }
+
+    fn simplify_pattern(&self, pattern: &HirPattern) -> Pattern {
+        match pattern {
+            HirPattern::Identifier(_) => Pattern::Wildcard,
+            HirPattern::Mutable(pattern, _) => self.simplify_pattern(pattern),
+            HirPattern::Tuple(fields, _) => Pattern::Constructor(
+                Constructor::Product,
+                vecmap(fields, |field| self.simplify_pattern(field)),
+            ),
+            HirPattern::Struct(Type::Struct(struct_type, generics), fields, _) => {
+                // Field patterns may be written in any order so we sort them into the order of the struct definition.
+                let struct_type = struct_type.borrow();
+                let mut field_patterns = vec![Pattern::Wildcard; struct_type.num_fields()];
+                for (name, field) in fields {
+                    if let Some((_, index)) = struct_type.get_field(&name.0.contents, generics) {
+                        field_patterns[index] = self.simplify_pattern(field);
+                    }
+                }
+                Pattern::Constructor(Constructor::Product, field_patterns)
+            }
+            // The struct pattern could not be resolved so an error has already been issued.
+            HirPattern::Struct(..) => Pattern::Wildcard,
+            HirPattern::Literal(literal, _) => match self.literal_constructor(literal) {
+                Some(constructor) => Pattern::Constructor(constructor, Vec::new()),
+                None => Pattern::Wildcard,
+            },
+            HirPattern::Variant(_, variant, fields, _) => Pattern::Constructor(
+                Constructor::Variant(*variant),
+                vecmap(fields, |field| self.simplify_pattern(field)),
+            ),
+        }
+    }
+
+    fn literal_constructor(&self, literal: &ExprId) -> Option<Constructor> {
+        match self.interner.expression(literal) {
+            HirExpression::Literal(HirLiteral::Bool(value)) => Some(Constructor::Bool(value)),
+            HirExpression::Literal(HirLiteral::Integer(value)) => Some(Constructor::Integer(value)),
+            HirExpression::Literal(HirLiteral::Str(value)) => Some(Constructor::Str(value)),
+            HirExpression::Prefix(HirPrefixExpression { operator: UnaryOp::Minus, rhs }) => {
+                match self.literal_constructor(&rhs)? {
+                    Constructor::Integer(value) => Some(Constructor::Integer(-value)),
+                    _ => None,
+                }
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Searches for a list of values, one per column of `rows`, which is not matched by any row.
+///
+/// Each row holds the patterns of a match rule, each of which must match the value of the
+/// corresponding type in `types`. Returns the missing values formatted as patterns.
+fn find_missing_value(rows: &[Vec<Pattern>], types: &[Type]) -> Option<Vec<String>> {
+    let Some((typ, remaining_types)) = types.split_first() else {
+        // There are no more values to match so only a missing rule leaves the value unmatched.
+        return rows.is_empty().then(Vec::new);
+    };
+
+    let mut head_constructors = Vec::new();
+    for row in rows {
+        if let Pattern::Constructor(constructor, _) = &row[0] {
+            if !head_constructors.contains(constructor) {
+                head_constructors.push(constructor.clone());
+            }
+        }
+    }
+
+    match complete_constructors(typ, &head_constructors) {
+        // Every value of `typ` is built from one of these constructors so we check each in turn.
+        Some(constructors) => constructors.into_iter().find_map(|constructor| {
+            let field_types = constructor_fields(typ, &constructor);
+            let arity = field_types.len();
+
+            let rows: Vec<_> =
+                rows.iter().filter_map(|row| specialize(row, &constructor, arity)).collect();
+            let types: Vec<_> =
+                field_types.into_iter().chain(remaining_types.iter().cloned()).collect();
+
+            let mut missing = find_missing_value(&rows, &types)?;
+            let fields = missing.drain(..arity).collect();
+            missing.insert(0, format_constructor(typ, &constructor, fields));
+            Some(missing)
+        }),
+        // There are values of `typ` which no rule's constructor matches so only wildcards can match them.
+        None => {
+            let rows: Vec<_> = rows
+                .iter()
+                .filter(|row| matches!(row[0], Pattern::Wildcard))
+                .map(|row| row[1..].to_vec())
+                .collect();
+
+            let mut missing = find_missing_value(&rows, remaining_types)?;
+            missing.insert(0, format_missing_constructor(typ, &head_constructors));
+            Some(missing)
+        }
+    }
+}
+
+/// Returns all constructors of `typ` if `used_constructors` contains every one of them.
+fn complete_constructors(
+    typ: &Type,
+    used_constructors: &[Constructor],
+) -> Option<Vec<Constructor>> {
+    match typ {
+        Type::Tuple(_) | Type::Struct(..) | Type::Unit => Some(vec![Constructor::Product]),
+        Type::Enum(enum_type, _) => {
+            Some(vecmap(0..enum_type.borrow().num_variants(), Constructor::Variant))
+        }
+        Type::Bool => {
+            let constructors = vec![Constructor::Bool(false), Constructor::Bool(true)];
+            constructors
+                .iter()
+                .all(|constructor| used_constructors.contains(constructor))
+                .then_some(constructors)
+        }
+        Type::Integer(signedness, bits) if *bits <= MAX_ENUMERABLE_INTEGER_BITS => {
+            // Literals which don't fit in the type can't match any of its values.
+            let in_range: Vec<_> = used_constructors
+                .iter()
+                .filter(|constructor| match constructor {
+                    Constructor::Integer(value) => fits_integer_type(*value, *signedness, *bits),
+                    _ => false,
+                })
+                .cloned()
+                .collect();
+            let num_values = 1 << bits;
+            (in_range.len() == num_values).then_some(in_range)
+        }
+        _ => None,
+    }
+}
+
+/// Returns true if `value` is a value of the integer type with the given signedness and bit size,
+/// with negative values of signed types being represented by their negation in the field.
+fn fits_integer_type(value: FieldElement, signedness: Signedness, bits: u32) -> bool {
+    match signedness {
+        Signedness::Unsigned => value.num_bits() <= bits,
+        Signedness::Signed => {
+            let Some(magnitude_bits) = bits.checked_sub(1) else {
+                return value.is_zero();
+            };
+            let negated = -value;
+            value.num_bits() <= magnitude_bits
+                || negated.num_bits() <= magnitude_bits
+                || negated == FieldElement::from(1_u128 << magnitude_bits)
+        }
+    }
+}
+
+fn constructor_fields(typ: &Type, constructor: &Constructor) -> Vec<Type> {
+    match (typ, constructor) {
+        (Type::Tuple(fields), Constructor::Product) => fields.clone(),
+        (Type::Struct(struct_type, generics), Constructor::Product) => {
+            vecmap(struct_type.borrow().get_fields(generics), |(_, typ)| typ)
+        }
+        (Type::Enum(enum_type, generics), Constructor::Variant(variant)) => {
+            enum_type.borrow().get_variant_fields(*variant, generics)
+        }
+        _ => Vec::new(),
+    }
+}
+
+/// Removes the first column of `row` if it can match values built from `constructor`,
+/// replacing it with patterns for each of the constructor's fields.
+fn specialize(row: &[Pattern], constructor: &Constructor, arity: usize) -> Option<Vec<Pattern>> {
+    let mut specialized = match &row[0] {
+        Pattern::Constructor(head, fields) if head == constructor && fields.len() == arity => {
+            fields.clone()
+        }
+        Pattern::Constructor(head, _) if head == constructor => vec![Pattern::Wildcard; arity],
+        Pattern::Constructor(..) => return None,
+        Pattern::Wildcard => vec![Pattern::Wildcard; arity],
+    };
+    specialized.extend_from_slice(&row[1..]);
+    Some(specialized)
+}
+
+fn format_constructor(typ: &Type, constructor: &Constructor, fields: Vec<String>) -> String {
+    match constructor {
+        Constructor::Bool(value) => value.to_string(),
+        Constructor::Integer(value) => value.to_string(),
+        Constructor::Str(value) => format!("{value:?}"),
+        Constructor::Product => match typ {
+            Type::Struct(struct_type, generics) => {
+                let struct_type = struct_type.borrow();
+                let field_names = struct_type.get_fields(generics);
+                let fields = field_names
+                    .into_iter()
+                    .zip(fields)
+                    .map(|((name, _), field)| format!("{name}: {field}"));
+                format!("{} {{ {} }}", struct_type.name, fields.collect::<Vec<_>>().join(", "))
+            }
+            _ => format!("({})", fields.join(", ")),
+        },
+        Constructor::Variant(variant) => {
+            let Type::Enum(enum_type, _) = typ else {
+                unreachable!("Variant constructors are only created for enum types")
+            };
+            let enum_type = enum_type.borrow();
+            let name = format!("{}::{}", enum_type.name, enum_type.variant_name(*variant));
+            if fields.is_empty() {
+                name
+            } else {
+                format!("{name}({})", fields.join(", "))
+            }
+        }
+    }
+}
+
+/// Formats a value of `typ` which isn't built from any of `used_constructors`.
+fn format_missing_constructor(typ: &Type, used_constructors: &[Constructor]) -> String {
+    match typ {
+        Type::Bool if !used_constructors.is_empty() => {
+            let used_true = used_constructors.contains(&Constructor::Bool(true));
+            (!used_true).to_string()
+        }
+        _ => "_".to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use acvm::FieldElement;
+
+    use super::{find_missing_value, Constructor, Pattern};
+    use crate::{Signedness, Type};
+
+    fn bool_pattern(value: bool) -> Pattern {
+        Pattern::Constructor(Constructor::Bool(value), Vec::new())
+    }
+
+    fn tuple_pattern(fields: Vec<Pattern>) -> Pattern {
+        Pattern::Constructor(Constructor::Product, fields)
+    }
+
+    #[test]
+    fn wildcard_is_exhaustive() {
+        let rows = vec![vec![Pattern::Wildcard]];
+        assert_eq!(find_missing_value(&rows, &[Type::FieldElement]), None);
+    }
+
+    #[test]
+    fn reports_missing_bool() {
+        let rows = vec![vec![bool_pattern(true)]];
+        assert_eq!(find_missing_value(&rows, &[Type::Bool]), Some(vec!["false".to_string()]));
+
+        let rows = vec![vec![bool_pattern(true)], vec![bool_pattern(false)]];
+        assert_eq!(find_missing_value(&rows, &[Type::Bool]), None);
+    }
+
+    #[test]
+    fn reports_missing_tuple() {
+        let typ = Type::Tuple(vec![Type::Bool, Type::FieldElement]);
+        let rows = vec![
+            vec![tuple_pattern(vec![bool_pattern(true), Pattern::Wildcard])],
+            vec![tuple_pattern(vec![
+                bool_pattern(false),
+                Pattern::Constructor(Constructor::Integer(1_u128.into()), Vec::new()),
+            ])],
+        ];
+
+        assert_eq!(find_missing_value(&rows, &[typ]), Some(vec!["(false, _)".to_string()]));
+    }
+
+    #[test]
+    fn small_integers_can_be_covered_by_literals() {
+        let typ = Type::Integer(crate::Signedness::Unsigned, 1);
+        let literal =
+            |value: u128| Pattern::Constructor(Constructor::Integer(value.into()), vec![]);
+
+        let rows = vec![vec![literal(0)]];
+        assert_eq!(find_missing_value(&rows, &[typ.clone()]), Some(vec!["_".to_string()]));
+
+        let rows = vec![vec![literal(0)], vec![literal(1)]];
+        assert_eq!(find_missing_value(&rows, &[typ]), None);
+    }
+
+    #[test]
+    fn out_of_range_literals_do_not_cover_integers() {
+        let literal =
+            |value: FieldElement| Pattern::Constructor(Constructor::Integer(value), vec![]);
+
+        let typ = Type::Integer(Signedness::Unsigned, 1);
+        let rows = vec![vec![literal(0_u128.into())], vec![literal(2_u128.into())]];
+        assert_eq!(find_missing_value(&rows, &[typ]), Some(vec!["_".to_string()]));
+
+        // The values of an i2 are -2, -1, 0 and 1, so 2 doesn't stand in for -2
+        let typ = Type::Integer(Signedness::Signed, 2);
+        let minus = |value: u128| literal(-FieldElement::from(value));
+        let rows = vec![
+            vec![literal(0_u128.into())],
+            vec![literal(1_u128.into())],
+            vec![minus(1)],
+            vec![literal(2_u128.into())],
+        ];
+        assert_eq!(find_missing_value(&rows, &[typ.clone()]), Some(vec!["_".to_string()]));
+
+        let rows = vec![
+            vec![literal(0_u128.into())],
+            vec![literal(1_u128.into())],
+            vec![minus(1)],
+            vec![minus(2)],
+        ];
+        assert_eq!(find_missing_value(&rows, &[typ]), None);
+    }
+}
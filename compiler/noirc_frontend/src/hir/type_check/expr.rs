@@ -8,10 +8,11 @@ use crate::{
             self, HirArrayLiteral, HirBinaryOp, HirExpression, HirLiteral, HirMethodCallExpression,
             HirMethodReference, HirPrefixExpression,
         },
+        traits::{TraitConstraint, TraitImpl},
         types::Type,
     },
-    node_interner::{DefinitionKind, ExprId, FuncId, TraitMethodId},
-    BinaryOpKind, Signedness, TypeBinding, TypeVariableKind, UnaryOp,
+    node_interner::{DefinitionKind, ExprId, FuncId, TraitId, TraitMethodId},
+    BinaryOpKind, Signedness, TypeBinding, TypeBindings, TypeVariableKind, UnaryOp,
 };
 
 use super::{errors::TypeCheckError, TypeChecker};
@@ -53,6 +54,13 @@ impl<'interner> TypeChecker<'interner> {
                 // variable to handle generic functions.
                 let t = self.interner.id_type_substitute_trait_as_type(ident.id);
                 let (typ, bindings) = t.instantiate(self.interner);
+
+                if let Some(DefinitionKind::Function(func_id)) =
+                    self.interner.try_definition(ident.id).map(|def| &def.kind)
+                {
+                    self.push_trait_constraints(*func_id, &bindings, *expr_id);
+                }
+
                 self.interner.store_instantiation_bindings(*expr_id, bindings);
                 typ
             }
@@ -123,11 +131,17 @@ impl<'interner> TypeChecker<'interner> {
                 let rhs_span = self.interner.expr_span(&infix_expr.rhs);
                 let span = lhs_span.merge(rhs_span);
 
-                self.infix_operand_type_rules(&lhs_type, &infix_expr.operator, &rhs_type, span)
-                    .unwrap_or_else(|error| {
-                        self.errors.push(error);
-                        Type::Error
-                    })
+                if let Some(method) = self.operator_trait_method(&lhs_type, &infix_expr.operator) {
+                    let lhs = (lhs_type, infix_expr.lhs, lhs_span);
+                    let rhs = (rhs_type, infix_expr.rhs, rhs_span);
+                    self.check_overloaded_operator(expr_id, &infix_expr, lhs, rhs, method)
+                } else {
+                    self.infix_operand_type_rules(&lhs_type, &infix_expr.operator, &rhs_type, span)
+                        .unwrap_or_else(|error| {
+                            self.errors.push(error);
+                            Type::Error
+                        })
+                }
             }
             HirExpression::Index(index_expr) => self.check_index_expression(expr_id, index_expr),
             HirExpression::Call(call_expr) => {
@@ -256,7 +270,11 @@ impl<'interner> TypeChecker<'interner> {
                 self.type_check_prefix_operand(&prefix_expr.operator, &rhs_type, span)
             }
             HirExpression::If(if_expr) => self.check_if_expr(&if_expr, expr_id),
+            HirExpression::Match(match_expr) => self.check_match_expr(&match_expr, expr_id),
             HirExpression::Constructor(constructor) => self.check_constructor(constructor, expr_id),
+            HirExpression::EnumConstructor(constructor) => {
+                self.check_enum_constructor(constructor, expr_id)
+            }
             HirExpression::MemberAccess(access) => self.check_member_access(access, *expr_id),
             HirExpression::Error => Type::Error,
             HirExpression::Tuple(elements) => {
@@ -285,16 +303,8 @@ impl<'interner> TypeChecker<'interner> {
 
                 Type::Function(params, Box::new(lambda.return_type), Box::new(env_type))
             }
-            HirExpression::TraitMethodReference(_, method) => {
-                let the_trait = self.interner.get_trait(method.trait_id);
-                let method = &the_trait.methods[method.method_index];
-
-                let typ = Type::Function(
-                    method.arguments.clone(),
-                    Box::new(method.return_type.clone()),
-                    Box::new(Type::Unit),
-                );
-
+            HirExpression::TraitMethodReference(object_type, method) => {
+                let typ = self.trait_method_type(&object_type, method);
                 let (typ, bindings) = typ.instantiate(self.interner);
                 self.interner.store_instantiation_bindings(*expr_id, bindings);
                 typ
@@ -504,22 +514,22 @@ impl<'interner> TypeChecker<'interner> {
         arguments: Vec<(Type, ExprId, Span)>,
         span: Span,
     ) -> Type {
-        let (fn_typ, param_len) = match method_ref {
+        let (fn_typ, param_len) = match &method_ref {
             HirMethodReference::FuncId(func_id) => {
-                if func_id == FuncId::dummy_id() {
+                if *func_id == FuncId::dummy_id() {
                     return Type::Error;
                 }
 
-                let func_meta = self.interner.function_meta(&func_id);
+                let func_meta = self.interner.function_meta(func_id);
                 let param_len = func_meta.parameters.len();
 
                 (func_meta.typ, param_len)
             }
-            HirMethodReference::TraitMethodId(_, method) => {
+            HirMethodReference::TraitMethodId(object_type, method) => {
                 let the_trait = self.interner.get_trait(method.trait_id);
-                let method = &the_trait.methods[method.method_index];
+                let param_len = the_trait.methods[method.method_index].arguments.len();
 
-                (method.get_type(), method.arguments.len())
+                (self.trait_method_type(object_type, *method), param_len)
             }
         };
 
@@ -535,6 +545,10 @@ impl<'interner> TypeChecker<'interner> {
 
         let (function_type, instantiation_bindings) = fn_typ.instantiate(self.interner);
 
+        if let HirMethodReference::FuncId(func_id) = method_ref {
+            self.push_trait_constraints(func_id, &instantiation_bindings, *function_ident_id);
+        }
+
         self.interner.store_instantiation_bindings(*function_ident_id, instantiation_bindings);
         self.interner.push_expr_type(function_ident_id, function_type.clone());
 
@@ -582,6 +596,39 @@ impl<'interner> TypeChecker<'interner> {
         }
     }
 
+    fn check_match_expr(
+        &mut self,
+        match_expr: &expr::HirMatchExpression,
+        expr_id: &ExprId,
+    ) -> Type {
+        let expr_type = self.check_expression(&match_expr.expression);
+
+        let mut match_type = None;
+        for (pattern, branch) in &match_expr.rules {
+            self.bind_pattern(pattern, expr_type.clone());
+            let branch_type = self.check_expression(branch);
+
+            let Some(expected_type) = &match_type else {
+                match_type = Some(branch_type);
+                continue;
+            };
+            let expr_span = self.interner.expr_span(branch);
+            self.unify(expected_type, &branch_type, || {
+                TypeCheckError::TypeMismatch {
+                    expected_typ: expected_type.to_string(),
+                    expr_typ: branch_type.to_string(),
+                    expr_span,
+                }
+                .add_context("Expected the types of all match branches to be equal")
+            });
+        }
+
+        let span = self.interner.expr_span(expr_id);
+        self.check_match_exhaustiveness(&match_expr.rules, &expr_type, span);
+
+        match_type.unwrap_or(Type::Unit)
+    }
+
     fn check_constructor(
         &mut self,
         constructor: expr::HirConstructorExpression,
@@ -621,6 +668,45 @@ impl<'interner> TypeChecker<'interner> {
         Type::Struct(typ, generics)
     }
 
+    fn check_enum_constructor(
+        &mut self,
+        constructor: expr::HirEnumConstructorExpression,
+        expr_id: &ExprId,
+    ) -> Type {
+        let typ = constructor.r#type;
+        let generics = constructor.enum_generics;
+        let span = self.interner.expr_span(expr_id);
+
+        let fields = typ.borrow().get_variant_fields(constructor.variant, &generics);
+        if fields.len() != constructor.arguments.len() {
+            let enum_type = typ.borrow();
+            self.errors.push(TypeCheckError::VariantFieldCountMismatch {
+                variant: format!(
+                    "{}::{}",
+                    enum_type.name,
+                    enum_type.variant_name(constructor.variant)
+                ),
+                expected: fields.len(),
+                found: constructor.arguments.len(),
+                span,
+            });
+        }
+
+        for (arg, param_type) in constructor.arguments.into_iter().zip(fields) {
+            let arg_type = self.check_expression(&arg);
+
+            self.unify_with_coercions(&arg_type, &param_type, arg, || {
+                TypeCheckError::TypeMismatch {
+                    expected_typ: param_type.to_string(),
+                    expr_typ: arg_type.to_string(),
+                    expr_span: span,
+                }
+            });
+        }
+
+        Type::Enum(typ, generics)
+    }
+
     fn check_member_access(&mut self, mut access: expr::HirMemberAccess, expr_id: ExprId) -> Type {
         let lhs_type = self.check_expression(&access.lhs).follow_bindings();
         let span = self.interner.expr_span(&expr_id);
@@ -847,6 +933,200 @@ impl<'interner> TypeChecker<'interner> {
         }
     }
 
+    /// Returns the method of the stdlib trait overloading `operator` if its left operand does not
+    /// natively support it, e.g. if it is a struct. Operators are only overloaded on generic types
+    /// which are constrained by the trait, so that `T == T` keeps comparing the values directly.
+    fn operator_trait_method(
+        &self,
+        lhs_type: &Type,
+        operator: &HirBinaryOp,
+    ) -> Option<TraitMethodId> {
+        let method = self.interner.get_operator_trait_method(operator.kind)?;
+        let overloaded = match lhs_type.follow_bindings() {
+            Type::Struct(..) | Type::Enum(..) | Type::Tuple(..) => true,
+            typ @ Type::NamedGeneric(..) => self.has_trait_constraint(&typ, method.trait_id),
+            _ => false,
+        };
+        overloaded.then_some(method)
+    }
+
+    /// Records the trait constraints of the given function, instantiated with `bindings`,
+    /// to verify that they hold once the function being checked is fully typed.
+    fn push_trait_constraints(
+        &mut self,
+        func_id: FuncId,
+        bindings: &TypeBindings,
+        expr_id: ExprId,
+    ) {
+        let func_meta = self.interner.function_meta(&func_id);
+        for constraint in func_meta.trait_constraints {
+            // The trait failed to resolve, which has already been reported
+            if constraint.trait_id == TraitId::dummy_id() {
+                continue;
+            }
+            let typ = constraint.typ.substitute(bindings);
+            let associated_types =
+                vecmap(&constraint.associated_types, |typ| typ.substitute(bindings));
+            let constraint =
+                TraitConstraint { typ, trait_id: constraint.trait_id, associated_types };
+            self.trait_constraints.push((constraint, expr_id));
+        }
+    }
+
+    /// Checks that an impl exists for each trait constraint of the generic functions referenced
+    /// in the function being checked. Generics of the function being checked only satisfy the
+    /// constraints which are part of its own where clause.
+    pub(super) fn verify_trait_constraints(&mut self) {
+        for (constraint, expr_id) in std::mem::take(&mut self.trait_constraints) {
+            let typ = constraint.typ.follow_bindings();
+            let result = match &typ {
+                Type::NamedGeneric(..) => {
+                    match self.find_trait_constraint(&typ, constraint.trait_id) {
+                        Some(own_constraint) => Ok(own_constraint.associated_types),
+                        None => Err(vec![TraitConstraint::new(typ, constraint.trait_id)]),
+                    }
+                }
+                _ => self
+                    .interner
+                    .lookup_trait_implementation(&typ, constraint.trait_id)
+                    .map(|trait_impl| self.impl_associated_types(&trait_impl.borrow(), &typ)),
+            };
+
+            match result {
+                Ok(associated_types) => {
+                    let span = self.interner.expr_span(&expr_id);
+                    let pairs = constraint.associated_types.iter().zip(&associated_types);
+                    for (expected, actual) in pairs {
+                        self.unify(actual, expected, || TypeCheckError::TypeMismatch {
+                            expected_typ: expected.to_string(),
+                            expr_typ: actual.to_string(),
+                            expr_span: span,
+                        });
+                    }
+                }
+                Err(erroring_constraints) => {
+                    let constraints = vecmap(erroring_constraints, |constraint| {
+                        let r#trait = self.interner.get_trait(constraint.trait_id);
+                        (constraint.typ, r#trait.name.to_string())
+                    });
+                    let span = self.interner.expr_span(&expr_id);
+                    self.errors.push(TypeCheckError::NoMatchingImplFound { constraints, span });
+                }
+            }
+        }
+    }
+
+    fn has_trait_constraint(&self, typ: &Type, trait_id: TraitId) -> bool {
+        self.find_trait_constraint(typ, trait_id).is_some()
+    }
+
+    /// Returns the constraint of the function being checked which constrains `typ` by the trait.
+    fn find_trait_constraint(&self, typ: &Type, trait_id: TraitId) -> Option<TraitConstraint> {
+        let func_meta = self.interner.function_meta(&self.current_function?);
+        func_meta
+            .trait_constraints
+            .into_iter()
+            .find(|constraint| constraint.typ == *typ && constraint.trait_id == trait_id)
+    }
+
+    /// Returns the type of a trait method called on `object_type`, in which the associated types
+    /// of the trait are replaced by the ones of `object_type` if they are known.
+    fn trait_method_type(&mut self, object_type: &Type, method: TraitMethodId) -> Type {
+        let the_trait = self.interner.get_trait(method.trait_id);
+        let typ = the_trait.methods[method.method_index].get_type();
+        if the_trait.types.is_empty() {
+            return typ;
+        }
+
+        let object_type = object_type.follow_bindings();
+        let associated_types = match &object_type {
+            Type::NamedGeneric(..) => self
+                .find_trait_constraint(&object_type, method.trait_id)
+                .map(|constraint| constraint.associated_types),
+            _ => {
+                self.interner.lookup_trait_implementation(&object_type, method.trait_id).ok().map(
+                    |trait_impl| self.impl_associated_types(&trait_impl.borrow(), &object_type),
+                )
+            }
+        };
+        // Otherwise the impl is only known once the method is monomorphized
+        let associated_types = associated_types
+            .unwrap_or_else(|| vecmap(&the_trait.types, |_| self.interner.next_type_variable()));
+
+        let bindings = the_trait
+            .types
+            .iter()
+            .zip(associated_types)
+            .map(|(trait_type, typ)| (trait_type.typevar_id, (trait_type.typevar.clone(), typ)))
+            .collect();
+        typ.substitute(&bindings)
+    }
+
+    /// Returns the associated types chosen by the given impl, which was found for `object_type`.
+    fn impl_associated_types(&self, trait_impl: &TraitImpl, object_type: &Type) -> Vec<Type> {
+        let (impl_type, bindings) = trait_impl.typ.instantiate_named_generics(self.interner);
+        // Binds the generics of the impl to the types they have for `object_type`
+        let _ = impl_type.try_unify(object_type);
+        vecmap(&trait_impl.associated_types, |typ| typ.substitute(&bindings))
+    }
+
+    /// Type checks an infix expression as a call to the trait method overloading its operator,
+    /// which replaces the operator during monomorphization.
+    fn check_overloaded_operator(
+        &mut self,
+        expr_id: &ExprId,
+        infix_expr: &expr::HirInfixExpression,
+        lhs: (Type, ExprId, Span),
+        rhs: (Type, ExprId, Span),
+        method: TraitMethodId,
+    ) -> Type {
+        let span = lhs.2.merge(rhs.2);
+        let object_type = lhs.0.follow_bindings();
+        let the_trait = self.interner.get_trait(method.trait_id);
+        let trait_method = &the_trait.methods[method.method_index];
+
+        let function_type = if matches!(object_type, Type::NamedGeneric(..)) {
+            // The impl is only known once the generic is monomorphized. Operator traits take
+            // `self` and another `Self` value and return either `Self` or a concrete type.
+            let return_type = if infix_expr.operator.kind.is_comparator() {
+                trait_method.return_type.clone()
+            } else {
+                object_type.clone()
+            };
+            let arguments = vec![object_type.clone(), object_type.clone()];
+            Type::Function(arguments, Box::new(return_type), Box::new(Type::Unit))
+        } else {
+            match self.interner.lookup_trait_implementation(&object_type, method.trait_id) {
+                Ok(trait_impl) => {
+                    let func_id = trait_impl.borrow().methods[method.method_index];
+                    self.interner.function_meta(&func_id).typ
+                }
+                Err(erroring_constraints) => {
+                    let constraints = vecmap(erroring_constraints, |constraint| {
+                        let r#trait = self.interner.get_trait(constraint.trait_id);
+                        (constraint.typ, r#trait.name.to_string())
+                    });
+                    self.errors.push(TypeCheckError::NoMatchingImplFound { constraints, span });
+                    return Type::Error;
+                }
+            }
+        };
+
+        let (function_type, bindings) = function_type.instantiate(self.interner);
+        self.interner.store_instantiation_bindings(*expr_id, bindings);
+        self.interner.push_overloaded_operator(*expr_id, method);
+
+        let return_type = self.bind_function_type(function_type, vec![lhs, rhs], span);
+
+        // The `Ordering` returned by `cmp` is converted into a bool during monomorphization
+        use BinaryOpKind::{Greater, GreaterEqual, Less, LessEqual};
+        if matches!(infix_expr.operator.kind, Less | LessEqual | Greater | GreaterEqual) {
+            Type::Bool
+        } else {
+            return_type
+        }
+    }
+
     fn lookup_method(
         &mut self,
         object_type: &Type,
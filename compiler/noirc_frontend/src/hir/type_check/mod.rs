@@ -8,13 +8,14 @@
 //! as all functions are required to give their full signatures. Closures are inferred but are
 //! never generalized and thus cannot be used polymorphically.
 mod errors;
+mod exhaustiveness;
 mod expr;
 mod stmt;
 
 pub use errors::TypeCheckError;
 
 use crate::{
-    hir_def::{expr::HirExpression, stmt::HirStatement},
+    hir_def::{expr::HirExpression, stmt::HirStatement, traits::TraitConstraint},
     node_interner::{ExprId, FuncId, NodeInterner, StmtId},
     Type,
 };
@@ -28,6 +29,11 @@ pub struct TypeChecker<'interner> {
     interner: &'interner mut NodeInterner,
     errors: Vec<TypeCheckError>,
     current_function: Option<FuncId>,
+
+    /// The trait constraints of each generic function referenced in the function being checked,
+    /// instantiated with the types it is referenced with, along with the referencing expression.
+    /// These are verified once the whole function has been checked and these types are known.
+    trait_constraints: Vec<(TraitConstraint, ExprId)>,
 }
 
 /// Type checks a function and assigns the
@@ -123,7 +129,13 @@ fn function_info(interner: &NodeInterner, function_body_id: &ExprId) -> (noirc_e
 
 impl<'interner> TypeChecker<'interner> {
     fn new(interner: &'interner mut NodeInterner) -> Self {
-        Self { delayed_type_checks: Vec::new(), interner, errors: vec![], current_function: None }
+        Self {
+            delayed_type_checks: Vec::new(),
+            interner,
+            errors: vec![],
+            current_function: None,
+            trait_constraints: Vec::new(),
+        }
     }
 
     pub fn push_delayed_type_check(&mut self, f: TypeCheckFn) {
@@ -135,6 +147,7 @@ impl<'interner> TypeChecker<'interner> {
         body: &ExprId,
     ) -> (Type, Vec<TypeCheckFn>, Vec<TypeCheckError>) {
         let body_type = self.check_expression(body);
+        self.verify_trait_constraints();
         (body_type, self.delayed_type_checks, self.errors)
     }
 
@@ -144,8 +157,10 @@ impl<'interner> TypeChecker<'interner> {
             interner,
             errors: vec![],
             current_function: None,
+            trait_constraints: Vec::new(),
         };
         this.check_statement(id);
+        this.verify_trait_constraints();
         this.errors
     }
 
@@ -6,7 +6,7 @@ use crate::node_interner::{DefinitionId, ExprId, FuncId, NodeInterner, StmtId, T
 use crate::{BinaryOp, BinaryOpKind, Ident, Shared, UnaryOp};
 
 use super::stmt::HirPattern;
-use super::types::{StructType, Type};
+use super::types::{EnumType, StructType, Type};
 
 /// A HirExpression is the result of an Expression in the AST undergoing
 /// name resolution. It is almost identical to the Expression AST node, but
@@ -22,11 +22,13 @@ pub enum HirExpression {
     Infix(HirInfixExpression),
     Index(HirIndexExpression),
     Constructor(HirConstructorExpression),
+    EnumConstructor(HirEnumConstructorExpression),
     MemberAccess(HirMemberAccess),
     Call(HirCallExpression),
     MethodCall(HirMethodCallExpression),
     Cast(HirCastExpression),
     If(HirIfExpression),
+    Match(HirMatchExpression),
     Tuple(Vec<ExprId>),
     Lambda(HirLambda),
     TraitMethodReference(Type, TraitMethodId),
@@ -120,6 +122,13 @@ pub struct HirIfExpression {
     pub alternative: Option<ExprId>,
 }
 
+/// Each rule is tried in order, the branch of the first rule whose pattern matches is evaluated.
+#[derive(Debug, Clone)]
+pub struct HirMatchExpression {
+    pub expression: ExprId,
+    pub rules: Vec<(HirPattern, ExprId)>,
+}
+
 // `lhs as type` in the source code
 #[derive(Debug, Clone)]
 pub struct HirCastExpression {
@@ -196,6 +205,17 @@ pub struct HirConstructorExpression {
     pub fields: Vec<(Ident, ExprId)>,
 }
 
+/// Construction of an enum variant, as in `Shape::Circle(radius)`
+#[derive(Debug, Clone)]
+pub struct HirEnumConstructorExpression {
+    pub r#type: Shared<EnumType>,
+    pub enum_generics: Vec<Type>,
+
+    /// The index of the variant being constructed within the enum definition.
+    pub variant: usize,
+    pub arguments: Vec<ExprId>,
+}
+
 /// Indexing, as in `array[index]`
 #[derive(Debug, Clone)]
 pub struct HirIndexExpression {
@@ -48,6 +48,8 @@ impl Parameters {
             HirPattern::Mutable(_, span) => *span,
             HirPattern::Tuple(_, span) => *span,
             HirPattern::Struct(_, _, span) => *span,
+            HirPattern::Literal(_, span) => *span,
+            HirPattern::Variant(_, _, _, span) => *span,
         });
 
         let merged_span = spans.pop().unwrap();
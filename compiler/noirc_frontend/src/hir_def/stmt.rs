@@ -14,6 +14,10 @@ pub enum HirStatement {
     Constrain(HirConstrainStatement),
     Assign(HirAssignStatement),
     For(HirForStatement),
+    /// `loop` statements are also represented as a `while` with a `true` condition.
+    While(HirWhileStatement),
+    Break,
+    Continue,
     Expression(ExprId),
     Semi(ExprId),
     Error,
@@ -43,6 +47,12 @@ pub struct HirForStatement {
     pub block: ExprId,
 }
 
+#[derive(Debug, Clone)]
+pub struct HirWhileStatement {
+    pub condition: ExprId,
+    pub block: ExprId,
+}
+
 /// Corresponds to `lvalue = expression;` in the source code
 #[derive(Debug, Clone)]
 pub struct HirAssignStatement {
@@ -63,14 +73,17 @@ pub enum HirPattern {
     Mutable(Box<HirPattern>, Span),
     Tuple(Vec<HirPattern>, Span),
     Struct(Type, Vec<(Ident, HirPattern)>, Span),
+    Literal(ExprId, Span),
+    /// A pattern matching the variant with the given index of an enum type.
+    Variant(Type, usize, Vec<HirPattern>, Span),
 }
 
 impl HirPattern {
     pub fn field_count(&self) -> usize {
         match self {
-            HirPattern::Identifier(_) => 0,
+            HirPattern::Identifier(_) | HirPattern::Literal(..) => 0,
             HirPattern::Mutable(pattern, _) => pattern.field_count(),
-            HirPattern::Tuple(fields, _) => fields.len(),
+            HirPattern::Tuple(fields, _) | HirPattern::Variant(_, _, fields, _) => fields.len(),
             HirPattern::Struct(_, fields, _) => fields.len(),
         }
     }
@@ -94,7 +107,9 @@ impl HirPattern {
             HirPattern::Identifier(ident) => ident.location.span,
             HirPattern::Mutable(_, span)
             | HirPattern::Tuple(_, span)
-            | HirPattern::Struct(_, _, span) => *span,
+            | HirPattern::Struct(_, _, span)
+            | HirPattern::Literal(_, span)
+            | HirPattern::Variant(_, _, _, span) => *span,
         }
     }
 }
@@ -27,10 +27,14 @@ pub struct TraitConstant {
     pub span: Span,
 }
 
+/// An associated type of a trait. In the signatures of the trait's methods, the associated
+/// type resolves to this TypeVariable, which is bound to the type chosen by an impl when
+/// checking the impl against the trait.
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct TraitType {
     pub name: Ident,
-    pub ty: Type,
+    pub typevar_id: TypeVariableId,
+    pub typevar: TypeVariable,
     pub span: Span,
 }
 
@@ -68,6 +72,9 @@ pub struct TraitImpl {
     pub file: FileId,
     pub methods: Vec<FuncId>, // methods[i] is the implementation of trait.methods[i] for Type typ
 
+    /// associated_types[i] is the type chosen for trait.types[i] by this impl.
+    pub associated_types: Vec<Type>,
+
     /// The where clause, if present, contains each trait requirement which must
     /// be satisfied for this impl to be selected. E.g. in `impl Eq for [T] where T: Eq`,
     /// `where_clause` would contain the one `T: Eq` constraint. If there is no where clause,
@@ -80,11 +87,14 @@ pub struct TraitConstraint {
     pub typ: Type,
     pub trait_id: TraitId,
     // pub trait_generics: Generics, TODO
+    /// associated_types[i] is the type standing for trait.types[i] in code constrained by
+    /// this constraint, which is bound to the type chosen by the impl of `typ`.
+    pub associated_types: Vec<Type>,
 }
 
 impl TraitConstraint {
     pub fn new(typ: Type, trait_id: TraitId) -> Self {
-        Self { typ, trait_id }
+        Self { typ, trait_id, associated_types: Vec::new() }
     }
 }
 
@@ -128,6 +138,20 @@ impl Trait {
         self.methods = methods;
     }
 
+    pub fn set_constants(&mut self, constants: Vec<TraitConstant>) {
+        self.constants = constants;
+    }
+
+    pub fn set_types(&mut self, types: Vec<TraitType>) {
+        self.types = types;
+    }
+
+    /// Associated constants are lowered to methods, so this is true for the name of a
+    /// method which was declared as a constant.
+    pub fn is_constant(&self, name: &Ident) -> bool {
+        self.constants.iter().any(|constant| constant.name == *name)
+    }
+
     pub fn find_method(&self, name: Ident) -> Option<TraitMethodId> {
         for (idx, method) in self.methods.iter().enumerate() {
             if method.name == name {
@@ -12,7 +12,10 @@ use iter_extended::vecmap;
 use noirc_errors::Span;
 use noirc_printable_type::PrintableType;
 
-use crate::{node_interner::StructId, Ident, Signedness};
+use crate::{
+    node_interner::{EnumId, StructId},
+    Ident, Signedness,
+};
 
 use super::{
     expr::{HirCallExpression, HirExpression, HirIdent},
@@ -51,6 +54,10 @@ pub enum Type {
     /// represents the generic arguments (if any) to this struct type.
     Struct(Shared<StructType>, Vec<Type>),
 
+    /// A user-defined enum type. As with structs, the `Shared<EnumType>` field refers to the
+    /// shared definition of this enum and the `Vec<Type>` holds its generic arguments (if any).
+    Enum(Shared<EnumType>, Vec<Type>),
+
     /// A tuple type with the given list of fields in the order they appear in source code.
     Tuple(Vec<Type>),
 
@@ -230,6 +237,114 @@ impl std::fmt::Display for StructType {
     }
 }
 
+/// The variants of an enum, each with the types of the fields it holds.
+pub type EnumVariants = Vec<(Ident, Vec<Type>)>;
+
+/// Represents an enum type in the type system. Each instance of this
+/// rust struct will be shared across all Type::Enum variants that represent
+/// the same enum type.
+#[derive(Debug, Eq)]
+pub struct EnumType {
+    /// A unique id representing this enum type. Used to check if two
+    /// enum types are equal.
+    pub id: EnumId,
+
+    pub name: Ident,
+
+    /// Variants are ordered by their declaration, which also determines the tag used to
+    /// represent each variant at runtime. As with struct fields, they should only be accessed
+    /// through methods which apply the generic arguments to the types they hold.
+    variants: EnumVariants,
+
+    pub generics: Generics,
+    pub span: Span,
+}
+
+impl std::hash::Hash for EnumType {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.id.hash(state);
+    }
+}
+
+impl PartialEq for EnumType {
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id
+    }
+}
+
+impl EnumType {
+    pub fn new(id: EnumId, name: Ident, span: Span, generics: Generics) -> EnumType {
+        EnumType { id, name, span, variants: Vec::new(), generics }
+    }
+
+    /// As with struct fields, the variants of an enum are resolved after the enum itself is
+    /// created so that enums may refer to each other.
+    pub fn set_variants(&mut self, variants: EnumVariants) {
+        assert!(self.variants.is_empty());
+        self.variants = variants;
+    }
+
+    pub fn num_variants(&self) -> usize {
+        self.variants.len()
+    }
+
+    /// Returns the index of the variant with the given name, if there is one.
+    pub fn variant_index(&self, variant_name: &str) -> Option<usize> {
+        self.variants.iter().position(|(name, _)| name.0.contents == variant_name)
+    }
+
+    pub fn variant_name(&self, index: usize) -> &Ident {
+        &self.variants[index].0
+    }
+
+    /// Returns the types held by the given variant, after being applied to the given generic arguments.
+    pub fn get_variant_fields(&self, index: usize, generic_args: &[Type]) -> Vec<Type> {
+        let substitutions = self.substitutions(generic_args);
+        vecmap(&self.variants[index].1, |typ| typ.substitute(&substitutions))
+    }
+
+    /// Returns all the variants of this type, after being applied to the given generic arguments.
+    pub fn get_variants(&self, generic_args: &[Type]) -> Vec<(String, Vec<Type>)> {
+        let substitutions = self.substitutions(generic_args);
+        vecmap(&self.variants, |(name, fields)| {
+            let fields = vecmap(fields, |typ| typ.substitute(&substitutions));
+            (name.0.contents.clone(), fields)
+        })
+    }
+
+    fn substitutions(&self, generic_args: &[Type]) -> TypeBindings {
+        assert_eq!(self.generics.len(), generic_args.len());
+
+        self.generics
+            .iter()
+            .zip(generic_args)
+            .map(|((old_id, old_var), new)| (*old_id, (old_var.clone(), new.clone())))
+            .collect()
+    }
+
+    /// True if the given index is the same index as a generic type of this enum
+    /// which is expected to be a numeric generic.
+    pub fn generic_is_numeric(&self, index_of_generic: usize) -> bool {
+        let target_id = self.generics[index_of_generic].0;
+        self.variants
+            .iter()
+            .flat_map(|(_, fields)| fields)
+            .any(|field| field.contains_numeric_typevar(target_id))
+    }
+
+    /// Instantiate this enum type, returning a Vec of the new generic args (in
+    /// the same order as self.generics)
+    pub fn instantiate(&self, interner: &mut NodeInterner) -> Vec<Type> {
+        vecmap(&self.generics, |_| interner.next_type_variable())
+    }
+}
+
+impl std::fmt::Display for EnumType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name)
+    }
+}
+
 /// Wrap around an unsolved type
 #[derive(Debug, Clone, Eq)]
 pub struct TypeAliasType {
@@ -512,6 +627,13 @@ impl Type {
                     }
                 })
             }
+            Type::Enum(enum_type, generics) => generics.iter().enumerate().any(|(i, generic)| {
+                if named_generic_id_matches_target(generic) {
+                    enum_type.borrow().generic_is_numeric(i)
+                } else {
+                    generic.contains_numeric_typevar(target_id)
+                }
+            }),
             Type::MutableReference(element) => element.contains_numeric_typevar(target_id),
             Type::String(length) => named_generic_id_matches_target(length),
             Type::FmtString(length, elements) => {
@@ -558,6 +680,12 @@ impl Type {
                 .get_fields(generics)
                 .into_iter()
                 .all(|(_, field)| field.is_valid_for_program_input()),
+            Type::Enum(definition, generics) => definition
+                .borrow()
+                .get_variants(generics)
+                .into_iter()
+                .flat_map(|(_, fields)| fields)
+                .all(|field| field.is_valid_for_program_input()),
         }
     }
 
@@ -621,6 +749,14 @@ impl std::fmt::Display for Type {
                     write!(f, "{}<{}>", s.borrow(), args.join(", "))
                 }
             }
+            Type::Enum(e, args) => {
+                let args = vecmap(args, |arg| arg.to_string());
+                if args.is_empty() {
+                    write!(f, "{}", e.borrow())
+                } else {
+                    write!(f, "{}<{}>", e.borrow(), args.join(", "))
+                }
+            }
             Type::TraitAsType(tr) => {
                 write!(f, "impl {}", tr.name)
             }
@@ -926,6 +1062,17 @@ impl Type {
                 }
             }
 
+            (Enum(id_a, args_a), Enum(id_b, args_b)) => {
+                if id_a == id_b && args_a.len() == args_b.len() {
+                    for (a, b) in args_a.iter().zip(args_b) {
+                        a.try_unify(b)?;
+                    }
+                    Ok(())
+                } else {
+                    Err(UnificationError)
+                }
+            }
+
             (NamedGeneric(binding_a, name_a), NamedGeneric(binding_b, name_b)) => {
                 // Ensure NamedGenerics are never bound during type checking
                 assert!(binding_a.borrow().is_unbound());
@@ -1119,7 +1266,7 @@ impl Type {
                 length.find_all_unbound_type_variables(interner, bindings);
                 env.find_all_unbound_type_variables(interner, bindings);
             }
-            Type::Struct(_, generics) => {
+            Type::Struct(_, generics) | Type::Enum(_, generics) => {
                 for generic in generics {
                     generic.find_all_unbound_type_variables(interner, bindings);
                 }
@@ -1196,6 +1343,10 @@ impl Type {
                 let args = vecmap(args, |arg| arg.substitute(type_bindings));
                 Type::Struct(fields.clone(), args)
             }
+            Type::Enum(variants, args) => {
+                let args = vecmap(args, |arg| arg.substitute(type_bindings));
+                Type::Enum(variants.clone(), args)
+            }
             Type::Tuple(fields) => {
                 let fields = vecmap(fields, |field| field.substitute(type_bindings));
                 Type::Tuple(fields)
@@ -1241,7 +1392,9 @@ impl Type {
                 len_occurs || field_occurs
             }
             Type::TraitAsType(_) => todo!(),
-            Type::Struct(_, generic_args) => generic_args.iter().any(|arg| arg.occurs(target_id)),
+            Type::Struct(_, generic_args) | Type::Enum(_, generic_args) => {
+                generic_args.iter().any(|arg| arg.occurs(target_id))
+            }
             Type::Tuple(fields) => fields.iter().any(|field| field.occurs(target_id)),
             Type::NamedGeneric(binding, _) | Type::TypeVariable(binding, _) => {
                 match &*binding.borrow() {
@@ -1269,6 +1422,31 @@ impl Type {
         }
     }
 
+    /// True if this type can be converted into a [`PrintableType`].
+    /// Expected to be called on a monomorphized type, see [`Type::follow_bindings`].
+    pub fn is_printable(&self) -> bool {
+        match self {
+            Type::FieldElement | Type::Integer(..) | Type::Bool => true,
+            Type::Array(size, typ) => size.evaluate_to_u64().is_some() && typ.is_printable(),
+            Type::String(size) => size.evaluate_to_u64().is_some(),
+            Type::TypeVariable(binding, TypeVariableKind::IntegerOrField) => {
+                match &*binding.borrow() {
+                    TypeBinding::Bound(typ) => typ.is_printable(),
+                    TypeBinding::Unbound(_) => true,
+                }
+            }
+            Type::Struct(def, args) => {
+                def.borrow().get_fields(args).iter().all(|(_, typ)| typ.is_printable())
+            }
+            Type::Enum(def, args) => def
+                .borrow()
+                .get_variants(args)
+                .iter()
+                .all(|(_, fields)| fields.iter().all(Type::is_printable)),
+            _ => false,
+        }
+    }
+
     /// Follow any TypeVariable bindings within this type. Doing so ensures
     /// that if the bindings are rebound or unbound from under the type then the
     /// returned type will not change (because it will no longer contain the
@@ -1291,6 +1469,10 @@ impl Type {
                 let args = vecmap(args, |arg| arg.follow_bindings());
                 Struct(def.clone(), args)
             }
+            Enum(def, args) => {
+                let args = vecmap(args, |arg| arg.follow_bindings());
+                Enum(def.clone(), args)
+            }
             Tuple(args) => Tuple(vecmap(args, |arg| arg.follow_bindings())),
             TypeVariable(var, _) | NamedGeneric(var, _) => {
                 if let TypeBinding::Bound(typ) = &*var.borrow() {
@@ -1418,6 +1600,13 @@ impl From<&Type> for PrintableType {
                 let fields = vecmap(fields, |(name, typ)| (name, typ.into()));
                 PrintableType::Struct { fields, name: struct_type.name.to_string() }
             }
+            Type::Enum(def, ref args) => {
+                let enum_type = def.borrow();
+                let variants = enum_type.get_variants(args);
+                let variants =
+                    vecmap(variants, |(name, fields)| (name, vecmap(fields, |typ| typ.into())));
+                PrintableType::Enum { variants, name: enum_type.name.to_string() }
+            }
             Type::TraitAsType(_) => unreachable!(),
             Type::Tuple(_) => todo!("printing tuple types is not yet implemented"),
             Type::TypeVariable(_, _) => unreachable!(),
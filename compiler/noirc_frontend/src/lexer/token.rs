@@ -69,6 +69,8 @@ pub enum Token {
     RightBracket,
     /// ->
     Arrow,
+    /// =>
+    FatArrow,
     /// |
     Pipe,
     /// #
@@ -184,6 +186,7 @@ impl fmt::Display for Token {
             Token::LeftBracket => write!(f, "["),
             Token::RightBracket => write!(f, "]"),
             Token::Arrow => write!(f, "->"),
+            Token::FatArrow => write!(f, "=>"),
             Token::Pipe => write!(f, "|"),
             Token::Pound => write!(f, "#"),
             Token::Comma => write!(f, ","),
@@ -503,6 +506,20 @@ impl Attribute {
                 Attribute::Secondary(SecondaryAttribute::ContractLibraryMethod)
             }
             ["event"] => Attribute::Secondary(SecondaryAttribute::Event),
+            ["derive", traits] => {
+                let traits: Vec<String> =
+                    traits.split(',').map(|name| name.trim().to_string()).collect();
+                for name in &traits {
+                    if name.is_empty() {
+                        return Err(LexerErrorKind::MalformedFuncAttribute {
+                            span,
+                            found: word.to_owned(),
+                        });
+                    }
+                    validate(name)?;
+                }
+                Attribute::Secondary(SecondaryAttribute::Derive(traits))
+            }
             ["deprecated", name] => {
                 if !name.starts_with('"') && !name.ends_with('"') {
                     return Err(LexerErrorKind::MalformedFuncAttribute {
@@ -582,6 +599,8 @@ pub enum SecondaryAttribute {
     ContractLibraryMethod,
     Event,
     Field(String),
+    // The traits to generate implementations of for the struct this is attached to
+    Derive(Vec<String>),
     Custom(String),
 }
 
@@ -596,6 +615,9 @@ impl fmt::Display for SecondaryAttribute {
             SecondaryAttribute::ContractLibraryMethod => write!(f, "#[contract_library_method]"),
             SecondaryAttribute::Event => write!(f, "#[event]"),
             SecondaryAttribute::Field(ref k) => write!(f, "#[field({k})]"),
+            SecondaryAttribute::Derive(ref traits) => {
+                write!(f, "#[derive({})]", traits.join(", "))
+            }
         }
     }
 }
@@ -619,6 +641,7 @@ impl AsRef<str> for SecondaryAttribute {
             SecondaryAttribute::Custom(string) | SecondaryAttribute::Field(string) => string,
             SecondaryAttribute::ContractLibraryMethod => "",
             SecondaryAttribute::Event => "",
+            SecondaryAttribute::Derive(_) => "",
         }
     }
 }
@@ -632,14 +655,17 @@ pub enum Keyword {
     Assert,
     AssertEq,
     Bool,
+    Break,
     Char,
     CompTime,
     Constrain,
+    Continue,
     Contract,
     Crate,
     Dep,
     Distinct,
     Else,
+    Enum,
     Field,
     Fn,
     For,
@@ -650,6 +676,8 @@ pub enum Keyword {
     In,
     Internal,
     Let,
+    Loop,
+    Match,
     Mod,
     Mut,
     Open,
@@ -672,14 +700,17 @@ impl fmt::Display for Keyword {
             Keyword::Assert => write!(f, "assert"),
             Keyword::AssertEq => write!(f, "assert_eq"),
             Keyword::Bool => write!(f, "bool"),
+            Keyword::Break => write!(f, "break"),
             Keyword::Char => write!(f, "char"),
             Keyword::CompTime => write!(f, "comptime"),
             Keyword::Constrain => write!(f, "constrain"),
+            Keyword::Continue => write!(f, "continue"),
             Keyword::Contract => write!(f, "contract"),
             Keyword::Crate => write!(f, "crate"),
             Keyword::Dep => write!(f, "dep"),
             Keyword::Distinct => write!(f, "distinct"),
             Keyword::Else => write!(f, "else"),
+            Keyword::Enum => write!(f, "enum"),
             Keyword::Field => write!(f, "Field"),
             Keyword::Fn => write!(f, "fn"),
             Keyword::For => write!(f, "for"),
@@ -690,6 +721,8 @@ impl fmt::Display for Keyword {
             Keyword::In => write!(f, "in"),
             Keyword::Internal => write!(f, "internal"),
             Keyword::Let => write!(f, "let"),
+            Keyword::Loop => write!(f, "loop"),
+            Keyword::Match => write!(f, "match"),
             Keyword::Mod => write!(f, "mod"),
             Keyword::Mut => write!(f, "mut"),
             Keyword::Open => write!(f, "open"),
@@ -715,14 +748,17 @@ impl Keyword {
             "assert" => Keyword::Assert,
             "assert_eq" => Keyword::AssertEq,
             "bool" => Keyword::Bool,
+            "break" => Keyword::Break,
             "char" => Keyword::Char,
             "comptime" => Keyword::CompTime,
             "constrain" => Keyword::Constrain,
+            "continue" => Keyword::Continue,
             "contract" => Keyword::Contract,
             "crate" => Keyword::Crate,
             "dep" => Keyword::Dep,
             "distinct" => Keyword::Distinct,
             "else" => Keyword::Else,
+            "enum" => Keyword::Enum,
             "Field" => Keyword::Field,
             "fn" => Keyword::Fn,
             "for" => Keyword::For,
@@ -733,6 +769,8 @@ impl Keyword {
             "in" => Keyword::In,
             "internal" => Keyword::Internal,
             "let" => Keyword::Let,
+            "loop" => Keyword::Loop,
+            "match" => Keyword::Match,
             "mod" => Keyword::Mod,
             "mut" => Keyword::Mut,
             "open" => Keyword::Open,
@@ -13,6 +13,7 @@
 pub mod ast;
 pub mod graph;
 pub mod lexer;
+pub mod macros_api;
 pub mod monomorphization;
 pub mod node_interner;
 pub mod parser;
@@ -0,0 +1,44 @@
+//! The API through which crates outside of the compiler can extend it with their own
+//! attributes, by transforming the AST of a crate before its definitions are collected and its
+//! HIR before it is type checked.
+//!
+//! Macro processors are registered on the [`HirContext`] and are run on every crate it compiles.
+pub use acvm::FieldElement;
+pub use fm::FileId;
+pub use noirc_errors::Span;
+
+pub use crate::graph::CrateId;
+pub use crate::hir::def_collector::errors::MacroError;
+pub use crate::hir::def_map::ModuleDefId;
+pub use crate::hir::Context as HirContext;
+pub use crate::hir_def::expr::{HirExpression, HirLiteral};
+pub use crate::hir_def::stmt::HirStatement;
+pub use crate::node_interner::{NodeInterner, StructId};
+pub use crate::parser::SortedModule;
+pub use crate::token::SecondaryAttribute;
+
+pub use crate::{
+    BlockExpression, CallExpression, CastExpression, Distinctness, Expression, ExpressionKind,
+    ForLoopStatement, ForRange, FunctionDefinition, FunctionReturnType, FunctionVisibility, Ident,
+    ImportStatement, IndexExpression, LetStatement, Literal, MemberAccessExpression,
+    MethodCallExpression, NoirFunction, NoirStruct, Path, PathKind, Pattern, PrefixExpression,
+    Signedness, Statement, StatementKind, StructType, Type, TypeImpl, UnaryOp, UnresolvedType,
+    UnresolvedTypeData, Visibility,
+};
+
+/// Transforms the crates being compiled, typically to implement custom attributes
+/// (e.g. `#[my_framework(entrypoint)]`) which the compiler knows nothing about.
+pub trait MacroProcessor {
+    /// Transforms the AST of the root module of a crate, before any of its definitions
+    /// are collected. Submodules declared inline are part of this AST.
+    fn process_untyped_ast(
+        &self,
+        ast: SortedModule,
+        crate_id: &CrateId,
+        context: &HirContext,
+    ) -> Result<SortedModule, (MacroError, FileId)>;
+
+    /// Transforms the HIR of a crate once its definitions have been resolved,
+    /// right before its functions are type checked.
+    fn process_typed_ast(&self, crate_id: &CrateId, context: &mut HirContext);
+}
@@ -1,6 +1,9 @@
+use std::collections::BTreeMap;
+
 use acvm::FieldElement;
 use iter_extended::vecmap;
 use noirc_errors::Location;
+use noirc_printable_type::PrintableType;
 
 use crate::{hir_def::function::FunctionSignature, BinaryOpKind, Distinctness, Signedness};
 
@@ -24,6 +27,9 @@ pub enum Expression {
     Index(Index),
     Cast(Cast),
     For(For),
+    While(While),
+    Break,
+    Continue,
     If(If),
     Tuple(Vec<Expression>),
     ExtractTupleField(Box<Expression>, usize),
@@ -48,7 +54,7 @@ pub enum Definition {
 
 /// ID of a local definition, e.g. from a let binding or
 /// function parameter that should be compiled before it is referenced.
-#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub struct LocalId(pub u32);
 
 /// A function ID corresponds directly to an index of `Program::functions`
@@ -78,6 +84,12 @@ pub struct For {
     pub end_range_location: Location,
 }
 
+#[derive(Debug, Clone, Hash)]
+pub struct While {
+    pub condition: Box<Expression>,
+    pub block: Box<Expression>,
+}
+
 #[derive(Debug, Clone, Hash)]
 pub enum Literal {
     Array(ArrayLiteral),
@@ -243,6 +255,18 @@ pub struct Program {
     /// forwarding to the next phase.
     pub return_distinctness: Distinctness,
     pub return_location: Option<Location>,
+    /// The user-defined local variables whose values are tracked for the debugger.
+    /// This is empty unless the program was monomorphized with [`monomorphize_debug`][super::monomorphize_debug].
+    pub debug_variables: BTreeMap<LocalId, DebugVariable>,
+}
+
+/// A local variable whose value can be inspected in the debugger
+#[derive(Debug, Clone, Hash)]
+pub struct DebugVariable {
+    pub name: String,
+    pub typ: PrintableType,
+    /// Where the variable is defined
+    pub location: Location,
 }
 
 impl Program {
@@ -251,8 +275,15 @@ impl Program {
         main_function_signature: FunctionSignature,
         return_distinctness: Distinctness,
         return_location: Option<Location>,
+        debug_variables: BTreeMap<LocalId, DebugVariable>,
     ) -> Program {
-        Program { functions, main_function_signature, return_distinctness, return_location }
+        Program {
+            functions,
+            main_function_signature,
+            return_distinctness,
+            return_location,
+            debug_variables,
+        }
     }
 
     pub fn main(&self) -> &Function {
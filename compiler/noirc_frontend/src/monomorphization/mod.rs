@@ -26,8 +26,8 @@ use crate::{
     },
     node_interner::{self, DefinitionKind, NodeInterner, StmtId, TraitMethodId},
     token::FunctionAttribute,
-    ContractFunctionType, FunctionKind, Type, TypeBinding, TypeBindings, TypeVariableKind,
-    Visibility,
+    BinaryOpKind, ContractFunctionType, FunctionKind, Type, TypeBinding, TypeBindings,
+    TypeVariableKind, Visibility,
 };
 
 use self::ast::{Definition, FuncId, Function, LocalId, Program};
@@ -76,6 +76,10 @@ struct Monomorphizer<'interner> {
     is_range_loop: bool,
 
     return_location: Option<Location>,
+
+    /// When set, the user-defined local variables are recorded in `debug_variables`
+    debug: bool,
+    debug_variables: BTreeMap<LocalId, ast::DebugVariable>,
 }
 
 type HirType = crate::Type;
@@ -92,7 +96,21 @@ type HirType = crate::Type;
 /// this function. Typically, this is the function named "main" in the source project,
 /// but it can also be, for example, an arbitrary test function for running `nargo test`.
 pub fn monomorphize(main: node_interner::FuncId, interner: &NodeInterner) -> Program {
-    let mut monomorphizer = Monomorphizer::new(interner);
+    monomorphize_program(main, interner, false)
+}
+
+/// Monomorphizes the program in the same way as [`monomorphize`] while also recording each
+/// user-defined local variable, which allows the debugger to display their values.
+pub fn monomorphize_debug(main: node_interner::FuncId, interner: &NodeInterner) -> Program {
+    monomorphize_program(main, interner, true)
+}
+
+fn monomorphize_program(
+    main: node_interner::FuncId,
+    interner: &NodeInterner,
+    debug: bool,
+) -> Program {
+    let mut monomorphizer = Monomorphizer::new(interner, debug);
     let function_sig = monomorphizer.compile_main(main);
 
     while !monomorphizer.queue.is_empty() {
@@ -106,11 +124,17 @@ pub fn monomorphize(main: node_interner::FuncId, interner: &NodeInterner) -> Pro
 
     let functions = vecmap(monomorphizer.finished_functions, |(_, f)| f);
     let FuncMeta { return_distinctness, .. } = interner.function_meta(&main);
-    Program::new(functions, function_sig, return_distinctness, monomorphizer.return_location)
+    Program::new(
+        functions,
+        function_sig,
+        return_distinctness,
+        monomorphizer.return_location,
+        monomorphizer.debug_variables,
+    )
 }
 
 impl<'interner> Monomorphizer<'interner> {
-    fn new(interner: &'interner NodeInterner) -> Self {
+    fn new(interner: &'interner NodeInterner, debug: bool) -> Self {
         Monomorphizer {
             globals: HashMap::new(),
             locals: HashMap::new(),
@@ -122,6 +146,8 @@ impl<'interner> Monomorphizer<'interner> {
             lambda_envs_stack: Vec::new(),
             is_range_loop: false,
             return_location: None,
+            debug,
+            debug_variables: BTreeMap::new(),
         }
     }
 
@@ -192,6 +218,24 @@ impl<'interner> Monomorphizer<'interner> {
         self.locals.insert(id, new_id);
     }
 
+    /// Records a user-defined variable for the debugger when monomorphizing in debug mode.
+    /// Variables whose values cannot be printed, such as references or functions, are skipped.
+    fn define_debug_variable(&mut self, ident: &HirIdent, new_id: LocalId, typ: &HirType) {
+        if !self.debug {
+            return;
+        }
+        let typ = typ.follow_bindings();
+        if typ.is_printable() {
+            let name = self.interner.definition_name(ident.id).to_owned();
+            let variable = ast::DebugVariable {
+                name,
+                typ: PrintableType::from(&typ),
+                location: ident.location,
+            };
+            self.debug_variables.insert(new_id, variable);
+        }
+    }
+
     /// Prerequisite: typ = typ.follow_bindings()
     fn define_global(&mut self, id: node_interner::FuncId, typ: HirType, new_id: FuncId) {
         self.globals.entry(id).or_default().insert(typ, new_id);
@@ -266,6 +310,7 @@ impl<'interner> Monomorphizer<'interner> {
                 let name = definition.name.clone();
                 new_params.push((new_id, definition.mutable, name, self.convert_type(typ)));
                 self.define_local(ident.id, new_id);
+                self.define_debug_variable(&ident, new_id, typ);
             }
             HirPattern::Mutable(pattern, _) => self.parameter(*pattern, typ, new_params),
             HirPattern::Tuple(fields, _) => {
@@ -291,6 +336,9 @@ impl<'interner> Monomorphizer<'interner> {
                     self.parameter(field, &field_type, new_params);
                 }
             }
+            HirPattern::Literal(..) | HirPattern::Variant(..) => {
+                unreachable!("Refutable patterns are rejected in parameters during parsing")
+            }
         }
     }
 
@@ -335,11 +383,19 @@ impl<'interner> Monomorphizer<'interner> {
             }
 
             HirExpression::Infix(infix) => {
-                let lhs = Box::new(self.expr(infix.lhs));
-                let rhs = Box::new(self.expr(infix.rhs));
+                let lhs = self.expr(infix.lhs);
+                let rhs = self.expr(infix.rhs);
                 let operator = infix.operator.kind;
                 let location = self.interner.expr_location(&expr);
-                ast::Expression::Binary(ast::Binary { lhs, rhs, operator, location })
+                match self.interner.get_overloaded_operator(expr) {
+                    Some(method) => {
+                        self.overloaded_operator(expr, &infix, method, lhs, rhs, location)
+                    }
+                    None => {
+                        let (lhs, rhs) = (Box::new(lhs), Box::new(rhs));
+                        ast::Expression::Binary(ast::Binary { lhs, rhs, operator, location })
+                    }
+                }
             }
 
             HirExpression::Index(index) => self.index(expr, index),
@@ -370,17 +426,21 @@ impl<'interner> Monomorphizer<'interner> {
                 })
             }
 
+            HirExpression::Match(match_expr) => self.match_expr(match_expr, expr),
+
             HirExpression::Tuple(fields) => {
                 let fields = vecmap(fields, |id| self.expr(id));
                 ast::Expression::Tuple(fields)
             }
             HirExpression::Constructor(constructor) => self.constructor(constructor, expr),
+            HirExpression::EnumConstructor(constructor) => self.enum_constructor(constructor, expr),
 
             HirExpression::Lambda(lambda) => self.lambda(lambda, expr),
 
             HirExpression::TraitMethodReference(typ, method) => {
-                if let Type::Function(_, _, _) = self.interner.id_type(expr) {
-                    self.resolve_trait_method_reference(typ, expr, method)
+                let function_type = self.interner.id_type(expr);
+                if let Type::Function(_, _, _) = function_type {
+                    self.resolve_trait_method_reference(typ, expr, &function_type, method)
                 } else {
                     unreachable!(
                         "Calling a non-function, this should've been caught in typechecking"
@@ -446,13 +506,15 @@ impl<'interner> Monomorphizer<'interner> {
                 self.is_range_loop = false;
                 let index_variable = self.next_local_id();
                 self.define_local(for_loop.identifier.id, index_variable);
+                let index_type = self.interner.id_type(for_loop.start_range);
+                self.define_debug_variable(&for_loop.identifier, index_variable, &index_type);
 
                 let block = Box::new(self.expr(for_loop.block));
 
                 ast::Expression::For(ast::For {
                     index_variable,
                     index_name: self.interner.definition_name(for_loop.identifier.id).to_owned(),
-                    index_type: self.convert_type(&self.interner.id_type(for_loop.start_range)),
+                    index_type: self.convert_type(&index_type),
                     start_range: Box::new(start),
                     end_range: Box::new(end),
                     start_range_location: self.interner.expr_location(&for_loop.start_range),
@@ -460,6 +522,13 @@ impl<'interner> Monomorphizer<'interner> {
                     block,
                 })
             }
+            HirStatement::While(while_loop) => {
+                let condition = Box::new(self.expr(while_loop.condition));
+                let block = Box::new(self.expr(while_loop.block));
+                ast::Expression::While(ast::While { condition, block })
+            }
+            HirStatement::Break => ast::Expression::Break,
+            HirStatement::Continue => ast::Expression::Continue,
             HirStatement::Expression(expr) => self.expr(expr),
             HirStatement::Semi(expr) => ast::Expression::Semi(Box::new(self.expr(expr))),
             HirStatement::Error => unreachable!(),
@@ -521,6 +590,35 @@ impl<'interner> Monomorphizer<'interner> {
         ast::Expression::Block(new_exprs)
     }
 
+    /// Enums are represented as a tuple of a tag, the index of the active variant, followed by a
+    /// tuple of fields for each variant. Each inactive variant is filled with zeroed values.
+    fn enum_constructor(
+        &mut self,
+        constructor: HirEnumConstructorExpression,
+        id: node_interner::ExprId,
+    ) -> ast::Expression {
+        let location = self.interner.expr_location(&id);
+        let variant_types = match self.convert_type(&self.interner.id_type(id)) {
+            ast::Type::Tuple(fields) => fields,
+            other => unreachable!("Expected enum to be represented as a tuple, found {other}"),
+        };
+
+        let tag = (constructor.variant as u128).into();
+        let mut fields =
+            vec![ast::Expression::Literal(ast::Literal::Integer(tag, ast::Type::Field, location))];
+
+        for (i, variant_type) in variant_types[1..].iter().enumerate() {
+            if i == constructor.variant {
+                let arguments = vecmap(&constructor.arguments, |arg| self.expr(*arg));
+                fields.push(ast::Expression::Tuple(arguments));
+            } else {
+                fields.push(self.zeroed_value_of_type(variant_type, location));
+            }
+        }
+
+        ast::Expression::Tuple(fields)
+    }
+
     fn block(&mut self, statement_ids: Vec<StmtId>) -> ast::Expression {
         ast::Expression::Block(vecmap(statement_ids, |id| self.statement(id)))
     }
@@ -535,6 +633,7 @@ impl<'interner> Monomorphizer<'interner> {
             HirPattern::Identifier(ident) => {
                 let new_id = self.next_local_id();
                 self.define_local(ident.id, new_id);
+                self.define_debug_variable(&ident, new_id, typ);
                 let definition = self.interner.definition(ident.id);
 
                 ast::Expression::Let(ast::Let {
@@ -564,6 +663,156 @@ impl<'interner> Monomorphizer<'interner> {
 
                 self.unpack_tuple_pattern(value, patterns_iter)
             }
+            // Literal patterns bind no variables, they are checked separately by `pattern_conditions`
+            HirPattern::Literal(..) => ast::Expression::Block(Vec::new()),
+            HirPattern::Variant(_, variant, patterns, _) => {
+                let (_, fields) = unwrap_enum_type(typ).swap_remove(variant);
+                let payload = ast::Expression::ExtractTupleField(Box::new(value), variant + 1);
+                self.unpack_tuple_pattern(payload, patterns.into_iter().zip(fields))
+            }
+        }
+    }
+
+    /// Lowers a match expression into a chain of if expressions over a fresh variable holding the
+    /// matched value: `match e { p1 => a, p2 => b, _ => c }` becomes
+    /// `{ let tmp = e; if p1 matches tmp { a } else if p2 matches tmp { b } else { c } }`.
+    ///
+    /// The match is known to be exhaustive so the final rule is taken whenever no other rule
+    /// matches. Its conditions are asserted rather than checked, as values which match no rule
+    /// can still reach the match: an enum whose tag is out of range may be passed in by the prover
+    /// or returned from an unconstrained function.
+    fn match_expr(
+        &mut self,
+        match_expr: HirMatchExpression,
+        id: node_interner::ExprId,
+    ) -> ast::Expression {
+        let value_type = self.interner.id_type(match_expr.expression);
+        let location = self.interner.expr_location(&match_expr.expression);
+        let value = self.expr(match_expr.expression);
+        let result_type = self.convert_type(&self.interner.id_type(id));
+
+        let fresh_id = self.next_local_id();
+        let value_ident = ast::Expression::Ident(ast::Ident {
+            location: None,
+            definition: Definition::Local(fresh_id),
+            mutable: false,
+            name: "_".into(),
+            typ: self.convert_type(&value_type),
+        });
+
+        let mut result = None;
+        for (pattern, branch) in match_expr.rules.into_iter().rev() {
+            let mut conditions = Vec::new();
+            self.pattern_conditions(
+                &pattern,
+                value_ident.clone(),
+                &value_type,
+                location,
+                &mut conditions,
+            );
+
+            let bindings = self.unpack_pattern(pattern, value_ident.clone(), &value_type);
+            let branch = ast::Expression::Block(vec![bindings, self.expr(branch)]);
+
+            let condition = conditions.into_iter().reduce(|lhs, rhs| ast::Binary {
+                location: rhs.location,
+                lhs: Box::new(ast::Expression::Binary(lhs)),
+                rhs: Box::new(ast::Expression::Binary(rhs)),
+                operator: BinaryOpKind::And,
+            });
+
+            result = Some(match (condition, result) {
+                (Some(condition), Some(alternative)) => ast::Expression::If(ast::If {
+                    condition: Box::new(ast::Expression::Binary(condition)),
+                    consequence: Box::new(branch),
+                    alternative: Some(Box::new(alternative)),
+                    typ: result_type.clone(),
+                }),
+                (Some(condition), None) => {
+                    let location = condition.location;
+                    let assertion = ast::Expression::Constrain(
+                        Box::new(ast::Expression::Binary(condition)),
+                        location,
+                        Some("Value matched no rule of the match expression".into()),
+                    );
+                    ast::Expression::Block(vec![assertion, branch])
+                }
+                (None, _) => branch,
+            });
+        }
+
+        let mut block = vec![ast::Expression::Let(ast::Let {
+            id: fresh_id,
+            mutable: false,
+            name: "_".into(),
+            expression: Box::new(value),
+        })];
+        block.extend(result);
+        ast::Expression::Block(block)
+    }
+
+    /// Collects the equality checks required for `value` to match each literal and enum variant
+    /// within `pattern`. Variant checks are attributed to `location`, the matched expression.
+    fn pattern_conditions(
+        &mut self,
+        pattern: &HirPattern,
+        value: ast::Expression,
+        typ: &HirType,
+        location: Location,
+        conditions: &mut Vec<ast::Binary>,
+    ) {
+        match pattern {
+            HirPattern::Identifier(_) => (),
+            HirPattern::Mutable(pattern, _) => {
+                self.pattern_conditions(pattern, value, typ, location, conditions);
+            }
+            HirPattern::Tuple(patterns, _) => {
+                let fields = unwrap_tuple_type(typ);
+                for (i, (pattern, field_type)) in patterns.iter().zip(fields).enumerate() {
+                    let field = ast::Expression::ExtractTupleField(Box::new(value.clone()), i);
+                    self.pattern_conditions(pattern, field, &field_type, location, conditions);
+                }
+            }
+            HirPattern::Struct(_, patterns, _) => {
+                let fields = unwrap_struct_type(typ);
+                for (i, (field_name, field_type)) in fields.into_iter().enumerate() {
+                    let pattern = patterns.iter().find(|(name, _)| name.0.contents == field_name);
+                    if let Some((_, pattern)) = pattern {
+                        let field = ast::Expression::ExtractTupleField(Box::new(value.clone()), i);
+                        self.pattern_conditions(pattern, field, &field_type, location, conditions);
+                    }
+                }
+            }
+            HirPattern::Literal(literal, _) => {
+                let location = self.interner.expr_location(literal);
+                conditions.push(ast::Binary {
+                    lhs: Box::new(value),
+                    rhs: Box::new(self.expr(*literal)),
+                    operator: BinaryOpKind::Equal,
+                    location,
+                });
+            }
+            HirPattern::Variant(_, variant, patterns, _) => {
+                let tag = ast::Expression::ExtractTupleField(Box::new(value.clone()), 0);
+                let variant_tag = (*variant as u128).into();
+                conditions.push(ast::Binary {
+                    lhs: Box::new(tag),
+                    rhs: Box::new(ast::Expression::Literal(ast::Literal::Integer(
+                        variant_tag,
+                        ast::Type::Field,
+                        location,
+                    ))),
+                    operator: BinaryOpKind::Equal,
+                    location,
+                });
+
+                let (_, fields) = unwrap_enum_type(typ).swap_remove(*variant);
+                let payload = ast::Expression::ExtractTupleField(Box::new(value), variant + 1);
+                for (i, (pattern, field_type)) in patterns.iter().zip(fields).enumerate() {
+                    let field = ast::Expression::ExtractTupleField(Box::new(payload.clone()), i);
+                    self.pattern_conditions(pattern, field, &field_type, location, conditions);
+                }
+            }
         }
     }
 
@@ -750,6 +999,15 @@ impl<'interner> Monomorphizer<'interner> {
                 ast::Type::Tuple(fields)
             }
 
+            HirType::Enum(def, args) => {
+                let variants = def.borrow().get_variants(args);
+                let mut fields = vec![ast::Type::Field];
+                fields.extend(variants.into_iter().map(|(_, variant_fields)| {
+                    ast::Type::Tuple(vecmap(variant_fields, |field| self.convert_type(&field)))
+                }));
+                ast::Type::Tuple(fields)
+            }
+
             HirType::Tuple(fields) => {
                 let fields = vecmap(fields, |x| self.convert_type(x));
                 ast::Type::Tuple(fields)
@@ -814,10 +1072,11 @@ impl<'interner> Monomorphizer<'interner> {
         &mut self,
         self_type: HirType,
         expr_id: node_interner::ExprId,
+        function_type: &HirType,
         method: TraitMethodId,
     ) -> ast::Expression {
-        let function_type = self.interner.id_type(expr_id);
-
+        // The type may be a generic which is bound to the concrete type it is monomorphized with
+        let self_type = self_type.follow_bindings();
         let trait_impl = self
             .interner
             .lookup_trait_implementation(&self_type, method.trait_id)
@@ -825,7 +1084,7 @@ impl<'interner> Monomorphizer<'interner> {
 
         let hir_func_id = trait_impl.borrow().methods[method.method_index];
 
-        let func_def = self.lookup_function(hir_func_id, expr_id, &function_type);
+        let func_def = self.lookup_function(hir_func_id, expr_id, function_type);
         let func_id = match func_def {
             Definition::Function(func_id) => func_id,
             _ => unreachable!(),
@@ -838,10 +1097,71 @@ impl<'interner> Monomorphizer<'interner> {
             mutable: false,
             location: None,
             name: the_trait.methods[method.method_index].name.0.contents.clone(),
-            typ: self.convert_type(&function_type),
+            typ: self.convert_type(function_type),
         })
     }
 
+    /// Calls the trait method overloading the operator of the given infix expression.
+    /// The result of `eq` is negated for `!=`, while the `Ordering` returned by `cmp`
+    /// is compared to the one matching the operator for `<`, `<=`, `>` and `>=`.
+    fn overloaded_operator(
+        &mut self,
+        expr_id: node_interner::ExprId,
+        infix: &HirInfixExpression,
+        method: TraitMethodId,
+        lhs: ast::Expression,
+        rhs: ast::Expression,
+        location: Location,
+    ) -> ast::Expression {
+        use BinaryOpKind::*;
+
+        let operator = infix.operator.kind;
+        let object_type = self.interner.id_type(infix.lhs);
+        let return_type = if operator.is_comparator() {
+            let the_trait = self.interner.get_trait(method.trait_id);
+            the_trait.methods[method.method_index].return_type.clone()
+        } else {
+            self.interner.id_type(expr_id)
+        };
+        let function_type = Type::Function(
+            vec![object_type.clone(), object_type.clone()],
+            Box::new(return_type.clone()),
+            Box::new(Type::Unit),
+        );
+
+        let func =
+            self.resolve_trait_method_reference(object_type, expr_id, &function_type, method);
+        let call = ast::Expression::Call(ast::Call {
+            func: Box::new(func),
+            arguments: vec![lhs, rhs],
+            return_type: self.convert_type(&return_type),
+            location,
+        });
+
+        match operator {
+            NotEqual => ast::Expression::Unary(ast::Unary {
+                operator: crate::UnaryOp::Not,
+                rhs: Box::new(call),
+                result_type: ast::Type::Bool,
+                location,
+            }),
+            Less | LessEqual | Greater | GreaterEqual => {
+                // `Ordering` holds a single field which is 0 for `less`, 1 for `equal`
+                // and 2 for `greater`.
+                let ordering = if matches!(operator, Less | GreaterEqual) { 0u128 } else { 2u128 };
+                let ordering = ast::Literal::Integer(ordering.into(), ast::Type::Field, location);
+                let operator = if matches!(operator, Less | Greater) { Equal } else { NotEqual };
+                ast::Expression::Binary(ast::Binary {
+                    lhs: Box::new(ast::Expression::ExtractTupleField(Box::new(call), 0)),
+                    rhs: Box::new(ast::Expression::Literal(ordering)),
+                    operator,
+                    location,
+                })
+            }
+            _ => call,
+        }
+    }
+
     fn function_call(
         &mut self,
         call: HirCallExpression,
@@ -1403,6 +1723,17 @@ fn unwrap_struct_type(typ: &HirType) -> Vec<(String, HirType)> {
     }
 }
 
+fn unwrap_enum_type(typ: &HirType) -> Vec<(String, Vec<HirType>)> {
+    match typ {
+        HirType::Enum(def, args) => def.borrow().get_variants(args),
+        HirType::TypeVariable(binding, TypeVariableKind::Normal) => match &*binding.borrow() {
+            TypeBinding::Bound(binding) => unwrap_enum_type(binding),
+            TypeBinding::Unbound(_) => unreachable!(),
+        },
+        other => unreachable!("unwrap_enum_type: expected enum, found {:?}", other),
+    }
+}
+
 fn perform_instantiation_bindings(bindings: &TypeBindings) {
     for (var, binding) in bindings.values() {
         *var.borrow_mut() = TypeBinding::Bound(binding.clone());
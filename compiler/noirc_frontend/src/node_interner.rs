@@ -7,13 +7,15 @@ use noirc_errors::{Location, Span, Spanned};
 
 use crate::ast::Ident;
 use crate::graph::CrateId;
-use crate::hir::def_collector::dc_crate::{UnresolvedStruct, UnresolvedTrait, UnresolvedTypeAlias};
+use crate::hir::def_collector::dc_crate::{
+    UnresolvedEnum, UnresolvedStruct, UnresolvedTrait, UnresolvedTypeAlias,
+};
 use crate::hir::def_map::{LocalModuleId, ModuleId};
 use crate::hir::StorageSlot;
 use crate::hir_def::stmt::HirLetStatement;
 use crate::hir_def::traits::TraitImpl;
 use crate::hir_def::traits::{Trait, TraitConstraint};
-use crate::hir_def::types::{StructType, Type};
+use crate::hir_def::types::{EnumType, StructType, Type};
 use crate::hir_def::{
     expr::HirExpression,
     function::{FuncMeta, HirFunction},
@@ -21,8 +23,8 @@ use crate::hir_def::{
 };
 use crate::token::{Attributes, SecondaryAttribute};
 use crate::{
-    ContractFunctionType, FunctionDefinition, FunctionVisibility, Generics, Shared, TypeAliasType,
-    TypeBinding, TypeBindings, TypeVariable, TypeVariableId, TypeVariableKind,
+    BinaryOpKind, ContractFunctionType, FunctionDefinition, FunctionVisibility, Generics, Shared,
+    TypeAliasType, TypeBinding, TypeBindings, TypeVariable, TypeVariableId, TypeVariableKind,
 };
 
 /// An arbitrary number to limit the recursion depth when searching for trait impls.
@@ -73,6 +75,12 @@ pub struct NodeInterner {
     structs: HashMap<StructId, Shared<StructType>>,
 
     struct_attributes: HashMap<StructId, StructAttributes>,
+
+    // Enum map.
+    //
+    // As with structs, each enum definition is shared across all type nodes referring to it.
+    enums: HashMap<EnumId, Shared<EnumType>>,
+
     // Type Aliases map.
     //
     // Map type aliases to the actual type.
@@ -103,6 +111,18 @@ pub struct NodeInterner {
     /// by splitting it up by type.
     trait_implementation_map: HashMap<TraitId, Vec<(Type, TraitImplId)>>,
 
+    /// The methods which associated constants of trait impls are lowered to, mapped to the
+    /// value of their constant if it is an integer known at compile time.
+    associated_constants: HashMap<FuncId, Option<u64>>,
+
+    /// The stdlib traits through which each binary operator can be overloaded,
+    /// e.g. `std::ops::Add` for `+`.
+    operator_traits: HashMap<BinaryOpKind, TraitId>,
+
+    /// The trait method called in place of each binary operator applied to operands which don't
+    /// natively support it, filled out during type checking.
+    overloaded_operators: HashMap<ExprId, TraitMethodId>,
+
     /// Map from ExprId (referring to a Function/Method call) to its corresponding TypeBindings,
     /// filled out during type checking from instantiated variables. Used during monomorphization
     /// to map call site types back onto function parameter types, and undo this binding as needed.
@@ -256,6 +276,30 @@ impl StructId {
     }
 }
 
+#[derive(Debug, Eq, PartialEq, Hash, Copy, Clone, PartialOrd, Ord)]
+pub struct EnumId(ModuleId);
+
+impl EnumId {
+    //dummy id for error reporting
+    // This can be anything, as the program will ultimately fail
+    // after resolution
+    pub fn dummy_id() -> EnumId {
+        EnumId(ModuleId { krate: CrateId::dummy_id(), local_id: LocalModuleId::dummy_id() })
+    }
+
+    pub fn module_id(self) -> ModuleId {
+        self.0
+    }
+
+    pub fn krate(self) -> CrateId {
+        self.0.krate
+    }
+
+    pub fn local_module_id(self) -> LocalModuleId {
+        self.0.local_id
+    }
+}
+
 #[derive(Debug, Eq, PartialEq, Hash, Copy, Clone, PartialOrd, Ord)]
 pub struct TypeAliasId(pub usize);
 
@@ -401,10 +445,14 @@ impl Default for NodeInterner {
             id_to_type: HashMap::new(),
             structs: HashMap::new(),
             struct_attributes: HashMap::new(),
+            enums: HashMap::new(),
             type_aliases: Vec::new(),
             traits: HashMap::new(),
             trait_implementations: Vec::new(),
             trait_implementation_map: HashMap::new(),
+            associated_constants: HashMap::new(),
+            operator_traits: HashMap::new(),
+            overloaded_operators: HashMap::new(),
             instantiation_bindings: HashMap::new(),
             field_indices: HashMap::new(),
             next_type_variable_id: std::cell::Cell::new(0),
@@ -498,6 +546,28 @@ impl NodeInterner {
         struct_id
     }
 
+    pub fn new_enum(
+        &mut self,
+        typ: &UnresolvedEnum,
+        krate: CrateId,
+        local_id: LocalModuleId,
+    ) -> EnumId {
+        let enum_id = EnumId(ModuleId { krate, local_id });
+        let name = typ.enum_def.name.clone();
+
+        // Variants will be filled in later
+        let generics = vecmap(&typ.enum_def.generics, |_| {
+            // Temporary type variable ids before the enum is resolved to its actual ids,
+            // see `new_struct`.
+            let id = TypeVariableId(0);
+            (id, Shared::new(TypeBinding::Unbound(id)))
+        });
+
+        let new_enum = EnumType::new(enum_id, name, typ.enum_def.span, generics);
+        self.enums.insert(enum_id, Shared::new(new_enum));
+        enum_id
+    }
+
     pub fn push_type_alias(&mut self, typ: &UnresolvedTypeAlias) -> TypeAliasId {
         let type_id = TypeAliasId(self.type_aliases.len());
 
@@ -520,6 +590,11 @@ impl NodeInterner {
         f(&mut value);
     }
 
+    pub fn update_enum(&mut self, type_id: EnumId, f: impl FnOnce(&mut EnumType)) {
+        let mut value = self.enums.get_mut(&type_id).unwrap().borrow_mut();
+        f(&mut value);
+    }
+
     pub fn update_trait(&mut self, trait_id: TraitId, f: impl FnOnce(&mut Trait)) {
         let value = self.traits.get_mut(&trait_id).unwrap();
         f(value);
@@ -822,6 +897,10 @@ impl NodeInterner {
         self.structs[&id].clone()
     }
 
+    pub fn get_enum(&self, id: EnumId) -> Shared<EnumType> {
+        self.enums[&id].clone()
+    }
+
     pub fn get_trait(&self, id: TraitId) -> Trait {
         self.traits[&id].clone()
     }
@@ -1116,6 +1195,65 @@ impl NodeInterner {
         self.lookup_primitive_method(&typ, method_name)
     }
 
+    /// Registers the given stdlib trait as the one overloading the binary operators it is named
+    /// after, if any. Each of these traits declares a single method.
+    pub(crate) fn try_add_operator_trait(&mut self, trait_id: TraitId) {
+        use BinaryOpKind::*;
+
+        let operators: &[BinaryOpKind] = match self.traits[&trait_id].name.0.contents.as_str() {
+            "Add" => &[Add],
+            "Sub" => &[Subtract],
+            "Mul" => &[Multiply],
+            "Div" => &[Divide],
+            "Rem" => &[Modulo],
+            "BitAnd" => &[And],
+            "BitOr" => &[Or],
+            "BitXor" => &[Xor],
+            "Shl" => &[ShiftLeft],
+            "Shr" => &[ShiftRight],
+            "Eq" => &[Equal, NotEqual],
+            "Ord" => &[Less, LessEqual, Greater, GreaterEqual],
+            _ => return,
+        };
+        for operator in operators {
+            self.operator_traits.insert(*operator, trait_id);
+        }
+    }
+
+    /// Returns the method of the stdlib trait overloading the given operator, if it was defined.
+    pub fn get_operator_trait_method(&self, operator: BinaryOpKind) -> Option<TraitMethodId> {
+        let trait_id = *self.operator_traits.get(&operator)?;
+        Some(TraitMethodId { trait_id, method_index: 0 })
+    }
+
+    /// Records that the given infix expression calls `method` rather than applying its operator.
+    pub fn push_overloaded_operator(&mut self, expr_id: ExprId, method: TraitMethodId) {
+        self.overloaded_operators.insert(expr_id, method);
+    }
+
+    /// Returns the trait method called in place of the operator of the given infix expression,
+    /// if its operands don't natively support it.
+    pub fn get_overloaded_operator(&self, expr_id: ExprId) -> Option<TraitMethodId> {
+        self.overloaded_operators.get(&expr_id).copied()
+    }
+
+    /// Records that the given method implements an associated constant of a trait.
+    pub fn push_associated_constant(&mut self, func_id: FuncId, value: Option<u64>) {
+        self.associated_constants.insert(func_id, value);
+    }
+
+    /// Returns true if the given method implements an associated constant of a trait,
+    /// in which case referring to the method calls it.
+    pub fn is_associated_constant(&self, func_id: FuncId) -> bool {
+        self.associated_constants.contains_key(&func_id)
+    }
+
+    /// Returns the value of the associated constant implemented by the given method,
+    /// if it is an integer known at compile time.
+    pub fn associated_constant_value(&self, func_id: FuncId) -> Option<u64> {
+        self.associated_constants.get(&func_id).copied().flatten()
+    }
+
     /// Returns what the next trait impl id is expected to be.
     /// Note that this does not actually reserve the slot so care should
     /// be taken that the next trait impl added matches this ID.
@@ -1213,6 +1351,7 @@ fn get_type_method_key(typ: &Type) -> Option<TypeMethodKey> {
         | Type::Error
         | Type::NotConstant
         | Type::Struct(_, _)
+        | Type::Enum(_, _)
         | Type::TraitAsType(_) => None,
     }
 }
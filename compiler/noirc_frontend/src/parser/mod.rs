@@ -12,7 +12,7 @@ mod labels;
 mod parser;
 
 use crate::token::{Keyword, Token};
-use crate::{ast::ImportStatement, Expression, NoirStruct};
+use crate::{ast::ImportStatement, Expression, NoirEnum, NoirStruct};
 use crate::{
     Ident, LetStatement, NoirFunction, NoirTrait, NoirTraitImpl, NoirTypeAlias, Recoverable,
     StatementKind, TypeImpl, UseTree,
@@ -31,6 +31,7 @@ pub(crate) enum TopLevelStatement {
     Module(Ident),
     Import(UseTree),
     Struct(NoirStruct),
+    Enum(NoirEnum),
     Trait(NoirTrait),
     TraitImpl(NoirTraitImpl),
     Impl(TypeImpl),
@@ -213,6 +214,7 @@ pub struct SortedModule {
     pub imports: Vec<ImportStatement>,
     pub functions: Vec<NoirFunction>,
     pub types: Vec<NoirStruct>,
+    pub enums: Vec<NoirEnum>,
     pub traits: Vec<NoirTrait>,
     pub trait_impls: Vec<NoirTraitImpl>,
     pub impls: Vec<TypeImpl>,
@@ -244,6 +246,10 @@ impl std::fmt::Display for SortedModule {
             write!(f, "{type_}")?;
         }
 
+        for enum_ in &self.enums {
+            write!(f, "{enum_}")?;
+        }
+
         for function in &self.functions {
             write!(f, "{function}")?;
         }
@@ -279,6 +285,7 @@ impl ParsedModule {
                 ItemKind::Import(import) => module.push_import(import),
                 ItemKind::Function(func) => module.push_function(func),
                 ItemKind::Struct(typ) => module.push_type(typ),
+                ItemKind::Enum(typ) => module.push_enum(typ),
                 ItemKind::Trait(noir_trait) => module.push_trait(noir_trait),
                 ItemKind::TraitImpl(trait_impl) => module.push_trait_impl(trait_impl),
                 ItemKind::Impl(r#impl) => module.push_impl(r#impl),
@@ -304,6 +311,7 @@ pub enum ItemKind {
     Import(UseTree),
     Function(NoirFunction),
     Struct(NoirStruct),
+    Enum(NoirEnum),
     Trait(NoirTrait),
     TraitImpl(NoirTraitImpl),
     Impl(TypeImpl),
@@ -359,6 +367,10 @@ impl SortedModule {
         self.types.push(typ);
     }
 
+    fn push_enum(&mut self, typ: NoirEnum) {
+        self.enums.push(typ);
+    }
+
     fn push_trait(&mut self, noir_trait: NoirTrait) {
         self.traits.push(noir_trait);
     }
@@ -478,6 +490,7 @@ impl std::fmt::Display for TopLevelStatement {
             TopLevelStatement::Trait(t) => t.fmt(f),
             TopLevelStatement::TraitImpl(i) => i.fmt(f),
             TopLevelStatement::Struct(s) => s.fmt(f),
+            TopLevelStatement::Enum(e) => e.fmt(f),
             TopLevelStatement::Impl(i) => i.fmt(f),
             TopLevelStatement::TypeAlias(t) => t.fmt(f),
             TopLevelStatement::SubModule(s) => s.fmt(f),
@@ -39,10 +39,11 @@ use crate::token::{Attribute, Attributes, Keyword, SecondaryAttribute, Token, To
 use crate::{
     BinaryOp, BinaryOpKind, BlockExpression, ConstrainKind, ConstrainStatement, Distinctness,
     ForLoopStatement, ForRange, FunctionDefinition, FunctionReturnType, FunctionVisibility, Ident,
-    IfExpression, InfixExpression, LValue, Lambda, Literal, NoirFunction, NoirStruct, NoirTrait,
-    NoirTraitImpl, NoirTypeAlias, Path, PathKind, Pattern, Recoverable, Statement, TraitBound,
-    TraitImplItem, TraitItem, TypeImpl, UnaryOp, UnresolvedTraitConstraint,
-    UnresolvedTypeExpression, UseTree, UseTreeKind, Visibility,
+    IfExpression, InfixExpression, LValue, Lambda, Literal, LoopStatement, MatchExpression,
+    NoirEnum, NoirFunction, NoirStruct, NoirTrait, NoirTraitImpl, NoirTypeAlias, Path, PathKind,
+    Pattern, Recoverable, Statement, TraitBound, TraitImplItem, TraitItem, TypeImpl, UnaryOp,
+    UnresolvedTraitConstraint, UnresolvedTypeExpression, UseTree, UseTreeKind, Visibility,
+    WhileStatement,
 };
 
 use chumsky::prelude::*;
@@ -84,6 +85,7 @@ fn module() -> impl NoirParser<ParsedModule> {
                     TopLevelStatement::Module(m) => push_item(ItemKind::ModuleDecl(m)),
                     TopLevelStatement::Import(i) => push_item(ItemKind::Import(i)),
                     TopLevelStatement::Struct(s) => push_item(ItemKind::Struct(s)),
+                    TopLevelStatement::Enum(e) => push_item(ItemKind::Enum(e)),
                     TopLevelStatement::Trait(t) => push_item(ItemKind::Trait(t)),
                     TopLevelStatement::TraitImpl(t) => push_item(ItemKind::TraitImpl(t)),
                     TopLevelStatement::Impl(i) => push_item(ItemKind::Impl(i)),
@@ -99,6 +101,7 @@ fn module() -> impl NoirParser<ParsedModule> {
 
 /// top_level_statement: function_definition
 ///                    | struct_definition
+///                    | enum_definition
 ///                    | trait_definition
 ///                    | implementation
 ///                    | submodule
@@ -111,6 +114,7 @@ fn top_level_statement(
     choice((
         function_definition(false).map(TopLevelStatement::Function),
         struct_definition(),
+        enum_definition(),
         trait_definition(),
         trait_implementation(),
         implementation(),
@@ -168,17 +172,22 @@ fn function_definition(allow_self: bool) -> impl NoirParser<NoirFunction> {
         .then(function_modifiers())
         .then_ignore(keyword(Keyword::Fn))
         .then(ident())
-        .then(generics())
+        .then(function_generics())
         .then(parenthesized(function_parameters(allow_self)))
         .then(function_return_type())
         .then(where_clause())
         .then(spanned(block(fresh_statement())))
         .validate(|(((args, ret), where_clause), (body, body_span)), span, emit| {
-            let ((((attributes, modifiers), name), generics), parameters) = args;
+            let ((((attributes, modifiers), name), (generics, mut trait_bounds)), parameters) =
+                args;
 
             // Validate collected attributes, filtering them into function and secondary variants
             let attrs = validate_attributes(attributes, span, emit);
             validate_where_clause(&generics, &where_clause, span, emit);
+
+            // Bounds declared alongside the generics are equivalent to a where clause
+            trait_bounds.extend(where_clause);
+            let where_clause = trait_bounds;
             FunctionDefinition {
                 span: body_span,
                 name,
@@ -249,6 +258,36 @@ fn generics() -> impl NoirParser<Vec<Ident>> {
         .map(|opt| opt.unwrap_or_default())
 }
 
+/// Parses the generics of a function, each of which may be followed by trait bounds,
+/// e.g. `<T: Eq + Hash, U>`. The bounds are returned as the equivalent where clause.
+fn function_generics() -> impl NoirParser<(Vec<Ident>, Vec<UnresolvedTraitConstraint>)> {
+    let bounds = just(Token::Colon).ignore_then(trait_bounds()).validate(|bounds, span, emit| {
+        emit(ParserError::with_reason(ParserErrorReason::ExperimentalFeature("Traits"), span));
+        bounds
+    });
+
+    ident()
+        .then(bounds.or_not())
+        .separated_by(just(Token::Comma))
+        .allow_trailing()
+        .at_least(1)
+        .delimited_by(just(Token::Less), just(Token::Greater))
+        .or_not()
+        .map(|generics| {
+            let mut idents = Vec::new();
+            let mut constraints = Vec::new();
+            for (ident, bounds) in generics.unwrap_or_default() {
+                let path = Path::from_ident(ident.clone());
+                let typ = UnresolvedTypeData::Named(path, Vec::new()).with_span(ident.span());
+                for trait_bound in bounds.unwrap_or_default() {
+                    constraints.push(UnresolvedTraitConstraint { typ: typ.clone(), trait_bound });
+                }
+                idents.push(ident);
+            }
+            (idents, constraints)
+        })
+}
+
 fn struct_definition() -> impl NoirParser<TopLevelStatement> {
     use self::Keyword::Struct;
     use Token::*;
@@ -275,6 +314,34 @@ fn struct_definition() -> impl NoirParser<TopLevelStatement> {
         })
 }
 
+fn enum_definition() -> impl NoirParser<TopLevelStatement> {
+    use self::Keyword::Enum;
+    use Token::*;
+
+    let variant_fields = parse_type()
+        .separated_by(just(Comma))
+        .allow_trailing()
+        .delimited_by(just(LeftParen), just(RightParen));
+
+    let variants = ident()
+        .then(variant_fields.or_not().map(Option::unwrap_or_default))
+        .separated_by(just(Comma))
+        .allow_trailing()
+        .delimited_by(just(LeftBrace), just(RightBrace))
+        .recover_with(nested_delimiters(
+            LeftBrace,
+            RightBrace,
+            [(LeftParen, RightParen), (LeftBracket, RightBracket)],
+            |_| vec![],
+        ));
+
+    keyword(Enum).ignore_then(ident()).then(generics()).then(variants).map_with_span(
+        |((name, generics), variants), span| {
+            TopLevelStatement::Enum(NoirEnum { name, generics, variants, span })
+        },
+    )
+}
+
 fn type_alias_definition() -> impl NoirParser<TopLevelStatement> {
     use self::Keyword::Type;
 
@@ -620,7 +687,16 @@ fn trait_implementation_body() -> impl NoirParser<Vec<TraitImplItem>> {
         .then_ignore(just(Token::Semicolon))
         .map(|(name, alias)| TraitImplItem::Type { name, alias });
 
-    function.or(alias).repeated()
+    let constant = keyword(Keyword::Let)
+        .ignore_then(ident())
+        .then_ignore(just(Token::Colon))
+        .then(parse_type())
+        .then_ignore(just(Token::Assign))
+        .then(expression())
+        .then_ignore(just(Token::Semicolon))
+        .map(|((name, typ), value)| TraitImplItem::Constant(name, typ, value));
+
+    function.or(alias).or(constant).repeated()
 }
 
 fn where_clause() -> impl NoirParser<Vec<UnresolvedTraitConstraint>> {
@@ -803,7 +879,11 @@ where
             assertion_eq(expr_parser.clone()),
             declaration(expr_parser.clone()),
             assignment(expr_parser.clone()),
-            for_loop(expr_no_constructors, statement),
+            for_loop(expr_no_constructors.clone(), statement.clone()),
+            while_loop(expr_no_constructors, statement.clone()),
+            loop_statement(statement),
+            break_statement(),
+            continue_statement(),
             return_statement(expr_parser.clone()),
             expr_parser.map(StatementKind::Expression),
         ))
@@ -906,7 +986,17 @@ where
 }
 
 fn pattern() -> impl NoirParser<Pattern> {
-    recursive(|pattern| {
+    pattern_impl(false)
+}
+
+/// Parses a pattern of a match rule. Unlike the patterns of `let` statements and parameters,
+/// these may be refutable: literals and enum variants only match some values of their type.
+fn match_pattern() -> impl NoirParser<Pattern> {
+    pattern_impl(true)
+}
+
+fn pattern_impl(allow_refutable: bool) -> impl NoirParser<Pattern> {
+    recursive(move |pattern| {
         let ident_pattern = ident().map(Pattern::Identifier).map_err(|mut error| {
             if matches!(error.found(), Token::IntType(..)) {
                 error = ParserError::with_reason(
@@ -935,11 +1025,54 @@ fn pattern() -> impl NoirParser<Pattern> {
             .map_with_span(|(typename, fields), span| Pattern::Struct(typename, fields, span));
 
         let tuple_pattern = pattern
+            .clone()
             .separated_by(just(Token::Comma))
             .delimited_by(just(Token::LeftParen), just(Token::RightParen))
             .map_with_span(Pattern::Tuple);
 
-        choice((mut_pattern, tuple_pattern, struct_pattern, ident_pattern))
+        let literal_pattern = just(Token::Minus)
+            .or_not()
+            .then(literal().map_with_span(Expression::new))
+            .map_with_span(|(minus, literal), span| match minus {
+                Some(_) => Expression::new(ExpressionKind::prefix(UnaryOp::Minus, literal), span),
+                None => literal,
+            })
+            .map(Pattern::Literal);
+
+        // Variants must be referred to by their full path, e.g. `Option::None`, to distinguish
+        // unit variants from new variable bindings.
+        let variant_path =
+            ident().then_ignore(just(Token::DoubleColon)).repeated().at_least(1).then(ident()).map(
+                |(mut segments, last_segment)| {
+                    segments.push(last_segment);
+                    Path { segments, kind: PathKind::Plain }
+                },
+            );
+
+        let variant_fields = pattern
+            .separated_by(just(Token::Comma))
+            .allow_trailing()
+            .delimited_by(just(Token::LeftParen), just(Token::RightParen));
+
+        let variant_pattern =
+            variant_path.then(variant_fields.or_not()).map_with_span(|(path, fields), span| {
+                Pattern::Variant(path, fields.unwrap_or_default(), span)
+            });
+
+        let (literal_pattern, variant_pattern) = if allow_refutable {
+            (literal_pattern.boxed(), variant_pattern.boxed())
+        } else {
+            (nothing().boxed(), nothing().boxed())
+        };
+
+        choice((
+            mut_pattern,
+            tuple_pattern,
+            struct_pattern,
+            literal_pattern,
+            variant_pattern,
+            ident_pattern,
+        ))
     })
     .labelled(ParsingRuleLabel::Pattern)
 }
@@ -1460,6 +1593,46 @@ where
     })
 }
 
+fn match_expr<'a, P, P2, S>(
+    expr_parser: P,
+    expr_no_constructors: P2,
+    statement: S,
+) -> impl NoirParser<ExpressionKind> + 'a
+where
+    P: ExprParser + 'a,
+    P2: ExprParser + 'a,
+    S: NoirParser<StatementKind> + 'a,
+{
+    // A block branch always ends the rule so that `{ .. } (a, b) => ..` isn't parsed as a call.
+    let branch = block_expr(statement).or(expr_parser);
+    let rule = match_pattern().then_ignore(just(Token::FatArrow)).then(branch);
+
+    // As in Rust, the comma separating two rules may be omitted if the first rule's branch is a block.
+    let rules = rule
+        .then(just(Token::Comma).or_not())
+        .repeated()
+        .validate(|rules, _span, emit| {
+            let last_rule = rules.len().saturating_sub(1);
+            vecmap(rules.into_iter().enumerate(), |(index, ((pattern, branch), comma))| {
+                let is_block = matches!(branch.kind, ExpressionKind::Block(_));
+                if comma.is_none() && !is_block && index != last_rule {
+                    emit(ParserError::with_reason(
+                        ParserErrorReason::MissingSeparatingComma,
+                        branch.span,
+                    ));
+                }
+                (pattern, branch)
+            })
+        })
+        .delimited_by(just(Token::LeftBrace), just(Token::RightBrace));
+
+    keyword(Keyword::Match).ignore_then(expr_no_constructors).then(rules).map(
+        |(expression, rules)| {
+            ExpressionKind::Match(Box::new(MatchExpression { expression, rules }))
+        },
+    )
+}
+
 fn lambda<'a>(
     expr_parser: impl NoirParser<Expression> + 'a,
 ) -> impl NoirParser<ExpressionKind> + 'a {
@@ -1487,6 +1660,39 @@ where
         })
 }
 
+fn while_loop<'a, P, S>(
+    expr_no_constructors: P,
+    statement: S,
+) -> impl NoirParser<StatementKind> + 'a
+where
+    P: ExprParser + 'a,
+    S: NoirParser<StatementKind> + 'a,
+{
+    keyword(Keyword::While)
+        .ignore_then(expr_no_constructors)
+        .then(block_expr(statement))
+        .map_with_span(|(condition, block), span| {
+            StatementKind::While(WhileStatement { condition, block, span })
+        })
+}
+
+fn loop_statement<'a, S>(statement: S) -> impl NoirParser<StatementKind> + 'a
+where
+    S: NoirParser<StatementKind> + 'a,
+{
+    keyword(Keyword::Loop)
+        .ignore_then(block_expr(statement))
+        .map_with_span(|block, span| StatementKind::Loop(LoopStatement { block, span }))
+}
+
+fn break_statement() -> impl NoirParser<StatementKind> {
+    keyword(Keyword::Break).to(StatementKind::Break)
+}
+
+fn continue_statement() -> impl NoirParser<StatementKind> {
+    keyword(Keyword::Continue).to(StatementKind::Continue)
+}
+
 /// The 'range' of a for loop. Either an actual range `start .. end` or an array expression.
 fn for_range<P>(expr_no_constructors: P) -> impl NoirParser<ForRange>
 where
@@ -1586,7 +1792,8 @@ where
     S: NoirParser<StatementKind> + 'a,
 {
     choice((
-        if_expr(expr_no_constructors, statement.clone()),
+        if_expr(expr_no_constructors.clone(), statement.clone()),
+        match_expr(expr_parser.clone(), expr_no_constructors, statement.clone()),
         array_expr(expr_parser.clone()),
         if allow_constructors {
             constructor(expr_parser.clone()).boxed()
@@ -2089,6 +2296,26 @@ mod test {
         );
     }
 
+    #[test]
+    fn parse_while_and_loop() {
+        parse_all(
+            while_loop(expression_no_constructors(expression()), fresh_statement()),
+            vec!["while x < y {}", "while i != 0 { i -= 1; if i == 5 { break; } }"],
+        );
+        parse_all_failing(
+            while_loop(expression_no_constructors(expression()), fresh_statement()),
+            vec!["while {}", "while x < y", "while (x < y);"],
+        );
+
+        parse_all(loop_statement(fresh_statement()), vec!["loop {}", "loop { continue; }"]);
+        parse_all_failing(loop_statement(fresh_statement()), vec!["loop", "loop x"]);
+
+        parse_all(
+            block(fresh_statement()),
+            vec!["{ break; }", "{ continue; }", "{ while true { break } }"],
+        );
+    }
+
     #[test]
     fn parse_function() {
         parse_all(
@@ -2113,6 +2340,8 @@ mod test {
                 "fn func_name<T>(f: Field, y : T) where T: SomeTrait + SomeTrait2<C> {}",
                 "fn func_name<T>(f: Field, y : T) where T: SomeTrait + SomeTrait2<C> + TraitY {}",
                 "fn func_name<T>(f: Field, y : T, z : U) where SomeStruct<T>: SomeTrait<U> {}",
+                "fn func_name<T: SomeTrait>(f: Field, y : T) {}",
+                "fn func_name<T: SomeTrait + SomeTrait2<C>, U>(y : T, z : U) where U: TraitY {}",
                 // 'where u32: SomeTrait' is allowed in Rust.
                 // It will result in compiler error in case SomeTrait isn't implemented for u32.
                 "fn func_name<T>(f: Field, y : T) where u32: SomeTrait {}",
@@ -2137,6 +2366,7 @@ mod test {
                 // A leading plus is not allowed.
                 "fn func_name<T>(f: Field, y : T) where T: + SomeTrait {}",
                 "fn func_name<T>(f: Field, y : T) where T: TraitX + <Y> {}",
+                "fn func_name<T:>(f: Field, y : T) {}",
             ],
         );
     }
@@ -2172,6 +2402,27 @@ mod test {
         );
     }
 
+    #[test]
+    fn parse_trait_impl() {
+        parse_all(
+            trait_implementation(),
+            vec![
+                "impl Empty for Foo {}",
+                "impl TraitWithAssociatedType for Foo { type Element = Field; }",
+                "impl TraitWithAssociatedConstant for Foo { let Size: Field = 3; }",
+                "impl<T> GenericTrait<T> for Foo<T> where T: SomeTrait { let Size: Field = 1 + 2; fn elem(self) -> T { self.x } }",
+            ],
+        );
+
+        parse_all_failing(
+            trait_implementation(),
+            vec![
+                "impl TraitWithAssociatedConstant for Foo { let Size: Field; }",
+                "impl TraitWithAssociatedConstant for Foo { let Size = 3; }",
+            ],
+        );
+    }
+
     #[test]
     fn parse_parenthesized_expression() {
         parse_all(
@@ -2189,6 +2440,26 @@ mod test {
         parse_all(tuple(expression()), vec!["()", "(x,)", "(a,b+2)", "(a,(b,c,),d,)"]);
     }
 
+    #[test]
+    fn parse_match_expr() {
+        parse_all(
+            match_expr(expression(), expression_no_constructors(expression()), fresh_statement()),
+            vec![
+                "match x {}",
+                "match x { 1 => a, _ => b }",
+                "match x { -1 => a, 0 => b, y => y, }",
+                "match (a, b) { (true, 1) => { x } (false, _) => y }",
+                "match foo { Foo { a, b: 2 } => a, Foo { a: _, b } => b }",
+                "match shape { Shape::Circle(r) => r, Shape::Square(_) => 0, shapes::Shape::Empty => 1 }",
+            ],
+        );
+
+        parse_all_failing(
+            match_expr(expression(), expression_no_constructors(expression()), fresh_statement()),
+            vec!["match x { 1 => a 2 => b }", "match x { 1 -> a }", "match x"],
+        );
+    }
+
     #[test]
     fn parse_if_expr() {
         parse_all(
@@ -2351,6 +2622,21 @@ mod test {
         parse_all_failing(struct_definition(), failing);
     }
 
+    #[test]
+    fn parse_enums() {
+        let cases = vec![
+            "enum Foo {}",
+            "enum Foo { A, B }",
+            "enum Shape { Circle(Field), Rectangle(Field, Field), Empty, }",
+            "enum Option<T> { None, Some(T) }",
+        ];
+        parse_all(enum_definition(), cases);
+
+        let failing =
+            vec!["enum {}", "enum Foo;", "enum Foo { A: Field }", "enum Foo { A(pub Field) }"];
+        parse_all_failing(enum_definition(), failing);
+    }
+
     #[test]
     fn parse_type_aliases() {
         let cases = vec!["type foo = u8", "type bar = String", "type baz<T> = Vec<T>"];
@@ -6,13 +6,14 @@ mod test {
 
     use core::panic;
     use std::collections::BTreeMap;
+    use std::rc::Rc;
 
     use fm::FileId;
 
     use noirc_errors::Location;
 
     use crate::hir::def_collector::dc_crate::CompilationError;
-    use crate::hir::def_collector::errors::{DefCollectorErrorKind, DuplicateType};
+    use crate::hir::def_collector::errors::{DefCollectorErrorKind, DuplicateType, MacroError};
     use crate::hir::def_map::ModuleData;
     use crate::hir::resolution::errors::ResolverError;
     use crate::hir::resolution::import::PathResolutionError;
@@ -20,12 +21,14 @@ mod test {
     use crate::hir::Context;
     use crate::node_interner::{NodeInterner, StmtId};
 
-    use crate::graph::CrateGraph;
+    use crate::graph::{CrateGraph, CrateId};
     use crate::hir::def_collector::dc_crate::DefCollector;
     use crate::hir_def::expr::HirExpression;
     use crate::hir_def::stmt::HirStatement;
+    use crate::macros_api::MacroProcessor;
     use crate::monomorphization::monomorphize;
-    use crate::parser::ParserErrorReason;
+    use crate::parser::{ParserErrorReason, SortedModule};
+    use crate::token::SecondaryAttribute;
     use crate::ParsedModule;
     use crate::{
         hir::def_map::{CrateDefMap, LocalModuleId},
@@ -712,6 +715,227 @@ mod test {
         }
     }
 
+    #[test]
+    fn check_generic_function_trait_bounds() {
+        let src = "
+        trait Hash {
+            fn hash(self) -> Field;
+        }
+
+        struct Foo {
+            x: Field,
+        }
+
+        impl Hash for Foo {
+            fn hash(self) -> Field {
+                self.x
+            }
+        }
+
+        fn hash_inline<T: Hash>(x: T) -> Field {
+            x.hash()
+        }
+
+        fn hash_where<T>(x: T) -> Field where T: Hash {
+            hash_inline(x)
+        }
+
+        fn main() {
+            let _ = hash_where(Foo { x: 1 });
+        }
+        ";
+        let errors = get_program_errors(src);
+        assert!(errors.is_empty(), "Expected no errors, got: {:?}", errors);
+    }
+
+    #[test]
+    fn check_generic_function_missing_trait_impl() {
+        let src = "
+        trait Hash {
+            fn hash(self) -> Field;
+        }
+
+        struct Foo {
+        }
+
+        fn hash<T: Hash>(x: T) -> Field {
+            x.hash()
+        }
+
+        fn unconstrained_generic<T>(x: T) -> Field {
+            hash(x)
+        }
+
+        fn main() {
+            let _ = hash(Foo {});
+        }
+        ";
+        let errors = get_program_errors(src);
+        assert!(!has_parser_error(&errors));
+        assert!(errors.len() == 2, "Expected 2 errors, got: {:?}", errors);
+
+        let mut constraints: Vec<_> = errors
+            .iter()
+            .map(|(err, _file_id)| match err {
+                CompilationError::TypeError(TypeCheckError::NoMatchingImplFound {
+                    constraints,
+                    ..
+                }) => constraints[0].0.to_string(),
+                _ => panic!("No other errors are expected! Found = {:?}", err),
+            })
+            .collect();
+        constraints.sort();
+        assert_eq!(constraints, vec!["Foo", "T"]);
+    }
+
+    #[test]
+    fn check_trait_associated_items() {
+        let src = "
+        trait Container {
+            let SIZE: u64;
+            let SCALE: Field = 2;
+
+            type Item;
+
+            fn first(self) -> Self::Item;
+        }
+
+        struct Pair {
+            values: [Field; 2],
+        }
+
+        impl Container for Pair {
+            let SIZE: u64 = 2;
+
+            type Item = Field;
+
+            fn first(self) -> Field {
+                let values: [Field; Self::SIZE] = self.values;
+                values[0]
+            }
+        }
+
+        fn first<C>(container: C) -> C::Item where C: Container {
+            container.first()
+        }
+
+        fn scaled_size<C>(_container: C) -> Field where C: Container {
+            C::SIZE as Field * C::SCALE
+        }
+
+        fn main() {
+            let pair = Pair { values: [1, 2] };
+            let _: Field = first(pair);
+            let _: [Field; Pair::SIZE] = pair.values;
+            let _ = scaled_size(pair);
+        }
+        ";
+        let errors = get_program_errors(src);
+        assert!(errors.is_empty(), "Expected no errors, got: {:?}", errors);
+    }
+
+    #[test]
+    fn check_trait_associated_type_mismatch() {
+        let src = "
+        trait Container {
+            type Item;
+
+            fn first(self) -> Self::Item;
+        }
+
+        struct Pair {
+            values: [Field; 2],
+        }
+
+        impl Container for Pair {
+            type Item = Field;
+
+            fn first(self) -> Field {
+                self.values[0]
+            }
+        }
+
+        fn first<C>(container: C) -> C::Item where C: Container {
+            container.first()
+        }
+
+        fn main() {
+            let _: bool = first(Pair { values: [1, 2] });
+        }
+        ";
+        let errors = get_program_errors(src);
+        assert!(errors.len() == 1, "Expected 1 error, got: {:?}", errors);
+        assert!(matches!(
+            errors[0].0,
+            CompilationError::TypeError(TypeCheckError::TypeMismatch { .. })
+        ));
+    }
+
+    #[test]
+    fn check_trait_missing_associated_items() {
+        let src = "
+        trait Container {
+            let SIZE: u64;
+
+            type Item;
+        }
+
+        struct Pair {
+        }
+
+        impl Container for Pair {
+            let LENGTH: u64 = 2;
+        }
+
+        fn main() {
+        }
+        ";
+        let errors = get_program_errors(src);
+        assert!(!has_parser_error(&errors));
+        assert!(errors.len() == 3, "Expected 3 errors, got: {:?}", errors);
+
+        for (err, _file_id) in errors {
+            match &err {
+                CompilationError::DefinitionError(
+                    DefCollectorErrorKind::TraitMissingConstant { constant_name, .. },
+                ) => assert_eq!(constant_name.0.contents, "SIZE"),
+                CompilationError::DefinitionError(DefCollectorErrorKind::TraitMissingType {
+                    type_name,
+                    ..
+                }) => assert_eq!(type_name.0.contents, "Item"),
+                CompilationError::DefinitionError(DefCollectorErrorKind::ConstantNotInTrait {
+                    impl_constant,
+                    ..
+                }) => assert_eq!(impl_constant.0.contents, "LENGTH"),
+                _ => panic!("No other errors are expected! Found = {:?}", err),
+            }
+        }
+    }
+
+    #[test]
+    fn check_unsupported_derive() {
+        let src = "
+        #[derive(Clone)]
+        struct Foo {
+            bar: Field,
+        }
+
+        fn main() {
+        }
+        ";
+        let errors = get_program_errors(src);
+        assert!(!has_parser_error(&errors));
+        assert!(errors.len() == 1, "Expected 1 error, got: {:?}", errors);
+
+        match &errors[0].0 {
+            CompilationError::DefinitionError(DefCollectorErrorKind::UnsupportedDerive {
+                trait_name,
+                ..
+            }) => assert_eq!(trait_name, "Clone"),
+            _ => panic!("No other errors are expected! Found = {:?}", errors[0].0),
+        }
+    }
+
     fn get_program_captures(src: &str) -> Vec<Vec<String>> {
         let (program, context, _errors) = get_program(src);
         let interner = context.def_interner;
@@ -743,6 +967,8 @@ mod test {
                 HirStatement::Constrain(constr_stmt) => constr_stmt.0,
                 HirStatement::Semi(semi_expr) => semi_expr,
                 HirStatement::For(for_loop) => for_loop.block,
+                HirStatement::While(while_loop) => while_loop.block,
+                HirStatement::Break | HirStatement::Continue => continue,
                 HirStatement::Error => panic!("Invalid HirStatement!"),
             };
             let expr = interner.expression(&expr_id);
@@ -1091,6 +1317,260 @@ mod test {
         }
     }
 
+    #[test]
+    fn resolve_match_expression() {
+        let src = r#"
+            struct Foo { a: Field, b: bool }
+
+            fn main(x: Field, foo: Foo) -> pub Field {
+                let y = match (x, foo.b) {
+                    (0, true) => 1,
+                    (-1, _) => { 2 }
+                    (z, false) => z,
+                    _ => 3,
+                };
+                match foo {
+                    Foo { b: true, a } => a + y,
+                    Foo { a: _, b: false } => y,
+                }
+            }
+        "#;
+        assert!(get_program_errors(src).is_empty());
+    }
+
+    #[test]
+    fn non_exhaustive_match() {
+        let src = r#"
+            fn main(x: (bool, Field)) -> pub Field {
+                match x {
+                    (true, _) => 1,
+                    (false, 0) => 2,
+                }
+            }
+        "#;
+        let errors = get_program_errors(src);
+        assert_eq!(errors.len(), 1);
+
+        match &errors[0].0 {
+            CompilationError::TypeError(TypeCheckError::NonExhaustiveMatch { missing, .. }) => {
+                assert_eq!(missing, "(false, _)");
+            }
+            _ => panic!("Expected a non-exhaustive match error, got {:?}", errors[0].0),
+        }
+    }
+
+    #[test]
+    fn match_branches_must_have_the_same_type() {
+        let src = r#"
+            fn main(x: bool) -> pub Field {
+                match x {
+                    true => 1,
+                    false => false,
+                }
+            }
+        "#;
+        let errors = get_program_errors(src);
+        assert_eq!(errors.len(), 1);
+        assert!(matches!(errors[0].0, CompilationError::TypeError(_)));
+    }
+
+    #[test]
+    fn literal_patterns_are_not_allowed_in_let_statements() {
+        let src = r#"
+            fn main(x: Field) {
+                let 1 = x;
+            }
+        "#;
+        assert!(has_parser_error(&get_program_errors(src)));
+    }
+
+    #[test]
+    fn resolve_enums() {
+        let src = r#"
+            enum Shape<T> {
+                Circle(T),
+                Rectangle(T, T),
+                Empty,
+            }
+
+            fn area(shape: Shape<u32>) -> u32 {
+                match shape {
+                    Shape::Circle(r) => 3 * r * r,
+                    Shape::Rectangle(w, h) => w * h,
+                    Shape::Empty => 0,
+                }
+            }
+
+            fn main(x: u32, shape: Shape<u32>) -> pub u32 {
+                let circle = Shape::Circle(x);
+                let empty: Shape<u32> = Shape::Empty;
+                area(circle) + area(shape) + area(empty)
+            }
+        "#;
+        assert!(get_program_errors(src).is_empty());
+    }
+
+    #[test]
+    fn non_exhaustive_enum_match() {
+        let src = r#"
+            enum Shape {
+                Circle(Field),
+                Rectangle(Field, bool),
+            }
+
+            fn main(shape: Shape) -> pub Field {
+                match shape {
+                    Shape::Circle(r) => r,
+                    Shape::Rectangle(w, true) => w,
+                }
+            }
+        "#;
+        let errors = get_program_errors(src);
+        assert_eq!(errors.len(), 1);
+
+        match &errors[0].0 {
+            CompilationError::TypeError(TypeCheckError::NonExhaustiveMatch { missing, .. }) => {
+                assert_eq!(missing, "Shape::Rectangle(_, false)");
+            }
+            _ => panic!("Expected a non-exhaustive match error, got {:?}", errors[0].0),
+        }
+    }
+
+    #[test]
+    fn last_match_rule_asserts_its_conditions() {
+        // An enum with an out-of-range tag must not silently take the last rule
+        let src = r#"
+            enum Shape {
+                Circle(Field),
+                Empty,
+            }
+
+            fn main(shape: Shape) -> pub Field {
+                match shape {
+                    Shape::Circle(r) => r,
+                    Shape::Empty => 0,
+                }
+            }
+        "#;
+        let (_program, context, errors) = get_program(src);
+        assert!(errors.is_empty());
+        let main_func_id = context.def_interner.find_function("main").unwrap();
+        let program = monomorphize(main_func_id, &context.def_interner).to_string();
+
+        assert_eq!(program.matches("if ").count(), 1);
+        assert_eq!(program.matches("constrain ").count(), 1);
+        assert!(program.contains(".0 == 1"));
+    }
+
+    #[test]
+    fn enum_variants_must_exist_and_have_the_right_fields() {
+        let src = r#"
+            enum Shape {
+                Circle(Field),
+            }
+
+            fn main(x: Field) {
+                let _ = Shape::Square(x);
+                let _ = Shape::Circle(x, x);
+                let _ = Shape::Circle(true);
+            }
+        "#;
+        let errors = get_program_errors(src);
+        assert_eq!(errors.len(), 3);
+
+        assert!(matches!(
+            errors[0].0,
+            CompilationError::ResolverError(ResolverError::NoSuchVariant { .. })
+        ));
+        assert!(matches!(
+            errors[1].0,
+            CompilationError::TypeError(TypeCheckError::VariantFieldCountMismatch { .. })
+        ));
+        assert!(matches!(
+            errors[2].0,
+            CompilationError::TypeError(TypeCheckError::TypeMismatch { .. })
+        ));
+    }
+
+    #[test]
+    fn resolve_unconstrained_loops() {
+        let src = r#"
+            unconstrained fn main(x: u32) -> pub u32 {
+                let mut i = 0;
+                while i < x {
+                    i += 1;
+                    if i == 3 {
+                        continue;
+                    }
+                }
+                loop {
+                    if i == 0 {
+                        break;
+                    }
+                    i -= 1;
+                }
+                for j in 0..x {
+                    if j == 5 { break }
+                    i += j;
+                }
+                i
+            }
+        "#;
+        assert!(get_program_errors(src).is_empty());
+    }
+
+    #[test]
+    fn loops_are_only_allowed_in_unconstrained_fns() {
+        let src = r#"
+            fn main(x: u32) {
+                let mut i = 0;
+                while i < x {
+                    i += 1;
+                }
+                loop {}
+                for _ in 0..x {
+                    continue;
+                }
+                break;
+            }
+        "#;
+        let errors = get_program_errors(src);
+        assert_eq!(errors.len(), 4);
+
+        for (error, _) in &errors[..2] {
+            assert!(matches!(
+                error,
+                CompilationError::ResolverError(ResolverError::LoopInConstrainedFn { .. })
+            ));
+        }
+        assert!(matches!(
+            errors[2].0,
+            CompilationError::ResolverError(ResolverError::JumpInConstrainedFn { .. })
+        ));
+        assert!(matches!(
+            errors[3].0,
+            CompilationError::ResolverError(ResolverError::JumpOutsideLoop { .. })
+        ));
+    }
+
+    #[test]
+    fn lambdas_cannot_jump_out_of_loops() {
+        let src = r#"
+            unconstrained fn main() {
+                loop {
+                    let f = |_x: Field| { break; };
+                    f(1);
+                }
+            }
+        "#;
+        let errors = get_program_errors(src);
+        assert_eq!(errors.len(), 1);
+        assert!(matches!(
+            errors[0].0,
+            CompilationError::ResolverError(ResolverError::JumpOutsideLoop { .. })
+        ));
+    }
+
     fn check_rewrite(src: &str, expected: &str) {
         let (_program, context, _errors) = get_program(src);
         let main_func_id = context.def_interner.find_function("main").unwrap();
@@ -1128,4 +1608,71 @@ fn lambda$f1(mut env$l1: (Field)) -> Field {
 "#;
         check_rewrite(src, expected_rewrite);
     }
+
+    /// Adds a `generated` function to the crate and rejects functions marked `#[forbidden]`
+    struct TestMacro;
+
+    impl MacroProcessor for TestMacro {
+        fn process_untyped_ast(
+            &self,
+            mut ast: SortedModule,
+            crate_id: &CrateId,
+            context: &Context,
+        ) -> Result<SortedModule, (MacroError, FileId)> {
+            let forbidden = SecondaryAttribute::Custom("forbidden".to_string());
+            if let Some(func) =
+                ast.functions.iter().find(|func| func.def.attributes.secondary.contains(&forbidden))
+            {
+                let error = MacroError {
+                    primary_message: "forbidden function".to_string(),
+                    secondary_message: None,
+                    span: Some(func.name_ident().span()),
+                };
+                return Err((error, context.crate_graph[crate_id].root_file_id));
+            }
+
+            let (generated, _) = parse_program("fn generated() -> Field { 1 }");
+            ast.functions.extend(generated.into_sorted().functions);
+            Ok(ast)
+        }
+
+        fn process_typed_ast(&self, _crate_id: &CrateId, _context: &mut Context) {}
+    }
+
+    fn get_program_errors_with_macro(src: &str) -> Vec<(CompilationError, FileId)> {
+        let root = std::path::Path::new("/");
+        let src = src.to_string();
+        let fm = FileManager::new(root, Box::new(move |_| Ok(src.clone())));
+        let mut context = Context::new(fm, CrateGraph::default());
+        let root_file_id = context.file_manager.add_file(std::path::Path::new("main.nr")).unwrap();
+        let crate_id = context.crate_graph.add_crate_root(root_file_id);
+        context.add_macro_processor(Rc::new(TestMacro));
+        remove_experimental_feature_warnings(CrateDefMap::collect_defs(crate_id, &mut context))
+    }
+
+    #[test]
+    fn macro_processor_transforms_ast() {
+        let src = "
+        fn main() -> pub Field {
+            generated()
+        }
+        ";
+        assert!(get_program_errors(src).len() == 1);
+        assert!(get_program_errors_with_macro(src).is_empty());
+    }
+
+    #[test]
+    fn macro_processor_reports_errors() {
+        let src = "
+        #[forbidden]
+        fn main() {}
+        ";
+        let errors = get_program_errors_with_macro(src);
+        assert!(errors.len() == 1);
+        assert!(matches!(
+            &errors[0].0,
+            CompilationError::DefinitionError(DefCollectorErrorKind::MacroError(error))
+                if error.primary_message == "forbidden function"
+        ));
+    }
 }
@@ -6,7 +6,7 @@ use regex::{Captures, Regex};
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
-#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[serde(tag = "kind", rename_all = "lowercase")]
 pub enum PrintableType {
     Field,
@@ -29,11 +29,15 @@ pub enum PrintableType {
     String {
         length: u64,
     },
+    Enum {
+        name: String,
+        variants: Vec<(String, Vec<PrintableType>)>,
+    },
 }
 
 impl PrintableType {
     /// Returns the number of field elements required to represent the type once encoded.
-    fn field_count(&self) -> u32 {
+    pub fn field_count(&self) -> u32 {
         match self {
             Self::Field
             | Self::SignedInteger { .. }
@@ -44,6 +48,10 @@ impl PrintableType {
                 fields.iter().fold(0, |acc, (_, field_type)| acc + field_type.field_count())
             }
             Self::String { length } => *length as u32,
+            // Enums are encoded as a tag followed by the fields of every variant.
+            Self::Enum { variants, .. } => variants.iter().fold(1, |acc, (_, fields)| {
+                acc + fields.iter().map(PrintableType::field_count).sum::<u32>()
+            }),
         }
     }
 }
@@ -57,6 +65,8 @@ pub enum PrintableValue {
     String(String),
     Vec(Vec<PrintableValue>),
     Struct(BTreeMap<String, PrintableValue>),
+    /// The name of the active variant of an enum along with the values it holds.
+    Variant(String, Vec<PrintableValue>),
 }
 
 /// In order to display a `PrintableValue` we need a `PrintableType` to accurately
@@ -215,6 +225,22 @@ fn to_string(value: &PrintableValue, typ: &PrintableType) -> Option<String> {
             output.push_str(" }");
         }
 
+        (PrintableValue::Variant(variant, values), PrintableType::Enum { name, variants }) => {
+            output.push_str(&format!("{name}::{variant}"));
+
+            // Values whose tag is out of range are decoded without fields
+            let field_types = variants
+                .iter()
+                .find(|(name, _)| name == variant)
+                .map_or(&[][..], |(_, field_types)| field_types.as_slice());
+            if !field_types.is_empty() {
+                let fields = values.iter().zip(field_types).map(|(value, field_type)| {
+                    PrintableValueDisplay::Plain(value.clone(), field_type.clone()).to_string()
+                });
+                output.push_str(&format!("({})", fields.collect::<Vec<_>>().join(", ")));
+            }
+        }
+
         _ => return None
     };
 
@@ -277,7 +303,7 @@ fn format_field_string(field: FieldElement) -> String {
 }
 
 /// Assumes that `field_iterator` contains enough [FieldElement] in order to decode the [PrintableType]
-fn decode_value(
+pub fn decode_value(
     field_iterator: &mut impl Iterator<Item = FieldElement>,
     typ: &PrintableType,
 ) -> PrintableValue {
@@ -315,6 +341,25 @@ fn decode_value(
 
             PrintableValue::Struct(struct_map)
         }
+        PrintableType::Enum { variants, .. } => {
+            let tag = field_iterator.next().unwrap();
+
+            // The fields of every variant are encoded so we must decode all of them,
+            // only keeping those belonging to the active variant.
+            let mut active_variant = None;
+            for (index, (variant_name, field_types)) in variants.iter().enumerate() {
+                let fields = vecmap(field_types, |typ| decode_value(field_iterator, typ));
+                if FieldElement::from(index as u128) == tag {
+                    active_variant = Some((variant_name.clone(), fields));
+                }
+            }
+
+            // The tag of a value provided by the prover, or overwritten in the debugger, may not
+            // belong to any variant.
+            let (variant_name, fields) =
+                active_variant.unwrap_or_else(|| (format!("<invalid tag {tag}>"), Vec::new()));
+            PrintableValue::Variant(variant_name, fields)
+        }
     }
 }
 
@@ -164,15 +164,16 @@ pub fn compile(
     let is_opcode_supported = acvm::pwg::default_is_opcode_supported(np_language);
 
     if contracts.unwrap_or_default() {
-        let compiled_contract = compile_contract(&mut context, crate_id, &compile_options)
-            .map_err(|errs| {
-                CompileError::with_file_diagnostics(
-                    "Failed to compile contract",
-                    errs,
-                    &context.file_manager,
-                )
-            })?
-            .0;
+        let compiled_contract =
+            compile_contract(&mut context, crate_id, &compile_options, None, true)
+                .map_err(|errs| {
+                    CompileError::with_file_diagnostics(
+                        "Failed to compile contract",
+                        errs,
+                        &context.file_manager,
+                    )
+                })?
+                .0;
 
         let optimized_contract =
             nargo::ops::optimize_contract(compiled_contract, np_language, &is_opcode_supported)
@@ -261,6 +262,7 @@ fn preprocess_contract(contract: CompiledContract) -> CompileResult {
         .into_iter()
         .map(|func| PreprocessedContractFunction {
             name: func.name,
+            hash: func.hash,
             function_type: func.function_type,
             is_internal: func.is_internal,
             abi: func.abi,
@@ -0,0 +1,331 @@
+//! An in-process Groth16 proving backend over BN254.
+//!
+//! This backend exists so that circuits can be proven and verified without downloading an external binary,
+//! e.g. in CI and tests. Its trusted setup is derived from a fixed, publicly known seed, meaning that anybody can
+//! forge proofs for it. It must not be used to secure anything of value.
+
+use std::collections::HashMap;
+
+use acvm::acir::circuit::{Circuit, Opcode, OpcodeLocation};
+use acvm::acir::native_types::{Expression, Witness, WitnessMap};
+use acvm::{FieldElement, Language};
+use ark_bn254::{Bn254, Fr};
+use ark_groth16::{prepare_verifying_key, Groth16, Proof, ProvingKey};
+use ark_relations::r1cs::{
+    ConstraintSynthesizer, ConstraintSystem, ConstraintSystemRef, LinearCombination,
+    SynthesisError, SynthesisMode, Variable,
+};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use ark_std::rand::{rngs::StdRng, SeedableRng};
+
+use crate::{BackendError, BackendOpcodeSupport, ProofSystem, BUILTIN_BACKEND_NAME};
+
+/// The seed from which the proving and verification keys of every circuit are derived.
+const SETUP_SEED: u64 = 0;
+
+/// A reference proving backend which implements Groth16 in-process.
+///
+/// Circuits are proven over their rank-1 constraint representation. Only arithmetic opcodes are constrained,
+/// Brillig and directive opcodes are ignored as they only compute witness values.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct Groth16Backend;
+
+impl ProofSystem for Groth16Backend {
+    fn get_exact_circuit_size(&self, circuit: &Circuit) -> Result<u32, BackendError> {
+        check_opcode_support(circuit)?;
+
+        let cs = ConstraintSystem::new_ref();
+        cs.set_mode(SynthesisMode::Setup);
+        AcirCircuit { circuit, witness: None }
+            .generate_constraints(cs.clone())
+            .map_err(|err| BackendError::SynthesisError(err.to_string()))?;
+
+        Ok(cs.num_constraints() as u32)
+    }
+
+    fn get_backend_info(&self) -> Result<(Language, BackendOpcodeSupport), BackendError> {
+        Ok((Language::R1CS, BackendOpcodeSupport::r1cs()))
+    }
+
+    fn prove(
+        &self,
+        circuit: &Circuit,
+        witness_values: WitnessMap,
+        _is_recursive: bool,
+    ) -> Result<Vec<u8>, BackendError> {
+        let proving_key = setup(circuit)?;
+
+        // Groth16 happily produces an invalid proof for an unsatisfied constraint system so we check this upfront.
+        let cs = ConstraintSystem::new_ref();
+        AcirCircuit { circuit, witness: Some(&witness_values) }
+            .generate_constraints(cs.clone())
+            .map_err(|err| BackendError::SynthesisError(err.to_string()))?;
+        if !cs.is_satisfied().map_err(|err| BackendError::SynthesisError(err.to_string()))? {
+            return Err(BackendError::UnsatisfiedConstraints);
+        }
+
+        let proof = Groth16::<Bn254>::create_random_proof_with_reduction(
+            AcirCircuit { circuit, witness: Some(&witness_values) },
+            &proving_key,
+            &mut ark_std::rand::thread_rng(),
+        )
+        .map_err(|err| BackendError::SynthesisError(err.to_string()))?;
+
+        let mut proof_bytes = Vec::new();
+        proof
+            .serialize_compressed(&mut proof_bytes)
+            .expect("serializing into a vector should not fail");
+        Ok(proof_bytes)
+    }
+
+    fn verify(
+        &self,
+        proof: &[u8],
+        public_inputs: WitnessMap,
+        circuit: &Circuit,
+        _is_recursive: bool,
+    ) -> Result<bool, BackendError> {
+        check_opcode_support(circuit)?;
+
+        let Ok(proof) = Proof::<Bn254>::deserialize_compressed(proof) else {
+            return Ok(false);
+        };
+        let verifying_key = prepare_verifying_key(&setup(circuit)?.vk);
+
+        let public_inputs: Vec<Fr> = circuit
+            .public_inputs()
+            .0
+            .iter()
+            .map(|witness| {
+                public_inputs.get(witness).copied().unwrap_or(FieldElement::zero()).into_repr()
+            })
+            .collect();
+
+        Groth16::<Bn254>::verify_proof(&verifying_key, &proof, &public_inputs)
+            .map_err(|err| BackendError::SynthesisError(err.to_string()))
+    }
+
+    fn write_vk(&self, circuit: &Circuit) -> Result<Vec<u8>, BackendError> {
+        let mut vk_bytes = Vec::new();
+        setup(circuit)?
+            .vk
+            .serialize_compressed(&mut vk_bytes)
+            .expect("serializing into a vector should not fail");
+        Ok(vk_bytes)
+    }
+}
+
+/// Runs the (deterministic) trusted setup for `circuit`.
+fn setup(circuit: &Circuit) -> Result<ProvingKey<Bn254>, BackendError> {
+    check_opcode_support(circuit)?;
+
+    let mut rng = StdRng::seed_from_u64(SETUP_SEED);
+    Groth16::<Bn254>::generate_random_parameters_with_reduction(
+        AcirCircuit { circuit, witness: None },
+        &mut rng,
+    )
+    .map_err(|err| BackendError::SynthesisError(err.to_string()))
+}
+
+fn check_opcode_support(circuit: &Circuit) -> Result<(), BackendError> {
+    let unsupported_opcode = circuit.opcodes.iter().position(|opcode| {
+        matches!(
+            opcode,
+            Opcode::BlackBoxFuncCall(_) | Opcode::MemoryInit { .. } | Opcode::MemoryOp { .. }
+        )
+    });
+    match unsupported_opcode {
+        Some(index) => Err(BackendError::UnsupportedOpcode(
+            OpcodeLocation::Acir(index),
+            BUILTIN_BACKEND_NAME.to_string(),
+        )),
+        None => Ok(()),
+    }
+}
+
+/// Adapts an ACIR circuit (and optionally its witness) into an arkworks constraint system.
+///
+/// The circuit's public inputs are allocated as instance variables in ascending witness order,
+/// which is the order in which they must be passed to the verifier.
+struct AcirCircuit<'a> {
+    circuit: &'a Circuit,
+    witness: Option<&'a WitnessMap>,
+}
+
+impl AcirCircuit<'_> {
+    fn value(&self, witness: &Witness) -> Result<FieldElement, SynthesisError> {
+        self.witness
+            .and_then(|witness_map| witness_map.get(witness))
+            .copied()
+            .ok_or(SynthesisError::AssignmentMissing)
+    }
+
+    fn variable(
+        &self,
+        cs: &ConstraintSystemRef<Fr>,
+        variables: &mut HashMap<Witness, Variable>,
+        witness: &Witness,
+    ) -> Result<Variable, SynthesisError> {
+        if let Some(variable) = variables.get(witness) {
+            return Ok(*variable);
+        }
+        let variable = cs.new_witness_variable(|| Ok(self.value(witness)?.into_repr()))?;
+        variables.insert(*witness, variable);
+        Ok(variable)
+    }
+
+    /// Enforces `expr = 0`.
+    ///
+    /// The first multiplication term is placed into the `A * B` product of the constraint while any
+    /// further terms are each moved into an additional constraint of their own.
+    fn enforce(
+        &self,
+        cs: &ConstraintSystemRef<Fr>,
+        variables: &mut HashMap<Witness, Variable>,
+        expr: &Expression,
+    ) -> Result<(), SynthesisError> {
+        let mut c = LinearCombination::zero();
+        for (q_l, w_l) in &expr.linear_combinations {
+            c = c - (q_l.into_repr(), self.variable(cs, variables, w_l)?);
+        }
+        if !expr.q_c.is_zero() {
+            c = c - (expr.q_c.into_repr(), Variable::One);
+        }
+
+        let Some(((q_m, w_l, w_r), remaining_terms)) = expr.mul_terms.split_first() else {
+            // 1 * c = 0
+            return cs.enforce_constraint(Variable::One.into(), c, LinearCombination::zero());
+        };
+
+        for (q_m, w_l, w_r) in remaining_terms {
+            let l = self.variable(cs, variables, w_l)?;
+            let r = self.variable(cs, variables, w_r)?;
+            let product =
+                cs.new_witness_variable(|| Ok((self.value(w_l)? * self.value(w_r)?).into_repr()))?;
+            cs.enforce_constraint(l.into(), r.into(), product.into())?;
+            c = c - (q_m.into_repr(), product);
+        }
+
+        let l = self.variable(cs, variables, w_l)?;
+        let r = self.variable(cs, variables, w_r)?;
+        cs.enforce_constraint(LinearCombination::zero() + (q_m.into_repr(), l), r.into(), c)
+    }
+}
+
+impl ConstraintSynthesizer<Fr> for AcirCircuit<'_> {
+    fn generate_constraints(self, cs: ConstraintSystemRef<Fr>) -> Result<(), SynthesisError> {
+        let mut variables = HashMap::new();
+        for witness in &self.circuit.public_inputs().0 {
+            let variable = cs.new_input_variable(|| Ok(self.value(witness)?.into_repr()))?;
+            variables.insert(*witness, variable);
+        }
+
+        for opcode in &self.circuit.opcodes {
+            if let Opcode::Arithmetic(expr) = opcode {
+                self.enforce(&cs, &mut variables, expr)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeSet;
+
+    use acvm::acir::{
+        circuit::{Circuit, Opcode, PublicInputs},
+        native_types::{Expression, Witness, WitnessMap},
+    };
+    use acvm::FieldElement;
+
+    use super::Groth16Backend;
+    use crate::{BackendError, ProofSystem};
+
+    /// Checks that `x * y = z` where `z` is public.
+    fn product_circuit() -> Circuit {
+        let expression = Expression {
+            mul_terms: vec![(FieldElement::one(), Witness(1), Witness(2))],
+            linear_combinations: vec![(-FieldElement::one(), Witness(3))],
+            q_c: FieldElement::zero(),
+        };
+        Circuit {
+            current_witness_index: 3,
+            opcodes: vec![Opcode::Arithmetic(expression)],
+            private_parameters: BTreeSet::from([Witness(1), Witness(2)]),
+            public_parameters: PublicInputs(BTreeSet::from([Witness(3)])),
+            return_values: PublicInputs::default(),
+            assert_messages: Default::default(),
+        }
+    }
+
+    fn witness(values: [u128; 3]) -> WitnessMap {
+        WitnessMap::from(std::collections::BTreeMap::from([
+            (Witness(1), FieldElement::from(values[0])),
+            (Witness(2), FieldElement::from(values[1])),
+            (Witness(3), FieldElement::from(values[2])),
+        ]))
+    }
+
+    fn public_inputs(value: u128) -> WitnessMap {
+        WitnessMap::from(std::collections::BTreeMap::from([(
+            Witness(3),
+            FieldElement::from(value),
+        )]))
+    }
+
+    #[test]
+    fn proves_and_verifies_circuit() -> Result<(), BackendError> {
+        let circuit = product_circuit();
+        let backend = Groth16Backend;
+
+        let proof = backend.prove(&circuit, witness([2, 3, 6]), false)?;
+
+        assert!(backend.verify(&proof, public_inputs(6), &circuit, false)?);
+        assert!(!backend.verify(&proof, public_inputs(7), &circuit, false)?);
+        assert!(!backend.verify(&[0; 4], public_inputs(6), &circuit, false)?);
+
+        Ok(())
+    }
+
+    #[test]
+    fn refuses_to_prove_unsatisfied_circuit() {
+        let circuit = product_circuit();
+
+        let result = Groth16Backend.prove(&circuit, witness([2, 3, 7]), false);
+
+        assert!(matches!(result, Err(BackendError::UnsatisfiedConstraints)));
+    }
+
+    #[test]
+    fn splits_multiple_mul_terms_into_separate_constraints() -> Result<(), BackendError> {
+        // x * y + x * x - z = 0
+        let expression = Expression {
+            mul_terms: vec![
+                (FieldElement::one(), Witness(1), Witness(2)),
+                (FieldElement::one(), Witness(1), Witness(1)),
+            ],
+            linear_combinations: vec![(-FieldElement::one(), Witness(3))],
+            q_c: FieldElement::zero(),
+        };
+        let circuit =
+            Circuit { opcodes: vec![Opcode::Arithmetic(expression)], ..product_circuit() };
+        let backend = Groth16Backend;
+
+        assert_eq!(backend.get_exact_circuit_size(&circuit)?, 2);
+
+        let proof = backend.prove(&circuit, witness([2, 3, 10]), false)?;
+        assert!(backend.verify(&proof, public_inputs(10), &circuit, false)?);
+
+        Ok(())
+    }
+
+    #[test]
+    fn verification_key_is_deterministic() -> Result<(), BackendError> {
+        let circuit = product_circuit();
+
+        assert_eq!(Groth16Backend.write_vk(&circuit)?, Groth16Backend.write_vk(&circuit)?);
+
+        Ok(())
+    }
+}
@@ -5,17 +5,23 @@ use std::{collections::HashSet, path::PathBuf};
 
 mod cli;
 mod download;
+mod groth16;
 mod proof_system;
 mod smart_contract;
 
-use acvm::acir::circuit::Opcode;
+use acvm::acir::circuit::{Opcode, OpcodeLocation};
 use bb_abstraction_leaks::ACVM_BACKEND_BARRETENBERG;
 use bb_abstraction_leaks::BB_VERSION;
 use cli::VersionCommand;
 pub use download::download_backend;
+pub use groth16::Groth16Backend;
+pub use proof_system::ProofSystem;
 
 const BACKENDS_DIR: &str = ".nargo/backends";
 
+/// The name under which the in-process [`Groth16Backend`] can be selected.
+pub const BUILTIN_BACKEND_NAME: &str = "builtin";
+
 pub fn backends_directory() -> PathBuf {
     let home_directory = dirs::home_dir().unwrap();
     home_directory.join(BACKENDS_DIR)
@@ -25,10 +31,10 @@ pub fn backends_directory() -> PathBuf {
 test_binary::build_test_binary_once!(mock_backend, "test-binaries");
 
 #[cfg(test)]
-fn get_mock_backend() -> Result<Backend, BackendError> {
+fn get_mock_backend() -> Result<BinaryBackend, BackendError> {
     std::env::set_var("NARGO_BACKEND_PATH", path_to_mock_backend());
 
-    let mock_backend = Backend::new("mock_backend".to_string());
+    let mock_backend = BinaryBackend::new("mock_backend".to_string());
     mock_backend.assert_binary_exists()?;
 
     Ok(mock_backend)
@@ -52,16 +58,62 @@ pub enum BackendError {
 
     #[error("The backend encountered an error: {0:?}")]
     CommandFailed(String),
+
+    #[error("The {0} backend does not support {1}")]
+    UnsupportedOperation(String, &'static str),
+
+    #[error("Opcode {0} is not supported by the {1} backend")]
+    UnsupportedOpcode(OpcodeLocation, String),
+
+    #[error("The witness does not satisfy the circuit's constraints")]
+    UnsatisfiedConstraints,
+
+    #[error("Failed to synthesize the constraint system: {0}")]
+    SynthesisError(String),
 }
 
+/// A proving backend which can be selected by name.
 #[derive(Debug)]
-pub struct Backend {
-    name: String,
-    binary_path: PathBuf,
+pub enum Backend {
+    /// A backend which is run as an external binary, e.g. barretenberg.
+    Binary(BinaryBackend),
+    /// The reference Groth16 backend which runs in-process and requires no downloads.
+    Builtin(Groth16Backend),
 }
 
 impl Backend {
     pub fn new(name: String) -> Backend {
+        if name == BUILTIN_BACKEND_NAME {
+            Backend::Builtin(Groth16Backend)
+        } else {
+            Backend::Binary(BinaryBackend::new(name))
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        match self {
+            Backend::Binary(backend) => backend.name(),
+            Backend::Builtin(_) => BUILTIN_BACKEND_NAME,
+        }
+    }
+
+    fn proof_system(&self) -> &dyn ProofSystem {
+        match self {
+            Backend::Binary(backend) => backend,
+            Backend::Builtin(backend) => backend,
+        }
+    }
+}
+
+/// A backend which shells out to an external binary implementing the barretenberg CLI.
+#[derive(Debug)]
+pub struct BinaryBackend {
+    name: String,
+    binary_path: PathBuf,
+}
+
+impl BinaryBackend {
+    pub fn new(name: String) -> BinaryBackend {
         let binary_path = if let Some(binary_path) = std::env::var_os("NARGO_BACKEND_PATH") {
             PathBuf::from(binary_path)
         } else {
@@ -69,7 +121,7 @@ impl Backend {
 
             backends_directory().join(&name).join(BINARY_NAME)
         };
-        Backend { name, binary_path }
+        BinaryBackend { name, binary_path }
     }
 
     pub fn name(&self) -> &str {
@@ -141,6 +193,18 @@ pub struct BackendOpcodeSupport {
 }
 
 impl BackendOpcodeSupport {
+    /// Opcode support for a generic R1CS proving system.
+    ///
+    /// Black box functions are not supported so that the ACVM compiler replaces them with arithmetic fallbacks
+    /// where possible. Memory opcodes are let through so that they can be reported when exporting the circuit.
+    pub fn r1cs() -> Self {
+        let opcodes = ["arithmetic", "directive", "brillig", "memory_init", "memory_op"];
+        Self {
+            opcodes: opcodes.into_iter().map(String::from).collect(),
+            black_box_functions: HashSet::new(),
+        }
+    }
+
     pub fn is_opcode_supported(&self, opcode: &Opcode) -> bool {
         match opcode {
             Opcode::Arithmetic(_) => self.opcodes.contains("arithmetic"),
@@ -157,11 +221,11 @@ impl BackendOpcodeSupport {
 
 #[cfg(test)]
 mod backend {
-    use crate::{Backend, BackendError};
+    use crate::{BackendError, BinaryBackend};
 
     #[test]
     fn raises_error_on_missing_binary() {
-        let bad_backend = Backend::new("i_dont_exist".to_string());
+        let bad_backend = BinaryBackend::new("i_dont_exist".to_string());
 
         let binary_path = bad_backend.assert_binary_exists();
 
@@ -11,10 +11,91 @@ use crate::cli::{
     GatesCommand, InfoCommand, ProofAsFieldsCommand, ProveCommand, VerifyCommand,
     VkAsFieldsCommand, WriteVkCommand,
 };
-use crate::{Backend, BackendError, BackendOpcodeSupport};
+use crate::{Backend, BackendError, BackendOpcodeSupport, BinaryBackend};
+
+/// The operations Nargo requires from a proving backend.
+pub trait ProofSystem {
+    /// Returns the number of gates in the backend's representation of `circuit`.
+    fn get_exact_circuit_size(&self, circuit: &Circuit) -> Result<u32, BackendError>;
+
+    /// Returns the language and opcodes which circuits must be compiled to for this backend.
+    fn get_backend_info(&self) -> Result<(Language, BackendOpcodeSupport), BackendError>;
+
+    /// Creates a proof of `circuit` being satisfied by `witness_values`, excluding the public inputs.
+    fn prove(
+        &self,
+        circuit: &Circuit,
+        witness_values: WitnessMap,
+        is_recursive: bool,
+    ) -> Result<Vec<u8>, BackendError>;
+
+    /// Checks `proof` against `circuit` for the given `public_inputs`.
+    fn verify(
+        &self,
+        proof: &[u8],
+        public_inputs: WitnessMap,
+        circuit: &Circuit,
+        is_recursive: bool,
+    ) -> Result<bool, BackendError>;
+
+    /// Returns the serialized verification key for `circuit`.
+    fn write_vk(&self, circuit: &Circuit) -> Result<Vec<u8>, BackendError>;
+}
+
+impl ProofSystem for Backend {
+    fn get_exact_circuit_size(&self, circuit: &Circuit) -> Result<u32, BackendError> {
+        self.proof_system().get_exact_circuit_size(circuit)
+    }
+
+    fn get_backend_info(&self) -> Result<(Language, BackendOpcodeSupport), BackendError> {
+        self.proof_system().get_backend_info()
+    }
+
+    fn prove(
+        &self,
+        circuit: &Circuit,
+        witness_values: WitnessMap,
+        is_recursive: bool,
+    ) -> Result<Vec<u8>, BackendError> {
+        self.proof_system().prove(circuit, witness_values, is_recursive)
+    }
+
+    fn verify(
+        &self,
+        proof: &[u8],
+        public_inputs: WitnessMap,
+        circuit: &Circuit,
+        is_recursive: bool,
+    ) -> Result<bool, BackendError> {
+        self.proof_system().verify(proof, public_inputs, circuit, is_recursive)
+    }
+
+    fn write_vk(&self, circuit: &Circuit) -> Result<Vec<u8>, BackendError> {
+        self.proof_system().write_vk(circuit)
+    }
+}
 
 impl Backend {
-    pub fn get_exact_circuit_size(&self, circuit: &Circuit) -> Result<u32, BackendError> {
+    pub fn get_intermediate_proof_artifacts(
+        &self,
+        circuit: &Circuit,
+        proof: &[u8],
+        public_inputs: WitnessMap,
+    ) -> Result<(Vec<FieldElement>, FieldElement, Vec<FieldElement>), BackendError> {
+        match self {
+            Backend::Binary(backend) => {
+                backend.get_intermediate_proof_artifacts(circuit, proof, public_inputs)
+            }
+            Backend::Builtin(_) => Err(BackendError::UnsupportedOperation(
+                self.name().to_string(),
+                "recursive proof artifacts",
+            )),
+        }
+    }
+}
+
+impl ProofSystem for BinaryBackend {
+    fn get_exact_circuit_size(&self, circuit: &Circuit) -> Result<u32, BackendError> {
         let binary_path = self.assert_binary_exists()?;
         self.assert_correct_version()?;
 
@@ -30,13 +111,13 @@ impl Backend {
             .run(binary_path)
     }
 
-    pub fn get_backend_info(&self) -> Result<(Language, BackendOpcodeSupport), BackendError> {
+    fn get_backend_info(&self) -> Result<(Language, BackendOpcodeSupport), BackendError> {
         let binary_path = self.assert_binary_exists()?;
         self.assert_correct_version()?;
         InfoCommand { crs_path: self.crs_directory() }.run(binary_path)
     }
 
-    pub fn prove(
+    fn prove(
         &self,
         circuit: &Circuit,
         witness_values: WitnessMap,
@@ -76,7 +157,7 @@ impl Backend {
         Ok(proof)
     }
 
-    pub fn verify(
+    fn verify(
         &self,
         proof: &[u8],
         public_inputs: WitnessMap,
@@ -115,6 +196,32 @@ impl Backend {
             .run(binary_path)
     }
 
+    fn write_vk(&self, circuit: &Circuit) -> Result<Vec<u8>, BackendError> {
+        let binary_path = self.assert_binary_exists()?;
+        self.assert_correct_version()?;
+
+        let temp_directory = tempdir().expect("could not create a temporary directory");
+        let temp_directory = temp_directory.path().to_path_buf();
+
+        // Create a temporary file for the circuit
+        let bytecode_path = temp_directory.join("circuit").with_extension("bytecode");
+        let serialized_circuit = Circuit::serialize_circuit(circuit);
+        write_to_file(&serialized_circuit, &bytecode_path);
+
+        // Create the verification key and read it back from the specified path
+        let vk_path = temp_directory.join("vk");
+        WriteVkCommand {
+            crs_path: self.crs_directory(),
+            bytecode_path,
+            vk_path_output: vk_path.clone(),
+        }
+        .run(binary_path)?;
+
+        Ok(std::fs::read(vk_path)?)
+    }
+}
+
+impl BinaryBackend {
     pub fn get_intermediate_proof_artifacts(
         &self,
         circuit: &Circuit,
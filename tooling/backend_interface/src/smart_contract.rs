@@ -1,12 +1,24 @@
 use super::proof_system::write_to_file;
 use crate::{
     cli::{ContractCommand, WriteVkCommand},
-    Backend, BackendError,
+    Backend, BackendError, BinaryBackend,
 };
 use acvm::acir::circuit::Circuit;
 use tempfile::tempdir;
 
 impl Backend {
+    pub fn eth_contract(&self, circuit: &Circuit) -> Result<String, BackendError> {
+        match self {
+            Backend::Binary(backend) => backend.eth_contract(circuit),
+            Backend::Builtin(_) => Err(BackendError::UnsupportedOperation(
+                self.name().to_string(),
+                "generating verifier contracts",
+            )),
+        }
+    }
+}
+
+impl BinaryBackend {
     pub fn eth_contract(&self, circuit: &Circuit) -> Result<String, BackendError> {
         let binary_path = self.assert_binary_exists()?;
         self.assert_correct_version()?;
@@ -1,16 +1,24 @@
 use acvm::acir::circuit::{Opcode, OpcodeLocation};
+use acvm::acir::native_types::{Expression, Witness};
+use acvm::brillig_vm::brillig::{HeapArray, RegisterOrMemory, Value};
+use acvm::brillig_vm::Registers;
 use acvm::pwg::{
-    ACVMStatus, BrilligSolver, BrilligSolverStatus, ForeignCallWaitInfo, StepResult, ACVM,
+    get_value, ACVMStatus, BrilligSolver, BrilligSolverStatus, ForeignCallWaitInfo, StepResult,
+    ACVM,
 };
-use acvm::BlackBoxFunctionSolver;
 use acvm::{acir::circuit::Circuit, acir::native_types::WitnessMap};
+use acvm::{BlackBoxFunctionSolver, FieldElement};
 
+use fm::FileId;
 use nargo::artifacts::debug::DebugArtifact;
 use nargo::errors::{ExecutionError, Location};
 use nargo::ops::ForeignCallExecutor;
 use nargo::NargoError;
+use noirc_errors::debug_info::{DebugInfo, DebugVarId, DebugVarValue};
+use noirc_printable_type::{decode_value, PrintableType, PrintableValueDisplay};
 
-use std::collections::{hash_set::Iter, HashSet};
+use std::collections::{hash_set::Iter, HashMap, HashSet};
+use std::path::Path;
 
 #[derive(Debug)]
 pub(super) enum DebugCommandResult {
@@ -20,13 +28,48 @@ pub(super) enum DebugCommandResult {
     Error(NargoError),
 }
 
+/// The value of a source-level variable as last assigned during execution
+enum VariableValue {
+    /// Flattened expressions over the witnesses of the circuit, which are evaluated
+    /// on demand as their witnesses may not have been solved yet.
+    Acir(Vec<Expression>),
+    /// Flattened field elements read from the Brillig VM at the time of the assignment,
+    /// as its registers and memory may be reused afterwards.
+    Brillig(Option<Vec<FieldElement>>),
+}
+
+/// A source-level variable which is visible at the current location
+pub(super) struct DebugVariable<'a> {
+    pub(super) name: &'a str,
+    pub(super) typ: &'a PrintableType,
+    /// The flattened value of the variable, or `None` if it can't be determined yet.
+    pub(super) value: Option<Vec<FieldElement>>,
+}
+
+impl DebugVariable<'_> {
+    /// Decodes the value of the variable for display, or returns `None` if it's not known.
+    pub(super) fn display_value(&self) -> Option<String> {
+        let fields = self.value.as_ref()?;
+        if fields.len() != self.typ.field_count() as usize {
+            return None;
+        }
+        let value = decode_value(&mut fields.iter().copied(), self.typ);
+        Some(PrintableValueDisplay::Plain(value, self.typ.clone()).to_string())
+    }
+}
+
 pub(super) struct DebugContext<'a, B: BlackBoxFunctionSolver> {
     acvm: ACVM<'a, B>,
     brillig_solver: Option<BrilligSolver<'a, B>>,
-    foreign_call_executor: ForeignCallExecutor,
+    foreign_call_executor: ForeignCallExecutor<'a>,
     debug_artifact: &'a DebugArtifact,
     show_output: bool,
     breakpoints: HashSet<OpcodeLocation>,
+    /// The variables of every function call seen so far, keyed by the call stack of the call
+    /// and kept in the order in which they were last assigned.
+    variables: HashMap<Vec<Location>, Vec<(DebugVarId, VariableValue)>>,
+    /// The call stack of the function call currently being executed
+    current_frame: Vec<Location>,
 }
 
 impl<'a, B: BlackBoxFunctionSolver> DebugContext<'a, B> {
@@ -36,14 +79,22 @@ impl<'a, B: BlackBoxFunctionSolver> DebugContext<'a, B> {
         debug_artifact: &'a DebugArtifact,
         initial_witness: WitnessMap,
     ) -> Self {
-        Self {
+        let mut context = Self {
             acvm: ACVM::new(blackbox_solver, &circuit.opcodes, initial_witness),
             brillig_solver: None,
             foreign_call_executor: ForeignCallExecutor::default(),
             debug_artifact,
             show_output: true,
             breakpoints: HashSet::new(),
-        }
+            variables: HashMap::new(),
+            current_frame: Vec::new(),
+        };
+        context.update_variables();
+        context
+    }
+
+    fn debug_info(&self) -> &'a DebugInfo {
+        &self.debug_artifact.debug_symbols[0]
     }
 
     pub(super) fn get_opcodes(&self) -> &[Opcode] {
@@ -75,6 +126,98 @@ impl<'a, B: BlackBoxFunctionSolver> DebugContext<'a, B> {
             .and_then(|location| self.debug_artifact.debug_symbols[0].opcode_location(location))
     }
 
+    /// Returns the full source call stack of the currently executing opcode. Unlike
+    /// [Self::get_current_source_location], the call stack of a Brillig opcode includes the
+    /// calls leading to the ACIR opcode which called into Brillig.
+    pub(super) fn get_source_call_stack(&self) -> Option<Vec<Location>> {
+        let location = self.get_current_opcode_location()?;
+        let call_stack = self.debug_info().opcode_location(&location)?;
+        Some(self.call_stack_prefix(&location).into_iter().chain(call_stack).collect())
+    }
+
+    // Brillig call stacks are relative to the ACIR opcode which called into Brillig,
+    // so we need the call stack of that opcode to place them within the program.
+    fn call_stack_prefix(&self, location: &OpcodeLocation) -> Vec<Location> {
+        match *location {
+            OpcodeLocation::Acir(_) => Vec::new(),
+            OpcodeLocation::Brillig { acir_index, .. } => self
+                .debug_info()
+                .opcode_location(&OpcodeLocation::Acir(acir_index))
+                .unwrap_or_default(),
+        }
+    }
+
+    /// Returns the locations of the opcodes at which execution enters the given source line,
+    /// that is, the opcodes mapped to the line which don't follow another one mapped to it.
+    pub(super) fn find_opcode_locations_at_line(
+        &self,
+        file_id: FileId,
+        line: usize,
+    ) -> Vec<OpcodeLocation> {
+        let is_at_line = |location: &OpcodeLocation| {
+            let source_location = self
+                .debug_info()
+                .opcode_location(location)
+                .and_then(|call_stack| call_stack.last().copied());
+            source_location.map_or(false, |source_location| {
+                source_location.file == file_id
+                    && self.debug_artifact.location_line_number(source_location).ok() == Some(line)
+            })
+        };
+        self.debug_info()
+            .locations
+            .keys()
+            .filter(|location| {
+                is_at_line(location)
+                    && !previous_opcode_location(location).map_or(false, |prev| is_at_line(&prev))
+            })
+            .copied()
+            .collect()
+    }
+
+    /// Returns the source files of the program matching `path`, which may either be the full
+    /// path of a file or its trailing components, such as `src/main.nr`.
+    pub(super) fn find_source_files(&self, path: &str) -> Vec<FileId> {
+        let path = Path::new(path);
+        let canonical_path = path.canonicalize().ok();
+        let file_map = &self.debug_artifact.file_map;
+        file_map
+            .iter()
+            .filter(|(_, debug_file)| {
+                debug_file.path.ends_with(path)
+                    || canonical_path.is_some()
+                        && debug_file.path.canonicalize().ok() == canonical_path
+            })
+            .map(|(file_id, _)| *file_id)
+            .collect()
+    }
+
+    pub(super) fn get_witness_map(&self) -> &WitnessMap {
+        self.acvm.witness_map()
+    }
+
+    pub(super) fn overwrite_witness(
+        &mut self,
+        witness: Witness,
+        value: FieldElement,
+    ) -> Option<FieldElement> {
+        self.acvm.overwrite_witness(witness, value)
+    }
+
+    /// Returns the registers of the Brillig VM, if a Brillig opcode is being executed.
+    pub(super) fn get_brillig_registers(&self) -> Option<&Registers> {
+        self.brillig_solver.as_ref().map(|solver| solver.get_registers())
+    }
+
+    /// Returns the memory of the Brillig VM, if a Brillig opcode is being executed.
+    pub(super) fn get_brillig_memory(&self) -> Option<&[Value]> {
+        self.brillig_solver.as_ref().map(|solver| solver.get_memory())
+    }
+
+    pub(super) fn set_show_output(&mut self, show_output: bool) {
+        self.show_output = show_output;
+    }
+
     fn step_brillig_opcode(&mut self) -> DebugCommandResult {
         let Some(mut solver) = self.brillig_solver.take() else {
             unreachable!("Missing Brillig solver");
@@ -82,6 +225,7 @@ impl<'a, B: BlackBoxFunctionSolver> DebugContext<'a, B> {
         match solver.step() {
             Ok(BrilligSolverStatus::InProgress) => {
                 self.brillig_solver = Some(solver);
+                self.update_variables();
                 if self.breakpoint_reached() {
                     DebugCommandResult::BreakpointReached(
                         self.get_current_opcode_location()
@@ -110,7 +254,11 @@ impl<'a, B: BlackBoxFunctionSolver> DebugContext<'a, B> {
             self.foreign_call_executor.execute(&foreign_call, self.show_output);
         match foreign_call_result {
             Ok(foreign_call_result) => {
-                self.acvm.resolve_pending_foreign_call(foreign_call_result);
+                if let Some(ref mut solver) = self.brillig_solver {
+                    solver.resolve_pending_foreign_call(foreign_call_result);
+                } else {
+                    self.acvm.resolve_pending_foreign_call(foreign_call_result);
+                }
                 // TODO: should we retry executing the opcode somehow in this case?
                 DebugCommandResult::Ok
             }
@@ -126,6 +274,7 @@ impl<'a, B: BlackBoxFunctionSolver> DebugContext<'a, B> {
         match status {
             ACVMStatus::Solved => DebugCommandResult::Done,
             ACVMStatus::InProgress => {
+                self.update_variables();
                 if self.breakpoint_reached() {
                     DebugCommandResult::BreakpointReached(
                         self.get_current_opcode_location()
@@ -148,10 +297,17 @@ impl<'a, B: BlackBoxFunctionSolver> DebugContext<'a, B> {
         if self.brillig_solver.is_some() {
             return self.step_brillig_opcode();
         }
+        // There is nothing left to step into once the last opcode has been solved, or if the
+        // circuit has no opcodes at all.
+        if self.acvm.instruction_pointer() >= self.get_opcodes().len() {
+            return DebugCommandResult::Done;
+        }
 
         match self.acvm.step_into_brillig_opcode() {
             StepResult::IntoBrillig(solver) => {
                 self.brillig_solver = Some(solver);
+                // Variables may be assigned by the first Brillig opcode before it is stepped.
+                self.update_variables();
                 self.step_brillig_opcode()
             }
             StepResult::Status(status) => self.handle_acvm_status(status),
@@ -159,12 +315,18 @@ impl<'a, B: BlackBoxFunctionSolver> DebugContext<'a, B> {
     }
 
     pub(super) fn step_acir_opcode(&mut self) -> DebugCommandResult {
-        let status = if let Some(solver) = self.brillig_solver.take() {
-            self.acvm.finish_brillig_with_solver(solver)
-        } else {
-            self.acvm.solve_opcode()
-        };
-        self.handle_acvm_status(status)
+        // Brillig opcodes are stepped through one by one so that we can keep track of the
+        // variables they assign, stopping only once the next ACIR opcode is reached.
+        let start_ip = self.acvm.instruction_pointer();
+        loop {
+            let result = self.step_into_opcode();
+            let in_same_opcode = self.acvm.instruction_pointer() == start_ip;
+            match result {
+                DebugCommandResult::Ok | DebugCommandResult::BreakpointReached(_)
+                    if in_same_opcode => {}
+                result => return result,
+            }
+        }
     }
 
     pub(super) fn next(&mut self) -> DebugCommandResult {
@@ -190,6 +352,76 @@ impl<'a, B: BlackBoxFunctionSolver> DebugContext<'a, B> {
         }
     }
 
+    /// Applies the variable assignments which take place once execution reaches
+    /// the current opcode and keeps track of the function call being executed.
+    fn update_variables(&mut self) {
+        let Some(location) = self.get_current_opcode_location() else {
+            return;
+        };
+        let debug_info = self.debug_info();
+        let frame_prefix = self.call_stack_prefix(&location);
+        if let Some(mut call_stack) = self.get_source_call_stack() {
+            call_stack.pop();
+            self.current_frame = call_stack;
+        }
+
+        let Some(assignments) = debug_info.variable_assignments.get(&location) else {
+            return;
+        };
+        for assignment in assignments {
+            let value = match &assignment.value {
+                DebugVarValue::Acir { predicate, values } => {
+                    // Assignments within a branch which isn't taken are skipped.
+                    let predicate = get_value(predicate, self.acvm.witness_map());
+                    if matches!(predicate, Ok(predicate) if predicate.is_zero()) {
+                        continue;
+                    }
+                    VariableValue::Acir(values.clone())
+                }
+                DebugVarValue::Brillig(values) => {
+                    let solver = self.brillig_solver.as_ref().expect("Missing Brillig solver");
+                    let typ = &debug_info.variables[&assignment.var_id].typ;
+                    VariableValue::Brillig(read_brillig_value(solver, values, typ))
+                }
+            };
+            let frame = frame_prefix.iter().chain(&assignment.frame).copied().collect();
+            let frame_variables: &mut Vec<_> = self.variables.entry(frame).or_default();
+            frame_variables.retain(|(var_id, _)| *var_id != assignment.var_id);
+            frame_variables.push((assignment.var_id, value));
+        }
+    }
+
+    /// Returns the variables of the function call currently being executed in the order in
+    /// which they were last assigned. Only the latest of several variables sharing a name is
+    /// returned, as the others are shadowed by it.
+    pub(super) fn get_variables(&self) -> Vec<DebugVariable<'a>> {
+        let Some(frame_variables) = self.variables.get(&self.current_frame) else {
+            return Vec::new();
+        };
+        let debug_info = self.debug_info();
+        let mut seen_names = HashSet::new();
+        let mut variables: Vec<_> = frame_variables
+            .iter()
+            .rev()
+            .filter_map(|(var_id, value)| {
+                let variable = &debug_info.variables[var_id];
+                if !seen_names.insert(variable.name.as_str()) {
+                    return None;
+                }
+                let value = match value {
+                    VariableValue::Acir(expressions) => expressions
+                        .iter()
+                        .map(|expression| get_value(expression, self.acvm.witness_map()).ok())
+                        .collect(),
+                    VariableValue::Brillig(fields) => fields.clone(),
+                };
+                Some(DebugVariable { name: &variable.name, typ: &variable.typ, value })
+            })
+            .collect();
+        variables.reverse();
+        variables
+    }
+
     fn breakpoint_reached(&self) -> bool {
         if let Some(location) = self.get_current_opcode_location() {
             self.breakpoints.contains(&location)
@@ -240,3 +472,352 @@ impl<'a, B: BlackBoxFunctionSolver> DebugContext<'a, B> {
         self.acvm.finalize()
     }
 }
+
+fn previous_opcode_location(location: &OpcodeLocation) -> Option<OpcodeLocation> {
+    match *location {
+        OpcodeLocation::Acir(0) | OpcodeLocation::Brillig { brillig_index: 0, .. } => None,
+        OpcodeLocation::Acir(acir_index) => Some(OpcodeLocation::Acir(acir_index - 1)),
+        OpcodeLocation::Brillig { acir_index, brillig_index } => {
+            Some(OpcodeLocation::Brillig { acir_index, brillig_index: brillig_index - 1 })
+        }
+    }
+}
+
+/// Reads the flattened value of a variable of type `typ` from the registers and memory of the
+/// Brillig VM, where each of `values` holds either a scalar or a pointer to an array.
+fn read_brillig_value<B: BlackBoxFunctionSolver>(
+    solver: &BrilligSolver<B>,
+    values: &[RegisterOrMemory],
+    typ: &PrintableType,
+) -> Option<Vec<FieldElement>> {
+    let mut fields = Vec::new();
+    read_brillig_registers(solver, &mut values.iter(), typ, &mut fields)?;
+    Some(fields)
+}
+
+fn read_brillig_registers<'v, B: BlackBoxFunctionSolver>(
+    solver: &BrilligSolver<B>,
+    values: &mut impl Iterator<Item = &'v RegisterOrMemory>,
+    typ: &PrintableType,
+    fields: &mut Vec<FieldElement>,
+) -> Option<()> {
+    let registers = solver.get_registers();
+    match typ {
+        PrintableType::Field
+        | PrintableType::SignedInteger { .. }
+        | PrintableType::UnsignedInteger { .. }
+        | PrintableType::Boolean => {
+            let RegisterOrMemory::RegisterIndex(register) = values.next()? else {
+                return None;
+            };
+            fields.push(registers.get(*register).to_field());
+        }
+        PrintableType::Array { .. } | PrintableType::String { .. } => {
+            let RegisterOrMemory::HeapArray(HeapArray { pointer, .. }) = values.next()? else {
+                return None;
+            };
+            let address = registers.get(*pointer).to_usize();
+            read_brillig_array(solver.get_memory(), address, typ, fields)?;
+        }
+        PrintableType::Struct { fields: field_types, .. } => {
+            for (_, field_type) in field_types {
+                read_brillig_registers(solver, values, field_type, fields)?;
+            }
+        }
+        PrintableType::Enum { variants, .. } => {
+            read_brillig_registers(solver, values, &PrintableType::Field, fields)?;
+            for field_type in variants.iter().flat_map(|(_, field_types)| field_types) {
+                read_brillig_registers(solver, values, field_type, fields)?;
+            }
+        }
+    }
+    Some(())
+}
+
+/// Reads the elements of an array or string starting at `address` in Brillig memory.
+fn read_brillig_array(
+    memory: &[Value],
+    mut address: usize,
+    typ: &PrintableType,
+    fields: &mut Vec<FieldElement>,
+) -> Option<()> {
+    match typ {
+        PrintableType::Array { length, typ } => {
+            for _ in 0..*length {
+                read_brillig_memory(memory, &mut address, typ, fields)?;
+            }
+        }
+        PrintableType::String { length } => {
+            for _ in 0..*length {
+                read_brillig_memory(memory, &mut address, &PrintableType::Field, fields)?;
+            }
+        }
+        _ => unreachable!("Expected an array or string type"),
+    }
+    Some(())
+}
+
+/// Reads a value of type `typ` stored at `address` in Brillig memory, advancing `address` past it.
+/// Nested arrays are stored as a pointer to their elements while structs are stored inline.
+fn read_brillig_memory(
+    memory: &[Value],
+    address: &mut usize,
+    typ: &PrintableType,
+    fields: &mut Vec<FieldElement>,
+) -> Option<()> {
+    match typ {
+        PrintableType::Field
+        | PrintableType::SignedInteger { .. }
+        | PrintableType::UnsignedInteger { .. }
+        | PrintableType::Boolean => {
+            fields.push(memory.get(*address)?.to_field());
+            *address += 1;
+        }
+        PrintableType::Array { .. } | PrintableType::String { .. } => {
+            let pointer = memory.get(*address)?.to_usize();
+            *address += 1;
+            read_brillig_array(memory, pointer, typ, fields)?;
+        }
+        PrintableType::Struct { fields: field_types, .. } => {
+            for (_, field_type) in field_types {
+                read_brillig_memory(memory, address, field_type, fields)?;
+            }
+        }
+        PrintableType::Enum { variants, .. } => {
+            read_brillig_memory(memory, address, &PrintableType::Field, fields)?;
+            for field_type in variants.iter().flat_map(|(_, field_types)| field_types) {
+                read_brillig_memory(memory, address, field_type, fields)?;
+            }
+        }
+    }
+    Some(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use acvm::BlackBoxResolutionError;
+    use fm::FileManager;
+    use noirc_abi::{input_parser::InputValue, InputMap};
+    use noirc_driver::{compile_main, prepare_crate, CompileOptions, CompiledProgram};
+    use noirc_frontend::{graph::CrateGraph, hir::Context};
+
+    struct StubbedSolver;
+
+    impl BlackBoxFunctionSolver for StubbedSolver {
+        fn schnorr_verify(
+            &self,
+            _public_key_x: &FieldElement,
+            _public_key_y: &FieldElement,
+            _signature: &[u8],
+            _message: &[u8],
+        ) -> Result<bool, BlackBoxResolutionError> {
+            panic!("Path not trodden by this test")
+        }
+        fn pedersen_commitment(
+            &self,
+            _inputs: &[FieldElement],
+            _domain_separator: u32,
+        ) -> Result<(FieldElement, FieldElement), BlackBoxResolutionError> {
+            panic!("Path not trodden by this test")
+        }
+        fn pedersen_hash(
+            &self,
+            _inputs: &[FieldElement],
+            _domain_separator: u32,
+        ) -> Result<FieldElement, BlackBoxResolutionError> {
+            panic!("Path not trodden by this test")
+        }
+        fn fixed_base_scalar_mul(
+            &self,
+            _low: &FieldElement,
+            _high: &FieldElement,
+        ) -> Result<(FieldElement, FieldElement), BlackBoxResolutionError> {
+            panic!("Path not trodden by this test")
+        }
+    }
+
+    /// Compiles `source` as the `src/main.nr` file of a binary package with debug instrumentation
+    fn compile(source: &str) -> CompiledProgram {
+        let source = source.to_owned();
+        let file_reader = move |_: &Path| -> std::io::Result<String> { Ok(source.clone()) };
+        let file_manager = FileManager::new(Path::new("/"), Box::new(file_reader));
+        let mut context = Context::new(file_manager, CrateGraph::default());
+        let crate_id = prepare_crate(&mut context, Path::new("src/main.nr"));
+
+        let options = CompileOptions { instrument_debug: true, ..CompileOptions::default() };
+        let (program, _) = compile_main(&mut context, crate_id, &options, None, false)
+            .expect("program should compile");
+        program
+    }
+
+    /// Compiles `source` along with the witness assigning `a` and `b` to the parameters of its
+    /// `main(a: Field, b: Field)` function.
+    fn debug_program(source: &str, a: u128, b: u128) -> (Circuit, DebugArtifact, WitnessMap) {
+        let program = compile(source);
+        let inputs = InputMap::from([
+            ("a".to_owned(), InputValue::Field(a.into())),
+            ("b".to_owned(), InputValue::Field(b.into())),
+        ]);
+        let initial_witness = program.abi.encode(&inputs, None).unwrap();
+        let circuit = program.circuit.clone();
+        (circuit, DebugArtifact::from(program), initial_witness)
+    }
+
+    /// Runs `main` to completion and returns the decoded values of its variables, as displayed
+    /// by the `vars` and `print` commands.
+    fn final_variables(source: &str, a: u128, b: u128) -> Vec<(String, Option<String>)> {
+        let (circuit, debug_artifact, initial_witness) = debug_program(source, a, b);
+        let mut context =
+            DebugContext::new(&StubbedSolver, &circuit, &debug_artifact, initial_witness);
+        assert!(matches!(context.cont(), DebugCommandResult::Done));
+        context
+            .get_variables()
+            .iter()
+            .map(|variable| (variable.name.to_owned(), variable.display_value()))
+            .collect()
+    }
+
+    fn variable(name: &str, value: &str) -> (String, Option<String>) {
+        (name.to_owned(), Some(value.to_owned()))
+    }
+
+    #[test]
+    fn decodes_acir_variables() {
+        let source = "
+            struct Point { x: Field, y: Field }
+
+            fn main(a: Field, b: Field) {
+                let point = Point { x: a, y: b };
+                let values = [a, b, a + b];
+                let sum = point.x + values[2];
+                assert(sum * b == 8);
+            }
+        ";
+        assert_eq!(
+            final_variables(source, 1, 2),
+            vec![
+                variable("a", "0x01"),
+                variable("b", "0x02"),
+                variable("point", "Point { x: 0x01, y: 0x02 }"),
+                variable("values", "[0x01, 0x02, 0x03]"),
+                variable("sum", "0x04"),
+            ]
+        );
+    }
+
+    #[test]
+    fn finishes_circuits_without_opcodes() {
+        // The assertion is folded away at compile time, leaving no opcodes to execute
+        let source = "
+            fn main(a: Field, b: Field) {
+                assert(a + b == b + a);
+            }
+        ";
+        let (circuit, debug_artifact, initial_witness) = debug_program(source, 1, 2);
+        assert!(circuit.opcodes.is_empty());
+
+        let mut context =
+            DebugContext::new(&StubbedSolver, &circuit, &debug_artifact, initial_witness);
+        assert!(matches!(context.step_into_opcode(), DebugCommandResult::Done));
+        assert!(matches!(context.cont(), DebugCommandResult::Done));
+        assert!(context.is_solved());
+    }
+
+    #[test]
+    fn decodes_brillig_variables() {
+        let source = "
+            struct Point { x: Field, y: Field }
+
+            unconstrained fn main(a: Field, b: Field) {
+                let point = Point { x: a, y: b };
+                let mut values = [a, b, a + b];
+                values[0] = point.y + 5;
+                let sum = point.x + values[0];
+                assert(sum == 8);
+            }
+        ";
+        // `values` was reassigned after `point`, so it's listed after it
+        assert_eq!(
+            final_variables(source, 1, 2),
+            vec![
+                variable("a", "0x01"),
+                variable("b", "0x02"),
+                variable("point", "Point { x: 0x01, y: 0x02 }"),
+                variable("values", "[0x07, 0x02, 0x03]"),
+                variable("sum", "0x08"),
+            ]
+        );
+    }
+
+    #[test]
+    fn only_shows_the_latest_of_shadowed_variables() {
+        let source = "
+            fn main(a: Field, b: Field) {
+                let sum = a + b;
+                let sum = [sum, sum * b];
+                assert(sum[1] == 6);
+            }
+        ";
+        assert_eq!(
+            final_variables(source, 1, 2),
+            vec![variable("a", "0x01"), variable("b", "0x02"), variable("sum", "[0x03, 0x06]")]
+        );
+    }
+
+    const LINES_SOURCE: &str = "fn main(a: Field, b: Field) {
+    assert(a != b);
+    // This line has no opcodes
+    assert(a != 3);
+}
+";
+
+    #[test]
+    fn finds_source_files_by_trailing_path() {
+        let (circuit, debug_artifact, initial_witness) = debug_program(LINES_SOURCE, 1, 2);
+        let context = DebugContext::new(&StubbedSolver, &circuit, &debug_artifact, initial_witness);
+
+        let main_files = context.find_source_files("src/main.nr");
+        assert_eq!(main_files.len(), 1);
+        assert_eq!(context.find_source_files("main.nr"), main_files);
+        assert_eq!(context.find_source_files("/src/main.nr"), main_files);
+        assert!(context.find_source_files("ain.nr").is_empty());
+        assert!(context.find_source_files("src/lib.nr").is_empty());
+    }
+
+    #[test]
+    fn resolves_source_lines_to_opcodes() {
+        let (circuit, debug_artifact, initial_witness) = debug_program(LINES_SOURCE, 1, 2);
+        let mut context =
+            DebugContext::new(&StubbedSolver, &circuit, &debug_artifact, initial_witness);
+        let file_id = context.find_source_files("src/main.nr")[0];
+
+        let first_assert = context.find_opcode_locations_at_line(file_id, 2);
+        let second_assert = context.find_opcode_locations_at_line(file_id, 4);
+        assert!(!first_assert.is_empty());
+        assert!(!second_assert.is_empty());
+        assert!(context.find_opcode_locations_at_line(file_id, 3).is_empty());
+        assert!(context.find_opcode_locations_at_line(file_id, 10).is_empty());
+        assert!(first_assert.iter().all(|location| !second_assert.contains(location)));
+
+        // Only the first opcode of a run of opcodes mapped to the same line is returned
+        let line_of = |location: &OpcodeLocation| {
+            let call_stack = debug_artifact.debug_symbols[0].opcode_location(location)?;
+            debug_artifact.location_line_number(*call_stack.last()?).ok()
+        };
+        assert!(second_assert.iter().all(|location| line_of(location) == Some(4)));
+        for location in second_assert.iter().filter_map(previous_opcode_location) {
+            assert_ne!(line_of(&location), Some(4));
+        }
+
+        // Execution stops at the opcodes of a breakpoint set on a line
+        context.add_breakpoint(second_assert[0]);
+        let DebugCommandResult::BreakpointReached(location) = context.cont() else {
+            panic!("execution should stop at the breakpoint");
+        };
+        assert_eq!(location, second_assert[0]);
+        let line = context.get_current_opcode_location().and_then(|location| line_of(&location));
+        assert_eq!(line, Some(4));
+    }
+}
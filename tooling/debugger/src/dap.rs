@@ -0,0 +1,297 @@
+//! Exposes the debugger over the Debug Adapter Protocol so that it can be driven by editors.
+mod protocol;
+
+pub use protocol::{Capabilities, DapError, DapServer, Request};
+
+use std::collections::HashMap;
+use std::io::{BufRead, Write};
+
+use acvm::acir::circuit::{Circuit, OpcodeLocation};
+use acvm::acir::native_types::WitnessMap;
+use acvm::BlackBoxFunctionSolver;
+use fm::FileId;
+use nargo::artifacts::debug::DebugArtifact;
+use noirc_errors::Location;
+
+use crate::context::{DebugCommandResult, DebugContext};
+use protocol::{
+    Breakpoint, ContinueResponseBody, ExitedEventBody, Scope, ScopesResponseBody,
+    SetBreakpointsArguments, SetBreakpointsResponseBody, Source, StackFrame,
+    StackTraceResponseBody, StepArguments, SteppingGranularity, StoppedEventBody, Thread,
+    ThreadsResponseBody, Variable, VariablesArguments, VariablesResponseBody,
+};
+
+/// Circuits are executed on a single thread, which is the only one reported to the client.
+const THREAD_ID: i64 = 1;
+
+/// The variables reference of the only scope, which holds the variables of the current frame.
+const LOCALS_REFERENCE: i64 = 1;
+
+/// The capabilities to report to the client when it sends the `initialize` request.
+pub fn capabilities() -> Capabilities {
+    Capabilities { supports_configuration_done_request: true, supports_stepping_granularity: true }
+}
+
+struct DapSession<'a, R, W, B: BlackBoxFunctionSolver> {
+    server: &'a mut DapServer<R, W>,
+    context: DebugContext<'a, B>,
+    debug_artifact: &'a DebugArtifact,
+    /// Whether execution has either completed or failed
+    finished: bool,
+    /// The opcode locations of the breakpoints set on each source file
+    source_breakpoints: HashMap<FileId, Vec<OpcodeLocation>>,
+    /// The id of the source breakpoint each opcode breakpoint was set for
+    breakpoint_ids: HashMap<OpcodeLocation, i64>,
+    next_breakpoint_id: i64,
+}
+
+/// Runs a debugging session for a circuit which has already been launched, handling requests
+/// until the client disconnects.
+///
+/// Execution stops before the first opcode and only starts once the client has finished
+/// setting up its breakpoints.
+pub fn run_session<R: BufRead, W: Write, B: BlackBoxFunctionSolver>(
+    server: &mut DapServer<R, W>,
+    blackbox_solver: &B,
+    circuit: &Circuit,
+    debug_artifact: &DebugArtifact,
+    initial_witness: WitnessMap,
+) -> Result<(), DapError> {
+    let mut context = DebugContext::new(blackbox_solver, circuit, debug_artifact, initial_witness);
+    // The output of the program would be interleaved with the protocol messages.
+    context.set_show_output(false);
+
+    let mut session = DapSession {
+        server,
+        context,
+        debug_artifact,
+        source_breakpoints: HashMap::new(),
+        breakpoint_ids: HashMap::new(),
+        next_breakpoint_id: 1,
+        finished: false,
+    };
+    session.server.send_event("initialized", ())?;
+    session.run_loop()
+}
+
+impl<'a, R: BufRead, W: Write, B: BlackBoxFunctionSolver> DapSession<'a, R, W, B> {
+    fn run_loop(&mut self) -> Result<(), DapError> {
+        while let Some(request) = self.server.poll_request()? {
+            match request.command.as_str() {
+                "disconnect" => {
+                    self.server.respond(&request, ())?;
+                    break;
+                }
+                "setBreakpoints" => self.handle_set_breakpoints(&request)?,
+                "setExceptionBreakpoints" => self.server.respond(&request, ())?,
+                "configurationDone" => {
+                    self.server.respond(&request, ())?;
+                    self.notify_stopped("entry", None, Vec::new())?;
+                }
+                "threads" => {
+                    let threads = vec![Thread { id: THREAD_ID, name: "main".to_string() }];
+                    self.server.respond(&request, ThreadsResponseBody { threads })?;
+                }
+                "stackTrace" => self.handle_stack_trace(&request)?,
+                "scopes" => {
+                    let scopes = vec![Scope {
+                        name: "Locals".to_string(),
+                        variables_reference: LOCALS_REFERENCE,
+                        expensive: false,
+                    }];
+                    self.server.respond(&request, ScopesResponseBody { scopes })?;
+                }
+                "variables" => self.handle_variables(&request)?,
+                "next" | "stepIn" | "stepOut" | "continue" if self.finished => {
+                    self.server.respond_error(&request, "Execution has already finished")?;
+                }
+                "next" | "stepIn" | "stepOut" => self.handle_step(&request)?,
+                "continue" => {
+                    self.server
+                        .respond(&request, ContinueResponseBody { all_threads_continued: true })?;
+                    let result = self.context.cont();
+                    self.handle_debug_command_result(result)?;
+                }
+                command => {
+                    self.server
+                        .respond_error(&request, &format!("Unsupported request {command}"))?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn handle_step(&mut self, request: &Request) -> Result<(), DapError> {
+        let granularity = match request.arguments::<StepArguments>() {
+            Ok(arguments) => arguments.granularity,
+            Err(error) => return self.server.respond_error(request, &error.to_string()),
+        };
+        self.server.respond(request, ())?;
+
+        // Function calls are inlined into the circuit so there are no frames to step over or
+        // out of, and stepping always moves to the next source location.
+        let result = if granularity == SteppingGranularity::Instruction {
+            self.context.step_into_opcode()
+        } else {
+            self.context.next()
+        };
+        self.handle_debug_command_result(result)
+    }
+
+    fn handle_debug_command_result(&mut self, result: DebugCommandResult) -> Result<(), DapError> {
+        match result {
+            DebugCommandResult::Ok => self.notify_stopped("step", None, Vec::new()),
+            DebugCommandResult::BreakpointReached(location) => {
+                let ids = self.breakpoint_ids.get(&location).copied().into_iter().collect();
+                self.notify_stopped("breakpoint", None, ids)
+            }
+            DebugCommandResult::Error(error) => {
+                // Execution can't go on but the client may still inspect where it failed.
+                self.finished = true;
+                self.notify_stopped("exception", Some(error.to_string()), Vec::new())
+            }
+            DebugCommandResult::Done => {
+                self.finished = true;
+                self.server.send_event("exited", ExitedEventBody { exit_code: 0 })?;
+                self.server.send_event("terminated", ())
+            }
+        }
+    }
+
+    fn notify_stopped(
+        &mut self,
+        reason: &str,
+        text: Option<String>,
+        hit_breakpoint_ids: Vec<i64>,
+    ) -> Result<(), DapError> {
+        let description = text.as_ref().map(|_| "Execution failed".to_string());
+        self.server.send_event(
+            "stopped",
+            StoppedEventBody {
+                reason: reason.to_string(),
+                description,
+                text,
+                thread_id: THREAD_ID,
+                all_threads_stopped: true,
+                hit_breakpoint_ids,
+            },
+        )
+    }
+
+    fn handle_set_breakpoints(&mut self, request: &Request) -> Result<(), DapError> {
+        let arguments: SetBreakpointsArguments = match request.arguments() {
+            Ok(arguments) => arguments,
+            Err(error) => return self.server.respond_error(request, &error.to_string()),
+        };
+        let file_id = arguments
+            .source
+            .path
+            .as_deref()
+            .and_then(|path| self.context.find_source_files(path).first().copied());
+
+        // The breakpoints sent for a source replace any previously set on it.
+        if let Some(old_locations) = file_id.and_then(|id| self.source_breakpoints.remove(&id)) {
+            for location in old_locations {
+                self.context.delete_breakpoint(&location);
+                self.breakpoint_ids.remove(&location);
+            }
+        }
+
+        let mut breakpoints = Vec::new();
+        let mut locations = Vec::new();
+        for source_breakpoint in arguments.breakpoints {
+            let id = self.next_breakpoint_id;
+            self.next_breakpoint_id += 1;
+
+            let line = source_breakpoint.line;
+            let opcode_locations = match file_id {
+                Some(file_id) => self.context.find_opcode_locations_at_line(file_id, line as usize),
+                None => Vec::new(),
+            };
+            let message = if file_id.is_none() {
+                Some("Source file is not part of the program".to_string())
+            } else if opcode_locations.is_empty() {
+                Some("No opcodes were generated for this line".to_string())
+            } else {
+                None
+            };
+            breakpoints.push(Breakpoint {
+                id,
+                verified: message.is_none(),
+                message,
+                source: arguments.source.clone(),
+                line,
+            });
+
+            for location in opcode_locations {
+                self.context.add_breakpoint(location);
+                self.breakpoint_ids.insert(location, id);
+                locations.push(location);
+            }
+        }
+        if let Some(file_id) = file_id {
+            self.source_breakpoints.insert(file_id, locations);
+        }
+
+        self.server.respond(request, SetBreakpointsResponseBody { breakpoints })
+    }
+
+    fn handle_stack_trace(&mut self, request: &Request) -> Result<(), DapError> {
+        let call_stack = self.context.get_source_call_stack().unwrap_or_default();
+        // The client expects the innermost frame first.
+        let stack_frames: Vec<_> = call_stack
+            .iter()
+            .rev()
+            .enumerate()
+            .map(|(index, location)| self.stack_frame(index as i64, *location))
+            .collect();
+        let total_frames = stack_frames.len() as i64;
+        self.server.respond(request, StackTraceResponseBody { stack_frames, total_frames })
+    }
+
+    fn stack_frame(&self, id: i64, location: Location) -> StackFrame {
+        let debug_file = &self.debug_artifact.file_map[&location.file];
+        let source = Source {
+            name: debug_file.path.file_name().map(|name| name.to_string_lossy().into_owned()),
+            path: Some(debug_file.path.to_string_lossy().into_owned()),
+        };
+        let line = self.debug_artifact.location_line_number(location).unwrap_or(0);
+        let column = self.debug_artifact.location_column_number(location).unwrap_or(0);
+
+        // Frames are named after the first line of the code they point to, as function
+        // calls are inlined and their names are not available.
+        let start = location.span.start() as usize;
+        let end = location.span.end() as usize;
+        let name = self
+            .debug_artifact
+            .location_source_code(location)
+            .ok()
+            .and_then(|source| source.get(start..end))
+            .and_then(|code| code.lines().next())
+            .unwrap_or("<unknown>")
+            .to_string();
+
+        StackFrame { id, name, source, line: line as i64, column: column as i64 }
+    }
+
+    fn handle_variables(&mut self, request: &Request) -> Result<(), DapError> {
+        let arguments: VariablesArguments = match request.arguments() {
+            Ok(arguments) => arguments,
+            Err(error) => return self.server.respond_error(request, &error.to_string()),
+        };
+        let variables = if arguments.variables_reference == LOCALS_REFERENCE {
+            self.context
+                .get_variables()
+                .iter()
+                .map(|variable| Variable {
+                    name: variable.name.to_string(),
+                    value: variable.display_value().unwrap_or_else(|| "<unknown>".to_string()),
+                    variables_reference: 0,
+                })
+                .collect()
+        } else {
+            Vec::new()
+        };
+        self.server.respond(request, VariablesResponseBody { variables })
+    }
+}
@@ -0,0 +1,458 @@
+//! The subset of the [Debug Adapter Protocol](https://microsoft.github.io/debug-adapter-protocol/specification)
+//! messages supported by the debugger, along with the base protocol used to exchange them.
+use std::io::{BufRead, Write};
+
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serde_json::{json, Value};
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum DapError {
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+
+    #[error("Invalid message header: {0}")]
+    InvalidHeader(String),
+}
+
+/// A request sent by the client
+#[derive(Debug, Deserialize)]
+pub struct Request {
+    pub seq: i64,
+    #[serde(rename = "type")]
+    message_type: String,
+    pub command: String,
+    #[serde(default)]
+    arguments: Value,
+}
+
+impl Request {
+    /// Deserializes the arguments of the request, treating missing arguments as empty.
+    pub fn arguments<T: DeserializeOwned>(&self) -> Result<T, serde_json::Error> {
+        match &self.arguments {
+            Value::Null => serde_json::from_value(json!({})),
+            arguments => serde_json::from_value(arguments.clone()),
+        }
+    }
+}
+
+/// Exchanges messages with the client over a pair of streams, each message being preceded
+/// by a `Content-Length` header.
+pub struct DapServer<R, W> {
+    input: R,
+    output: W,
+    next_seq: i64,
+}
+
+impl<R: BufRead, W: Write> DapServer<R, W> {
+    pub fn new(input: R, output: W) -> Self {
+        Self { input, output, next_seq: 1 }
+    }
+
+    /// Waits for the next request from the client, returning `None` once the input is closed.
+    pub fn poll_request(&mut self) -> Result<Option<Request>, DapError> {
+        loop {
+            let Some(content_length) = self.read_header()? else {
+                return Ok(None);
+            };
+            let mut content = vec![0; content_length];
+            self.input.read_exact(&mut content)?;
+
+            // The client may also send responses to reverse requests, which we never make.
+            let request: Request = serde_json::from_slice(&content)?;
+            if request.message_type == "request" {
+                return Ok(Some(request));
+            }
+        }
+    }
+
+    fn read_header(&mut self) -> Result<Option<usize>, DapError> {
+        let mut content_length = None;
+        loop {
+            let mut line = String::new();
+            if self.input.read_line(&mut line)? == 0 {
+                return Ok(None);
+            }
+            let line = line.trim_end();
+            if line.is_empty() {
+                break;
+            }
+            if let Some(value) = line.strip_prefix("Content-Length:") {
+                let value =
+                    value.trim().parse().map_err(|_| DapError::InvalidHeader(line.into()))?;
+                content_length = Some(value);
+            }
+        }
+        match content_length {
+            Some(content_length) => Ok(Some(content_length)),
+            None => Err(DapError::InvalidHeader("missing Content-Length".into())),
+        }
+    }
+
+    /// Sends a successful response to `request`, omitting the body if it serializes to `null`.
+    pub fn respond(&mut self, request: &Request, body: impl Serialize) -> Result<(), DapError> {
+        let mut response = json!({
+            "type": "response",
+            "request_seq": request.seq,
+            "success": true,
+            "command": request.command,
+        });
+        let body = serde_json::to_value(body)?;
+        if !body.is_null() {
+            response["body"] = body;
+        }
+        self.send(response)
+    }
+
+    pub fn respond_error(&mut self, request: &Request, message: &str) -> Result<(), DapError> {
+        self.send(json!({
+            "type": "response",
+            "request_seq": request.seq,
+            "success": false,
+            "command": request.command,
+            "message": message,
+        }))
+    }
+
+    /// Sends an event to the client, omitting the body if it serializes to `null`.
+    pub fn send_event(&mut self, event: &str, body: impl Serialize) -> Result<(), DapError> {
+        let mut message = json!({ "type": "event", "event": event });
+        let body = serde_json::to_value(body)?;
+        if !body.is_null() {
+            message["body"] = body;
+        }
+        self.send(message)
+    }
+
+    fn send(&mut self, mut message: Value) -> Result<(), DapError> {
+        message["seq"] = self.next_seq.into();
+        self.next_seq += 1;
+
+        let content = serde_json::to_string(&message)?;
+        write!(self.output, "Content-Length: {}\r\n\r\n{}", content.len(), content)?;
+        self.output.flush()?;
+        Ok(())
+    }
+}
+
+#[derive(Debug, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Capabilities {
+    pub supports_configuration_done_request: bool,
+    pub supports_stepping_granularity: bool,
+}
+
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct Source {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub path: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SetBreakpointsArguments {
+    pub source: Source,
+    #[serde(default)]
+    pub breakpoints: Vec<SourceBreakpoint>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SourceBreakpoint {
+    pub line: i64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Breakpoint {
+    pub id: i64,
+    pub verified: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+    pub source: Source,
+    pub line: i64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SetBreakpointsResponseBody {
+    pub breakpoints: Vec<Breakpoint>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Thread {
+    pub id: i64,
+    pub name: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ThreadsResponseBody {
+    pub threads: Vec<Thread>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct StackFrame {
+    pub id: i64,
+    pub name: String,
+    pub source: Source,
+    pub line: i64,
+    pub column: i64,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StackTraceResponseBody {
+    pub stack_frames: Vec<StackFrame>,
+    pub total_frames: i64,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Scope {
+    pub name: String,
+    pub variables_reference: i64,
+    pub expensive: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ScopesResponseBody {
+    pub scopes: Vec<Scope>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VariablesArguments {
+    pub variables_reference: i64,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Variable {
+    pub name: String,
+    pub value: String,
+    pub variables_reference: i64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct VariablesResponseBody {
+    pub variables: Vec<Variable>,
+}
+
+#[derive(Debug, Default, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum SteppingGranularity {
+    #[default]
+    Statement,
+    Line,
+    Instruction,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct StepArguments {
+    #[serde(default)]
+    pub granularity: SteppingGranularity,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ContinueResponseBody {
+    pub all_threads_continued: bool,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StoppedEventBody {
+    pub reason: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub text: Option<String>,
+    pub thread_id: i64,
+    pub all_threads_stopped: bool,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub hit_breakpoint_ids: Vec<i64>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExitedEventBody {
+    pub exit_code: i64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn exchanges_framed_messages() {
+        let content = r#"{"seq":1,"type":"request","command":"threads"}"#;
+        let input = format!("Content-Length: {}\r\n\r\n{}", content.len(), content);
+        let mut output = Vec::new();
+        let mut server = DapServer::new(input.as_bytes(), &mut output);
+
+        let request = server.poll_request().unwrap().expect("a request should be read");
+        assert_eq!(request.command, "threads");
+        let threads = vec![Thread { id: 1, name: "main".into() }];
+        server.respond(&request, ThreadsResponseBody { threads }).unwrap();
+        assert!(server.poll_request().unwrap().is_none());
+
+        let output = String::from_utf8(output).unwrap();
+        let (header, content) = output.split_once("\r\n\r\n").unwrap();
+        assert_eq!(header, format!("Content-Length: {}", content.len()));
+        let response: Value = serde_json::from_str(content).unwrap();
+        assert_eq!(response["request_seq"], 1);
+        assert_eq!(response["success"], true);
+        assert_eq!(response["body"]["threads"][0]["name"], "main");
+    }
+
+    fn request(content: &str) -> Request {
+        serde_json::from_str(content).unwrap()
+    }
+
+    #[test]
+    fn skips_messages_other_than_requests() {
+        let response = r#"{"seq":1,"type":"response","request_seq":1,"command":"runInTerminal"}"#;
+        let request = r#"{"seq":2,"type":"request","command":"threads"}"#;
+        let input = format!(
+            "Content-Length: {}\r\n\r\n{}Content-Length: {}\r\n\r\n{}",
+            response.len(),
+            response,
+            request.len(),
+            request
+        );
+        let mut server = DapServer::new(input.as_bytes(), Vec::new());
+        assert_eq!(server.poll_request().unwrap().unwrap().seq, 2);
+        assert!(server.poll_request().unwrap().is_none());
+    }
+
+    #[test]
+    fn rejects_messages_without_content_length() {
+        let mut server = DapServer::new("Content-Type: json\r\n\r\n{}".as_bytes(), Vec::new());
+        assert!(matches!(server.poll_request(), Err(DapError::InvalidHeader(_))));
+    }
+
+    #[test]
+    fn deserializes_request_arguments() {
+        let set_breakpoints = request(
+            r#"{"seq":1,"type":"request","command":"setBreakpoints","arguments":{
+                "source":{"name":"main.nr","path":"/project/src/main.nr"},
+                "breakpoints":[{"line":4},{"line":7,"column":2}],
+                "sourceModified":false
+            }}"#,
+        );
+        let arguments: SetBreakpointsArguments = set_breakpoints.arguments().unwrap();
+        assert_eq!(arguments.source.path.as_deref(), Some("/project/src/main.nr"));
+        let lines: Vec<_> = arguments.breakpoints.iter().map(|point| point.line).collect();
+        assert_eq!(lines, [4, 7]);
+
+        let variables = request(
+            r#"{"seq":2,"type":"request","command":"variables","arguments":{
+                "variablesReference":1
+            }}"#,
+        );
+        let arguments: VariablesArguments = variables.arguments().unwrap();
+        assert_eq!(arguments.variables_reference, 1);
+
+        let step = request(
+            r#"{"seq":3,"type":"request","command":"next","arguments":{
+                "granularity":"instruction"
+            }}"#,
+        );
+        let arguments: StepArguments = step.arguments().unwrap();
+        assert_eq!(arguments.granularity, SteppingGranularity::Instruction);
+
+        // Missing arguments are treated as empty ones
+        let step = request(r#"{"seq":4,"type":"request","command":"stepIn"}"#);
+        let arguments: StepArguments = step.arguments().unwrap();
+        assert_eq!(arguments.granularity, SteppingGranularity::Statement);
+        assert!(step.arguments::<VariablesArguments>().is_err());
+    }
+
+    #[test]
+    fn serializes_responses_and_events() {
+        let stopped = serde_json::to_value(StoppedEventBody {
+            reason: "breakpoint".to_string(),
+            description: None,
+            text: None,
+            thread_id: 1,
+            all_threads_stopped: true,
+            hit_breakpoint_ids: vec![3],
+        })
+        .unwrap();
+        assert_eq!(
+            stopped,
+            json!({
+                "reason": "breakpoint",
+                "threadId": 1,
+                "allThreadsStopped": true,
+                "hitBreakpointIds": [3]
+            })
+        );
+
+        let stack_trace = serde_json::to_value(StackTraceResponseBody {
+            stack_frames: vec![StackFrame {
+                id: 0,
+                name: "assert(x != y);".to_string(),
+                source: Source { name: None, path: Some("/project/src/main.nr".to_string()) },
+                line: 4,
+                column: 5,
+            }],
+            total_frames: 1,
+        })
+        .unwrap();
+        assert_eq!(
+            stack_trace,
+            json!({
+                "stackFrames": [{
+                    "id": 0,
+                    "name": "assert(x != y);",
+                    "source": { "path": "/project/src/main.nr" },
+                    "line": 4,
+                    "column": 5
+                }],
+                "totalFrames": 1
+            })
+        );
+
+        let capabilities = serde_json::to_value(Capabilities {
+            supports_configuration_done_request: true,
+            supports_stepping_granularity: true,
+        })
+        .unwrap();
+        assert_eq!(
+            capabilities,
+            json!({ "supportsConfigurationDoneRequest": true, "supportsSteppingGranularity": true })
+        );
+    }
+
+    #[test]
+    fn sends_error_responses_and_events_without_bodies() {
+        let mut output = Vec::new();
+        let mut server = DapServer::new(&b""[..], &mut output);
+        let restart = request(r#"{"seq":5,"type":"request","command":"restart"}"#);
+        server.respond_error(&restart, "Unsupported request restart").unwrap();
+        server.send_event("terminated", ()).unwrap();
+
+        let output = String::from_utf8(output).unwrap();
+        let messages: Vec<Value> = output
+            .split("Content-Length: ")
+            .filter(|message| !message.is_empty())
+            .map(|message| serde_json::from_str(message.split_once("\r\n\r\n").unwrap().1).unwrap())
+            .collect();
+        assert_eq!(
+            messages,
+            [
+                json!({
+                    "seq": 1,
+                    "type": "response",
+                    "request_seq": 5,
+                    "success": false,
+                    "command": "restart",
+                    "message": "Unsupported request restart"
+                }),
+                json!({ "seq": 2, "type": "event", "event": "terminated" }),
+            ]
+        );
+    }
+}
@@ -1,4 +1,5 @@
 mod context;
+pub mod dap;
 mod repl;
 
 use acvm::BlackBoxFunctionSolver;
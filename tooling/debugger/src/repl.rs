@@ -1,8 +1,9 @@
-use crate::context::{DebugCommandResult, DebugContext};
+use crate::context::{DebugCommandResult, DebugContext, DebugVariable};
 
-use acvm::acir::circuit::{Opcode, OpcodeLocation};
-use acvm::BlackBoxFunctionSolver;
+use acvm::acir::circuit::{Opcode, OpcodeLocation, OpcodeLocationFromStrError};
+use acvm::acir::native_types::Witness;
 use acvm::{acir::circuit::Circuit, acir::native_types::WitnessMap};
+use acvm::{BlackBoxFunctionSolver, FieldElement};
 
 use nargo::artifacts::debug::DebugArtifact;
 use nargo::NargoError;
@@ -16,6 +17,39 @@ use noirc_errors::Location;
 use owo_colors::OwoColorize;
 
 use std::ops::Range;
+use std::str::FromStr;
+use thiserror::Error;
+
+/// Where a breakpoint should be set or deleted, given either as an opcode location such as `3`
+/// or `3.12`, or as a source line such as `src/main.nr:7`.
+enum BreakpointTarget {
+    Opcode(OpcodeLocation),
+    SourceLine { file: String, line: usize },
+}
+
+#[derive(Debug, Error)]
+enum BreakpointTargetFromStrError {
+    #[error("Invalid line number in breakpoint location: {0}")]
+    InvalidLineNumber(String),
+    #[error(transparent)]
+    InvalidOpcodeLocation(#[from] OpcodeLocationFromStrError),
+}
+
+impl FromStr for BreakpointTarget {
+    type Err = BreakpointTargetFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.rsplit_once(':') {
+            Some((file, line)) => {
+                let line = line
+                    .parse()
+                    .map_err(|_| BreakpointTargetFromStrError::InvalidLineNumber(s.to_string()))?;
+                Ok(BreakpointTarget::SourceLine { file: file.to_string(), line })
+            }
+            None => Ok(BreakpointTarget::Opcode(s.parse()?)),
+        }
+    }
+}
 
 pub struct ReplDebugger<'a, B: BlackBoxFunctionSolver> {
     context: DebugContext<'a, B>,
@@ -190,6 +224,111 @@ impl<'a, B: BlackBoxFunctionSolver> ReplDebugger<'a, B> {
         }
     }
 
+    fn show_variables(&self) {
+        let variables = self.context.get_variables();
+        if variables.is_empty() {
+            println!("No variables in scope");
+        }
+        for variable in variables {
+            print_variable(&variable);
+        }
+    }
+
+    fn print_variable(&self, name: String) {
+        let variables = self.context.get_variables();
+        match variables.iter().find(|variable| variable.name == name) {
+            Some(variable) => print_variable(variable),
+            None => println!("Unknown variable {name}"),
+        }
+    }
+
+    fn show_brillig_registers(&self) {
+        let Some(registers) = self.context.get_brillig_registers() else {
+            println!("Not executing a Brillig opcode");
+            return;
+        };
+        if registers.inner.is_empty() {
+            println!("No registers have been set");
+        }
+        for (index, value) in registers.inner.iter().enumerate() {
+            println!("{index} = {}", value.to_field());
+        }
+    }
+
+    fn show_brillig_memory(&self) {
+        let Some(memory) = self.context.get_brillig_memory() else {
+            println!("Not executing a Brillig opcode");
+            return;
+        };
+        if memory.is_empty() {
+            println!("Memory is empty");
+        }
+        for (address, value) in memory.iter().enumerate() {
+            println!("[{address}] = {}", value.to_field());
+        }
+    }
+
+    fn show_witness_map(&self) {
+        for (witness, value) in self.context.get_witness_map().clone() {
+            println!("_{} = {value}", witness.witness_index());
+        }
+    }
+
+    fn show_witness(&self, index: u32) {
+        match self.context.get_witness_map().get_index(index) {
+            Some(value) => println!("_{index} = {value}"),
+            None => println!("Witness _{index} has no value yet"),
+        }
+    }
+
+    fn update_witness(&mut self, index: u32, value: String) {
+        let Some(field_value) = FieldElement::try_from_str(&value) else {
+            println!("Invalid field value: {value}");
+            return;
+        };
+        self.context.overwrite_witness(Witness::from(index), field_value);
+        println!("_{index} = {field_value}");
+    }
+
+    /// Resolves a breakpoint target into the opcodes the breakpoint applies to. A source line
+    /// resolves to every opcode at which execution enters it, which includes every inlined copy
+    /// of the function it belongs to.
+    fn resolve_breakpoint_target(&self, target: BreakpointTarget) -> Vec<OpcodeLocation> {
+        let (file, line) = match target {
+            BreakpointTarget::Opcode(location) => return vec![location],
+            BreakpointTarget::SourceLine { file, line } => (file, line),
+        };
+        let file_ids = self.context.find_source_files(&file);
+        let file_id = match file_ids.as_slice() {
+            [file_id] => *file_id,
+            [] => {
+                println!("Unknown source file {file}");
+                return Vec::new();
+            }
+            _ => {
+                println!("Source file {file} is ambiguous, please give more of its path");
+                return Vec::new();
+            }
+        };
+        let locations = self.context.find_opcode_locations_at_line(file_id, line);
+        if locations.is_empty() {
+            println!("No opcodes were generated for {file}:{line}");
+        }
+        locations
+    }
+
+    fn add_breakpoint_at_target(&mut self, target: BreakpointTarget) {
+        for location in self.resolve_breakpoint_target(target) {
+            self.add_breakpoint_at(location);
+        }
+    }
+
+    fn delete_breakpoint_at_target(&mut self, target: BreakpointTarget) {
+        for location in self.resolve_breakpoint_target(target) {
+            self.delete_breakpoint_at(location);
+        }
+    }
+
     fn add_breakpoint_at(&mut self, location: OpcodeLocation) {
         if !self.context.is_valid_opcode_location(&location) {
             println!("Invalid opcode location {location}");
@@ -300,6 +439,11 @@ fn print_dimmed_line(line_number: usize, line: &str) {
     println!("{}", format!("{:>3} {:2} {}", line_number, "", line).dimmed());
 }
 
+fn print_variable(variable: &DebugVariable) {
+    let value = variable.display_value().unwrap_or_else(|| "<unknown>".to_string());
+    println!("{} = {}", variable.name, value);
+}
+
 pub fn run<B: BlackBoxFunctionSolver>(
     blackbox_solver: &B,
     circuit: &Circuit,
@@ -376,9 +520,9 @@ pub fn run<B: BlackBoxFunctionSolver>(
         .add(
             "break",
             command! {
-                "add a breakpoint at an opcode location",
-                (LOCATION:OpcodeLocation) => |location| {
-                    ref_context.borrow_mut().add_breakpoint_at(location);
+                "add a breakpoint at an opcode location or a source line (file:line)",
+                (LOCATION:BreakpointTarget) => |target| {
+                    ref_context.borrow_mut().add_breakpoint_at_target(target);
                     Ok(CommandStatus::Done)
                 }
             },
@@ -386,9 +530,79 @@ pub fn run<B: BlackBoxFunctionSolver>(
         .add(
             "delete",
             command! {
-                "delete breakpoint at an opcode location",
-                (LOCATION:OpcodeLocation) => |location| {
-                    ref_context.borrow_mut().delete_breakpoint_at(location);
+                "delete breakpoint at an opcode location or a source line (file:line)",
+                (LOCATION:BreakpointTarget) => |target| {
+                    ref_context.borrow_mut().delete_breakpoint_at_target(target);
+                    Ok(CommandStatus::Done)
+                }
+            },
+        )
+        .add(
+            "registers",
+            command! {
+                "display the Brillig VM registers",
+                () => || {
+                    ref_context.borrow().show_brillig_registers();
+                    Ok(CommandStatus::Done)
+                }
+            },
+        )
+        .add(
+            "memory",
+            command! {
+                "display the Brillig VM memory",
+                () => || {
+                    ref_context.borrow().show_brillig_memory();
+                    Ok(CommandStatus::Done)
+                }
+            },
+        )
+        .add(
+            "witness",
+            command! {
+                "display the current witness map",
+                () => || {
+                    ref_context.borrow().show_witness_map();
+                    Ok(CommandStatus::Done)
+                }
+            },
+        )
+        .add(
+            "witness",
+            command! {
+                "display the value of a witness",
+                (INDEX:u32) => |index| {
+                    ref_context.borrow().show_witness(index);
+                    Ok(CommandStatus::Done)
+                }
+            },
+        )
+        .add(
+            "witness",
+            command! {
+                "overwrite the value of a witness",
+                (INDEX:u32, VALUE:String) => |index, value| {
+                    ref_context.borrow_mut().update_witness(index, value);
+                    Ok(CommandStatus::Done)
+                }
+            },
+        )
+        .add(
+            "vars",
+            command! {
+                "display the variables of the current function",
+                () => || {
+                    ref_context.borrow().show_variables();
+                    Ok(CommandStatus::Done)
+                }
+            },
+        )
+        .add(
+            "print",
+            command! {
+                "display the value of a variable",
+                (NAME:String) => |name| {
+                    ref_context.borrow().print_variable(name);
                     Ok(CommandStatus::Done)
                 }
             },
@@ -2,10 +2,12 @@ use std::future::{self, Future};
 
 use async_lsp::{ErrorCode, ResponseError};
 use nargo::{
-    ops::{run_test, TestStatus},
+    ops::{run_test, FuzzConfig, TestStatus},
     prepare_package,
 };
-use nargo_toml::{find_package_manifest, resolve_workspace_from_toml, PackageSelection};
+use nargo_toml::{
+    find_package_manifest, resolve_workspace_from_toml, DependencyResolution, PackageSelection,
+};
 use noirc_driver::{check_crate, CompileOptions, NOIR_ARTIFACT_VERSION_STRING};
 use noirc_frontend::hir::FunctionNameMatch;
 
@@ -42,6 +44,7 @@ fn on_test_run_request_inner(
         &toml_path,
         PackageSelection::Selected(crate_name.clone()),
         Some(NOIR_ARTIFACT_VERSION_STRING.to_string()),
+        DependencyResolution::default(),
     )
     .map_err(|err| {
         // If we found a manifest, but the workspace is invalid, we raise an error about it
@@ -73,8 +76,16 @@ fn on_test_run_request_inner(
                 )
             })?;
 
-            let test_result =
-                run_test(&state.solver, &context, test_function, false, &CompileOptions::default());
+            let (test_result, _) = run_test(
+                &state.solver,
+                &context,
+                test_function,
+                false,
+                &CompileOptions::default(),
+                &FuzzConfig::default(),
+                None,
+                None,
+            );
             let result = match test_result {
                 TestStatus::Pass => NargoTestRunResult {
                     id: params.id.clone(),
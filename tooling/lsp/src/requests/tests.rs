@@ -3,7 +3,9 @@ use std::future::{self, Future};
 use async_lsp::{ErrorCode, LanguageClient, ResponseError};
 use lsp_types::{LogMessageParams, MessageType};
 use nargo::prepare_package;
-use nargo_toml::{find_package_manifest, resolve_workspace_from_toml, PackageSelection};
+use nargo_toml::{
+    find_package_manifest, resolve_workspace_from_toml, DependencyResolution, PackageSelection,
+};
 use noirc_driver::{check_crate, NOIR_ARTIFACT_VERSION_STRING};
 
 use crate::{
@@ -44,6 +46,7 @@ fn on_tests_request_inner(
         &toml_path,
         PackageSelection::All,
         Some(NOIR_ARTIFACT_VERSION_STRING.to_string()),
+        DependencyResolution::default(),
     )
     .map_err(|err| {
         // If we found a manifest, but the workspace is invalid, we raise an error about it
@@ -30,6 +30,10 @@ pub struct PreprocessedContract {
 pub struct PreprocessedContractFunction {
     pub name: String,
 
+    /// Hash of the program this function was compiled from, used to short-circuit compiling it
+    /// again if it hasn't changed since.
+    pub hash: u64,
+
     pub function_type: ContractFunctionType,
 
     pub is_internal: bool,
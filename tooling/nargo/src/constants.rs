@@ -21,3 +21,7 @@ pub const PKG_FILE: &str = "Nargo.toml";
 pub const PROOF_EXT: &str = "proof";
 /// The extension for files containing proof witnesses.
 pub const WITNESS_EXT: &str = "tr";
+/// The extension for files containing circuits in the iden3 R1CS binary format.
+pub const R1CS_EXT: &str = "r1cs";
+/// The extension for files containing witnesses in the iden3 binary format.
+pub const WTNS_EXT: &str = "wtns";
@@ -22,6 +22,10 @@ pub enum NargoError {
     /// Oracle handling error
     #[error(transparent)]
     ForeignCallError(#[from] ForeignCallError),
+
+    /// Error returned by the resolver of an oracle which nargo doesn't handle itself
+    #[error("Failed to resolve oracle `{0}`: {1}")]
+    OracleResolutionError(String, String),
 }
 
 impl From<acvm::compiler::CompileError> for NargoError {
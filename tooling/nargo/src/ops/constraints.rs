@@ -0,0 +1,437 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use acvm::acir::circuit::brillig::BrilligOutputs;
+use acvm::acir::circuit::directives::Directive;
+use acvm::acir::circuit::opcodes::BlackBoxFuncCall;
+use acvm::acir::circuit::{Circuit, Opcode, OpcodeLocation};
+use acvm::acir::native_types::{Expression, Witness};
+use acvm::FieldElement;
+use noirc_errors::{debug_info::DebugInfo, CustomDiagnostic, FileDiagnostic};
+use num_bigint::BigUint;
+
+/// How weakly the outputs of an unconstrained opcode are constrained.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ConstraintKind {
+    /// The witnesses aren't used by any constraint, other than possibly a range check
+    Unconstrained,
+    /// The witnesses are only used in linear terms of arithmetic constraints, which don't
+    /// determine their values
+    OnlyLinearlyConstrained,
+}
+
+/// Witnesses computed by a Brillig or directive opcode which the constraints of the circuit
+/// don't pin down, meaning that a malicious prover may be able to choose their values.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnderConstrainedWitnesses {
+    /// The index of the opcode which computes the witnesses
+    pub acir_index: usize,
+    /// The name of the opcode which computes the witnesses
+    pub opcode_name: String,
+    pub kind: ConstraintKind,
+    pub witnesses: Vec<Witness>,
+}
+
+impl UnderConstrainedWitnesses {
+    /// Creates a warning pointing at the source code which computes the witnesses, if known.
+    pub fn diagnostic(&self, debug: &DebugInfo) -> Option<FileDiagnostic> {
+        let call_stack = debug.opcode_location(&OpcodeLocation::Acir(self.acir_index))?;
+        let location = *call_stack.last()?;
+
+        let (outputs, verb) = match self.witnesses.len() {
+            1 => ("An output".to_owned(), "is"),
+            count => (format!("{count} outputs"), "are"),
+        };
+        let source = match self.opcode_name.as_str() {
+            "brillig" => "an unconstrained function".to_owned(),
+            directive => format!("the `{directive}` directive"),
+        };
+        let constrained = match self.kind {
+            ConstraintKind::Unconstrained => "never constrained",
+            ConstraintKind::OnlyLinearlyConstrained => "only linearly constrained",
+        };
+
+        let mut diagnostic = CustomDiagnostic::simple_warning(
+            format!("{outputs} of {source} {verb} {constrained}"),
+            "computed here".to_owned(),
+            location.span,
+        );
+        diagnostic.add_note(
+            "A prover may choose any values for the outputs of unconstrained code which satisfy the circuit's constraints".to_owned(),
+        );
+        Some(diagnostic.in_file(location.file).with_call_stack(call_stack))
+    }
+}
+
+/// Finds the witnesses computed by unconstrained code, that is Brillig opcodes and directives,
+/// which the rest of the circuit fails to constrain.
+///
+/// The analysis starts from the circuit's parameters and repeatedly marks the witnesses whose
+/// values are determined by those already marked: the single unknown witness of an arithmetic
+/// constraint, the outputs of black box functions, values read from memory at known indices,
+/// and the range checked witnesses of a decomposition such as `a = b0 + 2 * b1` whose ranges
+/// can't overlap. Witnesses computed by unconstrained code which are never marked are reported
+/// if they are used by no constraint, or only by linear terms of arithmetic constraints.
+/// Witnesses which are constrained non-linearly, such as the control bits of a sorting network,
+/// are assumed to be constrained correctly.
+pub fn find_under_constrained_witnesses(circuit: &Circuit) -> Vec<UnderConstrainedWitnesses> {
+    // The smallest number of bits each witness is range checked to
+    let mut range_checked: BTreeMap<Witness, u32> = BTreeMap::new();
+    for opcode in &circuit.opcodes {
+        if let Opcode::BlackBoxFuncCall(BlackBoxFuncCall::RANGE { input }) = opcode {
+            let num_bits = range_checked.entry(input.witness).or_insert(input.num_bits);
+            *num_bits = (*num_bits).min(input.num_bits);
+        }
+    }
+
+    let mut determined: BTreeSet<Witness> = circuit.circuit_arguments();
+    while determine_witnesses(circuit, &range_checked, &mut determined) {}
+
+    let mut under_constrained = Vec::new();
+    for (acir_index, opcode) in circuit.opcodes.iter().enumerate() {
+        let mut unconstrained = Vec::new();
+        let mut only_linearly_constrained = Vec::new();
+        for witness in unconstrained_outputs(opcode) {
+            if determined.contains(&witness) {
+                continue;
+            }
+            match constraint_kind(circuit, &determined, witness) {
+                Some(ConstraintKind::Unconstrained) => unconstrained.push(witness),
+                Some(ConstraintKind::OnlyLinearlyConstrained) => {
+                    only_linearly_constrained.push(witness);
+                }
+                None => {}
+            }
+        }
+
+        for (kind, witnesses) in [
+            (ConstraintKind::Unconstrained, unconstrained),
+            (ConstraintKind::OnlyLinearlyConstrained, only_linearly_constrained),
+        ] {
+            if !witnesses.is_empty() {
+                under_constrained.push(UnderConstrainedWitnesses {
+                    acir_index,
+                    opcode_name: opcode.name().to_owned(),
+                    kind,
+                    witnesses,
+                });
+            }
+        }
+    }
+    under_constrained
+}
+
+/// The witnesses computed by an opcode without any constraints being applied to them.
+fn unconstrained_outputs(opcode: &Opcode) -> Vec<Witness> {
+    match opcode {
+        Opcode::Brillig(brillig) => brillig
+            .outputs
+            .iter()
+            .flat_map(|output| match output {
+                BrilligOutputs::Simple(witness) => vec![*witness],
+                BrilligOutputs::Array(witnesses) => witnesses.clone(),
+            })
+            .collect(),
+        Opcode::Directive(Directive::Quotient(quotient)) => vec![quotient.q, quotient.r],
+        Opcode::Directive(Directive::ToLeRadix { b, .. }) => b.clone(),
+        Opcode::Directive(Directive::PermutationSort { bits, .. }) => bits.clone(),
+        _ => Vec::new(),
+    }
+}
+
+/// Marks any witnesses whose values are determined by those in `determined`, returning whether
+/// any were marked.
+fn determine_witnesses(
+    circuit: &Circuit,
+    range_checked: &BTreeMap<Witness, u32>,
+    determined: &mut BTreeSet<Witness>,
+) -> bool {
+    let mut changed = false;
+    for opcode in &circuit.opcodes {
+        let newly_determined = match opcode {
+            Opcode::Arithmetic(expr) => {
+                let unknowns = unknown_witnesses(expr, determined);
+                let solvable = match unknowns.as_slice() {
+                    [unknown] => !is_squared(expr, *unknown),
+                    [_, _, ..] => is_unique_decomposition(expr, &unknowns, range_checked),
+                    [] => false,
+                };
+                if solvable {
+                    unknowns
+                } else {
+                    Vec::new()
+                }
+            }
+            Opcode::BlackBoxFuncCall(call) => {
+                let inputs_determined =
+                    call.get_inputs_vec().iter().all(|input| determined.contains(&input.witness));
+                if inputs_determined {
+                    call.get_outputs_vec()
+                } else {
+                    Vec::new()
+                }
+            }
+            Opcode::MemoryOp { op, .. } if !is_write(&op.operation) => {
+                if expression_witnesses(&op.index).all(|witness| determined.contains(&witness)) {
+                    expression_witnesses(&op.value).collect()
+                } else {
+                    Vec::new()
+                }
+            }
+            _ => Vec::new(),
+        };
+
+        for witness in newly_determined {
+            changed |= determined.insert(witness);
+        }
+    }
+    changed
+}
+
+/// Returns how `witness` is constrained if it is only constrained weakly, or `None` if it is
+/// constrained in a way the analysis can't reason about.
+fn constraint_kind(
+    circuit: &Circuit,
+    determined: &BTreeSet<Witness>,
+    witness: Witness,
+) -> Option<ConstraintKind> {
+    let mut used = false;
+    for opcode in &circuit.opcodes {
+        match opcode {
+            Opcode::Arithmetic(expr) => {
+                let in_linear_term = expr.linear_combinations.iter().any(|(_, w)| *w == witness);
+                let mut in_mul_term = false;
+                for (_, lhs, rhs) in &expr.mul_terms {
+                    let other = match (*lhs == witness, *rhs == witness) {
+                        (true, true) => return None,
+                        (true, false) => rhs,
+                        (false, true) => lhs,
+                        (false, false) => continue,
+                    };
+                    // Multiplying by a known value is still linear in `witness`
+                    if !determined.contains(other) {
+                        return None;
+                    }
+                    in_mul_term = true;
+                }
+                used |= in_linear_term || in_mul_term;
+            }
+            // Range checks bound a witness without fixing its value
+            Opcode::BlackBoxFuncCall(BlackBoxFuncCall::RANGE { .. }) => {}
+            Opcode::BlackBoxFuncCall(call) => {
+                let inputs = call.get_inputs_vec();
+                if inputs.iter().any(|input| input.witness == witness)
+                    || call.get_outputs_vec().contains(&witness)
+                {
+                    return None;
+                }
+            }
+            Opcode::MemoryOp { op, predicate, .. } => {
+                let mut expressions =
+                    [&op.operation, &op.index, &op.value].into_iter().chain(predicate);
+                if expressions.any(|expr| expression_witnesses(expr).any(|w| w == witness)) {
+                    return None;
+                }
+            }
+            Opcode::MemoryInit { init, .. } => {
+                if init.contains(&witness) {
+                    return None;
+                }
+            }
+            Opcode::Brillig(_) | Opcode::Directive(_) => {}
+        }
+    }
+
+    Some(if used { ConstraintKind::OnlyLinearlyConstrained } else { ConstraintKind::Unconstrained })
+}
+
+fn expression_witnesses(expr: &Expression) -> impl Iterator<Item = Witness> + '_ {
+    let mul_witnesses = expr.mul_terms.iter().flat_map(|(_, lhs, rhs)| [*lhs, *rhs]);
+    let linear_witnesses = expr.linear_combinations.iter().map(|(_, witness)| *witness);
+    mul_witnesses.chain(linear_witnesses)
+}
+
+fn unknown_witnesses(expr: &Expression, determined: &BTreeSet<Witness>) -> Vec<Witness> {
+    let unknowns: BTreeSet<Witness> =
+        expression_witnesses(expr).filter(|witness| !determined.contains(witness)).collect();
+    unknowns.into_iter().collect()
+}
+
+/// Whether the linear combination of `unknowns` in `expr` has a single solution because each
+/// unknown is range checked and its coefficient is larger than any value the unknowns with
+/// smaller coefficients can add up to, as with the limbs of `a = b0 + 2^8 * b1` for 8 bit
+/// limbs. The combination must also be unable to wrap around the field's modulus.
+fn is_unique_decomposition(
+    expr: &Expression,
+    unknowns: &[Witness],
+    range_checked: &BTreeMap<Witness, u32>,
+) -> bool {
+    let in_mul_term = |witness: &Witness| {
+        expr.mul_terms.iter().any(|(_, lhs, rhs)| lhs == witness || rhs == witness)
+    };
+    if unknowns.iter().any(in_mul_term) {
+        return false;
+    }
+
+    let modulus = FieldElement::modulus();
+    let mut terms = Vec::new();
+    for unknown in unknowns {
+        let Some(num_bits) = range_checked.get(unknown) else {
+            return false;
+        };
+        let coefficient: FieldElement = expr
+            .linear_combinations
+            .iter()
+            .filter(|(_, witness)| witness == unknown)
+            .fold(FieldElement::zero(), |sum, (coefficient, _)| sum + *coefficient);
+        // A negative coefficient has the same magnitude as its negation
+        let coefficient = BigUint::from_bytes_be(&coefficient.to_be_bytes());
+        let magnitude = coefficient.clone().min(&modulus - coefficient);
+        terms.push((magnitude, *num_bits));
+    }
+    terms.sort();
+
+    // The largest magnitude of the combination of the terms checked so far
+    let mut bound = BigUint::from(0u32);
+    for (magnitude, num_bits) in terms {
+        if magnitude <= bound {
+            return false;
+        }
+        let max_value = (BigUint::from(1u32) << num_bits) - 1u32;
+        bound += magnitude * max_value;
+    }
+    bound < modulus
+}
+
+/// Whether `witness` is multiplied by itself in `expr`, in which case it has two solutions.
+fn is_squared(expr: &Expression, witness: Witness) -> bool {
+    expr.mul_terms.iter().any(|(_, lhs, rhs)| *lhs == witness && *rhs == witness)
+}
+
+fn is_write(operation: &Expression) -> bool {
+    operation.to_const().map_or(false, |operation| operation.is_one())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeSet;
+
+    use acvm::acir::brillig::{BinaryFieldOp, Opcode as BrilligOpcode, RegisterIndex};
+    use acvm::acir::circuit::brillig::{Brillig, BrilligInputs, BrilligOutputs};
+    use acvm::acir::circuit::directives::{Directive, QuotientDirective};
+    use acvm::acir::circuit::opcodes::{BlackBoxFuncCall, FunctionInput};
+    use acvm::acir::circuit::{Circuit, Opcode};
+    use acvm::acir::native_types::{Expression, Witness};
+    use acvm::FieldElement;
+
+    use super::{find_under_constrained_witnesses, ConstraintKind};
+
+    fn inverse(input: Witness, output: Witness) -> Opcode {
+        Opcode::Brillig(Brillig {
+            inputs: vec![BrilligInputs::Single(input.into())],
+            outputs: vec![BrilligOutputs::Simple(output)],
+            bytecode: vec![BrilligOpcode::BinaryFieldOp {
+                op: BinaryFieldOp::Div,
+                lhs: RegisterIndex::from(0),
+                rhs: RegisterIndex::from(0),
+                destination: RegisterIndex::from(0),
+            }],
+            predicate: None,
+        })
+    }
+
+    fn circuit(opcodes: Vec<Opcode>) -> Circuit {
+        Circuit {
+            current_witness_index: 10,
+            opcodes,
+            private_parameters: BTreeSet::from([Witness(1)]),
+            ..Circuit::default()
+        }
+    }
+
+    #[test]
+    fn reports_unused_outputs() {
+        let report =
+            find_under_constrained_witnesses(&circuit(vec![inverse(Witness(1), Witness(2))]));
+
+        assert_eq!(report.len(), 1);
+        assert_eq!(report[0].acir_index, 0);
+        assert_eq!(report[0].kind, ConstraintKind::Unconstrained);
+        assert_eq!(report[0].witnesses, vec![Witness(2)]);
+    }
+
+    #[test]
+    fn accepts_outputs_determined_by_a_constraint() {
+        // x * inverse - 1 = 0
+        let constraint = Expression {
+            mul_terms: vec![(FieldElement::one(), Witness(1), Witness(2))],
+            linear_combinations: Vec::new(),
+            q_c: -FieldElement::one(),
+        };
+        let report = find_under_constrained_witnesses(&circuit(vec![
+            inverse(Witness(1), Witness(2)),
+            Opcode::Arithmetic(constraint),
+        ]));
+
+        assert!(report.is_empty());
+    }
+
+    /// Computes `x = divisor * q + r` in a directive and constrains it without bounding `q` or `r`
+    fn quotient(divisor: u128) -> Vec<Opcode> {
+        let quotient = Directive::Quotient(QuotientDirective {
+            a: Witness(1).into(),
+            b: FieldElement::from(divisor).into(),
+            q: Witness(2),
+            r: Witness(3),
+            predicate: None,
+        });
+        let constraint = Expression {
+            mul_terms: Vec::new(),
+            linear_combinations: vec![
+                (FieldElement::one(), Witness(1)),
+                (-FieldElement::from(divisor), Witness(2)),
+                (-FieldElement::one(), Witness(3)),
+            ],
+            q_c: FieldElement::zero(),
+        };
+        vec![Opcode::Directive(quotient), Opcode::Arithmetic(constraint)]
+    }
+
+    fn range_check(witness: Witness, num_bits: u32) -> Opcode {
+        Opcode::BlackBoxFuncCall(BlackBoxFuncCall::RANGE {
+            input: FunctionInput { witness, num_bits },
+        })
+    }
+
+    #[test]
+    fn reports_quotients_without_range_checks() {
+        let report = find_under_constrained_witnesses(&circuit(quotient(8)));
+        assert_eq!(report.len(), 1);
+        assert_eq!(report[0].kind, ConstraintKind::OnlyLinearlyConstrained);
+        assert_eq!(report[0].witnesses, vec![Witness(2), Witness(3)]);
+
+        // r < 8 = b, so each x has a single q and r
+        let mut opcodes = quotient(8);
+        opcodes.push(range_check(Witness(2), 32));
+        opcodes.push(range_check(Witness(3), 3));
+        assert!(find_under_constrained_witnesses(&circuit(opcodes)).is_empty());
+    }
+
+    #[test]
+    fn reports_decompositions_with_overlapping_ranges() {
+        // r < 8 allows both (q, r) and (q + 1, r - 7) when b = 7
+        let mut opcodes = quotient(7);
+        opcodes.push(range_check(Witness(2), 32));
+        opcodes.push(range_check(Witness(3), 3));
+        let report = find_under_constrained_witnesses(&circuit(opcodes));
+        assert_eq!(report.len(), 1);
+        assert_eq!(report[0].witnesses, vec![Witness(2), Witness(3)]);
+
+        // 8 * q + r can wrap around the field's modulus when q has 252 bits
+        let mut opcodes = quotient(8);
+        opcodes.push(range_check(Witness(2), 252));
+        opcodes.push(range_check(Witness(3), 3));
+        let report = find_under_constrained_witnesses(&circuit(opcodes));
+        assert_eq!(report.len(), 1);
+        assert_eq!(report[0].witnesses, vec![Witness(2), Witness(3)]);
+    }
+}
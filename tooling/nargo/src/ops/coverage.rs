@@ -0,0 +1,142 @@
+use std::collections::BTreeMap;
+
+use acvm::acir::circuit::OpcodeLocation;
+use noirc_errors::debug_info::DebugInfo;
+
+use crate::artifacts::debug::DebugArtifact;
+
+use super::profile::{source_line, ExecutionProfile};
+
+/// The source lines exercised while executing one or more circuits, as recorded by
+/// [`profile_circuit`][super::profile_circuit].
+///
+/// Only lines which generated ACIR or Brillig opcodes are known to the report. Code which was
+/// optimized away, or which belongs to functions which were never compiled, doesn't appear in it.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct CoverageReport {
+    /// The number of times each line was executed, keyed by file path and then line number
+    pub files: BTreeMap<String, BTreeMap<usize, usize>>,
+}
+
+impl CoverageReport {
+    /// Records which lines of a circuit were executed according to `profile`.
+    ///
+    /// A line counts as executed whenever an opcode generated by it, or by a function called from
+    /// it, is solved. Its execution count is that of its most executed opcode.
+    pub fn add(
+        &mut self,
+        debug_artifact: &DebugArtifact,
+        debug_info: &DebugInfo,
+        profile: &ExecutionProfile,
+    ) {
+        let mut lines: BTreeMap<(String, usize), usize> = BTreeMap::new();
+        for (location, call_stack) in &debug_info.locations {
+            let executions = match location {
+                OpcodeLocation::Acir(acir_index) => {
+                    usize::from(profile.solved_acir_opcodes.contains(acir_index))
+                }
+                OpcodeLocation::Brillig { .. } => {
+                    profile.brillig_steps.get(location).copied().unwrap_or_default()
+                }
+            };
+
+            for frame in call_stack {
+                if let Some(line) = source_line(debug_artifact, *frame) {
+                    let line_executions = lines.entry(line).or_default();
+                    *line_executions = (*line_executions).max(executions);
+                }
+            }
+        }
+
+        for ((path, line), executions) in lines {
+            *self.files.entry(path).or_default().entry(line).or_default() += executions;
+        }
+    }
+
+    /// Adds the executions recorded in `other` to this report.
+    pub fn merge(&mut self, other: CoverageReport) {
+        for (path, lines) in other.files {
+            let file = self.files.entry(path).or_default();
+            for (line, executions) in lines {
+                *file.entry(line).or_default() += executions;
+            }
+        }
+    }
+
+    /// Returns the number of lines known to the report along with how many of them were executed.
+    pub fn line_counts(&self) -> (usize, usize) {
+        let lines = self.files.values().flat_map(|lines| lines.values());
+        let found = lines.clone().count();
+        let hit = lines.filter(|executions| **executions > 0).count();
+        (found, hit)
+    }
+
+    /// Renders the report in the lcov tracefile format, as understood by `genhtml` and most
+    /// coverage services.
+    pub fn to_lcov(&self) -> String {
+        let mut lcov = String::new();
+        for (path, lines) in &self.files {
+            lcov.push_str("TN:\n");
+            lcov.push_str(&format!("SF:{path}\n"));
+            for (line, executions) in lines {
+                lcov.push_str(&format!("DA:{line},{executions}\n"));
+            }
+            let hit = lines.values().filter(|executions| **executions > 0).count();
+            lcov.push_str(&format!("LF:{}\nLH:{hit}\n", lines.len()));
+            lcov.push_str("end_of_record\n");
+        }
+        lcov
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::{BTreeMap, BTreeSet};
+
+    use acvm::acir::circuit::OpcodeLocation;
+    use fm::FileId;
+    use noirc_driver::DebugFile;
+    use noirc_errors::{debug_info::DebugInfo, Location, Span};
+
+    use super::CoverageReport;
+    use crate::artifacts::debug::DebugArtifact;
+    use crate::ops::ExecutionProfile;
+
+    #[test]
+    fn records_executed_lines_and_their_callers() {
+        let source = "fn main() {\n    foo();\n    bar();\n}\nfn foo() {\n    baz();\n}\n";
+        let file = FileId::dummy();
+        let file_map = BTreeMap::from([(
+            file,
+            DebugFile { source: source.to_owned(), path: "src/main.nr".into() },
+        )]);
+        let debug_artifact =
+            DebugArtifact { debug_symbols: Vec::new(), file_map, warnings: vec![] };
+
+        let at_line = |line: usize| {
+            let start = source.lines().take(line - 1).map(|line| line.len() + 1).sum::<usize>();
+            Location::new(Span::single_char(start as u32), file)
+        };
+
+        let brillig_location = OpcodeLocation::Brillig { acir_index: 1, brillig_index: 3 };
+        let debug_info = DebugInfo::new(BTreeMap::from([
+            (OpcodeLocation::Acir(0), vec![at_line(2), at_line(6)]),
+            (OpcodeLocation::Acir(2), vec![at_line(3)]),
+            (brillig_location, vec![at_line(2)]),
+        ]));
+        let profile = ExecutionProfile {
+            solved_acir_opcodes: BTreeSet::from([0]),
+            brillig_steps: BTreeMap::from([(brillig_location, 4)]),
+        };
+
+        let mut report = CoverageReport::default();
+        report.add(&debug_artifact, &debug_info, &profile);
+        report.merge(report.clone());
+
+        assert_eq!(report.line_counts(), (3, 2));
+        assert_eq!(
+            report.to_lcov(),
+            "TN:\nSF:src/main.nr\nDA:2,8\nDA:3,0\nDA:6,2\nLF:3\nLH:2\nend_of_record\n"
+        );
+    }
+}
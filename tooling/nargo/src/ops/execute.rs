@@ -5,17 +5,35 @@ use acvm::{acir::circuit::Circuit, acir::native_types::WitnessMap};
 use crate::errors::ExecutionError;
 use crate::NargoError;
 
-use super::foreign_calls::ForeignCallExecutor;
+use super::foreign_calls::{ForeignCallExecutor, OracleResolver};
 
 pub fn execute_circuit<B: BlackBoxFunctionSolver>(
     blackbox_solver: &B,
     circuit: &Circuit,
     initial_witness: WitnessMap,
     show_output: bool,
+    oracle_resolver: Option<&mut dyn OracleResolver>,
 ) -> Result<WitnessMap, NargoError> {
-    let mut acvm = ACVM::new(blackbox_solver, &circuit.opcodes, initial_witness);
+    let mut foreign_call_executor = ForeignCallExecutor::new(oracle_resolver);
+    execute_circuit_with_executor(
+        blackbox_solver,
+        circuit,
+        initial_witness,
+        show_output,
+        &mut foreign_call_executor,
+    )
+}
 
-    let mut foreign_call_executor = ForeignCallExecutor::default();
+/// Executes a circuit as [`execute_circuit`] does, resolving its foreign calls with
+/// `foreign_call_executor`.
+pub(crate) fn execute_circuit_with_executor<B: BlackBoxFunctionSolver>(
+    blackbox_solver: &B,
+    circuit: &Circuit,
+    initial_witness: WitnessMap,
+    show_output: bool,
+    foreign_call_executor: &mut ForeignCallExecutor,
+) -> Result<WitnessMap, NargoError> {
+    let mut acvm = ACVM::new(blackbox_solver, &circuit.opcodes, initial_witness);
 
     loop {
         let solver_status = acvm.solve();
@@ -25,30 +43,7 @@ pub fn execute_circuit<B: BlackBoxFunctionSolver>(
             ACVMStatus::InProgress => {
                 unreachable!("Execution should not stop while in `InProgress` state.")
             }
-            ACVMStatus::Failure(error) => {
-                let call_stack = match &error {
-                    OpcodeResolutionError::UnsatisfiedConstrain {
-                        opcode_location: ErrorLocation::Resolved(opcode_location),
-                    } => Some(vec![*opcode_location]),
-                    OpcodeResolutionError::BrilligFunctionFailed { call_stack, .. } => {
-                        Some(call_stack.clone())
-                    }
-                    _ => None,
-                };
-
-                return Err(NargoError::ExecutionError(match call_stack {
-                    Some(call_stack) => {
-                        if let Some(assert_message) = circuit.get_assert_message(
-                            *call_stack.last().expect("Call stacks should not be empty"),
-                        ) {
-                            ExecutionError::AssertionFailed(assert_message.to_owned(), call_stack)
-                        } else {
-                            ExecutionError::SolvingError(error)
-                        }
-                    }
-                    None => ExecutionError::SolvingError(error),
-                }));
-            }
+            ACVMStatus::Failure(error) => return Err(resolve_failure(circuit, error)),
             ACVMStatus::RequiresForeignCall(foreign_call) => {
                 let foreign_call_result =
                     foreign_call_executor.execute(&foreign_call, show_output)?;
@@ -60,3 +55,28 @@ pub fn execute_circuit<B: BlackBoxFunctionSolver>(
     let solved_witness = acvm.finalize();
     Ok(solved_witness)
 }
+
+/// Converts an error encountered while solving the circuit into a [`NargoError`],
+/// attaching the assertion message and call stack of the failing opcode when these are known.
+pub(crate) fn resolve_failure(circuit: &Circuit, error: OpcodeResolutionError) -> NargoError {
+    let call_stack = match &error {
+        OpcodeResolutionError::UnsatisfiedConstrain {
+            opcode_location: ErrorLocation::Resolved(opcode_location),
+        } => Some(vec![*opcode_location]),
+        OpcodeResolutionError::BrilligFunctionFailed { call_stack, .. } => Some(call_stack.clone()),
+        _ => None,
+    };
+
+    NargoError::ExecutionError(match call_stack {
+        Some(call_stack) => {
+            if let Some(assert_message) = circuit
+                .get_assert_message(*call_stack.last().expect("Call stacks should not be empty"))
+            {
+                ExecutionError::AssertionFailed(assert_message.to_owned(), call_stack)
+            } else {
+                ExecutionError::SolvingError(error)
+            }
+        }
+        None => ExecutionError::SolvingError(error),
+    })
+}
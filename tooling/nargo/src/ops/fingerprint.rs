@@ -0,0 +1,170 @@
+use std::hash::{Hash, Hasher};
+use std::path::{Path, PathBuf};
+
+use acvm::Language;
+use fxhash::FxHasher64;
+use noirc_driver::{CompileOptions, NOIR_ARTIFACT_VERSION_STRING};
+
+use crate::package::{Dependency, Package};
+
+/// Hashes everything which compiling `package` depends on: the version of the compiler, the
+/// options and language it compiles with, and the source files of the package and of each of its
+/// dependencies. The stdlib is embedded in the compiler so it is covered by its version.
+///
+/// Compiling packages with the same fingerprint produces the same artifacts, so a package doesn't
+/// need to be processed by the compiler at all as long as its fingerprint is unchanged.
+pub fn fingerprint_package(
+    package: &Package,
+    options: &CompileOptions,
+    np_language: Language,
+) -> u64 {
+    let mut hasher = FxHasher64::default();
+    NOIR_ARTIFACT_VERSION_STRING.hash(&mut hasher);
+    hash_compile_options(options, &mut hasher);
+    match np_language {
+        Language::R1CS => 0u8.hash(&mut hasher),
+        Language::PLONKCSat { width } => (1u8, width).hash(&mut hasher),
+    }
+    hash_package(package, &mut hasher);
+    hasher.finish()
+}
+
+/// Hashes the options which change the artifacts a package compiles to.
+///
+/// Options which only print information, such as `show_ssa`, aren't hashed as packages are always
+/// compiled again when they are set, see [`CompileOptions::force_compile`]. Neither are the
+/// options denying or silencing warnings, as packages with warnings aren't fingerprinted.
+fn hash_compile_options(options: &CompileOptions, hasher: &mut FxHasher64) {
+    let CompileOptions { ssa_passes, instrument_debug, .. } = options;
+    ssa_passes.hash(hasher);
+    instrument_debug.hash(hasher);
+}
+
+fn hash_package(package: &Package, hasher: &mut FxHasher64) {
+    package.package_type.to_string().hash(hasher);
+    package.entry_path.strip_prefix(&package.root_dir).unwrap_or(&package.entry_path).hash(hasher);
+
+    // Modules are resolved relative to the entry file, so any file a package can use is found
+    // in the directory containing it.
+    if let Some(source_dir) = package.entry_path.parent() {
+        let mut source_files = Vec::new();
+        collect_source_files(source_dir, &mut source_files);
+        source_files.sort();
+
+        for path in source_files {
+            path.strip_prefix(source_dir).unwrap_or(&path).hash(hasher);
+            std::fs::read(&path).unwrap_or_default().hash(hasher);
+        }
+    }
+
+    for (name, dependency) in &package.dependencies {
+        name.to_string().hash(hasher);
+        match dependency {
+            Dependency::Local { package } | Dependency::Remote { package } => {
+                hash_package(package, hasher);
+            }
+        }
+    }
+}
+
+fn collect_source_files(dir: &Path, source_files: &mut Vec<PathBuf>) {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return;
+    };
+    for path in entries.flatten().map(|entry| entry.path()) {
+        if path.is_dir() {
+            collect_source_files(&path, source_files);
+        } else if path.extension().map_or(false, |extension| extension == "nr") {
+            source_files.push(path);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use acvm::Language;
+    use noirc_driver::CompileOptions;
+
+    use super::fingerprint_package;
+    use crate::package::{Dependency, Package, PackageType};
+
+    fn write_package(root_dir: &Path, name: &str, dependencies: Vec<Package>) -> Package {
+        std::fs::create_dir_all(root_dir.join("src/foo")).unwrap();
+        std::fs::write(root_dir.join("src/main.nr"), "mod foo;\nfn main() {}\n").unwrap();
+        std::fs::write(root_dir.join("src/foo.nr"), "mod bar;\n").unwrap();
+        std::fs::write(root_dir.join("src/foo/bar.nr"), "fn bar() {}\n").unwrap();
+        Package {
+            compiler_required_version: None,
+            root_dir: root_dir.to_path_buf(),
+            package_type: PackageType::Binary,
+            entry_path: root_dir.join("src/main.nr"),
+            name: name.parse().unwrap(),
+            dependencies: dependencies
+                .into_iter()
+                .map(|package| (package.name.clone(), Dependency::Local { package }))
+                .collect(),
+        }
+    }
+
+    fn fingerprint(package: &Package) -> u64 {
+        fingerprint_package(package, &CompileOptions::default(), Language::R1CS)
+    }
+
+    #[test]
+    fn changes_with_the_sources_of_the_package() {
+        let dir = tempfile::tempdir().unwrap();
+        let package = write_package(dir.path(), "main", Vec::new());
+        let original = fingerprint(&package);
+        assert_eq!(fingerprint(&package), original);
+
+        std::fs::write(dir.path().join("src/foo/bar.nr"), "fn bar() { }\n").unwrap();
+        let edited = fingerprint(&package);
+        assert_ne!(edited, original);
+
+        std::fs::write(dir.path().join("src/foo/baz.nr"), "fn baz() {}\n").unwrap();
+        let added = fingerprint(&package);
+        assert_ne!(added, edited);
+
+        // Only Noir source files can be used by the package
+        std::fs::write(dir.path().join("src/notes.txt"), "not noir").unwrap();
+        std::fs::write(dir.path().join("Prover.toml"), "x = 1").unwrap();
+        assert_eq!(fingerprint(&package), added);
+    }
+
+    #[test]
+    fn changes_with_the_sources_of_dependencies() {
+        let dir = tempfile::tempdir().unwrap();
+        let dependency = write_package(&dir.path().join("dep"), "dep", Vec::new());
+        let package = write_package(&dir.path().join("main"), "main", vec![dependency]);
+        let original = fingerprint(&package);
+
+        std::fs::write(dir.path().join("dep/src/foo.nr"), "mod bar;\nfn foo() {}\n").unwrap();
+        assert_ne!(fingerprint(&package), original);
+    }
+
+    #[test]
+    fn changes_with_the_options_affecting_artifacts() {
+        let dir = tempfile::tempdir().unwrap();
+        let package = write_package(dir.path(), "main", Vec::new());
+        let options = CompileOptions::default();
+        let original = fingerprint_package(&package, &options, Language::R1CS);
+
+        let plonk = Language::PLONKCSat { width: 3 };
+        assert_ne!(fingerprint_package(&package, &options, plonk), original);
+
+        let instrumented = CompileOptions { instrument_debug: true, ..CompileOptions::default() };
+        assert_ne!(fingerprint_package(&package, &instrumented, Language::R1CS), original);
+
+        let pipeline = "inline,mem2reg".parse().unwrap();
+        let ssa_passes = CompileOptions { ssa_passes: Some(pipeline), ..CompileOptions::default() };
+        assert_ne!(fingerprint_package(&package, &ssa_passes, Language::R1CS), original);
+
+        // Options which don't change the artifacts don't change the fingerprint
+        let silenced = CompileOptions { silence_warnings: true, ..CompileOptions::default() };
+        assert_eq!(fingerprint_package(&package, &silenced, Language::R1CS), original);
+        let printed = CompileOptions { print_acir: true, ..CompileOptions::default() };
+        assert_eq!(fingerprint_package(&package, &printed, Language::R1CS), original);
+    }
+}
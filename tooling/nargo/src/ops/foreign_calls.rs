@@ -1,3 +1,5 @@
+use std::fmt::Write;
+
 use acvm::{
     acir::brillig::{ForeignCallParam, ForeignCallResult, Value},
     pwg::ForeignCallWaitInfo,
@@ -88,15 +90,41 @@ impl MockedCall {
     }
 }
 
+/// Resolves the foreign calls which are neither natively supported by nargo nor mocked,
+/// e.g. by forwarding them to an external service.
+pub trait OracleResolver: std::fmt::Debug {
+    /// Returns the result of `foreign_call`, or a message describing why it couldn't be resolved.
+    fn resolve(&mut self, foreign_call: &ForeignCallWaitInfo) -> Result<ForeignCallResult, String>;
+}
+
 #[derive(Debug, Default)]
-pub struct ForeignCallExecutor {
+pub struct ForeignCallExecutor<'a> {
     /// Mocks have unique ids used to identify them in Noir, allowing to update or remove them.
     last_mock_id: usize,
     /// The registered mocks
     mocked_responses: Vec<MockedCall>,
+    /// Resolves any foreign calls which are unknown to the executor
+    oracle_resolver: Option<&'a mut dyn OracleResolver>,
+    /// The output of `println` calls, if it is captured rather than printed
+    captured_output: Option<String>,
 }
 
-impl ForeignCallExecutor {
+impl<'a> ForeignCallExecutor<'a> {
+    pub fn new(oracle_resolver: Option<&'a mut dyn OracleResolver>) -> Self {
+        ForeignCallExecutor { oracle_resolver, ..ForeignCallExecutor::default() }
+    }
+
+    /// Captures the output of `println` calls rather than printing it, so that it can be
+    /// retrieved with [`take_captured_output`][Self::take_captured_output].
+    pub fn capture_output(&mut self) {
+        self.captured_output = Some(String::new());
+    }
+
+    /// Returns the output captured since [`capture_output`][Self::capture_output] was called.
+    pub fn take_captured_output(&mut self) -> String {
+        self.captured_output.take().unwrap_or_default()
+    }
+
     pub fn execute(
         &mut self,
         foreign_call: &ForeignCallWaitInfo,
@@ -106,7 +134,7 @@ impl ForeignCallExecutor {
         match ForeignCall::lookup(foreign_call_name) {
             Some(ForeignCall::Println) => {
                 if show_output {
-                    Self::execute_println(&foreign_call.inputs)?;
+                    self.execute_println(&foreign_call.inputs)?;
                 }
                 Ok(ForeignCallResult { values: vec![] })
             }
@@ -182,8 +210,18 @@ impl ForeignCallExecutor {
                 let response_position = self
                     .mocked_responses
                     .iter()
-                    .position(|response| response.matches(foreign_call_name, &foreign_call.inputs))
-                    .unwrap_or_else(|| panic!("Unknown foreign call {}", foreign_call_name));
+                    .position(|response| response.matches(foreign_call_name, &foreign_call.inputs));
+
+                // Mocks take precedence over the oracle resolver so that tests can override it.
+                let response_position = match (response_position, &mut self.oracle_resolver) {
+                    (Some(response_position), _) => response_position,
+                    (None, Some(oracle_resolver)) => {
+                        return oracle_resolver.resolve(foreign_call).map_err(|message| {
+                            NargoError::OracleResolutionError(foreign_call_name.to_owned(), message)
+                        });
+                    }
+                    (None, None) => panic!("Unknown foreign call {}", foreign_call_name),
+                };
 
                 let mock = self
                     .mocked_responses
@@ -220,9 +258,17 @@ impl ForeignCallExecutor {
         decode_string_value(&fields)
     }
 
-    fn execute_println(foreign_call_inputs: &[ForeignCallParam]) -> Result<(), NargoError> {
+    fn execute_println(
+        &mut self,
+        foreign_call_inputs: &[ForeignCallParam],
+    ) -> Result<(), NargoError> {
         let display_values: PrintableValueDisplay = foreign_call_inputs.try_into()?;
-        println!("{display_values}");
+        match &mut self.captured_output {
+            Some(output) => {
+                writeln!(output, "{display_values}").expect("writing to a String cannot fail");
+            }
+            None => println!("{display_values}"),
+        }
         Ok(())
     }
 }
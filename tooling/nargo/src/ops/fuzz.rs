@@ -0,0 +1,347 @@
+use acvm::FieldElement;
+use noirc_abi::{input_parser::InputValue, Abi, AbiType, InputMap, Sign};
+use rand::Rng;
+
+/// The number of times a test function which takes parameters is run, each time with new inputs,
+/// if not otherwise specified.
+pub const DEFAULT_FUZZ_RUNS: u32 = 100;
+
+/// The maximum number of times a failing test is run again while shrinking its inputs.
+const MAX_SHRINK_RUNS: usize = 1000;
+
+/// Controls how the inputs of test functions which take parameters are generated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FuzzConfig {
+    /// The number of times to run each test, each time with new inputs
+    pub runs: u32,
+    /// The seed from which inputs are generated, so that a failure can be reproduced
+    pub seed: u64,
+}
+
+impl FuzzConfig {
+    /// Creates a config which generates inputs from `seed`, or from a random seed if there is none.
+    pub fn new(runs: u32, seed: Option<u64>) -> Self {
+        FuzzConfig { runs, seed: seed.unwrap_or_else(rand::random) }
+    }
+}
+
+impl Default for FuzzConfig {
+    fn default() -> Self {
+        FuzzConfig::new(DEFAULT_FUZZ_RUNS, None)
+    }
+}
+
+/// Generates a random value for each of the parameters in `abi`.
+///
+/// Values are biased towards the edge cases of their types, such as zero and the maximum value
+/// of an integer, as these are where bugs tend to be.
+pub(crate) fn generate_inputs(abi: &Abi, rng: &mut impl Rng) -> InputMap {
+    abi.parameters
+        .iter()
+        .map(|param| (param.name.clone(), generate_value(&param.typ, rng)))
+        .collect()
+}
+
+fn generate_value(typ: &AbiType, rng: &mut impl Rng) -> InputValue {
+    match typ {
+        AbiType::Field => {
+            let edge_cases = [FieldElement::zero(), FieldElement::one(), -FieldElement::one()];
+            InputValue::Field(pick_edge_case_or(&edge_cases, rng, |rng| {
+                random_bits(FieldElement::max_num_bits(), rng)
+            }))
+        }
+        AbiType::Integer { sign, width } => {
+            let edge_cases = integer_edge_cases(sign, *width);
+            InputValue::Field(pick_edge_case_or(&edge_cases, rng, |rng| random_bits(*width, rng)))
+        }
+        AbiType::Boolean => InputValue::Field(FieldElement::from(rng.gen::<bool>())),
+        AbiType::Array { length, typ } => {
+            InputValue::Vec((0..*length).map(|_| generate_value(typ, rng)).collect())
+        }
+        AbiType::String { length } => {
+            InputValue::String((0..*length).map(|_| rng.gen_range(' '..='~')).collect())
+        }
+        AbiType::Struct { fields, .. } => InputValue::Struct(
+            fields.iter().map(|(name, typ)| (name.clone(), generate_value(typ, rng))).collect(),
+        ),
+        AbiType::Tuple { fields } => {
+            InputValue::Vec(fields.iter().map(|typ| generate_value(typ, rng)).collect())
+        }
+        AbiType::Enum { variants, .. } => {
+            let (name, fields) = &variants[rng.gen_range(0..variants.len())];
+            InputValue::Variant(
+                name.clone(),
+                fields.iter().map(|typ| generate_value(typ, rng)).collect(),
+            )
+        }
+    }
+}
+
+/// Picks one of `edge_cases` a quarter of the time, otherwise generates a value with `generate`.
+fn pick_edge_case_or<R: Rng>(
+    edge_cases: &[FieldElement],
+    rng: &mut R,
+    generate: impl FnOnce(&mut R) -> FieldElement,
+) -> FieldElement {
+    if rng.gen_ratio(1, 4) {
+        edge_cases[rng.gen_range(0..edge_cases.len())]
+    } else {
+        generate(rng)
+    }
+}
+
+/// Returns the boundaries of an integer type, encoded as they are in the ABI.
+fn integer_edge_cases(sign: &Sign, width: u32) -> Vec<FieldElement> {
+    let max = two_pow(width) - FieldElement::one();
+    match sign {
+        Sign::Unsigned => vec![FieldElement::zero(), FieldElement::one(), max],
+        // Negative values are encoded in two's complement, so `max` is -1.
+        Sign::Signed => vec![
+            FieldElement::zero(),
+            FieldElement::one(),
+            max,
+            two_pow(width - 1) - FieldElement::one(),
+            two_pow(width - 1),
+        ],
+    }
+}
+
+/// Generates a value of at most `width` bits, reduced into the field if it doesn't fit.
+fn random_bits(width: u32, rng: &mut impl Rng) -> FieldElement {
+    let mut bytes = vec![0u8; FieldElement::max_num_bytes() as usize];
+    rng.fill_bytes(&mut bytes);
+    for (index, byte) in bytes.iter_mut().rev().enumerate() {
+        let lowest_bit = 8 * index as u32;
+        if lowest_bit >= width {
+            *byte = 0;
+        } else if width - lowest_bit < 8 {
+            *byte &= (1 << (width - lowest_bit)) - 1;
+        }
+    }
+    FieldElement::from_be_bytes_reduce(&bytes)
+}
+
+fn two_pow(exponent: u32) -> FieldElement {
+    FieldElement::from(2u128).pow(&FieldElement::from(exponent as u128))
+}
+
+/// Halves `value` as an integer, rounding down.
+fn halve(value: FieldElement) -> FieldElement {
+    let mut carry = 0;
+    let bytes: Vec<u8> = value
+        .to_be_bytes()
+        .into_iter()
+        .map(|byte| {
+            let halved = (byte >> 1) | (carry << 7);
+            carry = byte & 1;
+            halved
+        })
+        .collect();
+    FieldElement::from_be_bytes_reduce(&bytes)
+}
+
+/// Simplifies the inputs of a failing test for as long as it keeps failing, so that the failure
+/// can be reported for inputs which are easier to reason about.
+///
+/// `run` runs the test with the given inputs, returning its failure if it still fails.
+pub(crate) fn shrink_inputs<F>(
+    abi: &Abi,
+    mut inputs: InputMap,
+    mut failure: F,
+    mut run: impl FnMut(&InputMap) -> Option<F>,
+) -> (InputMap, F) {
+    let mut runs = 0;
+    'shrinking: loop {
+        for candidate in shrink_candidates(abi, &inputs) {
+            if runs == MAX_SHRINK_RUNS {
+                break 'shrinking;
+            }
+            runs += 1;
+
+            if let Some(candidate_failure) = run(&candidate) {
+                inputs = candidate;
+                failure = candidate_failure;
+                continue 'shrinking;
+            }
+        }
+        break;
+    }
+    (inputs, failure)
+}
+
+/// Returns every set of inputs which differs from `inputs` by a single simpler value.
+fn shrink_candidates(abi: &Abi, inputs: &InputMap) -> Vec<InputMap> {
+    let mut candidates = Vec::new();
+    for param in &abi.parameters {
+        let Some(value) = inputs.get(&param.name) else {
+            continue;
+        };
+        for shrunk_value in shrink_value(value, &param.typ) {
+            let mut candidate = inputs.clone();
+            candidate.insert(param.name.clone(), shrunk_value);
+            candidates.push(candidate);
+        }
+    }
+    candidates
+}
+
+fn shrink_value(value: &InputValue, typ: &AbiType) -> Vec<InputValue> {
+    match (value, typ) {
+        (InputValue::Field(field), AbiType::Field) => {
+            shrink_field(*field).into_iter().map(InputValue::Field).collect()
+        }
+        (InputValue::Field(field), AbiType::Integer { sign, width }) => {
+            shrink_integer(*field, sign, *width).into_iter().map(InputValue::Field).collect()
+        }
+        (InputValue::Field(field), AbiType::Boolean) if field.is_one() => {
+            vec![InputValue::Field(FieldElement::zero())]
+        }
+        (InputValue::Vec(values), AbiType::Array { typ, .. }) => {
+            shrink_elements(values, std::iter::repeat(typ.as_ref()))
+                .into_iter()
+                .map(InputValue::Vec)
+                .collect()
+        }
+        (InputValue::Vec(values), AbiType::Tuple { fields }) => {
+            shrink_elements(values, fields.iter()).into_iter().map(InputValue::Vec).collect()
+        }
+        (InputValue::Struct(values), AbiType::Struct { fields, .. }) => {
+            let mut candidates = Vec::new();
+            for (name, typ) in fields {
+                let Some(value) = values.get(name) else {
+                    continue;
+                };
+                for shrunk_value in shrink_value(value, typ) {
+                    let mut candidate = values.clone();
+                    candidate.insert(name.clone(), shrunk_value);
+                    candidates.push(InputValue::Struct(candidate));
+                }
+            }
+            candidates
+        }
+        (InputValue::Variant(name, values), AbiType::Enum { variants, .. }) => {
+            let Some((_, fields)) = variants.iter().find(|(variant, _)| variant == name) else {
+                return Vec::new();
+            };
+            shrink_elements(values, fields.iter())
+                .into_iter()
+                .map(|values| InputValue::Variant(name.clone(), values))
+                .collect()
+        }
+        (InputValue::String(string), AbiType::String { .. }) => {
+            // Replace the characters one at a time, so the ones which matter stand out
+            match string.char_indices().find(|(_, char)| *char != 'a') {
+                Some((index, char)) => {
+                    let mut shrunk = string.clone();
+                    shrunk.replace_range(index..index + char.len_utf8(), "a");
+                    vec![InputValue::String(shrunk)]
+                }
+                None => Vec::new(),
+            }
+        }
+        _ => Vec::new(),
+    }
+}
+
+fn shrink_elements<'a>(
+    values: &[InputValue],
+    types: impl Iterator<Item = &'a AbiType>,
+) -> Vec<Vec<InputValue>> {
+    let mut candidates = Vec::new();
+    for (index, (value, typ)) in values.iter().zip(types).enumerate() {
+        for shrunk_value in shrink_value(value, typ) {
+            let mut candidate = values.to_vec();
+            candidate[index] = shrunk_value;
+            candidates.push(candidate);
+        }
+    }
+    candidates
+}
+
+/// Returns values closer to zero than `value`, treating it as an unsigned integer.
+fn shrink_field(value: FieldElement) -> Vec<FieldElement> {
+    if value.is_zero() {
+        return Vec::new();
+    }
+    distinct_candidates(value, [FieldElement::zero(), halve(value), value - FieldElement::one()])
+}
+
+/// Returns values closer to zero than `value`, taking the sign of the integer into account.
+fn shrink_integer(value: FieldElement, sign: &Sign, width: u32) -> Vec<FieldElement> {
+    let is_negative = matches!(sign, Sign::Signed) && value.num_bits() == width;
+    if !is_negative {
+        return shrink_field(value);
+    }
+
+    // Halving the two's complement encoding while keeping the sign bit set halves the magnitude
+    let mut candidates = vec![FieldElement::zero(), halve(value) + two_pow(width - 1)];
+    if value != two_pow(width) - FieldElement::one() {
+        candidates.push(value + FieldElement::one());
+    }
+    distinct_candidates(value, candidates)
+}
+
+fn distinct_candidates(
+    value: FieldElement,
+    candidates: impl IntoIterator<Item = FieldElement>,
+) -> Vec<FieldElement> {
+    let mut distinct = Vec::new();
+    for candidate in candidates {
+        if candidate != value && !distinct.contains(&candidate) {
+            distinct.push(candidate);
+        }
+    }
+    distinct
+}
+
+#[cfg(test)]
+mod tests {
+    use acvm::FieldElement;
+    use noirc_abi::Sign;
+    use rand::{rngs::StdRng, SeedableRng};
+
+    use super::{halve, random_bits, shrink_field, shrink_integer};
+
+    #[test]
+    fn random_bits_fit_in_width() {
+        let mut rng = StdRng::seed_from_u64(0);
+        for width in [1, 7, 8, 9, 64, 127] {
+            for _ in 0..100 {
+                assert!(random_bits(width, &mut rng).num_bits() <= width);
+            }
+        }
+    }
+
+    #[test]
+    fn halves_as_an_integer() {
+        assert_eq!(halve(FieldElement::from(513u128)), FieldElement::from(256u128));
+        assert_eq!(halve(FieldElement::from(u128::MAX)), FieldElement::from(u128::MAX >> 1));
+    }
+
+    #[test]
+    fn shrinks_towards_zero() {
+        assert_eq!(shrink_field(FieldElement::zero()), Vec::new());
+        assert_eq!(shrink_field(FieldElement::one()), vec![FieldElement::zero()]);
+        assert_eq!(
+            shrink_field(FieldElement::from(10u128)),
+            vec![FieldElement::zero(), FieldElement::from(5u128), FieldElement::from(9u128)]
+        );
+    }
+
+    #[test]
+    fn shrinks_negative_integers_towards_zero() {
+        // -6 and -3 as 8 bit integers
+        let minus_six = FieldElement::from(250u128);
+        let minus_three = FieldElement::from(253u128);
+        let minus_five = FieldElement::from(251u128);
+        assert_eq!(
+            shrink_integer(minus_six, &Sign::Signed, 8),
+            vec![FieldElement::zero(), minus_three, minus_five]
+        );
+        // -1 can only become zero
+        let minus_one = FieldElement::from(255u128);
+        assert_eq!(shrink_integer(minus_one, &Sign::Signed, 8), vec![FieldElement::zero()]);
+        // Whereas 255 is positive when unsigned
+        assert_eq!(shrink_integer(minus_one, &Sign::Unsigned, 8).len(), 3);
+    }
+}
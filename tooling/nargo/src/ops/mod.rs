@@ -1,9 +1,21 @@
+pub use self::constraints::{
+    find_under_constrained_witnesses, ConstraintKind, UnderConstrainedWitnesses,
+};
+pub use self::coverage::CoverageReport;
 pub use self::execute::execute_circuit;
-pub use self::foreign_calls::ForeignCallExecutor;
+pub use self::fingerprint::fingerprint_package;
+pub use self::foreign_calls::{ForeignCallExecutor, OracleResolver};
+pub use self::fuzz::{FuzzConfig, DEFAULT_FUZZ_RUNS};
 pub use self::optimize::{optimize_contract, optimize_program};
+pub use self::profile::{profile_circuit, ExecutionProfile, ProfileReport, SourceCost};
 pub use self::test::{run_test, TestStatus};
 
+mod constraints;
+mod coverage;
 mod execute;
+mod fingerprint;
 mod foreign_calls;
+mod fuzz;
 mod optimize;
+mod profile;
 mod test;
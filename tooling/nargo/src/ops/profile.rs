@@ -0,0 +1,287 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use acvm::acir::circuit::{Circuit, OpcodeLocation};
+use acvm::pwg::{ACVMStatus, BrilligSolverStatus, StepResult, ACVM};
+use acvm::{acir::native_types::WitnessMap, BlackBoxFunctionSolver};
+use codespan_reporting::files::Files;
+use noirc_errors::{debug_info::DebugInfo, Location};
+
+use crate::artifacts::debug::DebugArtifact;
+use crate::NargoError;
+
+use super::execute::resolve_failure;
+use super::foreign_calls::{ForeignCallExecutor, OracleResolver};
+
+/// Runtime information gathered while executing a circuit with [`profile_circuit`].
+#[derive(Debug, Default)]
+pub struct ExecutionProfile {
+    /// The indices of the ACIR opcodes which were solved
+    pub solved_acir_opcodes: BTreeSet<usize>,
+    /// The number of times each Brillig opcode was executed
+    pub brillig_steps: BTreeMap<OpcodeLocation, usize>,
+}
+
+/// Executes a circuit in the same way as [`execute_circuit`][super::execute_circuit] while
+/// counting how many times each Brillig opcode is executed.
+///
+/// Brillig opcodes are stepped through one at a time, so this is noticeably slower than
+/// `execute_circuit` for programs which spend a long time in unconstrained functions.
+pub fn profile_circuit<B: BlackBoxFunctionSolver>(
+    blackbox_solver: &B,
+    circuit: &Circuit,
+    initial_witness: WitnessMap,
+    show_output: bool,
+    oracle_resolver: Option<&mut dyn OracleResolver>,
+) -> Result<(WitnessMap, ExecutionProfile), NargoError> {
+    let mut foreign_call_executor = ForeignCallExecutor::new(oracle_resolver);
+    let mut profile = ExecutionProfile::default();
+    let solved_witness = profile_circuit_with_executor(
+        blackbox_solver,
+        circuit,
+        initial_witness,
+        show_output,
+        &mut foreign_call_executor,
+        &mut profile,
+    )?;
+    Ok((solved_witness, profile))
+}
+
+/// Executes a circuit as [`profile_circuit`] does, resolving its foreign calls with
+/// `foreign_call_executor` and adding to `profile`.
+///
+/// `profile` is updated even if execution fails, covering everything executed up to the failure.
+pub(crate) fn profile_circuit_with_executor<B: BlackBoxFunctionSolver>(
+    blackbox_solver: &B,
+    circuit: &Circuit,
+    initial_witness: WitnessMap,
+    show_output: bool,
+    foreign_call_executor: &mut ForeignCallExecutor,
+    profile: &mut ExecutionProfile,
+) -> Result<WitnessMap, NargoError> {
+    let mut acvm = ACVM::new(blackbox_solver, &circuit.opcodes, initial_witness);
+
+    loop {
+        let acir_index = acvm.instruction_pointer();
+        let status = match acvm.step_into_brillig_opcode() {
+            StepResult::Status(status) => status,
+            StepResult::IntoBrillig(mut solver) => loop {
+                let location =
+                    OpcodeLocation::Brillig { acir_index, brillig_index: solver.program_counter() };
+                let step = solver.step();
+                // The foreign call opcode is executed again once its result is known,
+                // so it is only counted then.
+                if !matches!(step, Ok(BrilligSolverStatus::ForeignCallWait(_))) {
+                    *profile.brillig_steps.entry(location).or_default() += 1;
+                }
+
+                match step {
+                    Ok(BrilligSolverStatus::InProgress) => {}
+                    Ok(BrilligSolverStatus::Finished) => {
+                        break acvm.finish_brillig_with_solver(solver);
+                    }
+                    Ok(BrilligSolverStatus::ForeignCallWait(foreign_call)) => {
+                        let foreign_call_result =
+                            foreign_call_executor.execute(&foreign_call, show_output)?;
+                        solver.resolve_pending_foreign_call(foreign_call_result);
+                    }
+                    Err(error) => return Err(resolve_failure(circuit, error)),
+                }
+            },
+        };
+        // The instruction pointer only moves on once an opcode has been solved
+        if acvm.instruction_pointer() > acir_index {
+            profile.solved_acir_opcodes.insert(acir_index);
+        }
+
+        match status {
+            ACVMStatus::Solved => break,
+            ACVMStatus::InProgress => {}
+            ACVMStatus::Failure(error) => return Err(resolve_failure(circuit, error)),
+            ACVMStatus::RequiresForeignCall(foreign_call) => {
+                let foreign_call_result =
+                    foreign_call_executor.execute(&foreign_call, show_output)?;
+                acvm.resolve_pending_foreign_call(foreign_call_result);
+            }
+        }
+    }
+
+    Ok(acvm.finalize())
+}
+
+/// The cost of a piece of source code within a circuit.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct SourceCost {
+    /// The number of ACIR opcodes generated for this code
+    pub acir_opcodes: usize,
+    /// The number of Brillig opcodes executed for this code
+    pub brillig_steps: usize,
+}
+
+impl SourceCost {
+    fn add(&mut self, other: SourceCost) {
+        self.acir_opcodes += other.acir_opcodes;
+        self.brillig_steps += other.brillig_steps;
+    }
+}
+
+/// The costs of a circuit attributed to the source code which generated them.
+#[derive(Debug, Default)]
+pub struct ProfileReport {
+    /// The cost of each source line, keyed by file path and line number.
+    /// Opcodes are only attributed to the innermost location of their call stack.
+    pub lines: BTreeMap<(String, usize), SourceCost>,
+    /// The cost of each call stack. Frames are `path:line` strings ordered from the outermost call.
+    /// Opcodes without any debug information are attributed to an `unknown` frame.
+    pub call_stacks: BTreeMap<Vec<String>, SourceCost>,
+}
+
+impl ProfileReport {
+    pub fn new(
+        circuit: &Circuit,
+        debug_artifact: &DebugArtifact,
+        debug_info: &DebugInfo,
+        profile: &ExecutionProfile,
+    ) -> Self {
+        let mut report = ProfileReport::default();
+
+        for acir_index in 0..circuit.opcodes.len() {
+            let location = OpcodeLocation::Acir(acir_index);
+            let call_stack = debug_info.locations.get(&location);
+            report.add(
+                debug_artifact,
+                call_stack,
+                SourceCost { acir_opcodes: 1, brillig_steps: 0 },
+            );
+        }
+
+        for (location, steps) in &profile.brillig_steps {
+            let call_stack = debug_info.locations.get(location);
+            let cost = SourceCost { acir_opcodes: 0, brillig_steps: *steps };
+            report.add(debug_artifact, call_stack, cost);
+        }
+
+        report
+    }
+
+    fn add(
+        &mut self,
+        debug_artifact: &DebugArtifact,
+        call_stack: Option<&Vec<Location>>,
+        cost: SourceCost,
+    ) {
+        let frames: Vec<_> = call_stack
+            .into_iter()
+            .flatten()
+            .filter_map(|location| source_line(debug_artifact, *location))
+            .collect();
+
+        if let Some(innermost) = frames.last() {
+            self.lines.entry(innermost.clone()).or_default().add(cost);
+        }
+
+        let mut frames: Vec<_> =
+            frames.into_iter().map(|(path, line)| format!("{path}:{line}")).collect();
+        if frames.is_empty() {
+            frames.push("unknown".to_owned());
+        }
+        self.call_stacks.entry(frames).or_default().add(cost);
+    }
+
+    /// Renders the number of ACIR opcodes generated by each call stack in the folded stack
+    /// format understood by flamegraph tools such as `inferno-flamegraph`.
+    pub fn folded_acir_opcodes(&self) -> String {
+        self.folded_stacks(|cost| cost.acir_opcodes)
+    }
+
+    /// Renders the number of Brillig opcodes executed by each call stack in the folded stack
+    /// format understood by flamegraph tools such as `inferno-flamegraph`.
+    pub fn folded_brillig_steps(&self) -> String {
+        self.folded_stacks(|cost| cost.brillig_steps)
+    }
+
+    fn folded_stacks(&self, count: impl Fn(&SourceCost) -> usize) -> String {
+        let mut folded = String::new();
+        for (frames, cost) in &self.call_stacks {
+            let count = count(cost);
+            if count > 0 {
+                // Semicolons separate frames so they can't appear within a frame
+                let frames: Vec<_> = frames.iter().map(|frame| frame.replace(';', ":")).collect();
+                folded.push_str(&format!("{} {count}\n", frames.join(";")));
+            }
+        }
+        folded
+    }
+}
+
+pub(crate) fn source_line(
+    debug_artifact: &DebugArtifact,
+    location: Location,
+) -> Option<(String, usize)> {
+    let path = debug_artifact.name(location.file).ok()?;
+    let line = debug_artifact.location_line_number(location).ok()?;
+    Some((path.to_string(), line))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use acvm::acir::circuit::{Circuit, Opcode, OpcodeLocation};
+    use acvm::acir::native_types::Expression;
+    use fm::FileId;
+    use noirc_driver::DebugFile;
+    use noirc_errors::{debug_info::DebugInfo, Location, Span};
+
+    use super::{ExecutionProfile, ProfileReport, SourceCost};
+    use crate::artifacts::debug::DebugArtifact;
+
+    #[test]
+    fn attributes_opcodes_to_source_lines_and_call_stacks() {
+        let source = "fn main() {\n    foo();\n}\nfn foo() {\n    bar();\n}\n";
+        let file = FileId::dummy();
+        let file_map = BTreeMap::from([(
+            file,
+            DebugFile { source: source.to_owned(), path: "src/main.nr".into() },
+        )]);
+        let debug_artifact =
+            DebugArtifact { debug_symbols: Vec::new(), file_map, warnings: vec![] };
+
+        let at_line = |line: usize| {
+            let start = source.lines().take(line - 1).map(|line| line.len() + 1).sum::<usize>();
+            Location::new(Span::single_char(start as u32), file)
+        };
+        let call_foo = at_line(2);
+        let call_bar = at_line(5);
+
+        let circuit = Circuit {
+            opcodes: vec![Opcode::Arithmetic(Expression::default()); 3],
+            ..Circuit::default()
+        };
+        let brillig_location = OpcodeLocation::Brillig { acir_index: 2, brillig_index: 0 };
+        let debug_info = DebugInfo::new(BTreeMap::from([
+            (OpcodeLocation::Acir(0), vec![call_foo]),
+            (OpcodeLocation::Acir(1), vec![call_foo, call_bar]),
+            (brillig_location, vec![call_foo, call_bar]),
+        ]));
+        let profile = ExecutionProfile {
+            brillig_steps: BTreeMap::from([(brillig_location, 7)]),
+            ..ExecutionProfile::default()
+        };
+
+        let report = ProfileReport::new(&circuit, &debug_artifact, &debug_info, &profile);
+
+        let path = "src/main.nr".to_owned();
+        assert_eq!(
+            report.lines,
+            BTreeMap::from([
+                ((path.clone(), 2), SourceCost { acir_opcodes: 1, brillig_steps: 0 }),
+                ((path, 5), SourceCost { acir_opcodes: 1, brillig_steps: 7 }),
+            ])
+        );
+        assert_eq!(
+            report.folded_acir_opcodes(),
+            "src/main.nr:2 1\nsrc/main.nr:2;src/main.nr:5 1\nunknown 1\n"
+        );
+        assert_eq!(report.folded_brillig_steps(), "src/main.nr:2;src/main.nr:5 7\n");
+    }
+}
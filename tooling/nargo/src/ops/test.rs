@@ -1,12 +1,18 @@
 use acvm::{acir::native_types::WitnessMap, BlackBoxFunctionSolver};
-use noirc_driver::{compile_no_check, CompileOptions};
+use noirc_abi::{input_parser::Format, Abi, InputMap};
+use noirc_driver::{compile_no_check, CompileOptions, CompiledProgram};
 use noirc_errors::{debug_info::DebugInfo, FileDiagnostic};
 use noirc_evaluator::errors::RuntimeError;
 use noirc_frontend::hir::{def_map::TestFunction, Context};
+use rand::{rngs::StdRng, SeedableRng};
 
+use crate::artifacts::debug::DebugArtifact;
 use crate::{errors::try_to_diagnose_runtime_error, NargoError};
 
-use super::execute_circuit;
+use super::execute::execute_circuit_with_executor;
+use super::fuzz::{generate_inputs, shrink_inputs, FuzzConfig};
+use super::profile::profile_circuit_with_executor;
+use super::{CoverageReport, ExecutionProfile, ForeignCallExecutor, OracleResolver};
 
 pub enum TestStatus {
     Pass,
@@ -14,23 +20,184 @@ pub enum TestStatus {
     CompileError(FileDiagnostic),
 }
 
+/// Runs a test function, returning its status along with the output of its `println` calls.
+///
+/// The output is captured rather than printed so that tests can be run in parallel, and is
+/// only recorded if `show_output` is set.
+///
+/// Test functions which take parameters are run once for each of `fuzz_config.runs` sets of
+/// randomly generated inputs. If any of these fail, the failing inputs are shrunk and included
+/// in the failure message.
+///
+/// If `coverage` is given, the lines executed by the test are added to it, whether it passes or
+/// not. This requires stepping through Brillig opcodes one at a time, as profiling does.
+#[allow(clippy::too_many_arguments)]
 pub fn run_test<B: BlackBoxFunctionSolver>(
     blackbox_solver: &B,
     context: &Context,
     test_function: TestFunction,
     show_output: bool,
     config: &CompileOptions,
-) -> TestStatus {
-    let program = compile_no_check(context, config, test_function.get_id(), None, false);
-    match program {
-        Ok(program) => {
-            // Run the backend to ensure the PWG evaluates functions like std::hash::pedersen,
-            // otherwise constraints involving these expressions will not error.
-            let circuit_execution =
-                execute_circuit(blackbox_solver, &program.circuit, WitnessMap::new(), show_output);
-            test_status_program_compile_pass(test_function, program.debug, circuit_execution)
+    fuzz_config: &FuzzConfig,
+    coverage: Option<&mut CoverageReport>,
+    oracle_resolver: Option<&mut dyn OracleResolver>,
+) -> (TestStatus, String) {
+    let program = match compile_no_check(context, config, test_function.get_id(), None, false) {
+        Ok(program) => program,
+        Err(err) => return (test_status_program_compile_fail(err, &test_function), String::new()),
+    };
+
+    let mut profile = coverage.is_some().then(ExecutionProfile::default);
+    let result = if program.abi.parameters.is_empty() {
+        // Run the backend to ensure the PWG evaluates functions like std::hash::pedersen,
+        // otherwise constraints involving these expressions will not error.
+        run_with_witness(
+            blackbox_solver,
+            &program,
+            &test_function,
+            WitnessMap::new(),
+            show_output,
+            profile.as_mut(),
+            oracle_resolver,
+        )
+    } else {
+        fuzz_test(
+            blackbox_solver,
+            &program,
+            &test_function,
+            show_output,
+            fuzz_config,
+            profile.as_mut(),
+            oracle_resolver,
+        )
+    };
+
+    if let (Some(coverage), Some(profile)) = (coverage, profile) {
+        let debug_artifact = DebugArtifact {
+            debug_symbols: Vec::new(),
+            file_map: program.file_map,
+            warnings: Vec::new(),
+        };
+        coverage.add(&debug_artifact, &program.debug, &profile);
+    }
+    result
+}
+
+fn run_with_witness<B: BlackBoxFunctionSolver>(
+    blackbox_solver: &B,
+    program: &CompiledProgram,
+    test_function: &TestFunction,
+    initial_witness: WitnessMap,
+    show_output: bool,
+    profile: Option<&mut ExecutionProfile>,
+    oracle_resolver: Option<&mut dyn OracleResolver>,
+) -> (TestStatus, String) {
+    let mut foreign_call_executor = ForeignCallExecutor::new(oracle_resolver);
+    foreign_call_executor.capture_output();
+
+    let circuit_execution = match profile {
+        Some(profile) => profile_circuit_with_executor(
+            blackbox_solver,
+            &program.circuit,
+            initial_witness,
+            show_output,
+            &mut foreign_call_executor,
+            profile,
+        ),
+        None => execute_circuit_with_executor(
+            blackbox_solver,
+            &program.circuit,
+            initial_witness,
+            show_output,
+            &mut foreign_call_executor,
+        ),
+    };
+    let status = test_status_program_compile_pass(test_function, &program.debug, circuit_execution);
+    (status, foreign_call_executor.take_captured_output())
+}
+
+fn run_with_inputs<B: BlackBoxFunctionSolver>(
+    blackbox_solver: &B,
+    program: &CompiledProgram,
+    test_function: &TestFunction,
+    inputs: &InputMap,
+    show_output: bool,
+    profile: Option<&mut ExecutionProfile>,
+    oracle_resolver: Option<&mut dyn OracleResolver>,
+) -> (TestStatus, String) {
+    let initial_witness =
+        program.abi.encode(inputs, None).expect("generated inputs should match the test's ABI");
+    run_with_witness(
+        blackbox_solver,
+        program,
+        test_function,
+        initial_witness,
+        show_output,
+        profile,
+        oracle_resolver,
+    )
+}
+
+/// Runs a test function which takes parameters with randomly generated inputs, until either it
+/// fails or it has been run `fuzz_config.runs` times.
+fn fuzz_test<B: BlackBoxFunctionSolver>(
+    blackbox_solver: &B,
+    program: &CompiledProgram,
+    test_function: &TestFunction,
+    show_output: bool,
+    fuzz_config: &FuzzConfig,
+    mut profile: Option<&mut ExecutionProfile>,
+    mut oracle_resolver: Option<&mut dyn OracleResolver>,
+) -> (TestStatus, String) {
+    let mut rng = StdRng::seed_from_u64(fuzz_config.seed);
+    let mut output = String::new();
+    for _ in 0..fuzz_config.runs {
+        let inputs = generate_inputs(&program.abi, &mut rng);
+        let (status, run_output) = run_with_inputs(
+            blackbox_solver,
+            program,
+            test_function,
+            &inputs,
+            show_output,
+            profile.as_deref_mut(),
+            oracle_resolver.as_mut().map(|resolver| &mut **resolver as &mut dyn OracleResolver),
+        );
+        output.push_str(&run_output);
+        if matches!(status, TestStatus::Pass) {
+            continue;
         }
-        Err(err) => test_status_program_compile_fail(err, test_function),
+
+        // The output of the runs made while shrinking would only drown out that of the failure
+        let (inputs, status) = shrink_inputs(&program.abi, inputs, status, |inputs| {
+            let (status, _) = run_with_inputs(
+                blackbox_solver,
+                program,
+                test_function,
+                inputs,
+                false,
+                profile.as_deref_mut(),
+                oracle_resolver.as_mut().map(|resolver| &mut **resolver as &mut dyn OracleResolver),
+            );
+            (!matches!(status, TestStatus::Pass)).then_some(status)
+        });
+        return (with_failing_inputs(status, &program.abi, &inputs, fuzz_config.seed), output);
+    }
+    (TestStatus::Pass, output)
+}
+
+/// Adds the inputs which a test failed for to its failure message, formatted as they would
+/// appear in a `Prover.toml` so that the failure can be reproduced.
+fn with_failing_inputs(status: TestStatus, abi: &Abi, inputs: &InputMap, seed: u64) -> TestStatus {
+    let TestStatus::Fail { message, error_diagnostic } = status else {
+        return status;
+    };
+
+    let inputs = Format::Toml.serialize(inputs, abi).unwrap_or_else(|err| err.to_string());
+    TestStatus::Fail {
+        message: format!(
+            "{message}\nFailing inputs (seed: {seed}), as they would appear in Prover.toml:\n{inputs}"
+        ),
+        error_diagnostic,
     }
 }
 
@@ -40,7 +207,7 @@ pub fn run_test<B: BlackBoxFunctionSolver>(
 /// that a constraint was never satisfiable.
 /// An example of this is the program `assert(false)`
 /// In that case, we check if the test function should fail, and if so, we return `TestStatus::Pass`.
-fn test_status_program_compile_fail(err: RuntimeError, test_function: TestFunction) -> TestStatus {
+fn test_status_program_compile_fail(err: RuntimeError, test_function: &TestFunction) -> TestStatus {
     // The test has failed compilation, but it should never fail. Report error.
     if !test_function.should_fail() {
         return TestStatus::CompileError(err.into());
@@ -61,8 +228,8 @@ fn test_status_program_compile_fail(err: RuntimeError, test_function: TestFuncti
 /// We now check whether execution passed/failed and whether it should have
 /// passed/failed to determine the test status.
 fn test_status_program_compile_pass(
-    test_function: TestFunction,
-    debug: DebugInfo,
+    test_function: &TestFunction,
+    debug: &DebugInfo,
     circuit_execution: Result<WitnessMap, NargoError>,
 ) -> TestStatus {
     let circuit_execution_err = match circuit_execution {
@@ -83,7 +250,7 @@ fn test_status_program_compile_pass(
     // If we reach here, then the circuit execution failed.
     //
     // Check if the function should have passed
-    let diagnostic = try_to_diagnose_runtime_error(&circuit_execution_err, &debug);
+    let diagnostic = try_to_diagnose_runtime_error(&circuit_execution_err, debug);
     let test_should_have_passed = !test_function.should_fail();
     if test_should_have_passed {
         return TestStatus::Fail {
@@ -100,7 +267,7 @@ fn test_status_program_compile_pass(
 }
 
 fn check_expected_failure_message(
-    test_function: TestFunction,
+    test_function: &TestFunction,
     failed_assertion: Option<String>,
     error_diagnostic: Option<FileDiagnostic>,
 ) -> TestStatus {
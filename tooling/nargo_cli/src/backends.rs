@@ -1,7 +1,7 @@
 use std::path::PathBuf;
 
 use backend_interface::backends_directory;
-pub(crate) use backend_interface::Backend;
+pub(crate) use backend_interface::{Backend, ProofSystem};
 
 fn active_backend_file_path() -> PathBuf {
     backends_directory().join(".selected_backend")
@@ -1,4 +1,4 @@
-use backend_interface::backends_directory;
+use backend_interface::{backends_directory, BUILTIN_BACKEND_NAME};
 use clap::Args;
 
 use crate::errors::CliError;
@@ -20,15 +20,15 @@ pub(super) fn get_available_backends() -> Vec<String> {
         .expect("Could not read backends directory contents");
 
     // TODO: Highlight the currently active backend.
-    backend_directory_contents
-        .into_iter()
-        .filter_map(|entry| {
-            let path = entry.ok()?.path();
-            if path.is_dir() {
-                path.file_name().map(|name| name.to_string_lossy().to_string())
-            } else {
-                None
-            }
-        })
-        .collect()
+    let installed_backends = backend_directory_contents.into_iter().filter_map(|entry| {
+        let path = entry.ok()?.path();
+        if path.is_dir() {
+            path.file_name().map(|name| name.to_string_lossy().to_string())
+        } else {
+            None
+        }
+    });
+
+    // The builtin backend runs in-process and so is always available.
+    std::iter::once(BUILTIN_BACKEND_NAME.to_string()).chain(installed_backends).collect()
 }
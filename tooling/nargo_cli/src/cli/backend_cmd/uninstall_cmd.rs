@@ -1,6 +1,6 @@
 use clap::Args;
 
-use backend_interface::backends_directory;
+use backend_interface::{backends_directory, BUILTIN_BACKEND_NAME};
 
 use crate::{
     backends::{
@@ -19,6 +19,10 @@ pub(crate) struct UninstallCommand {
 }
 
 pub(crate) fn run(args: UninstallCommand) -> Result<(), CliError> {
+    if args.backend == BUILTIN_BACKEND_NAME {
+        return Err(BackendError::BuiltinBackend(args.backend).into());
+    }
+
     let installed_backends = get_available_backends();
 
     if !installed_backends.contains(&args.backend) {
@@ -45,6 +45,7 @@ pub(crate) fn run(
         &toml_path,
         selection,
         Some(NOIR_ARTIFACT_VERSION_STRING.to_string()),
+        config.dependency_resolution(),
     )?;
 
     for package in &workspace {
@@ -3,7 +3,7 @@ use super::{
     compile_cmd::compile_bin_package,
     fs::{create_named_dir, write_to_file},
 };
-use crate::backends::Backend;
+use crate::backends::{Backend, ProofSystem};
 use crate::errors::CliError;
 
 use acvm::acir::circuit::Opcode;
@@ -44,6 +44,7 @@ pub(crate) fn run(
         &toml_path,
         selection,
         Some(NOIR_ARTIFACT_VERSION_STRING.to_string()),
+        config.dependency_resolution(),
     )?;
 
     let (np_language, opcode_support) = backend.get_backend_info()?;
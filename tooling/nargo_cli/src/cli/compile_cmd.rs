@@ -1,7 +1,7 @@
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 use acvm::acir::circuit::opcodes::BlackBoxFuncCall;
-use acvm::acir::circuit::Opcode;
+use acvm::acir::circuit::{Circuit, Opcode};
 use acvm::Language;
 use backend_interface::BackendOpcodeSupport;
 use fm::FileManager;
@@ -15,17 +15,23 @@ use nargo::prepare_package;
 use nargo::workspace::Workspace;
 use nargo_toml::{get_package_manifest, resolve_workspace_from_toml, PackageSelection};
 use noirc_driver::NOIR_ARTIFACT_VERSION_STRING;
-use noirc_driver::{CompilationResult, CompileOptions, CompiledContract, CompiledProgram};
+use noirc_driver::{
+    CompilationResult, CompileOptions, CompiledContract, CompiledProgram, ContractFunction,
+};
+use noirc_errors::FileDiagnostic;
 use noirc_frontend::graph::CrateName;
+use noirc_frontend::hir::def_map::ParseCache;
 
-use clap::Args;
+use clap::{Args, ValueEnum};
 
-use crate::backends::Backend;
+use crate::backends::{Backend, ProofSystem};
 use crate::errors::{CliError, CompileError};
 
 use super::fs::program::{
-    read_debug_artifact_from_file, read_program_from_file, save_contract_to_file,
-    save_debug_artifact_to_file, save_program_to_file,
+    delete_fingerprint_file, read_contract_from_file, read_debug_artifact_from_file,
+    read_fingerprint_from_file, read_program_from_file, save_contract_to_file,
+    save_debug_artifact_to_file, save_fingerprint_to_file, save_program_to_file, save_r1cs_to_file,
+    PackageFingerprint,
 };
 use super::NargoConfig;
 use rayon::prelude::*;
@@ -48,10 +54,36 @@ pub(crate) struct CompileCommand {
     #[clap(long, conflicts_with = "package")]
     workspace: bool,
 
+    /// The format in which to output the compiled circuits
+    #[arg(long, value_enum, default_value_t = CompileFormat::Acir)]
+    format: CompileFormat,
+
     #[clap(flatten)]
     compile_options: CompileOptions,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub(crate) enum CompileFormat {
+    /// ACIR optimized for the installed backend
+    Acir,
+    /// ACIR lowered to rank-1 constraints, additionally written out in the iden3 `.r1cs` binary format.
+    /// This does not require a backend to be installed.
+    R1cs,
+}
+
+impl CompileFormat {
+    /// The language to compile to and the opcodes which may be used when compiling in this format.
+    pub(crate) fn backend_info(
+        self,
+        backend: &Backend,
+    ) -> Result<(Language, BackendOpcodeSupport), CliError> {
+        match self {
+            CompileFormat::Acir => Ok(backend.get_backend_info()?),
+            CompileFormat::R1cs => Ok((Language::R1CS, BackendOpcodeSupport::r1cs())),
+        }
+    }
+}
+
 pub(crate) fn run(
     backend: &Backend,
     args: CompileCommand,
@@ -66,6 +98,7 @@ pub(crate) fn run(
         &toml_path,
         selection,
         Some(NOIR_ARTIFACT_VERSION_STRING.to_owned()),
+        config.dependency_resolution(),
     )?;
     let circuit_dir = workspace.target_directory_path();
 
@@ -75,8 +108,8 @@ pub(crate) fn run(
         .cloned()
         .partition(|package| package.is_binary());
 
-    let (np_language, opcode_support) = backend.get_backend_info()?;
-    let (_, compiled_contracts) = compile_workspace(
+    let (np_language, opcode_support) = args.format.backend_info(backend)?;
+    let (compiled_programs, compiled_contracts) = compile_workspace(
         &workspace,
         &binary_packages,
         &contract_packages,
@@ -85,9 +118,16 @@ pub(crate) fn run(
         &args.compile_options,
     )?;
 
-    // Save build artifacts to disk.
-    for (package, contract) in contract_packages.into_iter().zip(compiled_contracts) {
-        save_contract(contract, &package, &circuit_dir);
+    if args.format == CompileFormat::R1cs {
+        for (package, program) in binary_packages.iter().zip(&compiled_programs) {
+            save_r1cs(&program.circuit, &package.name.to_string(), &circuit_dir)?;
+        }
+        for (package, contract) in contract_packages.iter().zip(&compiled_contracts) {
+            for function in &contract.functions {
+                let circuit_name = format!("{}-{}-{}", package.name, contract.name, function.name);
+                save_r1cs(&function.bytecode, &circuit_name, &circuit_dir)?;
+            }
+        }
     }
 
     Ok(())
@@ -103,18 +143,35 @@ pub(super) fn compile_workspace(
 ) -> Result<(Vec<CompiledProgram>, Vec<CompiledContract>), CliError> {
     let is_opcode_supported = |opcode: &_| opcode_support.is_opcode_supported(opcode);
 
+    // Files shared between packages, such as the stdlib, are only parsed once.
+    let parse_cache = ParseCache::default();
+
     // Compile all of the packages in parallel.
     let program_results: Vec<(FileManager, CompilationResult<CompiledProgram>)> = binary_packages
         .par_iter()
         .map(|package| {
-            compile_program(workspace, package, compile_options, np_language, &is_opcode_supported)
+            compile_program(
+                workspace,
+                package,
+                compile_options,
+                np_language,
+                &is_opcode_supported,
+                &parse_cache,
+            )
         })
         .collect();
     let contract_results: Vec<(FileManager, CompilationResult<CompiledContract>)> =
         contract_packages
             .par_iter()
             .map(|package| {
-                compile_contract(package, compile_options, np_language, &is_opcode_supported)
+                compile_contract(
+                    workspace,
+                    package,
+                    compile_options,
+                    np_language,
+                    &is_opcode_supported,
+                    &parse_cache,
+                )
             })
             .collect();
 
@@ -156,8 +213,14 @@ pub(crate) fn compile_bin_package(
         return Err(CompileError::LibraryCrate(package.name.clone()).into());
     }
 
-    let (file_manager, compilation_result) =
-        compile_program(workspace, package, compile_options, np_language, &is_opcode_supported);
+    let (file_manager, compilation_result) = compile_program(
+        workspace,
+        package,
+        compile_options,
+        np_language,
+        &is_opcode_supported,
+        &ParseCache::default(),
+    );
 
     let program = report_errors(
         compilation_result,
@@ -175,9 +238,11 @@ fn compile_program(
     compile_options: &CompileOptions,
     np_language: Language,
     is_opcode_supported: &impl Fn(&Opcode) -> bool,
+    parse_cache: &ParseCache,
 ) -> (FileManager, CompilationResult<CompiledProgram>) {
-    let (mut context, crate_id) =
-        prepare_package(package, Box::new(|path| std::fs::read_to_string(path)));
+    let circuit_dir = workspace.target_directory_path();
+    let fingerprint = nargo::ops::fingerprint_package(package, compile_options, np_language);
+    let previous_fingerprint = read_fingerprint_from_file(&package.name, &circuit_dir).ok();
 
     let program_artifact_path = workspace.package_build_path(package);
     let mut debug_artifact_path = program_artifact_path.clone();
@@ -201,6 +266,22 @@ fn compile_program(
 
     let force_recompile =
         cached_program.as_ref().map_or(false, |p| p.noir_version != NOIR_ARTIFACT_VERSION_STRING);
+
+    // An unchanged package doesn't need to be processed by the compiler at all.
+    let unchanged = !force_recompile
+        && !compile_options.force_compile()
+        && previous_fingerprint.map_or(false, |previous| previous.fingerprint == fingerprint);
+    let cached_program = match cached_program {
+        Some(program) if unchanged => {
+            return (empty_file_manager(package), Ok((program, Vec::new())));
+        }
+        cached_program => cached_program,
+    };
+
+    let (mut context, crate_id) =
+        prepare_package(package, Box::new(|path| std::fs::read_to_string(path)));
+    context.set_parse_cache(parse_cache.clone());
+
     let (program, warnings) = match noirc_driver::compile_main(
         &mut context,
         crate_id,
@@ -228,34 +309,129 @@ fn compile_program(
         nargo::ops::optimize_program(program, np_language, &is_opcode_supported_pedersen_hash)
             .expect("Backend does not support an opcode that is in the IR");
 
-    save_program(optimized_program.clone(), package, &workspace.target_directory_path());
+    save_program(optimized_program.clone(), package, &circuit_dir);
+    save_fingerprint(fingerprint, &warnings, package.name.to_string(), package, &circuit_dir);
 
     (context.file_manager, Ok((optimized_program, warnings)))
 }
 
 fn compile_contract(
+    workspace: &Workspace,
     package: &Package,
     compile_options: &CompileOptions,
     np_language: Language,
     is_opcode_supported: &impl Fn(&Opcode) -> bool,
+    parse_cache: &ParseCache,
 ) -> (FileManager, CompilationResult<CompiledContract>) {
+    let circuit_dir = workspace.target_directory_path();
+    let fingerprint = nargo::ops::fingerprint_package(package, compile_options, np_language);
+    let previous_fingerprint = read_fingerprint_from_file(&package.name, &circuit_dir).ok();
+
+    // The name of a contract's artifact is only known once it is compiled,
+    // so it is recorded alongside the fingerprint of its package.
+    let cached_contract = previous_fingerprint
+        .as_ref()
+        .and_then(|previous| read_contract(&previous.artifact_name, &circuit_dir))
+        .filter(|contract| contract.noir_version == NOIR_ARTIFACT_VERSION_STRING);
+
+    let unchanged = !compile_options.force_compile()
+        && previous_fingerprint.map_or(false, |previous| previous.fingerprint == fingerprint);
+    let cached_contract = match cached_contract {
+        Some(contract) if unchanged => {
+            return (empty_file_manager(package), Ok((contract, Vec::new())));
+        }
+        cached_contract => cached_contract,
+    };
+
     let (mut context, crate_id) =
         prepare_package(package, Box::new(|path| std::fs::read_to_string(path)));
-    let (contract, warnings) =
-        match noirc_driver::compile_contract(&mut context, crate_id, compile_options) {
-            Ok(contracts_and_warnings) => contracts_and_warnings,
-            Err(errors) => {
-                return (context.file_manager, Err(errors));
-            }
-        };
+    context.set_parse_cache(parse_cache.clone());
+
+    // Functions which are unchanged since the contract was last compiled are reused.
+    let (contract, warnings) = match noirc_driver::compile_contract(
+        &mut context,
+        crate_id,
+        compile_options,
+        cached_contract,
+        false,
+    ) {
+        Ok(contracts_and_warnings) => contracts_and_warnings,
+        Err(errors) => {
+            return (context.file_manager, Err(errors));
+        }
+    };
 
     let optimized_contract =
         nargo::ops::optimize_contract(contract, np_language, &is_opcode_supported)
             .expect("Backend does not support an opcode that is in the IR");
 
+    let artifact_name = format!("{}-{}", package.name, optimized_contract.name);
+    save_contract(optimized_contract.clone(), package, &circuit_dir);
+    save_fingerprint(fingerprint, &warnings, artifact_name, package, &circuit_dir);
+
     (context.file_manager, Ok((optimized_contract, warnings)))
 }
 
+/// Records the fingerprint of the package an artifact was just compiled from. Compiling a package
+/// with warnings isn't recorded so that they are reported again the next time it is compiled.
+fn save_fingerprint(
+    fingerprint: u64,
+    warnings: &[FileDiagnostic],
+    artifact_name: String,
+    package: &Package,
+    circuit_dir: &Path,
+) {
+    if warnings.is_empty() {
+        let fingerprint = PackageFingerprint { fingerprint, artifact_name };
+        save_fingerprint_to_file(&fingerprint, &package.name, circuit_dir);
+    } else {
+        // The artifact was overwritten so any previous fingerprint no longer describes it.
+        delete_fingerprint_file(&package.name, circuit_dir);
+    }
+}
+
+/// No files are read when reusing the artifact of an unchanged package.
+fn empty_file_manager(package: &Package) -> FileManager {
+    FileManager::new(&package.root_dir, Box::new(|path| std::fs::read_to_string(path)))
+}
+
+fn read_contract(artifact_name: &str, circuit_dir: &Path) -> Option<CompiledContract> {
+    let contract = read_contract_from_file(circuit_dir.join(artifact_name)).ok()?;
+    let debug_artifact =
+        read_debug_artifact_from_file(circuit_dir.join(format!("debug_{artifact_name}.json")))
+            .ok()?;
+    // The warnings of a contract aren't stored per function, so one which was compiled with
+    // warnings is compiled again in full for all of them to be reported.
+    if !debug_artifact.warnings.is_empty() {
+        return None;
+    }
+
+    let functions = contract
+        .functions
+        .into_iter()
+        .zip(debug_artifact.debug_symbols)
+        .map(|(func, debug)| ContractFunction {
+            name: func.name,
+            hash: func.hash,
+            function_type: func.function_type,
+            is_internal: func.is_internal,
+            abi: func.abi,
+            bytecode: func.bytecode,
+            debug,
+            warnings: Vec::new(),
+        })
+        .collect();
+
+    Some(CompiledContract {
+        noir_version: contract.noir_version,
+        name: contract.name,
+        functions,
+        events: contract.events,
+        file_map: debug_artifact.file_map,
+        warnings: debug_artifact.warnings,
+    })
+}
+
 fn save_program(program: CompiledProgram, package: &Package, circuit_dir: &Path) {
     let preprocessed_program = PreprocessedProgram {
         hash: program.hash,
@@ -289,6 +465,7 @@ fn save_contract(contract: CompiledContract, package: &Package, circuit_dir: &Pa
 
     let preprocessed_functions = vecmap(contract.functions, |func| PreprocessedContractFunction {
         name: func.name,
+        hash: func.hash,
         function_type: func.function_type,
         is_internal: func.is_internal,
         abi: func.abi,
@@ -316,6 +493,17 @@ fn save_contract(contract: CompiledContract, package: &Package, circuit_dir: &Pa
     );
 }
 
+pub(crate) fn save_r1cs(
+    circuit: &Circuit,
+    circuit_name: &str,
+    circuit_dir: &Path,
+) -> Result<PathBuf, CliError> {
+    let r1cs = circuit
+        .serialize_r1cs()
+        .map_err(|err| CompileError::R1CSExport(circuit_name.to_owned(), err))?;
+    Ok(save_r1cs_to_file(&r1cs, circuit_name, circuit_dir))
+}
+
 /// Helper function for reporting any errors in a `CompilationResult<T>`
 /// structure that is commonly used as a return result in this file.
 pub(crate) fn report_errors<T>(
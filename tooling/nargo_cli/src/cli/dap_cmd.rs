@@ -0,0 +1,131 @@
+use std::io::{stdin, stdout, BufRead, BufReader, Write};
+use std::path::PathBuf;
+
+use acvm::acir::native_types::WitnessMap;
+use clap::Args;
+use nargo::artifacts::debug::DebugArtifact;
+use nargo::constants::PROVER_INPUT_FILE;
+use nargo_toml::{get_package_manifest, resolve_workspace_from_toml, PackageSelection};
+use noir_debugger::dap::{self, DapServer};
+use noirc_abi::input_parser::Format;
+use noirc_driver::{CompileOptions, CompiledProgram, NOIR_ARTIFACT_VERSION_STRING};
+use noirc_frontend::graph::CrateName;
+use serde::Deserialize;
+
+use super::compile_cmd::compile_bin_package;
+use super::fs::inputs::read_inputs_from_file;
+use super::NargoConfig;
+use crate::backends::{Backend, ProofSystem};
+use crate::errors::CliError;
+
+/// Starts a Debug Adapter Protocol server on stdio
+///
+/// This allows editors such as VS Code to debug the execution of a circuit. The program to debug
+/// is compiled once the editor sends the `launch` request.
+#[derive(Debug, Clone, Args)]
+pub(crate) struct DapCommand;
+
+/// The arguments of the `launch` request, as set in the editor's launch configuration
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct LaunchArguments {
+    /// The directory of the workspace holding the program to debug
+    project_folder: PathBuf,
+    /// The name of the binary package to debug, if the workspace has several
+    package: Option<CrateName>,
+    /// The name of the toml file which contains the inputs for the prover
+    prover_name: Option<String>,
+}
+
+pub(crate) fn run(
+    backend: &Backend,
+    _args: DapCommand,
+    config: NargoConfig,
+) -> Result<(), CliError> {
+    let mut server = DapServer::new(BufReader::new(stdin().lock()), stdout().lock());
+
+    while let Some(request) = server.poll_request()? {
+        match request.command.as_str() {
+            "initialize" => server.respond(&request, dap::capabilities())?,
+            "launch" => {
+                let launched = request
+                    .arguments::<LaunchArguments>()
+                    .map_err(|error| CliError::Generic(error.to_string()))
+                    .and_then(|arguments| load_program(backend, arguments, &config));
+                match launched {
+                    Ok((program, initial_witness)) => {
+                        server.respond(&request, ())?;
+                        return start_session(&mut server, program, initial_witness);
+                    }
+                    // The client may try launching again, e.g. after fixing the program.
+                    Err(error) => server.respond_error(&request, &error.to_string())?,
+                }
+            }
+            "disconnect" => {
+                server.respond(&request, ())?;
+                break;
+            }
+            command => {
+                let message = format!("Cannot handle {command} before the program is launched");
+                server.respond_error(&request, &message)?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn load_program(
+    backend: &Backend,
+    arguments: LaunchArguments,
+    config: &NargoConfig,
+) -> Result<(CompiledProgram, WitnessMap), CliError> {
+    let toml_path = get_package_manifest(&arguments.project_folder)?;
+    let selection =
+        arguments.package.map_or(PackageSelection::DefaultOrAll, PackageSelection::Selected);
+    let workspace = resolve_workspace_from_toml(
+        &toml_path,
+        selection,
+        Some(NOIR_ARTIFACT_VERSION_STRING.to_string()),
+        config.dependency_resolution(),
+    )?;
+    let (np_language, opcode_support) = backend.get_backend_info()?;
+
+    let Some(package) = workspace.into_iter().find(|p| p.is_binary()) else {
+        return Err(CliError::Generic(
+            "No matching binary packages found in workspace. Only binary packages can be debugged."
+                .to_string(),
+        ));
+    };
+
+    let compile_options = CompileOptions { instrument_debug: true, ..CompileOptions::default() };
+    let compiled_program =
+        compile_bin_package(&workspace, package, &compile_options, np_language, &|opcode| {
+            opcode_support.is_opcode_supported(opcode)
+        })?;
+
+    let prover_name = arguments.prover_name.as_deref().unwrap_or(PROVER_INPUT_FILE);
+    let (inputs_map, _) =
+        read_inputs_from_file(&package.root_dir, prover_name, Format::Toml, &compiled_program.abi)?;
+    let initial_witness = compiled_program.abi.encode(&inputs_map, None)?;
+
+    Ok((compiled_program, initial_witness))
+}
+
+fn start_session<R: BufRead, W: Write>(
+    server: &mut DapServer<R, W>,
+    program: CompiledProgram,
+    initial_witness: WitnessMap,
+) -> Result<(), CliError> {
+    #[allow(deprecated)]
+    let blackbox_solver = barretenberg_blackbox_solver::BarretenbergSolver::new();
+
+    let debug_artifact = DebugArtifact {
+        debug_symbols: vec![program.debug],
+        file_map: program.file_map,
+        warnings: program.warnings,
+    };
+
+    dap::run_session(server, &blackbox_solver, &program.circuit, &debug_artifact, initial_witness)?;
+    Ok(())
+}
@@ -13,7 +13,7 @@ use noirc_frontend::graph::CrateName;
 use super::compile_cmd::compile_bin_package;
 use super::fs::{inputs::read_inputs_from_file, witness::save_witness_to_dir};
 use super::NargoConfig;
-use crate::backends::Backend;
+use crate::backends::{Backend, ProofSystem};
 use crate::errors::CliError;
 
 /// Executes a circuit in debug mode
@@ -45,6 +45,7 @@ pub(crate) fn run(
         &toml_path,
         selection,
         Some(NOIR_ARTIFACT_VERSION_STRING.to_string()),
+        config.dependency_resolution(),
     )?;
     let target_dir = &workspace.target_directory_path();
     let (np_language, opcode_support) = backend.get_backend_info()?;
@@ -56,8 +57,10 @@ pub(crate) fn run(
         return Ok(());
     };
 
+    // Variable inspection requires the program to be instrumented when it's compiled.
+    let compile_options = CompileOptions { instrument_debug: true, ..args.compile_options };
     let compiled_program =
-        compile_bin_package(&workspace, package, &args.compile_options, np_language, &|opcode| {
+        compile_bin_package(&workspace, package, &compile_options, np_language, &|opcode| {
             opcode_support.is_opcode_supported(opcode)
         })?;
 
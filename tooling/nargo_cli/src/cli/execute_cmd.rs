@@ -4,18 +4,26 @@ use clap::Args;
 use nargo::artifacts::debug::DebugArtifact;
 use nargo::constants::PROVER_INPUT_FILE;
 use nargo::errors::try_to_diagnose_runtime_error;
+use nargo::ops::{ExecutionProfile, OracleResolver, ProfileReport};
 use nargo::package::Package;
+use nargo::NargoError;
 use nargo_toml::{get_package_manifest, resolve_workspace_from_toml, PackageSelection};
 use noirc_abi::input_parser::{Format, InputValue};
 use noirc_abi::InputMap;
 use noirc_driver::{CompileOptions, CompiledProgram, NOIR_ARTIFACT_VERSION_STRING};
 use noirc_frontend::graph::CrateName;
-
-use super::compile_cmd::compile_bin_package;
-use super::fs::{inputs::read_inputs_from_file, witness::save_witness_to_dir};
+use prettytable::{row, table};
+
+use super::compile_cmd::{compile_bin_package, save_r1cs, CompileFormat};
+use super::fs::{
+    inputs::read_inputs_from_file,
+    profile::save_profile_to_dir,
+    witness::{save_witness_to_dir, save_wtns_to_file},
+};
+use super::oracle_resolver::JsonRpcOracleResolver;
 use super::NargoConfig;
 use crate::backends::Backend;
-use crate::errors::CliError;
+use crate::errors::{CliError, CompileError};
 
 /// Executes a circuit to calculate its return value
 #[derive(Debug, Clone, Args)]
@@ -35,6 +43,21 @@ pub(crate) struct ExecuteCommand {
     #[clap(long, conflicts_with = "package")]
     workspace: bool,
 
+    /// Report the number of ACIR opcodes generated and Brillig opcodes executed by each line
+    /// of source code, and save these per call stack as folded stacks for flamegraph tools
+    #[clap(long)]
+    profile: bool,
+
+    /// The URL of a JSON-RPC server, or the command of a process speaking JSON-RPC over stdio,
+    /// which resolves any oracles that Nargo can't resolve itself
+    #[clap(long)]
+    oracle_resolver: Option<String>,
+
+    /// The format to compile the program to. With `r1cs`, the circuit and the solved witness are
+    /// also written to the target directory in the iden3 `.r1cs` and `.wtns` binary formats
+    #[clap(long, value_enum, default_value_t = CompileFormat::Acir)]
+    format: CompileFormat,
+
     #[clap(flatten)]
     compile_options: CompileOptions,
 }
@@ -52,10 +75,14 @@ pub(crate) fn run(
         &toml_path,
         selection,
         Some(NOIR_ARTIFACT_VERSION_STRING.to_string()),
+        config.dependency_resolution(),
     )?;
     let target_dir = &workspace.target_directory_path();
 
-    let (np_language, opcode_support) = backend.get_backend_info()?;
+    let mut oracle_resolver =
+        args.oracle_resolver.as_deref().map(JsonRpcOracleResolver::new).transpose()?;
+
+    let (np_language, opcode_support) = args.format.backend_info(backend)?;
     for package in &workspace {
         let compiled_program = compile_bin_package(
             &workspace,
@@ -65,40 +92,80 @@ pub(crate) fn run(
             &|opcode| opcode_support.is_opcode_supported(opcode),
         )?;
 
-        let (return_value, solved_witness) =
-            execute_program_and_decode(compiled_program, package, &args.prover_name)?;
+        let (return_value, solved_witness, profile) = execute_program_and_decode(
+            &compiled_program,
+            package,
+            &args.prover_name,
+            args.profile,
+            oracle_resolver.as_mut().map(|resolver| resolver as &mut dyn OracleResolver),
+        )?;
 
         println!("[{}] Circuit witness successfully solved", package.name);
         if let Some(return_value) = return_value {
             println!("[{}] Circuit output: {return_value:?}", package.name);
         }
         if let Some(witness_name) = &args.witness_name {
-            let witness_path = save_witness_to_dir(solved_witness, witness_name, target_dir)?;
+            let witness_path =
+                save_witness_to_dir(solved_witness.clone(), witness_name, target_dir)?;
 
             println!("[{}] Witness saved to {}", package.name, witness_path.display());
         }
+        if args.format == CompileFormat::R1cs {
+            let circuit_name = package.name.to_string();
+            let r1cs_path = save_r1cs(&compiled_program.circuit, &circuit_name, target_dir)?;
+            let wtns = solved_witness
+                .serialize_wtns(&compiled_program.circuit)
+                .map_err(|err| CompileError::R1CSExport(circuit_name.clone(), err))?;
+            let wtns_path = save_wtns_to_file(&wtns, &circuit_name, target_dir);
+
+            println!("[{}] R1CS saved to {}", package.name, r1cs_path.display());
+            println!("[{}] R1CS witness saved to {}", package.name, wtns_path.display());
+        }
+        if let Some(profile) = profile {
+            let debug_artifact: DebugArtifact = compiled_program.clone().into();
+            let report = ProfileReport::new(
+                &compiled_program.circuit,
+                &debug_artifact,
+                &compiled_program.debug,
+                &profile,
+            );
+            print_profile_report(&report);
+
+            let profile_paths = save_profile_to_dir(&report, &package.name.to_string(), target_dir);
+            for profile_path in profile_paths {
+                println!("[{}] Profile saved to {}", package.name, profile_path.display());
+            }
+        }
     }
     Ok(())
 }
 
 fn execute_program_and_decode(
-    program: CompiledProgram,
+    program: &CompiledProgram,
     package: &Package,
     prover_name: &str,
-) -> Result<(Option<InputValue>, WitnessMap), CliError> {
+    profile: bool,
+    oracle_resolver: Option<&mut dyn OracleResolver>,
+) -> Result<(Option<InputValue>, WitnessMap, Option<ExecutionProfile>), CliError> {
     // Parse the initial witness values from Prover.toml
     let (inputs_map, _) =
         read_inputs_from_file(&package.root_dir, prover_name, Format::Toml, &program.abi)?;
-    let solved_witness = execute_program(&program, &inputs_map)?;
+    let (solved_witness, profile) = if profile {
+        let (solved_witness, profile) = profile_program(program, &inputs_map, oracle_resolver)?;
+        (solved_witness, Some(profile))
+    } else {
+        (execute_program(program, &inputs_map, oracle_resolver)?, None)
+    };
     let public_abi = program.abi.public_abi();
     let (_, return_value) = public_abi.decode(&solved_witness)?;
 
-    Ok((return_value, solved_witness))
+    Ok((return_value, solved_witness, profile))
 }
 
 pub(crate) fn execute_program(
     compiled_program: &CompiledProgram,
     inputs_map: &InputMap,
+    oracle_resolver: Option<&mut dyn OracleResolver>,
 ) -> Result<WitnessMap, CliError> {
     #[allow(deprecated)]
     let blackbox_solver = barretenberg_blackbox_solver::BarretenbergSolver::new();
@@ -110,21 +177,62 @@ pub(crate) fn execute_program(
         &compiled_program.circuit,
         initial_witness,
         true,
+        oracle_resolver,
     );
-    match solved_witness_err {
-        Ok(solved_witness) => Ok(solved_witness),
-        Err(err) => {
-            let debug_artifact = DebugArtifact {
-                debug_symbols: vec![compiled_program.debug.clone()],
-                file_map: compiled_program.file_map.clone(),
-                warnings: compiled_program.warnings.clone(),
-            };
-
-            if let Some(diagnostic) = try_to_diagnose_runtime_error(&err, &compiled_program.debug) {
-                diagnostic.report(&debug_artifact, false);
-            }
+    solved_witness_err.map_err(|err| report_execution_error(compiled_program, err))
+}
 
-            Err(crate::errors::CliError::NargoError(err))
-        }
+/// Executes the program as [`execute_program`] does while recording how many times each
+/// Brillig opcode is executed.
+fn profile_program(
+    compiled_program: &CompiledProgram,
+    inputs_map: &InputMap,
+    oracle_resolver: Option<&mut dyn OracleResolver>,
+) -> Result<(WitnessMap, ExecutionProfile), CliError> {
+    #[allow(deprecated)]
+    let blackbox_solver = barretenberg_blackbox_solver::BarretenbergSolver::new();
+
+    let initial_witness = compiled_program.abi.encode(inputs_map, None)?;
+
+    nargo::ops::profile_circuit(
+        &blackbox_solver,
+        &compiled_program.circuit,
+        initial_witness,
+        true,
+        oracle_resolver,
+    )
+    .map_err(|err| report_execution_error(compiled_program, err))
+}
+
+fn report_execution_error(compiled_program: &CompiledProgram, err: NargoError) -> CliError {
+    let debug_artifact = DebugArtifact {
+        debug_symbols: vec![compiled_program.debug.clone()],
+        file_map: compiled_program.file_map.clone(),
+        warnings: compiled_program.warnings.clone(),
+    };
+
+    if let Some(diagnostic) = try_to_diagnose_runtime_error(&err, &compiled_program.debug) {
+        diagnostic.report(&debug_artifact, false);
+    }
+
+    CliError::NargoError(err)
+}
+
+/// Prints the cost of each source line, starting with the most expensive lines.
+fn print_profile_report(report: &ProfileReport) {
+    let mut lines: Vec<_> = report.lines.iter().collect();
+    lines.sort_by(|(_, a), (_, b)| {
+        b.acir_opcodes.cmp(&a.acir_opcodes).then_with(|| b.brillig_steps.cmp(&a.brillig_steps))
+    });
+
+    let mut table = table!([Fm->"File", Fm->"Line", Fm->"ACIR Opcodes", Fm->"Brillig Steps"]);
+    for ((path, line), cost) in lines {
+        table.add_row(row![
+            path,
+            Fc->line,
+            Fc->cost.acir_opcodes,
+            Fc->cost.brillig_steps,
+        ]);
     }
+    table.printstd();
 }
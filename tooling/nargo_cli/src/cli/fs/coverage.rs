@@ -0,0 +1,18 @@
+use std::path::{Path, PathBuf};
+
+use nargo::ops::CoverageReport;
+
+use super::{create_named_dir, write_to_file};
+
+/// Saves the coverage report as an lcov tracefile.
+pub(crate) fn save_coverage_to_dir<P: AsRef<Path>>(
+    report: &CoverageReport,
+    coverage_dir: P,
+) -> PathBuf {
+    create_named_dir(coverage_dir.as_ref(), "coverage");
+
+    let lcov_path = coverage_dir.as_ref().join("lcov.info");
+    write_to_file(report.to_lcov().as_bytes(), &lcov_path);
+
+    lcov_path
+}
@@ -0,0 +1,23 @@
+use std::path::{Path, PathBuf};
+
+use nargo::ops::ProfileReport;
+
+use super::{create_named_dir, write_to_file};
+
+/// Saves the folded stacks of ACIR opcodes and of Brillig steps, in that order,
+/// so that they can be rendered as flamegraphs.
+pub(crate) fn save_profile_to_dir<P: AsRef<Path>>(
+    report: &ProfileReport,
+    profile_name: &str,
+    profile_dir: P,
+) -> Vec<PathBuf> {
+    create_named_dir(profile_dir.as_ref(), "profile");
+
+    let acir_path = profile_dir.as_ref().join(format!("{profile_name}-acir-opcodes.folded"));
+    write_to_file(report.folded_acir_opcodes().as_bytes(), &acir_path);
+
+    let brillig_path = profile_dir.as_ref().join(format!("{profile_name}-brillig-steps.folded"));
+    write_to_file(report.folded_brillig_steps().as_bytes(), &brillig_path);
+
+    vec![acir_path, brillig_path]
+}
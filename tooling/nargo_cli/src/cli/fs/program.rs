@@ -1,9 +1,13 @@
 use std::path::{Path, PathBuf};
 
-use nargo::artifacts::{
-    contract::PreprocessedContract, debug::DebugArtifact, program::PreprocessedProgram,
+use nargo::{
+    artifacts::{
+        contract::PreprocessedContract, debug::DebugArtifact, program::PreprocessedProgram,
+    },
+    constants::R1CS_EXT,
 };
 use noirc_frontend::graph::CrateName;
+use serde::{Deserialize, Serialize};
 
 use crate::errors::FilesystemError;
 
@@ -35,6 +39,42 @@ pub(crate) fn save_debug_artifact_to_file<P: AsRef<Path>>(
     save_build_artifact_to_file(debug_artifact, &artifact_name, circuit_dir)
 }
 
+pub(crate) fn save_r1cs_to_file<P: AsRef<Path>>(
+    r1cs: &[u8],
+    circuit_name: &str,
+    circuit_dir: P,
+) -> PathBuf {
+    create_named_dir(circuit_dir.as_ref(), "target");
+    let r1cs_path = circuit_dir.as_ref().join(circuit_name).with_extension(R1CS_EXT);
+
+    write_to_file(r1cs, &r1cs_path);
+
+    r1cs_path
+}
+
+/// The fingerprint of the package which the build artifact named `artifact_name` was last
+/// compiled from, see [`nargo::ops::fingerprint_package`].
+#[derive(Serialize, Deserialize)]
+pub(crate) struct PackageFingerprint {
+    pub(crate) fingerprint: u64,
+    pub(crate) artifact_name: String,
+}
+
+pub(crate) fn save_fingerprint_to_file<P: AsRef<Path>>(
+    fingerprint: &PackageFingerprint,
+    crate_name: &CrateName,
+    circuit_dir: P,
+) -> PathBuf {
+    let artifact_name = format!("fingerprint_{crate_name}");
+    save_build_artifact_to_file(fingerprint, &artifact_name, circuit_dir)
+}
+
+pub(crate) fn delete_fingerprint_file<P: AsRef<Path>>(crate_name: &CrateName, circuit_dir: P) {
+    let file_path = circuit_dir.as_ref().join(format!("fingerprint_{crate_name}.json"));
+    // There is nothing to delete if the package was never compiled without warnings.
+    let _ = std::fs::remove_file(file_path);
+}
+
 fn save_build_artifact_to_file<P: AsRef<Path>, T: ?Sized + serde::Serialize>(
     build_artifact: &T,
     artifact_name: &str,
@@ -61,6 +101,33 @@ pub(crate) fn read_program_from_file<P: AsRef<Path>>(
     Ok(program)
 }
 
+pub(crate) fn read_contract_from_file<P: AsRef<Path>>(
+    circuit_path: P,
+) -> Result<PreprocessedContract, FilesystemError> {
+    let file_path = circuit_path.as_ref().with_extension("json");
+
+    let input_string =
+        std::fs::read(&file_path).map_err(|_| FilesystemError::PathNotValid(file_path))?;
+    let contract = serde_json::from_slice(&input_string)
+        .map_err(|err| FilesystemError::ProgramSerializationError(err.to_string()))?;
+
+    Ok(contract)
+}
+
+pub(crate) fn read_fingerprint_from_file<P: AsRef<Path>>(
+    crate_name: &CrateName,
+    circuit_dir: P,
+) -> Result<PackageFingerprint, FilesystemError> {
+    let file_path = circuit_dir.as_ref().join(format!("fingerprint_{crate_name}.json"));
+
+    let input_string =
+        std::fs::read(&file_path).map_err(|_| FilesystemError::PathNotValid(file_path))?;
+    let fingerprint = serde_json::from_slice(&input_string)
+        .map_err(|err| FilesystemError::ProgramSerializationError(err.to_string()))?;
+
+    Ok(fingerprint)
+}
+
 pub(crate) fn read_debug_artifact_from_file<P: AsRef<Path>>(
     debug_artifact_path: P,
 ) -> Result<DebugArtifact, FilesystemError> {
@@ -1,7 +1,7 @@
 use std::path::{Path, PathBuf};
 
 use acvm::acir::native_types::WitnessMap;
-use nargo::constants::WITNESS_EXT;
+use nargo::constants::{WITNESS_EXT, WTNS_EXT};
 
 use super::{create_named_dir, write_to_file};
 use crate::errors::FilesystemError;
@@ -20,3 +20,16 @@ pub(crate) fn save_witness_to_dir<P: AsRef<Path>>(
 
     Ok(witness_path)
 }
+
+pub(crate) fn save_wtns_to_file<P: AsRef<Path>>(
+    wtns: &[u8],
+    circuit_name: &str,
+    circuit_dir: P,
+) -> PathBuf {
+    create_named_dir(circuit_dir.as_ref(), "target");
+    let wtns_path = circuit_dir.as_ref().join(circuit_name).with_extension(WTNS_EXT);
+
+    write_to_file(wtns, &wtns_path);
+
+    wtns_path
+}
@@ -1,21 +1,27 @@
 use std::collections::HashMap;
 
+use acvm::acir::circuit::Circuit;
 use acvm::Language;
 use backend_interface::BackendError;
 use clap::Args;
 use iter_extended::vecmap;
-use nargo::{artifacts::debug::DebugArtifact, package::Package};
+use nargo::{
+    artifacts::debug::DebugArtifact, ops::find_under_constrained_witnesses, package::Package,
+};
 use nargo_toml::{get_package_manifest, resolve_workspace_from_toml, PackageSelection};
 use noirc_driver::{
     CompileOptions, CompiledContract, CompiledProgram, NOIR_ARTIFACT_VERSION_STRING,
 };
-use noirc_errors::{debug_info::OpCodesCount, Location};
+use noirc_errors::{
+    debug_info::{DebugInfo, OpCodesCount},
+    Location,
+};
 use noirc_frontend::graph::CrateName;
 use prettytable::{row, table, Row};
 use rayon::prelude::*;
 use serde::Serialize;
 
-use crate::backends::Backend;
+use crate::backends::{Backend, ProofSystem};
 use crate::errors::CliError;
 
 use super::{compile_cmd::compile_workspace, NargoConfig};
@@ -25,6 +31,7 @@ use super::{compile_cmd::compile_workspace, NargoConfig};
 /// Current information provided:
 /// 1. The number of ACIR opcodes
 /// 2. Counts the final number gates in the circuit used by a backend
+/// 3. Optionally, warns about witnesses computed by unconstrained code which are not constrained
 #[derive(Debug, Clone, Args)]
 pub(crate) struct InfoCommand {
     /// The name of the package to detail
@@ -42,6 +49,11 @@ pub(crate) struct InfoCommand {
     #[clap(long, hide = true)]
     profile_info: bool,
 
+    /// Warn about values computed by unconstrained functions or directives which the circuit
+    /// doesn't constrain, or only constrains linearly
+    #[clap(long)]
+    constraints: bool,
+
     #[clap(flatten)]
     compile_options: CompileOptions,
 }
@@ -59,6 +71,7 @@ pub(crate) fn run(
         &toml_path,
         selection,
         Some(NOIR_ARTIFACT_VERSION_STRING.to_string()),
+        config.dependency_resolution(),
     )?;
 
     let (binary_packages, contract_packages): (Vec<_>, Vec<_>) = workspace
@@ -94,6 +107,32 @@ pub(crate) fn run(
         }
     }
 
+    if args.constraints {
+        let mut under_constrained = 0;
+        for compiled_program in &compiled_programs {
+            let debug_artifact: DebugArtifact = compiled_program.clone().into();
+            under_constrained += report_under_constrained_witnesses(
+                &compiled_program.circuit,
+                &compiled_program.debug,
+                &debug_artifact,
+            );
+        }
+
+        for compiled_contract in &compiled_contracts {
+            let debug_artifact: DebugArtifact = compiled_contract.into();
+            for contract_function in &compiled_contract.functions {
+                under_constrained += report_under_constrained_witnesses(
+                    &contract_function.bytecode,
+                    &contract_function.debug,
+                    &debug_artifact,
+                );
+            }
+        }
+
+        let plural = if under_constrained == 1 { "" } else { "s" };
+        eprintln!("Found {under_constrained} under-constrained witness group{plural}");
+    }
+
     let program_info = binary_packages
         .into_par_iter()
         .zip(compiled_programs)
@@ -176,6 +215,21 @@ fn print_span_opcodes(
         );
     }
 }
+
+/// Reports the witnesses of `circuit` which are computed by unconstrained code but left
+/// unconstrained, returning the number of groups of such witnesses.
+fn report_under_constrained_witnesses(
+    circuit: &Circuit,
+    debug: &DebugInfo,
+    debug_artifact: &DebugArtifact,
+) -> usize {
+    let under_constrained = find_under_constrained_witnesses(circuit);
+    let diagnostics: Vec<_> =
+        under_constrained.iter().filter_map(|witnesses| witnesses.diagnostic(debug)).collect();
+    noirc_errors::reporter::report_all(debug_artifact, &diagnostics, false, false);
+    under_constrained.len()
+}
+
 fn byte_index(string: &str, index: u32) -> usize {
     let mut byte_index = 0;
     let mut char_index = 0;
@@ -1,6 +1,6 @@
 use clap::{Args, Parser, Subcommand};
 use const_format::formatcp;
-use nargo_toml::find_package_root;
+use nargo_toml::{find_package_root, DependencyResolution};
 use noirc_driver::NOIR_ARTIFACT_VERSION_STRING;
 use std::path::PathBuf;
 
@@ -9,11 +9,13 @@ use color_eyre::eyre;
 use crate::backends::get_active_backend;
 
 mod fs;
+mod oracle_resolver;
 
 mod backend_cmd;
 mod check_cmd;
 mod codegen_verifier_cmd;
 mod compile_cmd;
+mod dap_cmd;
 mod debug_cmd;
 mod execute_cmd;
 mod fmt_cmd;
@@ -23,6 +25,7 @@ mod lsp_cmd;
 mod new_cmd;
 mod prove_cmd;
 mod test_cmd;
+mod update_cmd;
 mod verify_cmd;
 
 const GIT_HASH: &str = env!("GIT_COMMIT");
@@ -53,6 +56,20 @@ pub(crate) struct NargoConfig {
     // REMINDER: Also change this flag in the LSP test lens if renamed
     #[arg(long, hide = true, global = true, default_value = "./")]
     program_dir: PathBuf,
+
+    /// Require Nargo.lock to be up to date, failing rather than updating it
+    #[arg(long, global = true)]
+    locked: bool,
+
+    /// Fail rather than fetch dependencies over the network
+    #[arg(long, global = true)]
+    offline: bool,
+}
+
+impl NargoConfig {
+    pub(crate) fn dependency_resolution(&self) -> DependencyResolution {
+        DependencyResolution { locked: self.locked, offline: self.offline, update: false }
+    }
 }
 
 #[non_exhaustive]
@@ -70,9 +87,12 @@ enum NargoCommand {
     Execute(execute_cmd::ExecuteCommand),
     #[command(hide = true)] // Hidden while the feature is being built out
     Debug(debug_cmd::DebugCommand),
+    #[command(hide = true)] // Hidden while the feature is being built out
+    Dap(dap_cmd::DapCommand),
     Prove(prove_cmd::ProveCommand),
     Verify(verify_cmd::VerifyCommand),
     Test(test_cmd::TestCommand),
+    Update(update_cmd::UpdateCommand),
     Info(info_cmd::InfoCommand),
     Lsp(lsp_cmd::LspCommand),
 }
@@ -91,6 +111,7 @@ pub(crate) fn start_cli() -> eyre::Result<()> {
         NargoCommand::New(_)
             | NargoCommand::Init(_)
             | NargoCommand::Lsp(_)
+            | NargoCommand::Dap(_)
             | NargoCommand::Backend(_)
     ) {
         config.program_dir = find_package_root(&config.program_dir)?;
@@ -105,6 +126,7 @@ pub(crate) fn start_cli() -> eyre::Result<()> {
         NargoCommand::Check(args) => check_cmd::run(&backend, args, config),
         NargoCommand::Compile(args) => compile_cmd::run(&backend, args, config),
         NargoCommand::Debug(args) => debug_cmd::run(&backend, args, config),
+        NargoCommand::Dap(args) => dap_cmd::run(&backend, args, config),
         NargoCommand::Execute(args) => execute_cmd::run(&backend, args, config),
         NargoCommand::Prove(args) => prove_cmd::run(&backend, args, config),
         NargoCommand::Verify(args) => verify_cmd::run(&backend, args, config),
@@ -114,6 +136,7 @@ pub(crate) fn start_cli() -> eyre::Result<()> {
         NargoCommand::Backend(args) => backend_cmd::run(args),
         NargoCommand::Lsp(args) => lsp_cmd::run(&backend, args, config),
         NargoCommand::Fmt(args) => fmt_cmd::run(args, config),
+        NargoCommand::Update(args) => update_cmd::run(args, config),
     }?;
 
     Ok(())
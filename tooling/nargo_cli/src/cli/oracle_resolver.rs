@@ -0,0 +1,273 @@
+use std::io::{BufRead, BufReader, Write};
+use std::process::{Child, ChildStdin, ChildStdout, Command, Stdio};
+
+use acvm::acir::brillig::{ForeignCallParam, ForeignCallResult, Value};
+use acvm::pwg::ForeignCallWaitInfo;
+use acvm::FieldElement;
+use nargo::ops::OracleResolver;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+use crate::errors::CliError;
+
+/// Resolves oracles by sending each foreign call as a JSON-RPC 2.0 request, either to an HTTP
+/// server or to a spawned process.
+///
+/// The method of a request is the name of the oracle and its params are the oracle's inputs, each
+/// being either a single field or an array of fields. The result of the response holds the
+/// outputs in the same form. Fields are encoded as hex strings.
+#[derive(Debug)]
+pub(crate) struct JsonRpcOracleResolver {
+    transport: Transport,
+    next_id: u64,
+}
+
+#[derive(Debug)]
+enum Transport {
+    /// Each request is POSTed to the server at `url`.
+    Http { client: reqwest::blocking::Client, url: String },
+    /// Each request is written to the process' stdin as a single line, with the process
+    /// expected to answer with its response as a single line on stdout.
+    Process { process: Child, stdin: ChildStdin, stdout: BufReader<ChildStdout> },
+}
+
+/// A single input or output of an oracle, as it is sent over JSON-RPC.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(untagged)]
+enum JsonRpcParam {
+    Single(String),
+    Array(Vec<String>),
+}
+
+#[derive(Debug, Deserialize)]
+struct JsonRpcResponse {
+    id: Option<serde_json::Value>,
+    result: Option<Vec<JsonRpcParam>>,
+    error: Option<JsonRpcError>,
+}
+
+#[derive(Debug, Deserialize)]
+struct JsonRpcError {
+    code: i64,
+    message: String,
+}
+
+impl JsonRpcOracleResolver {
+    /// Connects to `resolver`, which is either the URL of a JSON-RPC server or a command which
+    /// spawns a process speaking JSON-RPC over its stdio. The command is split into arguments as
+    /// a shell would, so that arguments containing spaces can be quoted.
+    pub(crate) fn new(resolver: &str) -> Result<Self, CliError> {
+        let transport = if resolver.starts_with("http://") || resolver.starts_with("https://") {
+            Transport::Http { client: reqwest::blocking::Client::new(), url: resolver.to_owned() }
+        } else {
+            let args = shell_words::split(resolver).map_err(|err| {
+                CliError::Generic(format!("Invalid oracle resolver command `{resolver}`: {err}"))
+            })?;
+            let (program, args) = args
+                .split_first()
+                .ok_or_else(|| CliError::Generic("Oracle resolver cannot be empty".to_owned()))?;
+            let mut process = Command::new(program)
+                .args(args)
+                .stdin(Stdio::piped())
+                .stdout(Stdio::piped())
+                .spawn()
+                .map_err(|err| {
+                    CliError::Generic(format!(
+                        "Could not start oracle resolver `{resolver}`: {err}"
+                    ))
+                })?;
+            let stdin = process.stdin.take().expect("stdin should be piped");
+            let stdout = BufReader::new(process.stdout.take().expect("stdout should be piped"));
+            Transport::Process { process, stdin, stdout }
+        };
+
+        Ok(JsonRpcOracleResolver { transport, next_id: 0 })
+    }
+
+    fn send(&mut self, request: &serde_json::Value) -> Result<JsonRpcResponse, String> {
+        match &mut self.transport {
+            Transport::Http { client, url } => client
+                .post(url.as_str())
+                .header("Content-Type", "application/json")
+                .body(request.to_string())
+                .send()
+                .and_then(|response| response.error_for_status())
+                .and_then(|response| response.text())
+                .map_err(|err| err.to_string())
+                .and_then(|body| serde_json::from_str(&body).map_err(|err| err.to_string())),
+            Transport::Process { stdin, stdout, .. } => {
+                writeln!(stdin, "{request}").and_then(|_| stdin.flush()).map_err(|err| {
+                    format!("Could not write to the oracle resolver process: {err}")
+                })?;
+
+                let mut line = String::new();
+                match stdout.read_line(&mut line) {
+                    Ok(0) => Err("The oracle resolver process exited".to_owned()),
+                    Ok(_) => serde_json::from_str(&line).map_err(|err| err.to_string()),
+                    Err(err) => {
+                        Err(format!("Could not read from the oracle resolver process: {err}"))
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl OracleResolver for JsonRpcOracleResolver {
+    fn resolve(&mut self, foreign_call: &ForeignCallWaitInfo) -> Result<ForeignCallResult, String> {
+        let params: Vec<_> = foreign_call.inputs.iter().map(encode_param).collect();
+        let id = self.next_id;
+        self.next_id += 1;
+        let request = json!({
+            "jsonrpc": "2.0",
+            "id": id,
+            "method": foreign_call.function,
+            "params": params,
+        });
+
+        let response = self.send(&request)?;
+        if response.id != Some(json!(id)) {
+            let response_id = response.id.unwrap_or(serde_json::Value::Null);
+            return Err(format!("Response id {response_id} doesn't match the request id {id}"));
+        }
+        match (response.result, response.error) {
+            (_, Some(JsonRpcError { code, message })) => Err(format!("{message} (code {code})")),
+            (Some(result), None) => {
+                let values = result.iter().map(decode_param).collect::<Result<_, _>>()?;
+                Ok(ForeignCallResult { values })
+            }
+            (None, None) => Err("Response has neither a result nor an error".to_owned()),
+        }
+    }
+}
+
+impl Drop for JsonRpcOracleResolver {
+    fn drop(&mut self) {
+        if let Transport::Process { process, .. } = &mut self.transport {
+            // The process may well have exited already, in which case there is nothing to do.
+            let _ = process.kill();
+            let _ = process.wait();
+        }
+    }
+}
+
+fn encode_param(param: &ForeignCallParam) -> JsonRpcParam {
+    let encode_value = |value: &Value| format!("0x{}", value.to_field().to_hex());
+    match param {
+        ForeignCallParam::Single(value) => JsonRpcParam::Single(encode_value(value)),
+        ForeignCallParam::Array(values) => {
+            JsonRpcParam::Array(values.iter().map(encode_value).collect())
+        }
+    }
+}
+
+fn decode_param(param: &JsonRpcParam) -> Result<ForeignCallParam, String> {
+    let decode_value = |hex: &String| {
+        FieldElement::from_hex(hex)
+            .map(Value::from)
+            .ok_or_else(|| format!("Invalid hex for field element: {hex}"))
+    };
+    match param {
+        JsonRpcParam::Single(value) => decode_value(value).map(ForeignCallParam::Single),
+        JsonRpcParam::Array(values) => {
+            values.iter().map(decode_value).collect::<Result<_, _>>().map(ForeignCallParam::Array)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use acvm::acir::brillig::{ForeignCallParam, Value};
+    use acvm::pwg::ForeignCallWaitInfo;
+    use nargo::ops::OracleResolver;
+
+    use super::{decode_param, encode_param, JsonRpcOracleResolver};
+
+    #[test]
+    fn params_roundtrip_through_json() {
+        let params = vec![
+            ForeignCallParam::Single(Value::from(42u128)),
+            ForeignCallParam::Array(vec![Value::from(1u128), Value::from(u128::MAX)]),
+        ];
+
+        let json = serde_json::to_string(&params.iter().map(encode_param).collect::<Vec<_>>())
+            .expect("params should serialize");
+        let decoded: Vec<super::JsonRpcParam> =
+            serde_json::from_str(&json).expect("params should deserialize");
+        let decoded: Vec<_> =
+            decoded.iter().map(|param| decode_param(param).expect("param should decode")).collect();
+
+        assert_eq!(decoded, params);
+    }
+
+    /// Starts a process which replies to each request with `response`, in which `%s` is replaced
+    /// by the id of the request.
+    fn echo_resolver(response: &str) -> JsonRpcOracleResolver {
+        let response = response.replace('"', "\\\"");
+        let script = [
+            "while IFS= read -r line; do",
+            r#"id=${line#*\"id\":};"#,
+            format!(r#"printf "{response}\n" "${{id%%,*}}";"#).as_str(),
+            "done",
+        ]
+        .join(" ");
+        JsonRpcOracleResolver::new(&format!("sh -c '{script}'")).unwrap()
+    }
+
+    fn foreign_call(function: &str) -> ForeignCallWaitInfo {
+        ForeignCallWaitInfo {
+            function: function.to_owned(),
+            inputs: vec![ForeignCallParam::Single(Value::from(1u128))],
+        }
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn resolves_oracles_over_stdio() {
+        let response = r#"{"jsonrpc":"2.0","id":%s,"result":["0x2a",["0x01"]]}"#;
+        let mut resolver = echo_resolver(response);
+        for _ in 0..2 {
+            let result = resolver.resolve(&foreign_call("get_answer")).unwrap();
+            assert_eq!(
+                result.values,
+                vec![
+                    ForeignCallParam::Single(Value::from(42u128)),
+                    ForeignCallParam::Array(vec![Value::from(1u128)]),
+                ]
+            );
+        }
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn reports_error_responses() {
+        let mut resolver = echo_resolver(
+            r#"{"jsonrpc":"2.0","id":%s,"error":{"code":-32601,"message":"Method not found"}}"#,
+        );
+        let error = resolver.resolve(&foreign_call("unknown")).unwrap_err();
+        assert_eq!(error, "Method not found (code -32601)");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn rejects_responses_to_other_requests() {
+        // The id of the response is the id of the request preceded by a 1
+        let mut resolver = echo_resolver(r#"{"jsonrpc":"2.0","id":1%s,"result":[]}"#);
+        let error = resolver.resolve(&foreign_call("get_answer")).unwrap_err();
+        assert_eq!(error, "Response id 10 doesn't match the request id 0");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn reports_exited_processes() {
+        let mut resolver = JsonRpcOracleResolver::new("sh -c 'read -r line'").unwrap();
+        let error = resolver.resolve(&foreign_call("get_answer")).unwrap_err();
+        assert_eq!(error, "The oracle resolver process exited");
+    }
+
+    #[test]
+    fn rejects_invalid_commands() {
+        assert!(JsonRpcOracleResolver::new("").is_err());
+        assert!(JsonRpcOracleResolver::new("sh -c 'unterminated").is_err());
+    }
+}
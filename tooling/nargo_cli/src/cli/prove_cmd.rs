@@ -13,7 +13,11 @@ use super::fs::{
     proof::save_proof_to_dir,
 };
 use super::NargoConfig;
-use crate::{backends::Backend, cli::execute_cmd::execute_program, errors::CliError};
+use crate::{
+    backends::{Backend, ProofSystem},
+    cli::execute_cmd::execute_program,
+    errors::CliError,
+};
 
 /// Create proof for this program. The proof is returned as a hex encoded string.
 #[derive(Debug, Clone, Args)]
@@ -55,6 +59,7 @@ pub(crate) fn run(
         &toml_path,
         selection,
         Some(NOIR_ARTIFACT_VERSION_STRING.to_string()),
+        config.dependency_resolution(),
     )?;
 
     let (np_language, opcode_support) = backend.get_backend_info()?;
@@ -94,7 +99,7 @@ pub(crate) fn prove_package(
     let (inputs_map, _) =
         read_inputs_from_file(&package.root_dir, prover_name, Format::Toml, &compiled_program.abi)?;
 
-    let solved_witness = execute_program(&compiled_program, &inputs_map)?;
+    let solved_witness = execute_program(&compiled_program, &inputs_map, None)?;
 
     // Write public inputs into Verifier.toml
     let public_abi = compiled_program.abi.public_abi();
@@ -1,21 +1,40 @@
-use std::io::Write;
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::time::Instant;
 
-use acvm::BlackBoxFunctionSolver;
+use acvm::acir::brillig::ForeignCallResult;
+use acvm::pwg::ForeignCallWaitInfo;
 use clap::Args;
 use nargo::{
-    ops::{run_test, TestStatus},
+    ops::{run_test, CoverageReport, FuzzConfig, OracleResolver, TestStatus, DEFAULT_FUZZ_RUNS},
     package::Package,
     prepare_package,
 };
 use nargo_toml::{get_package_manifest, resolve_workspace_from_toml, PackageSelection};
 use noirc_driver::{CompileOptions, NOIR_ARTIFACT_VERSION_STRING};
-use noirc_frontend::{graph::CrateName, hir::FunctionNameMatch};
-use termcolor::{Color, ColorChoice, ColorSpec, StandardStream, WriteColor};
+use noirc_errors::FileDiagnostic;
+use noirc_frontend::{
+    graph::{CrateId, CrateName},
+    hir::{
+        def_map::{ParseCache, TestFunction},
+        Context, FunctionNameMatch,
+    },
+};
+use rayon::prelude::*;
+use termcolor::{Buffer, NoColor};
 
 use crate::{backends::Backend, cli::check_cmd::check_crate_and_report_errors, errors::CliError};
 
+use super::fs::coverage::save_coverage_to_dir;
+use super::oracle_resolver::JsonRpcOracleResolver;
 use super::NargoConfig;
 
+mod formatters;
+
+use formatters::{TestFormat, TestOutcome, TestReporter, TestResult};
+
 /// Run the tests for this program
 #[derive(Debug, Clone, Args)]
 pub(crate) struct TestCommand {
@@ -38,6 +57,28 @@ pub(crate) struct TestCommand {
     #[clap(long, conflicts_with = "package")]
     workspace: bool,
 
+    /// The URL of a JSON-RPC server, or the command of a process speaking JSON-RPC over stdio,
+    /// which resolves any oracles that Nargo can't resolve itself
+    #[clap(long)]
+    oracle_resolver: Option<String>,
+
+    /// The number of times to run each test function which takes parameters, each time with
+    /// randomly generated inputs
+    #[clap(long, default_value_t = DEFAULT_FUZZ_RUNS)]
+    fuzz_runs: u32,
+
+    /// The seed from which the inputs of test functions are generated, to reproduce a failure
+    #[clap(long)]
+    fuzz_seed: Option<u64>,
+
+    /// Record which lines are executed by the tests, saving an lcov report to the target directory
+    #[clap(long)]
+    coverage: bool,
+
+    /// The format in which test results are reported
+    #[clap(long, value_enum, default_value_t = TestFormat::Pretty)]
+    format: TestFormat,
+
     #[clap(flatten)]
     compile_options: CompileOptions,
 }
@@ -55,6 +96,7 @@ pub(crate) fn run(
         &toml_path,
         selection,
         Some(NOIR_ARTIFACT_VERSION_STRING.to_string()),
+        config.dependency_resolution(),
     )?;
 
     let pattern = match &args.test_name {
@@ -68,93 +110,223 @@ pub(crate) fn run(
         None => FunctionNameMatch::Anything,
     };
 
-    #[allow(deprecated)]
-    let blackbox_solver = barretenberg_blackbox_solver::BarretenbergSolver::new();
-    for package in &workspace {
-        // By unwrapping here with `?`, we stop the test runner upon a package failing
-        // TODO: We should run the whole suite even if there are failures in a package
-        run_tests(&blackbox_solver, package, pattern, args.show_output, &args.compile_options)?;
-    }
+    let reporter = TestReporter::new(args.format);
+    let parse_cache = ParseCache::default();
 
-    Ok(())
-}
+    // Each package is checked up front so that its errors are reported once, rather than by
+    // every thread which runs one of its tests.
+    let mut test_names = Vec::new();
+    for package in &workspace {
+        let (mut context, crate_id) = prepare_package_context(package, &parse_cache);
+        check_crate_and_report_errors(
+            &mut context,
+            crate_id,
+            args.compile_options.deny_warnings,
+            args.compile_options.silence_warnings,
+        )?;
 
-fn run_tests<S: BlackBoxFunctionSolver>(
-    blackbox_solver: &S,
-    package: &Package,
-    test_name: FunctionNameMatch,
-    show_output: bool,
-    compile_options: &CompileOptions,
-) -> Result<(), CliError> {
-    let (mut context, crate_id) =
-        prepare_package(package, Box::new(|path| std::fs::read_to_string(path)));
-    check_crate_and_report_errors(
-        &mut context,
-        crate_id,
-        compile_options.deny_warnings,
-        compile_options.silence_warnings,
-    )?;
+        let names: Vec<String> = context
+            .get_all_test_functions_in_crate_matching(&crate_id, pattern)
+            .into_iter()
+            .map(|(test_name, _)| test_name)
+            .collect();
+        reporter.package_started(&package.name.to_string(), names.len());
+        test_names.push(names);
+    }
 
-    let test_functions = context.get_all_test_functions_in_crate_matching(&crate_id, test_name);
+    let oracle_resolver = args
+        .oracle_resolver
+        .as_deref()
+        .map(JsonRpcOracleResolver::new)
+        .transpose()?
+        .map(Mutex::new);
+    let runner = TestRunner {
+        packages: (&workspace).into_iter().collect(),
+        tests: test_names
+            .iter()
+            .enumerate()
+            .flat_map(|(package_index, names)| names.iter().map(move |name| (package_index, name)))
+            .collect(),
+        next_test: AtomicUsize::new(0),
+        capture_output: reporter.captures_output(args.show_output),
+        colored_diagnostics: reporter.colored_diagnostics(),
+        compile_options: &args.compile_options,
+        fuzz_config: FuzzConfig::new(args.fuzz_runs, args.fuzz_seed),
+        parse_cache: &parse_cache,
+        oracle_resolver: oracle_resolver.as_ref(),
+        reporter: Mutex::new(reporter),
+        coverage: args.coverage.then(|| Mutex::new(CoverageReport::default())),
+    };
 
-    println!("[{}] Running {} test functions", package.name, test_functions.len());
-    let mut failing = 0;
+    let workers = rayon::current_num_threads().min(runner.tests.len());
+    (0..workers).into_par_iter().for_each(|_| runner.run_worker());
 
-    let writer = StandardStream::stderr(ColorChoice::Always);
-    let mut writer = writer.lock();
+    let reporter =
+        runner.reporter.into_inner().expect("test reporter mutex should not be poisoned");
+    let failing = reporter.finish();
 
-    for (test_name, test_function) in test_functions {
-        write!(writer, "[{}] Testing {test_name}... ", package.name)
-            .expect("Failed to write to stdout");
-        writer.flush().expect("Failed to flush writer");
+    if let Some(coverage) = runner.coverage {
+        let mut coverage = coverage.into_inner().expect("coverage mutex should not be poisoned");
+        // Only the packages being tested are of interest, rather than the standard library or
+        // any dependencies
+        coverage.files.retain(|path, _| {
+            runner.packages.iter().any(|package| Path::new(path).starts_with(&package.root_dir))
+        });
+        let lcov_path = save_coverage_to_dir(&coverage, workspace.target_directory_path());
 
-        match run_test(blackbox_solver, &context, test_function, show_output, compile_options) {
-            TestStatus::Pass { .. } => {
-                writer
-                    .set_color(ColorSpec::new().set_fg(Some(Color::Green)))
-                    .expect("Failed to set color");
-                writeln!(writer, "ok").expect("Failed to write to stdout");
-            }
-            TestStatus::Fail { message, error_diagnostic } => {
-                let writer = StandardStream::stderr(ColorChoice::Always);
-                let mut writer = writer.lock();
-                writer
-                    .set_color(ColorSpec::new().set_fg(Some(Color::Red)))
-                    .expect("Failed to set color");
-                writeln!(writer, "{message}").expect("Failed to write to stdout");
-                writer.reset().expect("Failed to reset writer");
-                if let Some(diag) = error_diagnostic {
-                    noirc_errors::reporter::report_all(
-                        context.file_manager.as_file_map(),
-                        &[diag],
-                        compile_options.deny_warnings,
-                        compile_options.silence_warnings,
-                    );
-                }
-                failing += 1;
-            }
-            TestStatus::CompileError(err) => {
-                noirc_errors::reporter::report_all(
-                    context.file_manager.as_file_map(),
-                    &[err],
-                    compile_options.deny_warnings,
-                    compile_options.silence_warnings,
-                );
-                failing += 1;
-            }
+        let (found, hit) = coverage.line_counts();
+        let percentage = if found == 0 { 100.0 } else { 100.0 * hit as f64 / found as f64 };
+        let summary = format!(
+            "Coverage: {hit} of {found} lines ({percentage:.1}%), saved to {}",
+            lcov_path.display()
+        );
+        // Keep stdout free for the report in the other formats
+        if args.format == TestFormat::Pretty {
+            println!("{summary}");
+        } else {
+            eprintln!("{summary}");
         }
-        writer.reset().expect("Failed to reset writer");
     }
 
     if failing == 0 {
-        write!(writer, "[{}] ", package.name).expect("Failed to write to stdout");
-        writer.set_color(ColorSpec::new().set_fg(Some(Color::Green))).expect("Failed to set color");
-        writeln!(writer, "All tests passed").expect("Failed to write to stdout");
+        Ok(())
     } else {
         let plural = if failing == 1 { "" } else { "s" };
-        return Err(CliError::Generic(format!("[{}] {failing} test{plural} failed", package.name)));
+        Err(CliError::Generic(format!("{failing} test{plural} failed")))
+    }
+}
+
+/// Runs the tests of a workspace on several threads at once.
+///
+/// Neither a [`Context`] nor the blackbox solver can be shared between threads, so each thread
+/// checks its own copy of every package it runs tests from. Sharing the [`ParseCache`] means
+/// that the files of each package are still only parsed once.
+struct TestRunner<'a> {
+    packages: Vec<&'a Package>,
+    /// Every test to run, as the index of its package along with its name
+    tests: Vec<(usize, &'a String)>,
+    /// The index into `tests` of the next test which should be picked up by a thread
+    next_test: AtomicUsize,
+    /// Whether the output of the tests' `println` calls is captured for the report
+    capture_output: bool,
+    colored_diagnostics: bool,
+    compile_options: &'a CompileOptions,
+    fuzz_config: FuzzConfig,
+    parse_cache: &'a ParseCache,
+    oracle_resolver: Option<&'a Mutex<JsonRpcOracleResolver>>,
+    reporter: Mutex<TestReporter>,
+    /// The lines executed by every test which has finished, if coverage is being recorded
+    coverage: Option<Mutex<CoverageReport>>,
+}
+
+impl TestRunner<'_> {
+    /// Runs tests until there are none left.
+    fn run_worker(&self) {
+        #[allow(deprecated)]
+        let blackbox_solver = barretenberg_blackbox_solver::BarretenbergSolver::new();
+        let mut contexts: HashMap<usize, (Context, HashMap<String, TestFunction>)> = HashMap::new();
+        let mut coverage = self.coverage.as_ref().map(|_| CoverageReport::default());
+
+        loop {
+            let test_index = self.next_test.fetch_add(1, Ordering::Relaxed);
+            let Some(&(package_index, test_name)) = self.tests.get(test_index) else {
+                break;
+            };
+            let package = self.packages[package_index];
+            let (context, test_functions) =
+                contexts.entry(package_index).or_insert_with(|| self.check_package(package));
+            let test_function = test_functions
+                .remove(test_name)
+                .expect("test function should have been found when checking its package");
+
+            let mut oracle_resolver = self.oracle_resolver.map(SharedOracleResolver);
+            let start = Instant::now();
+            let (status, output) = run_test(
+                &blackbox_solver,
+                context,
+                test_function,
+                self.capture_output,
+                self.compile_options,
+                &self.fuzz_config,
+                coverage.as_mut(),
+                oracle_resolver.as_mut().map(|resolver| resolver as &mut dyn OracleResolver),
+            );
+            let duration = start.elapsed();
+
+            let (outcome, message, diagnostic) = match status {
+                TestStatus::Pass => (TestOutcome::Pass, None, None),
+                TestStatus::Fail { message, error_diagnostic } => {
+                    (TestOutcome::Fail, Some(message), error_diagnostic)
+                }
+                TestStatus::CompileError(diagnostic) => {
+                    (TestOutcome::CompileError, None, Some(diagnostic))
+                }
+            };
+            let result = TestResult {
+                index: test_index,
+                package: package.name.to_string(),
+                name: test_name.clone(),
+                outcome,
+                duration,
+                output,
+                message,
+                diagnostic: diagnostic.map(|diagnostic| self.render(context, &diagnostic)),
+            };
+            self.reporter
+                .lock()
+                .expect("test reporter mutex should not be poisoned")
+                .test_finished(result);
+        }
+
+        if let (Some(shared_coverage), Some(coverage)) = (&self.coverage, coverage) {
+            shared_coverage.lock().expect("coverage mutex should not be poisoned").merge(coverage);
+        }
     }
 
-    writer.reset().expect("Failed to reset writer");
-    Ok(())
+    fn check_package(&self, package: &Package) -> (Context, HashMap<String, TestFunction>) {
+        let (mut context, crate_id) = prepare_package_context(package, self.parse_cache);
+        // Any errors have already been reported when the package was first checked
+        let _ =
+            noirc_driver::check_crate(&mut context, crate_id, self.compile_options.deny_warnings);
+
+        let test_functions = context
+            .get_all_test_functions_in_crate_matching(&crate_id, FunctionNameMatch::Anything)
+            .into_iter()
+            .collect();
+        (context, test_functions)
+    }
+
+    fn render(&self, context: &Context, diagnostic: &FileDiagnostic) -> String {
+        let files = context.file_manager.as_file_map();
+        let deny_warnings = self.compile_options.deny_warnings;
+        if self.colored_diagnostics {
+            let mut buffer = Buffer::ansi();
+            diagnostic.emit(files, deny_warnings, &mut buffer);
+            String::from_utf8_lossy(buffer.as_slice()).into_owned()
+        } else {
+            let mut writer = NoColor::new(Vec::new());
+            diagnostic.emit(files, deny_warnings, &mut writer);
+            String::from_utf8_lossy(&writer.into_inner()).into_owned()
+        }
+    }
+}
+
+fn prepare_package_context(package: &Package, parse_cache: &ParseCache) -> (Context, CrateId) {
+    let (mut context, crate_id) =
+        prepare_package(package, Box::new(|path| std::fs::read_to_string(path)));
+    context.set_parse_cache(parse_cache.clone());
+    (context, crate_id)
+}
+
+/// Lets the tests running on each thread take turns using the same oracle resolver.
+#[derive(Debug)]
+struct SharedOracleResolver<'a>(&'a Mutex<JsonRpcOracleResolver>);
+
+impl OracleResolver for SharedOracleResolver<'_> {
+    fn resolve(&mut self, foreign_call: &ForeignCallWaitInfo) -> Result<ForeignCallResult, String> {
+        self.0
+            .lock()
+            .map_err(|_| "Oracle resolver is unavailable".to_owned())?
+            .resolve(foreign_call)
+    }
 }
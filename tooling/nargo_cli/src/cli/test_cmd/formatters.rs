@@ -0,0 +1,286 @@
+use std::io::Write;
+use std::time::Duration;
+
+use clap::ValueEnum;
+use serde::Serialize;
+use termcolor::{Color, ColorChoice, ColorSpec, StandardStream, WriteColor};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub(crate) enum TestFormat {
+    /// Human readable output, printed as each test finishes
+    Pretty,
+    /// A single JSON object describing every test, printed once all tests have finished
+    Json,
+    /// A JUnit XML report, printed once all tests have finished
+    Junit,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum TestOutcome {
+    Pass,
+    Fail,
+    CompileError,
+}
+
+/// The result of running a single test function.
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct TestResult {
+    /// The position of the test in the order in which tests were collected
+    #[serde(skip)]
+    pub(crate) index: usize,
+    pub(crate) package: String,
+    pub(crate) name: String,
+    pub(crate) outcome: TestOutcome,
+    /// The time taken to compile and execute the test, in seconds
+    #[serde(serialize_with = "serialize_duration")]
+    pub(crate) duration: Duration,
+    /// The output of the test's `println` calls, which the pretty format only captures if
+    /// `--show-output` was passed
+    pub(crate) output: String,
+    /// Why the test failed, for tests which didn't fail to compile
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) message: Option<String>,
+    /// The rendered diagnostic pointing at the cause of the failure
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) diagnostic: Option<String>,
+}
+
+impl TestResult {
+    pub(crate) fn passed(&self) -> bool {
+        self.outcome == TestOutcome::Pass
+    }
+}
+
+fn serialize_duration<S: serde::Serializer>(
+    duration: &Duration,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    serializer.serialize_f64(duration.as_secs_f64())
+}
+
+/// Reports the results of tests as they finish, in one of the [`TestFormat`]s.
+pub(crate) struct TestReporter {
+    format: TestFormat,
+    results: Vec<TestResult>,
+}
+
+impl TestReporter {
+    pub(crate) fn new(format: TestFormat) -> Self {
+        TestReporter { format, results: Vec::new() }
+    }
+
+    /// Whether the output of tests should be captured for this format. The machine readable
+    /// formats always include it, while it is only printed to the terminal with `--show-output`.
+    pub(crate) fn captures_output(&self, show_output: bool) -> bool {
+        show_output || self.format != TestFormat::Pretty
+    }
+
+    /// Whether diagnostics should be rendered with colors for this format.
+    pub(crate) fn colored_diagnostics(&self) -> bool {
+        self.format == TestFormat::Pretty
+    }
+
+    pub(crate) fn package_started(&self, package: &str, test_count: usize) {
+        if self.format == TestFormat::Pretty {
+            println!("[{package}] Running {test_count} test functions");
+        }
+    }
+
+    pub(crate) fn test_finished(&mut self, result: TestResult) {
+        if self.format == TestFormat::Pretty {
+            print_pretty(&result);
+        }
+        self.results.push(result);
+    }
+
+    /// Prints the report of every test, returning the number of tests which failed.
+    ///
+    /// Results are reported in the order in which the tests were collected, rather than the
+    /// order in which they finished.
+    pub(crate) fn finish(mut self) -> usize {
+        self.results.sort_by_key(|result| result.index);
+        let failing = self.results.iter().filter(|result| !result.passed()).count();
+
+        match self.format {
+            TestFormat::Pretty => print_pretty_summary(&self.results),
+            TestFormat::Json => print_json(&self.results, failing),
+            TestFormat::Junit => println!("{}", junit_report(&self.results)),
+        }
+        failing
+    }
+}
+
+fn print_pretty(result: &TestResult) {
+    let writer = StandardStream::stderr(ColorChoice::Always);
+    let mut writer = writer.lock();
+
+    write!(writer, "[{}] Testing {}... ", result.package, result.name)
+        .expect("Failed to write to stderr");
+    let color = if result.passed() { Color::Green } else { Color::Red };
+    writer.set_color(ColorSpec::new().set_fg(Some(color))).expect("Failed to set color");
+    match &result.message {
+        Some(message) => writeln!(writer, "{message}"),
+        None if result.passed() => writeln!(writer, "ok"),
+        None => writeln!(writer, "failed"),
+    }
+    .expect("Failed to write to stderr");
+    writer.reset().expect("Failed to reset writer");
+
+    if !result.output.is_empty() {
+        write!(writer, "{}", result.output).expect("Failed to write to stderr");
+    }
+    if let Some(diagnostic) = &result.diagnostic {
+        write!(writer, "{diagnostic}").expect("Failed to write to stderr");
+    }
+}
+
+fn print_pretty_summary(results: &[TestResult]) {
+    let writer = StandardStream::stderr(ColorChoice::Always);
+    let mut writer = writer.lock();
+
+    let mut packages: Vec<&str> = results.iter().map(|result| result.package.as_str()).collect();
+    packages.dedup();
+    for package in packages {
+        let package_results = results.iter().filter(|result| result.package == package);
+        if package_results.clone().all(TestResult::passed) {
+            write!(writer, "[{package}] ").expect("Failed to write to stderr");
+            writer
+                .set_color(ColorSpec::new().set_fg(Some(Color::Green)))
+                .expect("Failed to set color");
+            writeln!(writer, "All tests passed").expect("Failed to write to stderr");
+            writer.reset().expect("Failed to reset writer");
+        }
+    }
+}
+
+fn print_json(results: &[TestResult], failing: usize) {
+    #[derive(Serialize)]
+    struct JsonReport<'a> {
+        tests: &'a [TestResult],
+        passed: usize,
+        failed: usize,
+    }
+
+    let report = JsonReport { tests: results, passed: results.len() - failing, failed: failing };
+    println!("{}", serde_json::to_string(&report).expect("test report should be serializable"));
+}
+
+fn junit_report(results: &[TestResult]) -> String {
+    let mut packages: Vec<&str> = results.iter().map(|result| result.package.as_str()).collect();
+    packages.dedup();
+
+    let total_time: f64 = results.iter().map(|result| result.duration.as_secs_f64()).sum();
+    let failing = results.iter().filter(|result| !result.passed()).count();
+
+    let mut report = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    report.push_str(&format!(
+        "<testsuites name=\"nargo test\" tests=\"{}\" failures=\"{failing}\" time=\"{total_time:.3}\">\n",
+        results.len()
+    ));
+    for package in packages {
+        let package_results: Vec<_> =
+            results.iter().filter(|result| result.package == package).collect();
+        let package_time: f64 =
+            package_results.iter().map(|result| result.duration.as_secs_f64()).sum();
+        let package_failing = package_results.iter().filter(|result| !result.passed()).count();
+
+        report.push_str(&format!(
+            "  <testsuite name=\"{}\" tests=\"{}\" failures=\"{package_failing}\" time=\"{package_time:.3}\">\n",
+            xml_escape(package),
+            package_results.len()
+        ));
+        for result in package_results {
+            report.push_str(&format!(
+                "    <testcase name=\"{}\" classname=\"{}\" time=\"{:.3}\">\n",
+                xml_escape(&result.name),
+                xml_escape(package),
+                result.duration.as_secs_f64()
+            ));
+            if !result.passed() {
+                let message = result.message.as_deref().unwrap_or("compilation failed");
+                let diagnostic = result.diagnostic.as_deref().unwrap_or_default();
+                report.push_str(&format!(
+                    "      <failure message=\"{}\">{}</failure>\n",
+                    xml_escape(message),
+                    xml_escape(diagnostic)
+                ));
+            }
+            if !result.output.is_empty() {
+                report.push_str(&format!(
+                    "      <system-out>{}</system-out>\n",
+                    xml_escape(&result.output)
+                ));
+            }
+            report.push_str("    </testcase>\n");
+        }
+        report.push_str("  </testsuite>\n");
+    }
+    report.push_str("</testsuites>");
+    report
+}
+
+fn xml_escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for char in text.chars() {
+        match char {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            // Control characters other than whitespace can't be represented in XML 1.0
+            char if char.is_control() && !matches!(char, '\n' | '\r' | '\t') => {}
+            char => escaped.push(char),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::{junit_report, xml_escape, TestFormat, TestOutcome, TestReporter, TestResult};
+
+    fn test_result(name: &str, outcome: TestOutcome) -> TestResult {
+        TestResult {
+            index: 0,
+            package: "foo".to_string(),
+            name: name.to_string(),
+            outcome,
+            duration: Duration::from_millis(1500),
+            output: String::new(),
+            message: None,
+            diagnostic: None,
+        }
+    }
+
+    #[test]
+    fn escapes_xml_special_characters() {
+        assert_eq!(xml_escape("a < b && \"c\"\u{1b}[0m"), "a &lt; b &amp;&amp; &quot;c&quot;[0m");
+    }
+
+    #[test]
+    fn junit_report_records_failures_and_durations() {
+        let mut failing = test_result("tests::fails", TestOutcome::Fail);
+        failing.message = Some("Failed constraint".to_string());
+        let report = junit_report(&[test_result("tests::passes", TestOutcome::Pass), failing]);
+
+        assert!(
+            report.contains("<testsuite name=\"foo\" tests=\"2\" failures=\"1\" time=\"3.000\">")
+        );
+        assert!(
+            report.contains("<testcase name=\"tests::passes\" classname=\"foo\" time=\"1.500\">")
+        );
+        assert!(report.contains("<failure message=\"Failed constraint\"></failure>"));
+    }
+
+    #[test]
+    fn machine_readable_formats_always_capture_output() {
+        assert!(!TestReporter::new(TestFormat::Pretty).captures_output(false));
+        assert!(TestReporter::new(TestFormat::Pretty).captures_output(true));
+        assert!(TestReporter::new(TestFormat::Json).captures_output(false));
+        assert!(TestReporter::new(TestFormat::Junit).captures_output(false));
+    }
+}
@@ -0,0 +1,32 @@
+use clap::Args;
+use nargo_toml::{
+    get_package_manifest, resolve_workspace_from_toml, DependencyResolution, PackageSelection,
+};
+use noirc_driver::NOIR_ARTIFACT_VERSION_STRING;
+
+use crate::errors::CliError;
+
+use super::NargoConfig;
+
+/// Resolves the git dependencies of the workspace again, updating Nargo.lock
+#[derive(Debug, Clone, Args)]
+pub(crate) struct UpdateCommand;
+
+pub(crate) fn run(_args: UpdateCommand, config: NargoConfig) -> Result<(), CliError> {
+    if config.locked || config.offline {
+        return Err(CliError::Generic(
+            "Cannot update Nargo.lock when --locked or --offline is passed".to_string(),
+        ));
+    }
+
+    let toml_path = get_package_manifest(&config.program_dir)?;
+    let resolution = DependencyResolution { update: true, ..DependencyResolution::default() };
+    resolve_workspace_from_toml(
+        &toml_path,
+        PackageSelection::All,
+        Some(NOIR_ARTIFACT_VERSION_STRING.to_string()),
+        resolution,
+    )?;
+
+    Ok(())
+}
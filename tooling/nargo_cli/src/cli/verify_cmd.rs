@@ -3,7 +3,10 @@ use super::{
     compile_cmd::compile_bin_package,
     fs::{inputs::read_inputs_from_file, load_hex_data},
 };
-use crate::{backends::Backend, errors::CliError};
+use crate::{
+    backends::{Backend, ProofSystem},
+    errors::CliError,
+};
 
 use clap::Args;
 use nargo::constants::{PROOF_EXT, VERIFIER_INPUT_FILE};
@@ -46,6 +49,7 @@ pub(crate) fn run(
         &toml_path,
         selection,
         Some(NOIR_ARTIFACT_VERSION_STRING.to_string()),
+        config.dependency_resolution(),
     )?;
 
     let (np_language, opcode_support) = backend.get_backend_info()?;
@@ -1,3 +1,4 @@
+use acvm::acir::circuit::R1CSSerializationError;
 use acvm::acir::native_types::WitnessMapError;
 use hex::FromHexError;
 use nargo::NargoError;
@@ -55,6 +56,9 @@ pub(crate) enum CliError {
     #[error(transparent)]
     LspError(#[from] async_lsp::Error),
 
+    #[error(transparent)]
+    DapError(#[from] noir_debugger::dap::DapError),
+
     /// Error from Nargo
     #[error(transparent)]
     NargoError(#[from] NargoError),
@@ -84,6 +88,9 @@ pub(crate) enum BackendError {
     #[error("The backend {0} is already installed")]
     AlreadyInstalled(String),
 
+    #[error("The backend {0} is built into nargo and cannot be uninstalled")]
+    BuiltinBackend(String),
+
     #[error("Backend installation failed: {0}")]
     InstallationError(#[from] std::io::Error),
 }
@@ -101,6 +108,10 @@ pub(crate) enum CompileError {
     /// These errors are already written to stderr.
     #[error("Aborting due to {} previous error{}", .0.error_count, if .0.error_count == 1 { "" } else { "s" })]
     ReportedErrors(ReportedErrors),
+
+    /// The compiled circuit could not be exported to the R1CS format.
+    #[error("Circuit for `{0}` cannot be exported as R1CS: {1}")]
+    R1CSExport(String, R1CSSerializationError),
 }
 
 impl From<ReportedErrors> for CompileError {
@@ -0,0 +1,84 @@
+//! Checks that `nargo compile` reuses the artifacts of packages which are unchanged since they
+//! were last compiled, and compiles them again once they or their dependencies change.
+
+use assert_cmd::prelude::*;
+use std::process::Command;
+use std::time::SystemTime;
+
+use assert_fs::prelude::{FileWriteStr, PathChild};
+use assert_fs::TempDir;
+
+const MAIN_MANIFEST: &str = "[package]
+name = \"main\"
+type = \"bin\"
+authors = [\"\"]
+[dependencies]
+dep = { path = \"../dep\" }";
+
+const DEP_MANIFEST: &str = "[package]
+name = \"dep\"
+type = \"lib\"
+authors = [\"\"]
+[dependencies]";
+
+fn compile(test_dir: &TempDir, args: &[&str]) {
+    let mut cmd = Command::cargo_bin("nargo").unwrap();
+    cmd.env("HOME", test_dir.child("home").path())
+        .arg("--program-dir")
+        .arg(test_dir.child("main").path())
+        .arg("compile")
+        .args(args);
+    cmd.assert().success();
+}
+
+/// Returns when the artifact of the `main` package was last written
+fn artifact_modified(test_dir: &TempDir) -> SystemTime {
+    let artifact = test_dir.child("main").child("target").child("main.json");
+    std::fs::metadata(artifact.path()).unwrap().modified().unwrap()
+}
+
+#[test]
+fn reuses_artifacts_of_unchanged_packages() {
+    let test_dir = TempDir::new().unwrap();
+    // The builtin backend is selected through a fresh home directory so nothing is downloaded.
+    test_dir.child("home/.nargo/backends/.selected_backend").write_str("builtin").unwrap();
+
+    let main_dir = test_dir.child("main");
+    main_dir.child("Nargo.toml").write_str(MAIN_MANIFEST).unwrap();
+    main_dir
+        .child("src/main.nr")
+        .write_str("fn main(x: Field, y: pub Field) {\n    assert(dep::double(x) != y);\n}\n")
+        .unwrap();
+
+    let dep_dir = test_dir.child("dep");
+    dep_dir.child("Nargo.toml").write_str(DEP_MANIFEST).unwrap();
+    let dep_source = "pub fn double(x: Field) -> Field {\n    x * 2\n}";
+    dep_dir.child("src/lib.nr").write_str(dep_source).unwrap();
+
+    compile(&test_dir, &[]);
+    let compiled = artifact_modified(&test_dir);
+
+    compile(&test_dir, &[]);
+    assert_eq!(artifact_modified(&test_dir), compiled, "an unchanged package was compiled again");
+
+    main_dir
+        .child("src/main.nr")
+        .write_str("fn main(x: Field, y: pub Field) {\n    assert(dep::double(x) != y + 1);\n}\n")
+        .unwrap();
+    compile(&test_dir, &[]);
+    let source_changed = artifact_modified(&test_dir);
+    assert_ne!(source_changed, compiled, "a package whose source changed wasn't compiled again");
+
+    let dep_source = "pub fn double(x: Field) -> Field {\n    x + x\n}";
+    dep_dir.child("src/lib.nr").write_str(dep_source).unwrap();
+    compile(&test_dir, &[]);
+    let dependency_changed = artifact_modified(&test_dir);
+    assert_ne!(dependency_changed, source_changed, "a changed dependency wasn't compiled again");
+
+    compile(&test_dir, &["--instrument-debug"]);
+    assert_ne!(
+        artifact_modified(&test_dir),
+        dependency_changed,
+        "a package compiled with different options wasn't compiled again"
+    );
+}
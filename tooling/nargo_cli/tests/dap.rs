@@ -0,0 +1,127 @@
+//! Drives a `nargo dap` session with a scripted sequence of Debug Adapter Protocol requests, as
+//! sent by an editor, and checks the responses and events it produces.
+
+use assert_cmd::prelude::*;
+use serde_json::{json, Value};
+use std::process::Command;
+
+use assert_fs::prelude::{FileWriteStr, PathChild};
+
+/// Frames each message with the `Content-Length` header of the base protocol
+fn encode_requests(requests: &[Value]) -> Vec<u8> {
+    let mut input = Vec::new();
+    for (seq, request) in requests.iter().enumerate() {
+        let mut request = request.clone();
+        request["seq"] = (seq + 1).into();
+        request["type"] = "request".into();
+        let content = request.to_string();
+        input.extend(format!("Content-Length: {}\r\n\r\n{}", content.len(), content).into_bytes());
+    }
+    input
+}
+
+fn decode_messages(mut output: &str) -> Vec<Value> {
+    let mut messages = Vec::new();
+    while let Some((header, rest)) = output.split_once("\r\n\r\n") {
+        let length: usize = header.trim_start_matches("Content-Length: ").parse().unwrap();
+        messages.push(serde_json::from_str(&rest[..length]).unwrap());
+        output = &rest[length..];
+    }
+    messages
+}
+
+const MAIN_SOURCE: &str = "fn main(x: Field, y: Field) {
+    let sum = x + y;
+    let product = sum * y;
+    assert(product != x);
+}
+";
+
+#[test]
+fn scripted_debugging_session() {
+    let test_dir = assert_fs::TempDir::new().unwrap();
+    let project_dir = test_dir.child("dap_session");
+
+    project_dir
+        .child("Nargo.toml")
+        .write_str(
+            "[package]\nname = \"dap_session\"\ntype = \"bin\"\nauthors = [\"\"]\n[dependencies]",
+        )
+        .unwrap();
+    let main_file = project_dir.child("src").child("main.nr");
+    main_file.write_str(MAIN_SOURCE).unwrap();
+    project_dir.child("Prover.toml").write_str("x = 1\ny = 2").unwrap();
+
+    // The builtin backend is selected through a fresh home directory so nothing is downloaded.
+    let home_dir = test_dir.child("home");
+    home_dir.child(".nargo/backends/.selected_backend").write_str("builtin").unwrap();
+
+    let main_path = main_file.path().to_string_lossy().into_owned();
+    let input = encode_requests(&[
+        json!({ "command": "initialize", "arguments": { "adapterID": "noir" } }),
+        json!({ "command": "launch", "arguments": { "projectFolder": project_dir.path() } }),
+        json!({
+            "command": "setBreakpoints",
+            "arguments": {
+                "source": { "path": main_path },
+                "breakpoints": [{ "line": 4 }, { "line": 5 }]
+            }
+        }),
+        json!({ "command": "configurationDone" }),
+        json!({ "command": "continue", "arguments": { "threadId": 1 } }),
+        json!({ "command": "stackTrace", "arguments": { "threadId": 1 } }),
+        json!({ "command": "scopes", "arguments": { "frameId": 0 } }),
+        json!({ "command": "variables", "arguments": { "variablesReference": 1 } }),
+        json!({ "command": "continue", "arguments": { "threadId": 1 } }),
+        json!({ "command": "disconnect" }),
+    ]);
+
+    let mut cmd = Command::cargo_bin("nargo").unwrap();
+    cmd.env("HOME", home_dir.path()).arg("dap").write_stdin(input);
+    let output = cmd.assert().success().get_output().stdout.clone();
+    let messages = decode_messages(&String::from_utf8(output).unwrap());
+
+    let response = |command: &str| {
+        messages
+            .iter()
+            .find(|message| message["type"] == "response" && message["command"] == command)
+            .unwrap_or_else(|| panic!("no response to {command}"))
+    };
+    let events: Vec<_> = messages
+        .iter()
+        .filter(|message| message["type"] == "event")
+        .map(|message| message["event"].as_str().unwrap())
+        .collect();
+    assert_eq!(events, ["initialized", "stopped", "stopped", "exited", "terminated"]);
+    for message in &messages {
+        assert_ne!(message["success"], false, "request failed: {message}");
+    }
+
+    // Only the line with an assertion has opcodes to break at
+    let breakpoints = &response("setBreakpoints")["body"]["breakpoints"];
+    assert_eq!(breakpoints[0]["verified"], true);
+    assert_eq!(breakpoints[1]["verified"], false);
+
+    let stopped: Vec<_> = messages.iter().filter(|message| message["event"] == "stopped").collect();
+    assert_eq!(stopped[0]["body"]["reason"], "entry");
+    assert_eq!(stopped[1]["body"]["reason"], "breakpoint");
+    assert_eq!(stopped[1]["body"]["hitBreakpointIds"], json!([breakpoints[0]["id"]]));
+
+    let stack_frames = &response("stackTrace")["body"]["stackFrames"];
+    assert_eq!(stack_frames.as_array().unwrap().len(), 1);
+    assert_eq!(stack_frames[0]["line"], 4);
+    assert_eq!(stack_frames[0]["source"]["path"], main_path);
+
+    let scopes = &response("scopes")["body"]["scopes"];
+    assert_eq!(scopes[0]["variablesReference"], 1);
+
+    let variables = &response("variables")["body"]["variables"];
+    let value = |name: &str| {
+        let variables = variables.as_array().unwrap();
+        let variable = variables.iter().find(|variable| variable["name"] == name);
+        variable.unwrap_or_else(|| panic!("no variable {name}"))["value"].clone()
+    };
+    assert_eq!(value("x"), "0x01");
+    assert_eq!(value("y"), "0x02");
+    assert_eq!(value("sum"), "0x03");
+}
@@ -0,0 +1,47 @@
+//! Checks that `nargo execute --format r1cs` writes a circuit and witness which can be consumed
+//! by iden3 R1CS tooling, without requiring a backend to be installed.
+
+use assert_cmd::prelude::*;
+use predicates::prelude::*;
+use std::process::Command;
+
+use assert_fs::prelude::{FileWriteStr, PathAssert, PathChild};
+
+#[test]
+fn execute_writes_r1cs_and_wtns_files() {
+    let test_dir = assert_fs::TempDir::new().unwrap();
+    let project_dir = test_dir.child("r1cs_export");
+
+    project_dir
+        .child("Nargo.toml")
+        .write_str(
+            "[package]\nname = \"r1cs_export\"\ntype = \"bin\"\nauthors = [\"\"]\n[dependencies]",
+        )
+        .unwrap();
+    project_dir
+        .child("src")
+        .child("main.nr")
+        .write_str("fn main(x: Field, y: pub Field) -> pub Field {\n    x * y + 1\n}\n")
+        .unwrap();
+    project_dir.child("Prover.toml").write_str("x = 3\ny = 4").unwrap();
+
+    let mut cmd = Command::cargo_bin("nargo").unwrap();
+    cmd.arg("--program-dir").arg(project_dir.path()).arg("execute").arg("--format").arg("r1cs");
+    cmd.assert().success().stdout(predicate::str::contains("R1CS witness saved to"));
+
+    let target_dir = project_dir.child("target");
+    target_dir.child("r1cs_export.r1cs").assert(predicate::path::is_file());
+    target_dir.child("r1cs_export.wtns").assert(predicate::path::is_file());
+
+    let wtns = std::fs::read(target_dir.child("r1cs_export.wtns").path()).unwrap();
+    assert_eq!(&wtns[0..4], b"wtns");
+
+    // The witness starts with the constant one wire, followed by the return value, which is the
+    // first public wire.
+    let field_size = u32::from_le_bytes(wtns[24..28].try_into().unwrap()) as usize;
+    // File header, header section (field size, modulus and wire count), data section header
+    let data_start = 12 + (12 + 4 + field_size + 4) + 12;
+    let wire = |index: usize| &wtns[data_start + index * field_size..][..field_size];
+    assert_eq!(wire(0)[0], 1);
+    assert_eq!(wire(1)[0], 13);
+}
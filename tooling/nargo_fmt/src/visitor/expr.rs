@@ -190,7 +190,9 @@ impl FmtVisitor<'_> {
 
                 self.format_if(*if_expr)
             }
-            ExpressionKind::Lambda(_) | ExpressionKind::Variable(_) => self.slice(span).to_string(),
+            ExpressionKind::Lambda(_) | ExpressionKind::Match(_) | ExpressionKind::Variable(_) => {
+                self.slice(span).to_string()
+            }
             ExpressionKind::Error => unreachable!(),
         }
     }
@@ -67,6 +67,7 @@ impl super::FmtVisitor<'_> {
                 }
                 ItemKind::Import(_)
                 | ItemKind::Struct(_)
+                | ItemKind::Enum(_)
                 | ItemKind::Trait(_)
                 | ItemKind::TraitImpl(_)
                 | ItemKind::Impl(_)
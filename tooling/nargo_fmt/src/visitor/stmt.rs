@@ -72,6 +72,18 @@ impl super::FmtVisitor<'_> {
                     let result = format!("for {identifier} in {range} {block}");
                     self.push_rewrite(result, span);
                 }
+                StatementKind::While(while_stmt) => {
+                    let condition = self.format_sub_expr(while_stmt.condition);
+                    let block = self.format_sub_expr(while_stmt.block);
+
+                    self.push_rewrite(format!("while {condition} {block}"), span);
+                }
+                StatementKind::Loop(loop_stmt) => {
+                    let block = self.format_sub_expr(loop_stmt.block);
+                    self.push_rewrite(format!("loop {block}"), span);
+                }
+                StatementKind::Break => self.push_rewrite("break;".to_string(), span),
+                StatementKind::Continue => self.push_rewrite("continue;".to_string(), span),
                 StatementKind::Assign(_) => {
                     self.push_rewrite(self.slice(span).to_string(), span);
                 }
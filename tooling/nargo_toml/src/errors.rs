@@ -48,10 +48,38 @@ pub enum ManifestError {
     #[error("Invalid directory path {directory} in {toml}: It must point to a subdirectory")]
     InvalidDirectory { toml: PathBuf, directory: PathBuf },
 
+    #[error("Dependency on {git} in {toml} must specify exactly one of `tag` or `version`")]
+    InvalidGitReference { toml: PathBuf, git: String },
+
+    #[error("Invalid version requirement `{version}` in {toml}: {error}")]
+    InvalidVersionRequirement { toml: PathBuf, version: String, error: String },
+
     /// Encountered error while downloading git repository.
     #[error("{0}")]
     GitError(String),
 
+    #[error("No tag of {git} matches the version requirement `{version}`")]
+    NoMatchingVersion { git: String, version: String },
+
+    /// Lockfile is unreadable.
+    #[error("{lockfile} is badly formed, could not parse.\n\n {error}")]
+    MalformedLockfile { lockfile: PathBuf, error: toml::de::Error },
+
+    #[error("{lockfile} has version {version} which is not supported by this version of Nargo")]
+    UnsupportedLockfileVersion { lockfile: PathBuf, version: u32 },
+
+    #[error("{0} needs to be updated but --locked was passed to prevent this")]
+    LockfileOutOfDate(PathBuf),
+
+    #[error("Cannot fetch {0} as --offline was passed")]
+    OfflineFetch(String),
+
+    #[error("The checkout of {git} at {rev} in {checkout} does not match the checksum in Nargo.lock. Delete it to fetch it again.")]
+    ChecksumMismatch { git: String, rev: String, checkout: PathBuf },
+
+    #[error("Cannot write file {0}")]
+    WriteFailed(PathBuf),
+
     #[error("Selected package `{0}` was not found")]
     MissingSelectedPackage(CrateName),
 
@@ -1,11 +1,15 @@
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use semver::{Version, VersionReq};
 
 /// Creates a unique folder name for a GitHub repo
-/// by using its URL and tag
-fn resolve_folder_name(base: &url::Url, tag: &str) -> String {
+/// by using its URL and the commit hash it is checked out at
+fn resolve_folder_name(base: &url::Url, rev: &str) -> String {
     let mut folder_name = base.domain().unwrap().to_owned();
     folder_name.push_str(base.path());
-    folder_name.push_str(tag);
+    folder_name.push('/');
+    folder_name.push_str(rev);
     folder_name
 }
 
@@ -13,32 +17,93 @@ fn nargo_crates() -> PathBuf {
     dirs::home_dir().unwrap().join("nargo")
 }
 
-fn git_dep_location(base: &url::Url, tag: &str) -> PathBuf {
-    let folder_name = resolve_folder_name(base, tag);
+pub(crate) fn git_dep_location(url: &str, rev: &str) -> Result<PathBuf, String> {
+    let base = url::Url::parse(url).map_err(|err| err.to_string())?;
+    let folder_name = resolve_folder_name(&base, rev);
+
+    Ok(nargo_crates().join(folder_name))
+}
+
+/// Returns the commit hash which `tag` currently points to in the repository at `url`.
+///
+/// Branch names are accepted as well, although tags take precedence.
+pub(crate) fn resolve_tag(url: &str, tag: &str) -> Result<String, String> {
+    // An annotated tag points to a tag object, and the commit it points to is only listed
+    // under the `^{}` suffix when that is asked for explicitly.
+    let candidates =
+        [format!("refs/tags/{tag}^{{}}"), format!("refs/tags/{tag}"), format!("refs/heads/{tag}")];
+    let patterns: Vec<&str> = candidates.iter().map(String::as_str).collect();
+    let refs = list_remote_refs(url, &patterns)?;
 
-    nargo_crates().join(folder_name)
+    candidates
+        .iter()
+        .find_map(|candidate| refs.iter().find(|(name, _)| name == candidate))
+        .map(|(_, rev)| rev.clone())
+        .ok_or_else(|| format!("Could not find tag `{tag}` in {url}"))
 }
 
+/// Returns the highest version tag in the repository at `url` which satisfies `version_req`.
+///
+/// Tags are interpreted as semver versions, optionally prefixed with a `v`.
+pub(crate) fn resolve_version(
+    url: &str,
+    version_req: &VersionReq,
+) -> Result<Option<String>, String> {
+    let refs = list_remote_refs(url, &["refs/tags/*"])?;
+
+    let tag = refs
+        .iter()
+        .filter_map(|(name, _)| name.strip_prefix("refs/tags/"))
+        .filter(|tag| !tag.ends_with("^{}"))
+        .filter_map(|tag| {
+            let version = Version::parse(tag.strip_prefix('v').unwrap_or(tag)).ok()?;
+            version_req.matches(&version).then_some((version, tag))
+        })
+        .max_by(|(a, _), (b, _)| a.cmp(b))
+        .map(|(_, tag)| tag.to_string());
+    Ok(tag)
+}
+
+/// Lists the `(name, commit hash)` pairs of the refs of the repository at `url`
+/// which match any of `patterns`.
+fn list_remote_refs(url: &str, patterns: &[&str]) -> Result<Vec<(String, String)>, String> {
+    let output = Command::new("git")
+        .arg("ls-remote")
+        .arg(url)
+        .args(patterns)
+        .output()
+        .map_err(|err| format!("git ls-remote command failed to start: {err}"))?;
+    if !output.status.success() {
+        return Err(format!(
+            "Could not list the refs of {url}: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+
+    let refs = String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(|line| {
+            let (rev, name) = line.split_once('\t')?;
+            Some((name.to_string(), rev.to_string()))
+        })
+        .collect();
+    Ok(refs)
+}
+
+/// Clones `tag` of the repository at `url` into `loc`, failing if the tag no longer points to `rev`.
+///
 /// XXX: I'd prefer to use a GitHub library however, there
 /// does not seem to be an easy way to download a repo at a specific
 /// tag
 /// github-rs looks promising, however it seems to require an API token
 ///
 /// One advantage of using "git clone" is that there is effectively no rate limit
-pub(crate) fn clone_git_repo(url: &str, tag: &str) -> Result<PathBuf, String> {
-    use std::process::Command;
+pub(crate) fn clone_git_repo(url: &str, tag: &str, rev: &str, loc: &Path) -> Result<(), String> {
+    // Clone next to `loc` first so that an interrupted clone is never mistaken for a checkout
+    let partial_loc = loc.with_extension("partial");
+    let _ = std::fs::remove_dir_all(&partial_loc);
 
-    let base = match url::Url::parse(url) {
-        Ok(base) => base,
-        Err(err) => return Err(err.to_string()),
-    };
-
-    let loc = git_dep_location(&base, tag);
-    if loc.exists() {
-        return Ok(loc);
-    }
-
-    Command::new("git")
+    let status = Command::new("git")
         .arg("-c")
         .arg("advice.detachedHead=false")
         .arg("clone")
@@ -46,10 +111,182 @@ pub(crate) fn clone_git_repo(url: &str, tag: &str) -> Result<PathBuf, String> {
         .arg("1")
         .arg("--branch")
         .arg(tag)
-        .arg(base.as_str())
-        .arg(&loc)
+        .arg(url)
+        .arg(&partial_loc)
         .status()
         .expect("git clone command failed to start");
+    if !status.success() {
+        return Err(format!("Could not clone tag `{tag}` of {url}"));
+    }
+
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(&partial_loc)
+        .arg("rev-parse")
+        .arg("HEAD")
+        .output()
+        .expect("git rev-parse command failed to start");
+    let cloned_rev = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    if cloned_rev != rev {
+        let _ = std::fs::remove_dir_all(&partial_loc);
+        return Err(format!(
+            "Tag `{tag}` of {url} points to commit {cloned_rev} rather than {rev}. Run `nargo update` if it has been moved on purpose."
+        ));
+    }
+
+    std::fs::rename(&partial_loc, loc).map_err(|err| err.to_string())
+}
+
+/// Returns the id of the git tree checked out in `dir`, which is a checksum of the contents of
+/// the commit, after checking that the checkout is at commit `rev` and hasn't been modified.
+pub(crate) fn checkout_checksum(dir: &Path, rev: &str) -> Result<String, String> {
+    let output = git_output(dir, &["rev-parse", "HEAD", "HEAD^{tree}"])?;
+    let (checkout_rev, tree) = output
+        .split_once('\n')
+        .ok_or_else(|| format!("Could not read the commit checked out in {}", dir.display()))?;
+    if checkout_rev != rev {
+        return Err(format!(
+            "{} is checked out at commit {checkout_rev} rather than {rev}. Delete it to fetch it again.",
+            dir.display()
+        ));
+    }
+
+    // Files which were added, removed or edited since the commit was checked out aren't
+    // covered by its tree, so the checkout must be clean for the tree to describe it.
+    let changes = git_output(dir, &["status", "--porcelain"])?;
+    if !changes.is_empty() {
+        return Err(format!(
+            "The checkout in {} has been modified. Delete it to fetch it again.",
+            dir.display()
+        ));
+    }
+
+    Ok(tree.to_string())
+}
 
-    Ok(loc)
+/// Runs git in `dir`, returning its trimmed standard output.
+fn git_output(dir: &Path, args: &[&str]) -> Result<String, String> {
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(dir)
+        .args(args)
+        .output()
+        .map_err(|err| format!("git {} command failed to start: {err}", args[0]))?;
+    if !output.status.success() {
+        return Err(format!(
+            "git {} failed in {}: {}",
+            args[0],
+            dir.display(),
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+    use std::process::Command;
+
+    use semver::VersionReq;
+
+    use super::{checkout_checksum, clone_git_repo, resolve_tag, resolve_version};
+
+    fn git(dir: &Path, args: &[&str]) -> String {
+        let output = Command::new("git")
+            .arg("-C")
+            .arg(dir)
+            .args(["-c", "user.name=Nargo", "-c", "user.email=nargo@noir-lang.org"])
+            .args(["-c", "commit.gpgsign=false", "-c", "tag.gpgsign=false"])
+            .args(args)
+            .output()
+            .unwrap();
+        assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+        String::from_utf8_lossy(&output.stdout).trim().to_string()
+    }
+
+    /// Commits `source` as the library of the repository, returning the new commit hash
+    fn commit(repo: &Path, source: &str) -> String {
+        std::fs::write(repo.join("src/lib.nr"), source).unwrap();
+        git(repo, &["add", "--all"]);
+        git(repo, &["commit", "--quiet", "--message", source]);
+        git(repo, &["rev-parse", "HEAD"])
+    }
+
+    /// Creates a repository on branch `main` whose commits are tagged `v0.1.0`, with a
+    /// lightweight tag, and `v0.2.0`, with an annotated tag.
+    fn create_repo(repo: &Path) -> (String, String) {
+        std::fs::create_dir_all(repo.join("src")).unwrap();
+        git(repo, &["init", "--quiet"]);
+        git(repo, &["symbolic-ref", "HEAD", "refs/heads/main"]);
+        let first = commit(repo, "fn one() {}");
+        git(repo, &["tag", "v0.1.0"]);
+        let second = commit(repo, "fn two() {}");
+        git(repo, &["tag", "--annotate", "v0.2.0", "--message", "Release 0.2.0"]);
+        (first, second)
+    }
+
+    fn url(repo: &Path) -> String {
+        format!("file://{}", repo.display())
+    }
+
+    #[test]
+    fn resolves_tags_and_branches_to_commits() {
+        let dir = tempfile::tempdir().unwrap();
+        let repo = dir.path().join("repo");
+        let (first, second) = create_repo(&repo);
+
+        assert_eq!(resolve_tag(&url(&repo), "v0.1.0"), Ok(first));
+        assert_eq!(resolve_tag(&url(&repo), "v0.2.0"), Ok(second.clone()));
+        assert_eq!(resolve_tag(&url(&repo), "main"), Ok(second));
+        assert!(resolve_tag(&url(&repo), "v0.3.0").is_err());
+    }
+
+    #[test]
+    fn resolves_the_highest_matching_version() {
+        let dir = tempfile::tempdir().unwrap();
+        let repo = dir.path().join("repo");
+        create_repo(&repo);
+
+        let resolve = |version| {
+            resolve_version(&url(&repo), &VersionReq::parse(version).unwrap()).unwrap()
+        };
+        assert_eq!(resolve("^0.1"), Some("v0.1.0".to_string()));
+        assert_eq!(resolve(">=0.1"), Some("v0.2.0".to_string()));
+        assert_eq!(resolve("^1"), None);
+    }
+
+    #[test]
+    fn checksums_unmodified_checkouts_of_a_commit() {
+        let dir = tempfile::tempdir().unwrap();
+        let repo = dir.path().join("repo");
+        let (first, second) = create_repo(&repo);
+        let checkout = dir.path().join("checkout");
+        clone_git_repo(&url(&repo), "v0.1.0", &first, &checkout).unwrap();
+
+        // The checksum is the tree of the commit, regardless of where it is checked out
+        let tree = git(&repo, &["rev-parse", &format!("{first}^{{tree}}")]);
+        assert_eq!(checkout_checksum(&checkout, &first), Ok(tree.clone()));
+        assert!(checkout_checksum(&checkout, &second).is_err());
+
+        std::fs::write(checkout.join("src/lib.nr"), "fn edited() {}").unwrap();
+        assert!(checkout_checksum(&checkout, &first).is_err());
+        std::fs::write(checkout.join("src/lib.nr"), "fn one() {}").unwrap();
+        assert_eq!(checkout_checksum(&checkout, &first), Ok(tree));
+
+        std::fs::write(checkout.join("src/extra.nr"), "fn extra() {}").unwrap();
+        assert!(checkout_checksum(&checkout, &first).is_err());
+    }
+
+    #[test]
+    fn rejects_tags_which_moved_from_the_locked_commit() {
+        let dir = tempfile::tempdir().unwrap();
+        let repo = dir.path().join("repo");
+        let (first, _) = create_repo(&repo);
+        let checkout = dir.path().join("checkout");
+
+        assert!(clone_git_repo(&url(&repo), "v0.2.0", &first, &checkout).is_err());
+        assert!(!checkout.exists());
+        assert!(!checkout.with_extension("partial").exists());
+    }
 }
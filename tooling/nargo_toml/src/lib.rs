@@ -18,10 +18,12 @@ use serde::Deserialize;
 
 mod errors;
 mod git;
+mod lock;
 mod semver;
 
 pub use errors::ManifestError;
-use git::clone_git_repo;
+pub use lock::DependencyResolution;
+use lock::{DependencyResolver, GitReference};
 
 /// Returns the [PathBuf] of the directory containing the `Nargo.toml` by searching from `current_path` to the root of its [Path].
 ///
@@ -98,7 +100,11 @@ struct PackageConfig {
 }
 
 impl PackageConfig {
-    fn resolve_to_package(&self, root_dir: &Path) -> Result<Package, ManifestError> {
+    fn resolve_to_package(
+        &self,
+        root_dir: &Path,
+        resolver: &mut DependencyResolver,
+    ) -> Result<Package, ManifestError> {
         let name = if let Some(name) = &self.package.name {
             name.parse().map_err(|_| ManifestError::InvalidPackageName {
                 toml: root_dir.join("Nargo.toml"),
@@ -114,7 +120,7 @@ impl PackageConfig {
                 toml: root_dir.join("Nargo.toml"),
                 name: name.into(),
             })?;
-            let resolved_dep = dep_config.resolve_to_dependency(root_dir)?;
+            let resolved_dep = dep_config.resolve_to_dependency(root_dir, resolver)?;
 
             dependencies.insert(name, resolved_dep);
         }
@@ -245,15 +251,25 @@ struct PackageMetadata {
 /// Enum representing the different types of ways to
 /// supply a source for the dependency
 enum DependencyConfig {
-    Github { git: String, tag: String, directory: Option<String> },
+    Github { git: String, tag: Option<String>, version: Option<String>, directory: Option<String> },
     Path { path: String },
 }
 
 impl DependencyConfig {
-    fn resolve_to_dependency(&self, pkg_root: &Path) -> Result<Dependency, ManifestError> {
+    fn resolve_to_dependency(
+        &self,
+        pkg_root: &Path,
+        resolver: &mut DependencyResolver,
+    ) -> Result<Dependency, ManifestError> {
         let dep = match self {
-            Self::Github { git, tag, directory } => {
-                let dir_path = clone_git_repo(git, tag).map_err(ManifestError::GitError)?;
+            Self::Github { git, tag, version, directory } => {
+                let reference = GitReference::new(
+                    &pkg_root.join("Nargo.toml"),
+                    git,
+                    tag.as_ref(),
+                    version.as_ref(),
+                )?;
+                let dir_path = resolver.resolve_git_dependency(git, &reference)?;
                 let project_path = if let Some(directory) = directory {
                     let internal_path = dir_path.join(directory).normalize();
                     if !internal_path.starts_with(&dir_path) {
@@ -267,13 +283,13 @@ impl DependencyConfig {
                     dir_path
                 };
                 let toml_path = project_path.join("Nargo.toml");
-                let package = resolve_package_from_toml(&toml_path)?;
+                let package = resolve_package_from_toml(&toml_path, resolver)?;
                 Dependency::Remote { package }
             }
             Self::Path { path } => {
                 let dir_path = pkg_root.join(path);
                 let toml_path = dir_path.join("Nargo.toml");
-                let package = resolve_package_from_toml(&toml_path)?;
+                let package = resolve_package_from_toml(&toml_path, resolver)?;
                 Dependency::Local { package }
             }
         };
@@ -291,10 +307,11 @@ impl DependencyConfig {
 fn toml_to_workspace(
     nargo_toml: NargoToml,
     package_selection: PackageSelection,
+    resolver: &mut DependencyResolver,
 ) -> Result<Workspace, ManifestError> {
     let workspace = match nargo_toml.config {
         Config::Package { package_config } => {
-            let member = package_config.resolve_to_package(&nargo_toml.root_dir)?;
+            let member = package_config.resolve_to_package(&nargo_toml.root_dir, resolver)?;
             match &package_selection {
                 PackageSelection::Selected(selected_name) if selected_name != &member.name => {
                     return Err(ManifestError::MissingSelectedPackage(member.name))
@@ -312,7 +329,7 @@ fn toml_to_workspace(
             for (index, member_path) in workspace_config.members.into_iter().enumerate() {
                 let package_root_dir = nargo_toml.root_dir.join(&member_path);
                 let package_toml_path = package_root_dir.join("Nargo.toml");
-                let member = resolve_package_from_toml(&package_toml_path)?;
+                let member = resolve_package_from_toml(&package_toml_path, resolver)?;
 
                 match &package_selection {
                     PackageSelection::Selected(selected_name) => {
@@ -369,12 +386,15 @@ fn read_toml(toml_path: &Path) -> Result<NargoToml, ManifestError> {
 }
 
 /// Resolves a Nargo.toml file into a `Package` struct as defined by our `nargo` core.
-fn resolve_package_from_toml(toml_path: &Path) -> Result<Package, ManifestError> {
+fn resolve_package_from_toml(
+    toml_path: &Path,
+    resolver: &mut DependencyResolver,
+) -> Result<Package, ManifestError> {
     let nargo_toml = read_toml(toml_path)?;
 
     match nargo_toml.config {
         Config::Package { package_config } => {
-            package_config.resolve_to_package(&nargo_toml.root_dir)
+            package_config.resolve_to_package(&nargo_toml.root_dir, resolver)
         }
         Config::Workspace { .. } => {
             Err(ManifestError::UnexpectedWorkspace(toml_path.to_path_buf()))
@@ -390,13 +410,19 @@ pub enum PackageSelection {
 }
 
 /// Resolves a Nargo.toml file into a `Workspace` struct as defined by our `nargo` core.
+///
+/// Git dependencies are resolved to the revisions recorded in the workspace's `Nargo.lock`,
+/// which is updated with any dependencies missing from it as allowed by `resolution`.
 pub fn resolve_workspace_from_toml(
     toml_path: &Path,
     package_selection: PackageSelection,
     current_compiler_version: Option<String>,
+    resolution: DependencyResolution,
 ) -> Result<Workspace, ManifestError> {
     let nargo_toml = read_toml(toml_path)?;
-    let workspace = toml_to_workspace(nargo_toml, package_selection)?;
+    let mut resolver = DependencyResolver::new(&nargo_toml.root_dir, resolution)?;
+    let workspace = toml_to_workspace(nargo_toml, package_selection, &mut resolver)?;
+    resolver.finish()?;
     if let Some(current_compiler_version) = current_compiler_version {
         semver::semver_check_workspace(workspace.clone(), current_compiler_version)?;
     }
@@ -415,6 +441,7 @@ fn parse_standard_toml() {
         [dependencies]
         rand = { tag = "next", git = "https://github.com/rust-lang-nursery/rand"}
         cool = { tag = "next", git = "https://github.com/rust-lang-nursery/rand"}
+        ranged = { version = "0.8", git = "https://github.com/rust-lang-nursery/rand"}
         hello = {path = "./noir_driver"}
     "#;
 
@@ -0,0 +1,284 @@
+use std::path::{Path, PathBuf};
+
+use semver::VersionReq;
+use serde::{Deserialize, Serialize};
+
+use crate::{git, ManifestError};
+
+const LOCKFILE_NAME: &str = "Nargo.lock";
+const LOCKFILE_VERSION: u32 = 1;
+const LOCKFILE_HEADER: &str =
+    "# This file is automatically generated by Nargo.\n# It is not intended for manual editing.\n";
+
+/// Controls how git dependencies are resolved against the `Nargo.lock` of a workspace.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct DependencyResolution {
+    /// Fail rather than make any changes to `Nargo.lock`
+    pub locked: bool,
+    /// Fail rather than access the network to fetch dependencies
+    pub offline: bool,
+    /// Ignore the revisions recorded in `Nargo.lock`, resolving every dependency again
+    pub update: bool,
+}
+
+/// The ways in which a git dependency can be specified in `Nargo.toml`.
+#[derive(Debug, Clone)]
+pub(crate) enum GitReference {
+    /// An exact tag (or branch) of the repository
+    Tag(String),
+    /// The highest tag of the repository which satisfies a semver version requirement
+    Version(VersionReq),
+}
+
+impl GitReference {
+    pub(crate) fn new(
+        toml: &Path,
+        git: &str,
+        tag: Option<&String>,
+        version: Option<&String>,
+    ) -> Result<GitReference, ManifestError> {
+        match (tag, version) {
+            (Some(tag), None) => Ok(GitReference::Tag(tag.clone())),
+            (None, Some(version)) => {
+                VersionReq::parse(version).map(GitReference::Version).map_err(|err| {
+                    ManifestError::InvalidVersionRequirement {
+                        toml: toml.to_path_buf(),
+                        version: version.clone(),
+                        error: err.to_string(),
+                    }
+                })
+            }
+            _ => Err(ManifestError::InvalidGitReference {
+                toml: toml.to_path_buf(),
+                git: git.to_string(),
+            }),
+        }
+    }
+}
+
+/// The contents of a `Nargo.lock`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct Lockfile {
+    version: u32,
+    #[serde(default, rename = "dependency")]
+    dependencies: Vec<LockedDependency>,
+}
+
+/// Records exactly what a git dependency was resolved to.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+struct LockedDependency {
+    git: String,
+    /// The version requirement in `Nargo.toml`, unless the dependency is specified by its tag
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    version: Option<String>,
+    tag: String,
+    /// The commit hash `tag` pointed to
+    rev: String,
+    /// The id of the git tree of `rev`, which is a checksum of its contents
+    checksum: String,
+}
+
+impl LockedDependency {
+    fn matches(&self, git: &str, reference: &GitReference) -> bool {
+        self.git == git
+            && match reference {
+                GitReference::Tag(tag) => self.version.is_none() && &self.tag == tag,
+                GitReference::Version(version_req) => self.version == Some(version_req.to_string()),
+            }
+    }
+}
+
+/// Resolves the git dependencies of a workspace, keeping its `Nargo.lock` up to date.
+pub(crate) struct DependencyResolver {
+    lockfile_path: PathBuf,
+    resolution: DependencyResolution,
+    previous: Option<Lockfile>,
+    resolved: Vec<LockedDependency>,
+}
+
+impl DependencyResolver {
+    pub(crate) fn new(
+        workspace_root: &Path,
+        resolution: DependencyResolution,
+    ) -> Result<DependencyResolver, ManifestError> {
+        let lockfile_path = workspace_root.join(LOCKFILE_NAME);
+
+        let previous = match std::fs::read_to_string(&lockfile_path) {
+            Ok(contents) => {
+                let lockfile: Lockfile = toml::from_str(&contents).map_err(|error| {
+                    ManifestError::MalformedLockfile { lockfile: lockfile_path.clone(), error }
+                })?;
+                if lockfile.version != LOCKFILE_VERSION {
+                    return Err(ManifestError::UnsupportedLockfileVersion {
+                        lockfile: lockfile_path,
+                        version: lockfile.version,
+                    });
+                }
+                Some(lockfile)
+            }
+            Err(_) => None,
+        };
+
+        Ok(DependencyResolver { lockfile_path, resolution, previous, resolved: Vec::new() })
+    }
+
+    /// Returns the directory containing the checkout of the git dependency, fetching it if it
+    /// isn't available locally.
+    ///
+    /// The revision recorded in `Nargo.lock` is used if there is one, otherwise `reference` is
+    /// resolved against the repository. Either way the checkout is verified to be an unmodified
+    /// checkout of that revision, matching the checksum of its contents.
+    pub(crate) fn resolve_git_dependency(
+        &mut self,
+        git: &str,
+        reference: &GitReference,
+    ) -> Result<PathBuf, ManifestError> {
+        let locked = match self.find_locked(git, reference) {
+            Some(locked) => locked,
+            None if self.resolution.locked => {
+                return Err(ManifestError::LockfileOutOfDate(self.lockfile_path.clone()))
+            }
+            None => self.lock(git, reference)?,
+        };
+
+        let location = git::git_dep_location(git, &locked.rev).map_err(ManifestError::GitError)?;
+        if !location.exists() {
+            self.fetch(&locked.tag, &locked.rev, git, &location)?;
+        }
+        let checksum =
+            git::checkout_checksum(&location, &locked.rev).map_err(ManifestError::GitError)?;
+        if checksum != locked.checksum {
+            return Err(ManifestError::ChecksumMismatch {
+                git: git.to_string(),
+                rev: locked.rev,
+                checkout: location,
+            });
+        }
+
+        if !self.resolved.contains(&locked) {
+            self.resolved.push(locked);
+        }
+        Ok(location)
+    }
+
+    /// Writes the dependencies which were resolved to `Nargo.lock`, dropping any which are no
+    /// longer used.
+    pub(crate) fn finish(self) -> Result<(), ManifestError> {
+        let mut dependencies = self.resolved;
+        dependencies.sort();
+        let lockfile = Lockfile { version: LOCKFILE_VERSION, dependencies };
+
+        let unchanged = match &self.previous {
+            Some(previous) => previous == &lockfile,
+            // Workspaces without git dependencies don't need a lockfile
+            None => lockfile.dependencies.is_empty(),
+        };
+        if unchanged {
+            return Ok(());
+        }
+        if self.resolution.locked {
+            return Err(ManifestError::LockfileOutOfDate(self.lockfile_path));
+        }
+
+        let contents = toml::to_string(&lockfile).expect("lockfile should be serializable");
+        std::fs::write(&self.lockfile_path, format!("{LOCKFILE_HEADER}{contents}"))
+            .map_err(|_| ManifestError::WriteFailed(self.lockfile_path))
+    }
+
+    fn find_locked(&self, git: &str, reference: &GitReference) -> Option<LockedDependency> {
+        let previous = self.previous.iter().filter(|_| !self.resolution.update);
+        self.resolved
+            .iter()
+            .chain(previous.flat_map(|lockfile| &lockfile.dependencies))
+            .find(|locked| locked.matches(git, reference))
+            .cloned()
+    }
+
+    /// Resolves `reference` to the revision it currently points to, fetching that revision.
+    fn lock(&self, git: &str, reference: &GitReference) -> Result<LockedDependency, ManifestError> {
+        if self.resolution.offline {
+            return Err(ManifestError::OfflineFetch(git.to_string()));
+        }
+
+        let (tag, version) = match reference {
+            GitReference::Tag(tag) => (tag.clone(), None),
+            GitReference::Version(version_req) => {
+                let tag = git::resolve_version(git, version_req)
+                    .map_err(ManifestError::GitError)?
+                    .ok_or_else(|| ManifestError::NoMatchingVersion {
+                        git: git.to_string(),
+                        version: version_req.to_string(),
+                    })?;
+                (tag, Some(version_req.to_string()))
+            }
+        };
+        let rev = git::resolve_tag(git, &tag).map_err(ManifestError::GitError)?;
+
+        let location = git::git_dep_location(git, &rev).map_err(ManifestError::GitError)?;
+        if !location.exists() {
+            self.fetch(&tag, &rev, git, &location)?;
+        }
+        // A checkout which was already there may have been modified since it was fetched.
+        let checksum = git::checkout_checksum(&location, &rev).map_err(ManifestError::GitError)?;
+
+        Ok(LockedDependency { git: git.to_string(), version, tag, rev, checksum })
+    }
+
+    fn fetch(&self, tag: &str, rev: &str, git: &str, location: &Path) -> Result<(), ManifestError> {
+        if self.resolution.offline {
+            return Err(ManifestError::OfflineFetch(git.to_string()));
+        }
+        git::clone_git_repo(git, tag, rev, location).map_err(ManifestError::GitError)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lockfile_roundtrips_through_toml() {
+        let lockfile = Lockfile {
+            version: LOCKFILE_VERSION,
+            dependencies: vec![
+                LockedDependency {
+                    git: "https://github.com/noir-lang/a".to_string(),
+                    version: None,
+                    tag: "v0.1.0".to_string(),
+                    rev: "0123456789abcdef0123456789abcdef01234567".to_string(),
+                    checksum: "4b825dc642cb6eb9a060e54bf8d69288fbee4904".to_string(),
+                },
+                LockedDependency {
+                    git: "https://github.com/noir-lang/b".to_string(),
+                    version: Some("^0.2".to_string()),
+                    tag: "v0.2.3".to_string(),
+                    rev: "89abcdef0123456789abcdef0123456789abcdef".to_string(),
+                    checksum: "d564d0bc3dd917926892c55e3706cc116d5b165e".to_string(),
+                },
+            ],
+        };
+
+        let contents = toml::to_string(&lockfile).unwrap();
+        assert_eq!(toml::from_str::<Lockfile>(&contents).unwrap(), lockfile);
+    }
+
+    #[test]
+    fn locked_dependencies_match_their_reference() {
+        let git = "https://github.com/noir-lang/a";
+        let locked = LockedDependency {
+            git: git.to_string(),
+            version: Some(VersionReq::parse("0.1").unwrap().to_string()),
+            tag: "v0.1.4".to_string(),
+            rev: String::new(),
+            checksum: String::new(),
+        };
+
+        assert!(locked.matches(git, &GitReference::Version(VersionReq::parse("^0.1").unwrap())));
+        assert!(!locked.matches(git, &GitReference::Version(VersionReq::parse("0.2").unwrap())));
+        assert!(!locked.matches(git, &GitReference::Tag("v0.1.4".to_string())));
+        assert!(!locked.matches(
+            "https://github.com/noir-lang/b",
+            &GitReference::Version(VersionReq::parse("0.1").unwrap())
+        ));
+    }
+}
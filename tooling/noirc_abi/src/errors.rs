@@ -1,5 +1,5 @@
 use crate::{input_parser::InputValue, AbiParameter, AbiType};
-use acvm::acir::native_types::Witness;
+use acvm::{acir::native_types::Witness, FieldElement};
 use thiserror::Error;
 
 #[derive(Debug, Error)]
@@ -14,6 +14,8 @@ pub enum InputParserError {
     AbiTypeMismatch(AbiType),
     #[error("Expected argument `{0}`, but none was found")]
     MissingArgument(String),
+    #[error("`{variant}` is not a variant of the enum `{path}`")]
+    UnknownVariant { variant: String, path: String },
 }
 
 impl From<toml::ser::Error> for InputParserError {
@@ -52,4 +54,6 @@ pub enum AbiError {
     ReturnTypeMismatch { return_type: AbiType, value: InputValue },
     #[error("No return value is expected but received {0:?}")]
     UnexpectedReturnValue(InputValue),
+    #[error("Tag {tag} does not correspond to any variant of the enum `{path}`")]
+    InvalidEnumTag { tag: FieldElement, path: String },
 }
@@ -1,4 +1,4 @@
-use super::{parse_str_to_field, InputValue};
+use super::{enum_variant_fields, parse_str_to_field, InputValue};
 use crate::{errors::InputParserError, Abi, AbiType, MAIN_RETURN_NAME};
 use acvm::FieldElement;
 use iter_extended::{try_btree_map, try_vecmap};
@@ -111,6 +111,20 @@ impl JsonTypes {
                 JsonTypes::Array(fields)
             }
 
+            // Unit variants are written as just their name while other variants are written as a
+            // table from the variant's name to its fields, e.g. `{ Circle = ["5"] }`.
+            (InputValue::Variant(variant, values), AbiType::Enum { .. }) => {
+                if values.is_empty() {
+                    JsonTypes::String(variant.clone())
+                } else {
+                    let fields = enum_variant_fields(abi_type, variant)?;
+                    let values = try_vecmap(values.iter().zip(fields), |(value, typ)| {
+                        JsonTypes::try_from_input_value(value, typ)
+                    })?;
+                    JsonTypes::Table(BTreeMap::from([(variant.clone(), JsonTypes::Array(values))]))
+                }
+            }
+
             _ => return Err(InputParserError::AbiTypeMismatch(abi_type.clone())),
         };
         Ok(json_value)
@@ -183,6 +197,28 @@ impl InputValue {
                 InputValue::Vec(tuple_fields)
             }
 
+            (JsonTypes::String(variant), AbiType::Enum { .. }) => {
+                if !enum_variant_fields(param_type, &variant)?.is_empty() {
+                    return Err(InputParserError::AbiTypeMismatch(param_type.clone()));
+                }
+                InputValue::Variant(variant, Vec::new())
+            }
+
+            (JsonTypes::Table(table), AbiType::Enum { .. }) if table.len() == 1 => {
+                let (variant, values) = table.into_iter().next().unwrap();
+                let fields = enum_variant_fields(param_type, &variant)?;
+
+                let values = match values {
+                    JsonTypes::Array(values) if values.len() == fields.len() => values,
+                    _ => return Err(InputParserError::AbiTypeMismatch(param_type.clone())),
+                };
+
+                let values = try_vecmap(values.into_iter().zip(fields), |(value, typ)| {
+                    InputValue::try_from_json(value, typ, arg_name)
+                })?;
+                InputValue::Variant(variant, values)
+            }
+
             (_, _) => return Err(InputParserError::AbiTypeMismatch(param_type.clone())),
         };
 
@@ -20,6 +20,8 @@ pub enum InputValue {
     String(String),
     Vec(Vec<InputValue>),
     Struct(BTreeMap<String, InputValue>),
+    /// The name of the active variant of an enum along with the values it holds.
+    Variant(String, Vec<InputValue>),
 }
 
 impl InputValue {
@@ -75,6 +77,15 @@ impl InputValue {
                     .all(|(input_value, abi_param)| input_value.matches_abi(abi_param))
             }
 
+            (InputValue::Variant(variant, values), AbiType::Enum { variants, .. }) => {
+                let Some((_, fields)) = variants.iter().find(|(name, _)| name == variant) else {
+                    return false;
+                };
+
+                values.len() == fields.len()
+                    && values.iter().zip(fields).all(|(value, typ)| value.matches_abi(typ))
+            }
+
             // All other InputValue-AbiType combinations are fundamentally incompatible.
             _ => false,
         }
@@ -158,6 +169,28 @@ mod serialization_tests {
                     },
                     visibility: AbiVisibility::Private,
                 },
+                AbiParameter {
+                    name: "baz".into(),
+                    typ: AbiType::Enum {
+                        path: "MyEnum".into(),
+                        variants: vec![
+                            ("Empty".into(), vec![]),
+                            ("Pair".into(), vec![AbiType::Field, AbiType::Boolean]),
+                        ],
+                    },
+                    visibility: AbiVisibility::Private,
+                },
+                AbiParameter {
+                    name: "qux".into(),
+                    typ: AbiType::Enum {
+                        path: "MyEnum".into(),
+                        variants: vec![
+                            ("Empty".into(), vec![]),
+                            ("Pair".into(), vec![AbiType::Field, AbiType::Boolean]),
+                        ],
+                    },
+                    visibility: AbiVisibility::Private,
+                },
             ],
             return_type: Some(AbiType::String { length: 5 }),
             // These two fields are unused when serializing/deserializing to file.
@@ -180,6 +213,14 @@ mod serialization_tests {
                     ),
                 ])),
             ),
+            (
+                "baz".into(),
+                InputValue::Variant(
+                    "Pair".into(),
+                    vec![InputValue::Field(7u128.into()), InputValue::Field(true.into())],
+                ),
+            ),
+            ("qux".into(), InputValue::Variant("Empty".into(), vec![])),
             (MAIN_RETURN_NAME.into(), InputValue::String("hello".to_owned())),
         ]);
 
@@ -193,6 +234,25 @@ mod serialization_tests {
     }
 }
 
+/// Returns the field types of the variant named `variant` of the enum type `abi_type`.
+fn enum_variant_fields<'a>(
+    abi_type: &'a AbiType,
+    variant: &str,
+) -> Result<&'a [AbiType], InputParserError> {
+    let AbiType::Enum { path, variants } = abi_type else {
+        return Err(InputParserError::AbiTypeMismatch(abi_type.clone()));
+    };
+
+    variants
+        .iter()
+        .find(|(name, _)| name == variant)
+        .map(|(_, fields)| fields.as_slice())
+        .ok_or_else(|| InputParserError::UnknownVariant {
+            variant: variant.to_owned(),
+            path: path.clone(),
+        })
+}
+
 fn parse_str_to_field(value: &str) -> Result<FieldElement, InputParserError> {
     let big_num = if let Some(hex) = value.strip_prefix("0x") {
         BigUint::from_str_radix(hex, 16)
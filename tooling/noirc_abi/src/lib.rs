@@ -68,6 +68,14 @@ pub enum AbiType {
     String {
         length: u64,
     },
+    Enum {
+        path: String,
+        #[serde(
+            serialize_with = "serialization::serialize_enum_variants",
+            deserialize_with = "serialization::deserialize_enum_variants"
+        )]
+        variants: Vec<(String, Vec<AbiType>)>,
+    },
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
@@ -168,6 +176,14 @@ impl AbiType {
                     context.fully_qualified_struct_path(context.root_crate_id(), struct_type.id);
                 Self::Struct { fields, path }
             }
+            Type::Enum(def, ref args) => {
+                let enum_type = def.borrow();
+                let variants = vecmap(enum_type.get_variants(args), |(name, fields)| {
+                    (name, vecmap(fields, |typ| Self::from_type(context, &typ)))
+                });
+                let path = context.fully_qualified_enum_path(context.root_crate_id(), enum_type.id);
+                Self::Enum { variants, path }
+            }
             Type::Tuple(fields) => {
                 let fields = vecmap(fields, |typ| Self::from_type(context, typ));
                 Self::Tuple { fields }
@@ -193,6 +209,10 @@ impl AbiType {
                 fields.iter().fold(0, |acc, field_typ| acc + field_typ.field_count())
             }
             AbiType::String { length } => *length as u32,
+            // Enums are encoded as a tag followed by the fields of every variant.
+            AbiType::Enum { variants, .. } => variants.iter().fold(1, |acc, (_, fields)| {
+                acc + fields.iter().map(AbiType::field_count).sum::<u32>()
+            }),
         }
     }
 }
@@ -386,6 +406,24 @@ impl Abi {
                     encoded_value.extend(Self::encode_value(value, typ)?);
                 }
             }
+            (InputValue::Variant(variant, values), AbiType::Enum { variants, .. }) => {
+                let tag = variants.iter().position(|(name, _)| *name == variant).unwrap();
+                encoded_value.push((tag as u128).into());
+
+                // Every variant is encoded so that the enum always has the same size,
+                // with the fields of inactive variants set to zero.
+                for (index, (_, fields)) in variants.iter().enumerate() {
+                    if index == tag {
+                        for (value, typ) in values.iter().zip(fields) {
+                            encoded_value.extend(Self::encode_value(value.clone(), typ)?);
+                        }
+                    } else {
+                        let field_count = fields.iter().map(AbiType::field_count).sum::<u32>();
+                        let zeroes = std::iter::repeat(FieldElement::zero());
+                        encoded_value.extend(zeroes.take(field_count as usize));
+                    }
+                }
+            }
             _ => unreachable!("value should have already been checked to match abi type"),
         }
         Ok(encoded_value)
@@ -486,6 +524,22 @@ fn decode_value(
 
             InputValue::Vec(tuple_elements)
         }
+        AbiType::Enum { path, variants } => {
+            let tag = field_iterator.next().unwrap();
+            let index = tag.try_to_u64().map(|index| index as usize);
+
+            // The fields of every variant are encoded so we must decode all of them,
+            // only keeping those belonging to the active variant.
+            let mut active_variant = None;
+            for (variant_index, (name, fields)) in variants.iter().enumerate() {
+                let values = try_vecmap(fields, |typ| decode_value(field_iterator, typ))?;
+                if Some(variant_index) == index {
+                    active_variant = Some(InputValue::Variant(name.clone(), values));
+                }
+            }
+
+            active_variant.ok_or_else(|| AbiError::InvalidEnumTag { tag, path: path.clone() })?
+        }
     };
 
     Ok(value)
@@ -503,7 +557,7 @@ fn decode_string_value(field_elements: &[FieldElement]) -> String {
     final_string.to_owned()
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ContractEvent {
     /// Event name
     name: String,
@@ -594,4 +648,44 @@ mod test {
         // We also decode the return value (we can do this immediately as we know it shares a witness with an input).
         assert_eq!(return_value.unwrap(), reconstructed_inputs["thing2"]);
     }
+
+    #[test]
+    fn enums_encode_every_variant() {
+        let typ = AbiType::Enum {
+            path: "Shape".to_string(),
+            variants: vec![
+                ("Circle".to_string(), vec![AbiType::Field]),
+                ("Rectangle".to_string(), vec![AbiType::Field, AbiType::Field]),
+            ],
+        };
+        let abi = Abi {
+            parameters: vec![AbiParameter {
+                name: "shape".to_string(),
+                typ,
+                visibility: AbiVisibility::Private,
+            }],
+            param_witnesses: BTreeMap::from([(
+                "shape".to_string(),
+                vec![(Witness(1)..Witness(5))],
+            )]),
+            return_type: None,
+            return_witnesses: Vec::new(),
+        };
+
+        let shape = InputValue::Variant(
+            "Rectangle".to_string(),
+            vec![InputValue::Field(2_u128.into()), InputValue::Field(3_u128.into())],
+        );
+        let inputs: InputMap = BTreeMap::from([("shape".to_string(), shape.clone())]);
+
+        // The tag is followed by a zeroed `Circle` payload and then the `Rectangle` payload.
+        let witness_map = abi.encode(&inputs, None).unwrap();
+        let encoded: Vec<FieldElement> = (1..5).map(|i| witness_map[&Witness(i)]).collect();
+        let expected: Vec<FieldElement> =
+            vec![1_u128, 0, 2, 3].into_iter().map(Into::into).collect();
+        assert_eq!(encoded, expected);
+
+        let (reconstructed_inputs, _) = abi.decode(&witness_map).unwrap();
+        assert_eq!(reconstructed_inputs["shape"], shape);
+    }
 }
@@ -41,6 +41,40 @@ where
     Ok(vecmap(fields_vector, |StructField { name, typ }| (name, typ)))
 }
 
+// Enum variants are serialized in the same manner as struct fields as the ordering of the variants determines
+// the tag used to represent each of them.
+
+#[derive(Serialize, Deserialize)]
+struct EnumVariant {
+    name: String,
+    fields: Vec<AbiType>,
+}
+
+pub(crate) fn serialize_enum_variants<S>(
+    variants: &[(String, Vec<AbiType>)],
+    s: S,
+) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    let variants_vector = vecmap(variants, |(name, fields)| EnumVariant {
+        name: name.to_owned(),
+        fields: fields.to_owned(),
+    });
+
+    variants_vector.serialize(s)
+}
+
+pub(crate) fn deserialize_enum_variants<'de, D>(
+    deserializer: D,
+) -> Result<Vec<(String, Vec<AbiType>)>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let variants_vector = Vec::<EnumVariant>::deserialize(deserializer)?;
+    Ok(vecmap(variants_vector, |EnumVariant { name, fields }| (name, fields)))
+}
+
 #[cfg(test)]
 mod tests {
     use crate::{AbiParameter, AbiType, AbiVisibility, Sign};